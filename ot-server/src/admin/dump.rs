@@ -0,0 +1,224 @@
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Write},
+    path::Path,
+};
+
+use anyhow::{bail, Context};
+use chrono::{DateTime, Utc};
+use sea_orm::{DatabaseConnection, TransactionTrait};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    entities::*,
+    repository::{
+        activity_repository::{ActivityMutation, ActivityQuery},
+        billing_rate_repository::{BillingRateMutation, BillingRateQuery},
+        budget_alert_repository::{BudgetAlertMutation, BudgetAlertQuery},
+        client_repository::{ClientMutation, ClientQuery},
+        comment_repository::{CommentMutation, CommentQuery},
+        invoice_repository::{InvoiceMutation, InvoiceQuery},
+        label_repository::{LabelMutation, LabelQuery},
+        project_repository::{ProjectMutation, ProjectQuery},
+        recurrence_repository::{RecurrenceMutation, RecurrenceQuery},
+        section_repository::{SectionMutation, SectionQuery},
+        sub_task_repository::{SubTaskMutation, SubTaskQuery},
+        task_dependency_repository::{TaskDependencyMutation, TaskDependencyQuery},
+        task_event_repository::{TaskEventMutation, TaskEventQuery},
+        task_repository::{TaskMutation, TaskQuery},
+        timer_session_repository::{TimerSessionMutation, TimerSessionQuery},
+        tracking_policy_repository::{TrackingPolicyMutation, TrackingPolicyQuery},
+    },
+    utils::error::AppError,
+};
+
+/// Bumped whenever the dump layout changes incompatibly.
+const DUMP_VERSION: u32 = 14;
+
+#[derive(Serialize, Deserialize)]
+struct DatabaseDump {
+    version: u32,
+    exported_at: DateTime<Utc>,
+    clients: Vec<client::Model>,
+    projects: Vec<project::Model>,
+    sections: Vec<section::Model>,
+    tasks: Vec<task::Model>,
+    sub_tasks: Vec<sub_task::Model>,
+    task_dependencies: Vec<task_dependency::Model>,
+    task_recurrences: Vec<task_recurrence::Model>,
+    task_occurrences: Vec<task_occurrence::Model>,
+    comments: Vec<comment::Model>,
+    comment_revisions: Vec<comment_revision::Model>,
+    task_events: Vec<task_event::Model>,
+    labels: Vec<label::Model>,
+    project_labels: Vec<project_label::Model>,
+    task_labels: Vec<task_label::Model>,
+    sub_task_labels: Vec<sub_task_label::Model>,
+    billing_rates: Vec<billing_rate::Model>,
+    invoices: Vec<invoice::Model>,
+    invoice_lines: Vec<invoice_line::Model>,
+    budget_alerts: Vec<budget_alert::Model>,
+    timer_sessions: Vec<timer_session::Model>,
+    tracking_policies: Vec<tracking_policy::Model>,
+    activity_data: Vec<activity_data::Model>,
+    window_activity_data: Vec<window_activity_data::Model>,
+}
+
+pub async fn dump(db: &DatabaseConnection, file: &Path) -> anyhow::Result<()> {
+    let dump = DatabaseDump {
+        version: DUMP_VERSION,
+        exported_at: Utc::now(),
+        clients: ClientQuery::get_clients(db).await?,
+        projects: ProjectQuery::get_all_projects(db).await?,
+        sections: SectionQuery::get_sections(db).await?,
+        tasks: TaskQuery::get_tasks(db, Default::default()).await?,
+        sub_tasks: SubTaskQuery::get_sub_tasks(db, Default::default()).await?,
+        task_dependencies: TaskDependencyQuery::get_task_dependencies(db).await?,
+        task_recurrences: RecurrenceQuery::get_task_recurrences(db).await?,
+        task_occurrences: RecurrenceQuery::get_task_occurrences(db).await?,
+        comments: CommentQuery::get_comments(db).await?,
+        comment_revisions: CommentQuery::get_comment_revisions(db).await?,
+        task_events: TaskEventQuery::get_task_events(db).await?,
+        labels: LabelQuery::get_labels(db).await?,
+        project_labels: LabelQuery::get_project_labels(db).await?,
+        task_labels: LabelQuery::get_task_labels(db).await?,
+        sub_task_labels: LabelQuery::get_sub_task_labels(db).await?,
+        billing_rates: BillingRateQuery::get_rates(db, Default::default()).await?,
+        invoices: InvoiceQuery::get_invoices(db, Default::default()).await?,
+        invoice_lines: InvoiceQuery::get_all_lines(db).await?,
+        budget_alerts: BudgetAlertQuery::get_all_alerts(db).await?,
+        timer_sessions: TimerSessionQuery::get_timer_sessions(db).await?,
+        tracking_policies: TrackingPolicyQuery::get_policies(db).await?,
+        activity_data: ActivityQuery::get_activity_data(db).await?,
+        window_activity_data: ActivityQuery::get_window_activity_data(db).await?,
+    };
+
+    if file == Path::new("-") {
+        let mut out = io::stdout().lock();
+        serde_json::to_writer_pretty(&mut out, &dump)?;
+        writeln!(out)?;
+    } else {
+        let out =
+            File::create(file).with_context(|| format!("failed to create {}", file.display()))?;
+        let mut out = BufWriter::new(out);
+        serde_json::to_writer_pretty(&mut out, &dump)?;
+        out.flush()?;
+        eprintln!(
+            "Wrote {} client(s), {} project(s), {} section(s), {} task(s), {} sub task(s), {} comment(s), {} label(s), {} rate(s), {} invoice(s), {} activity and {} window activity row(s) to {}",
+            dump.clients.len(),
+            dump.projects.len(),
+            dump.sections.len(),
+            dump.tasks.len(),
+            dump.sub_tasks.len(),
+            dump.comments.len(),
+            dump.labels.len(),
+            dump.billing_rates.len(),
+            dump.invoices.len(),
+            dump.activity_data.len(),
+            dump.window_activity_data.len(),
+            file.display()
+        );
+    }
+    Ok(())
+}
+
+/// Load a dump in a single transaction; nothing is written if any row fails.
+pub async fn restore(db: &DatabaseConnection, file: &Path, replace: bool) -> anyhow::Result<()> {
+    let input = File::open(file).with_context(|| format!("failed to open {}", file.display()))?;
+    let dump: DatabaseDump = serde_json::from_reader(BufReader::new(input))
+        .with_context(|| format!("{} is not a valid dump", file.display()))?;
+    if dump.version != DUMP_VERSION {
+        bail!(
+            "unsupported dump version {} (expected {})",
+            dump.version,
+            DUMP_VERSION
+        );
+    }
+
+    let txn = db.begin().await?;
+    let counts = async {
+        if replace {
+            // Sections, tasks, sub tasks, task dependencies, recurrences,
+            // comments, task events, label assignments and budget alerts
+            // cascade from their project
+            ProjectMutation::delete_all_projects(&txn).await?;
+            LabelMutation::delete_all_labels(&txn).await?;
+            BillingRateMutation::delete_all_rates(&txn).await?;
+            InvoiceMutation::delete_all_invoices(&txn).await?;
+            ClientMutation::delete_all_clients(&txn).await?;
+            ActivityMutation::delete_all(&txn).await?;
+            TimerSessionMutation::delete_all(&txn).await?;
+            TrackingPolicyMutation::delete_all_policies(&txn).await?;
+        }
+        Ok::<_, AppError>((
+            ClientMutation::import_clients(&txn, dump.clients).await?,
+            ProjectMutation::import_projects(&txn, dump.projects).await?,
+            SectionMutation::import_sections(&txn, dump.sections).await?,
+            TaskMutation::import_tasks(&txn, dump.tasks).await?,
+            SubTaskMutation::import_sub_tasks(&txn, dump.sub_tasks).await?,
+            TaskDependencyMutation::import_task_dependencies(&txn, dump.task_dependencies).await?,
+            RecurrenceMutation::import_task_recurrences(&txn, dump.task_recurrences).await?
+                + RecurrenceMutation::import_task_occurrences(&txn, dump.task_occurrences).await?,
+            CommentMutation::import_comments(&txn, dump.comments).await?,
+            CommentMutation::import_comment_revisions(&txn, dump.comment_revisions).await?,
+            TaskEventMutation::import_task_events(&txn, dump.task_events).await?,
+            LabelMutation::import_labels(&txn, dump.labels).await?,
+            LabelMutation::import_project_labels(&txn, dump.project_labels).await?
+                + LabelMutation::import_task_labels(&txn, dump.task_labels).await?
+                + LabelMutation::import_sub_task_labels(&txn, dump.sub_task_labels).await?,
+            BillingRateMutation::import_rates(&txn, dump.billing_rates).await?,
+            InvoiceMutation::import_invoices(&txn, dump.invoices).await?,
+            InvoiceMutation::import_invoice_lines(&txn, dump.invoice_lines).await?,
+            BudgetAlertMutation::import_budget_alerts(&txn, dump.budget_alerts).await?,
+            TimerSessionMutation::import_timer_sessions(&txn, dump.timer_sessions).await?,
+            TrackingPolicyMutation::import_policies(&txn, dump.tracking_policies).await?,
+            ActivityMutation::import_activity_data(&txn, dump.activity_data).await?,
+            ActivityMutation::import_window_activity_data(&txn, dump.window_activity_data).await?,
+        ))
+    }
+    .await
+    .context(
+        "restore rolled back, nothing was written (use --replace to overwrite existing rows)",
+    )?;
+    txn.commit().await?;
+    let (
+        clients,
+        projects,
+        sections,
+        tasks,
+        sub_tasks,
+        _,
+        _,
+        comments,
+        _,
+        _,
+        labels,
+        _,
+        rates,
+        invoices,
+        _,
+        _,
+        _,
+        _,
+        activity,
+        window_activity,
+    ) = counts;
+
+    println!(
+        "Restored {} client(s), {} project(s), {} section(s), {} task(s), {} sub task(s), {} comment(s), {} label(s), {} rate(s), {} invoice(s), {} activity and {} window activity row(s) from {} (exported {})",
+        clients,
+        projects,
+        sections,
+        tasks,
+        sub_tasks,
+        comments,
+        labels,
+        rates,
+        invoices,
+        activity,
+        window_activity,
+        file.display(),
+        dump.exported_at.to_rfc3339()
+    );
+    Ok(())
+}
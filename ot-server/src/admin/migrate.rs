@@ -0,0 +1,27 @@
+use sea_orm::DatabaseConnection;
+use sea_orm_migration::MigratorTrait;
+
+use crate::{config::cli::MigrateCommand, migrator::Migrator};
+
+pub async fn run(db: &DatabaseConnection, command: MigrateCommand) -> anyhow::Result<()> {
+    match command {
+        MigrateCommand::Up { steps } => {
+            let pending = Migrator::get_pending_migrations(db).await?.len();
+            Migrator::up(db, steps).await?;
+            let applied = steps.map_or(pending, |steps| pending.min(steps as usize));
+            println!("Applied {} migration(s)", applied);
+        }
+        MigrateCommand::Down { steps } => {
+            let applied = Migrator::get_applied_migrations(db).await?.len();
+            Migrator::down(db, Some(steps)).await?;
+            println!("Rolled back {} migration(s)", applied.min(steps as usize));
+        }
+        MigrateCommand::Status => {
+            for migration in Migrator::get_migration_with_status(db).await? {
+                println!("{:<8} {}", migration.status(), migration.name());
+            }
+        }
+        MigrateCommand::Check { .. } => unreachable!("check runs on a scratch database"),
+    }
+    Ok(())
+}
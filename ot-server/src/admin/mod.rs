@@ -0,0 +1,44 @@
+//! Operator commands run from the server binary, e.g. `ot-server migrate status`.
+//!
+//! Everything here goes through the services and repositories the API uses,
+//! so the same validation and cascade rules apply.
+
+mod dump;
+mod migrate;
+mod project;
+mod purge;
+mod schema_check;
+mod seed;
+
+use crate::config::{
+    cli::{Command, MigrateCommand},
+    database,
+    settings::Settings,
+};
+
+/// Run an operator command to completion. `Command::Serve` is handled by `main`.
+pub async fn run(command: Command, settings: &Settings) -> anyhow::Result<()> {
+    match command {
+        Command::Serve => unreachable!("serve is handled by main"),
+        Command::Migrate(MigrateCommand::Check { scratch_database }) => {
+            schema_check::run(&settings.database, scratch_database).await
+        }
+        Command::Migrate(command) => {
+            // Connect without migrating so `down` and `status` see the real state
+            let db = database::connect(&settings.database).await?;
+            migrate::run(&db, command).await
+        }
+        command => {
+            let db = database::initialize_database(&settings.database).await?;
+            match command {
+                Command::Project(command) => project::run_project(&db, command).await,
+                Command::Section(command) => project::run_section(&db, command).await,
+                Command::Seed => seed::run(&db).await,
+                Command::Dump { file } => dump::dump(&db, &file).await,
+                Command::Restore { file, replace } => dump::restore(&db, &file, replace).await,
+                Command::PurgeActivity(args) => purge::run(&db, args).await,
+                Command::Serve | Command::Migrate(_) => unreachable!(),
+            }
+        }
+    }
+}
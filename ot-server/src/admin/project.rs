@@ -0,0 +1,113 @@
+use anyhow::Context;
+use sea_orm::DatabaseConnection;
+use validator::Validate;
+
+use crate::{
+    config::cli::{ProjectCommand, SectionCommand},
+    dtos::{
+        project_dto::{ProjectCreateDto, ProjectFilterParams, ProjectUpdateDto},
+        section_dto::{SectionCreateDto, SectionUpdateDto},
+    },
+    services::{project_service::ProjectService, section_service::SectionService},
+};
+
+pub async fn run_project(db: &DatabaseConnection, command: ProjectCommand) -> anyhow::Result<()> {
+    match command {
+        ProjectCommand::List { archived } => {
+            let filter = ProjectFilterParams {
+                archived: Some(archived),
+                ..Default::default()
+            };
+            for project in ProjectService::get_projects(db, filter).await? {
+                println!(
+                    "{}  {}  (v{}, created {})",
+                    project.id,
+                    project.name,
+                    project.version,
+                    project.created_at.format("%Y-%m-%d %H:%M")
+                );
+            }
+        }
+        ProjectCommand::Create { name } => {
+            let data = ProjectCreateDto {
+                name,
+                ..Default::default()
+            };
+            data.validate()?;
+            let project = ProjectService::create_project(db, data).await?;
+            println!("Created project {} ({})", project.name, project.id);
+        }
+        ProjectCommand::Rename { id, name } => {
+            let current = ProjectService::get_project_by_id(db, id)
+                .await?
+                .with_context(|| format!("project {} not found", id))?;
+            let data = ProjectUpdateDto {
+                name,
+                version: current.version,
+                client_id: None,
+                billable: None,
+                budget_minutes: None,
+                budget_amount: None,
+                budget_currency: None,
+            };
+            data.validate()?;
+            let project = ProjectService::update_project(db, id, data).await?;
+            println!("Renamed project {} to {}", project.id, project.name);
+        }
+        ProjectCommand::Delete { id } => {
+            ProjectService::delete_project(db, id).await?;
+            println!("Deleted project {}", id);
+        }
+        ProjectCommand::Archive { id } => {
+            let project = ProjectService::archive_project(db, id).await?;
+            println!("Archived project {} ({})", project.name, project.id);
+        }
+        ProjectCommand::Unarchive { id } => {
+            let project = ProjectService::unarchive_project(db, id).await?;
+            println!("Unarchived project {} ({})", project.name, project.id);
+        }
+    }
+    Ok(())
+}
+
+pub async fn run_section(db: &DatabaseConnection, command: SectionCommand) -> anyhow::Result<()> {
+    match command {
+        SectionCommand::List { project } => {
+            let sections = match project {
+                Some(project_id) => {
+                    SectionService::get_sections_by_project_id(db, project_id).await?
+                }
+                None => SectionService::get_sections(db).await?,
+            };
+            for section in sections {
+                println!(
+                    "{}  {}  (project {})",
+                    section.id, section.name, section.project_id
+                );
+            }
+        }
+        SectionCommand::Create { project_id, name } => {
+            ProjectService::get_project_by_id(db, project_id)
+                .await?
+                .with_context(|| format!("project {} not found", project_id))?;
+            let section =
+                SectionService::create_section(db, SectionCreateDto { name, project_id }).await?;
+            println!("Created section {} ({})", section.name, section.id);
+        }
+        SectionCommand::Rename { id, name } => {
+            SectionService::get_section_by_id(db, id)
+                .await?
+                .with_context(|| format!("section {} not found", id))?;
+            let section = SectionService::update_section(db, id, SectionUpdateDto { name }).await?;
+            println!("Renamed section {} to {}", section.id, section.name);
+        }
+        SectionCommand::Delete { id } => {
+            let deleted = SectionService::delete_section(db, id).await?;
+            if deleted == 0 {
+                anyhow::bail!("section {} not found", id);
+            }
+            println!("Deleted section {}", id);
+        }
+    }
+    Ok(())
+}
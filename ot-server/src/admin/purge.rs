@@ -0,0 +1,33 @@
+use chrono::{Duration, Utc};
+use sea_orm::{DatabaseConnection, TransactionTrait};
+
+use crate::{
+    config::cli::PurgeArgs,
+    repository::activity_repository::{ActivityMutation, ActivityQuery},
+};
+
+pub async fn run(db: &DatabaseConnection, args: PurgeArgs) -> anyhow::Result<()> {
+    let cutoff = (Utc::now() - Duration::days(i64::from(args.older_than_days))).fixed_offset();
+
+    if args.dry_run {
+        let (activity, window_activity) = ActivityQuery::count_before(db, cutoff).await?;
+        println!(
+            "Would delete {} activity and {} window activity row(s) older than {}",
+            activity,
+            window_activity,
+            cutoff.to_rfc3339()
+        );
+        return Ok(());
+    }
+
+    let txn = db.begin().await?;
+    let (activity, window_activity) = ActivityMutation::purge_before(&txn, cutoff).await?;
+    txn.commit().await?;
+    println!(
+        "Deleted {} activity and {} window activity row(s) older than {}",
+        activity,
+        window_activity,
+        cutoff.to_rfc3339()
+    );
+    Ok(())
+}
@@ -0,0 +1,196 @@
+use chrono::{Duration, DurationRound, Utc};
+use sea_orm::DatabaseConnection;
+
+use crate::{
+    dtos::{
+        activity_dto::ActivityDataCreateDto, project_dto::ProjectCreateDto,
+        section_dto::SectionCreateDto, sub_task_dto::SubTaskCreateDto, task_dto::TaskCreateDto,
+    },
+    repository::{activity_repository::ActivityMutation, project_repository::ProjectQuery},
+    services::{
+        project_service::ProjectService, section_service::SectionService,
+        sub_task_service::SubTaskService, task_service::TaskService,
+    },
+};
+
+/// Project → section → task → sub tasks.
+type SeedProject = (
+    &'static str,
+    &'static [(
+        &'static str,
+        &'static [(&'static str, &'static [&'static str])],
+    )],
+);
+
+const PROJECTS: &[SeedProject] = &[
+    (
+        "Website Redesign",
+        &[
+            (
+                "Discovery",
+                &[
+                    ("Stakeholder interviews", &["Marketing", "Support", "Sales"]),
+                    ("Audit current analytics", &[]),
+                ],
+            ),
+            (
+                "Design",
+                &[
+                    (
+                        "Wireframe landing page",
+                        &["Desktop layout", "Mobile layout"],
+                    ),
+                    ("Pick typography and palette", &[]),
+                ],
+            ),
+            (
+                "Build",
+                &[
+                    (
+                        "Set up component library",
+                        &["Buttons", "Forms", "Navigation"],
+                    ),
+                    ("Migrate blog content", &[]),
+                ],
+            ),
+        ],
+    ),
+    (
+        "Mobile App",
+        &[
+            (
+                "Backlog",
+                &[
+                    (
+                        "Offline mode",
+                        &["Cache API responses", "Conflict resolution"],
+                    ),
+                    ("Push notifications", &[]),
+                ],
+            ),
+            (
+                "In Progress",
+                &[("Login with SSO", &["OAuth callback", "Token refresh"])],
+            ),
+            ("Done", &[("Onboarding screens", &[])]),
+        ],
+    ),
+    (
+        "Internal Tools",
+        &[(
+            "Maintenance",
+            &[
+                ("Rotate database credentials", &[]),
+                ("Upgrade CI runners", &["Linux", "macOS"]),
+            ],
+        )],
+    ),
+];
+
+/// Days of activity generated, ending yesterday.
+const ACTIVITY_DAYS: i64 = 5;
+/// Ten-minute tracking intervals per working day (09:00–13:00).
+const INTERVALS_PER_DAY: i64 = 24;
+
+pub async fn run(db: &DatabaseConnection) -> anyhow::Result<()> {
+    if !ProjectQuery::get_all_projects(db).await?.is_empty() {
+        anyhow::bail!(
+            "database already has projects; delete them or use `restore --replace` before seeding"
+        );
+    }
+
+    let (mut sections, mut sub_tasks) = (0, 0);
+    let mut task_ids = Vec::new();
+    for (project_name, project_sections) in PROJECTS {
+        let project = ProjectService::create_project(
+            db,
+            ProjectCreateDto {
+                name: project_name.to_string(),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        for (section_name, section_tasks) in *project_sections {
+            let section = SectionService::create_section(
+                db,
+                SectionCreateDto {
+                    name: section_name.to_string(),
+                    project_id: project.id,
+                },
+            )
+            .await?;
+            sections += 1;
+
+            for (task_name, task_sub_tasks) in *section_tasks {
+                let task = TaskService::create_task(
+                    db,
+                    TaskCreateDto {
+                        name: task_name.to_string(),
+                        section_id: section.id,
+                        ..Default::default()
+                    },
+                    section.id,
+                )
+                .await?;
+                task_ids.push(task.id);
+
+                for sub_task_name in *task_sub_tasks {
+                    SubTaskService::create_sub_task_for_task_and_section(
+                        db,
+                        SubTaskCreateDto {
+                            name: sub_task_name.to_string(),
+                            section_id: section.id,
+                            task_id: Some(task.id),
+                            ..Default::default()
+                        },
+                        section.id,
+                        Some(task.id),
+                    )
+                    .await?;
+                    sub_tasks += 1;
+                }
+            }
+        }
+    }
+
+    let mut activity = 0;
+    let today = Utc::now().duration_trunc(Duration::days(1))?;
+    for day in 1..=ACTIVITY_DAYS {
+        let day_start = today - Duration::days(day) + Duration::hours(9);
+        for interval in 0..INTERVALS_PER_DAY {
+            let start_time = day_start + Duration::minutes(10 * interval);
+            // Deterministic but uneven levels so charts have some shape
+            let keyboard = ((day * 17 + interval * 29) % 70 + 20) as f32;
+            let mouse = ((day * 11 + interval * 37) % 60 + 30) as f32;
+            ActivityMutation::create_activity_data(
+                db,
+                ActivityDataCreateDto {
+                    id: None,
+                    screenshots: vec![],
+                    keyboard_activity_percent: keyboard,
+                    mouse_activity_percent: mouse,
+                    total_percent: (keyboard + mouse) / 2.0,
+                    track_interval: 600,
+                    start_time: start_time.fixed_offset(),
+                    end_time: (start_time + Duration::minutes(10)).fixed_offset(),
+                    // An hour on each task in turn
+                    task_id: Some(task_ids[((day * 4 + interval / 6) as usize) % task_ids.len()]),
+                    person: None,
+                },
+            )
+            .await?;
+            activity += 1;
+        }
+    }
+
+    println!(
+        "Seeded {} project(s), {} section(s), {} task(s), {} sub task(s) and {} activity row(s)",
+        PROJECTS.len(),
+        sections,
+        task_ids.len(),
+        sub_tasks,
+        activity
+    );
+    Ok(())
+}
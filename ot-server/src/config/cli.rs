@@ -0,0 +1,241 @@
+use std::{net::IpAddr, path::PathBuf};
+
+use clap::{Args, Parser, Subcommand};
+use sea_orm::prelude::Uuid;
+
+use super::{
+    env::apply_tls,
+    settings::{LogFormat, Settings},
+};
+
+/// Command line flags; the highest-precedence configuration layer.
+#[derive(Debug, Parser)]
+#[command(name = "ot-server", version, about = "One Track API server")]
+pub struct Cli {
+    /// Path to a TOML config file (defaults to ./ot-server.toml when present)
+    #[arg(short, long, global = true, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Address to bind the HTTP listener to
+    #[arg(long, global = true, value_name = "ADDR")]
+    pub bind_address: Option<IpAddr>,
+
+    /// Port to listen on
+    #[arg(short, long, global = true)]
+    pub port: Option<u16>,
+
+    /// PEM certificate chain; enables TLS together with --tls-key
+    #[arg(long, global = true, value_name = "FILE")]
+    pub tls_cert: Option<PathBuf>,
+
+    /// PEM private key; enables TLS together with --tls-cert
+    #[arg(long, global = true, value_name = "FILE")]
+    pub tls_key: Option<PathBuf>,
+
+    /// Database server URL, without the database name
+    #[arg(long, global = true, value_name = "URL")]
+    pub database_url: Option<String>,
+
+    /// Database name
+    #[arg(long, global = true, value_name = "NAME")]
+    pub database: Option<String>,
+
+    /// Maximum number of pooled database connections
+    #[arg(long, global = true, value_name = "N")]
+    pub db_max_connections: Option<u32>,
+
+    /// Minimum number of idle database connections kept open
+    #[arg(long, global = true, value_name = "N")]
+    pub db_min_connections: Option<u32>,
+
+    /// Seconds to wait for a new database connection to open
+    #[arg(long, global = true, value_name = "SECS")]
+    pub db_connect_timeout_secs: Option<u64>,
+
+    /// Seconds to wait for a free connection from the pool
+    #[arg(long, global = true, value_name = "SECS")]
+    pub db_acquire_timeout_secs: Option<u64>,
+
+    /// Seconds an idle database connection is kept before it is closed
+    #[arg(long, global = true, value_name = "SECS")]
+    pub db_idle_timeout_secs: Option<u64>,
+
+    /// Seconds after which a database connection is replaced
+    #[arg(long, global = true, value_name = "SECS")]
+    pub db_max_lifetime_secs: Option<u64>,
+
+    /// Log filter directive, e.g. `info` or `ot_server=debug,sea_orm=warn`
+    #[arg(long, global = true, value_name = "FILTER")]
+    pub log_level: Option<String>,
+
+    /// Log output format
+    #[arg(long, global = true, value_enum)]
+    pub log_format: Option<LogFormat>,
+
+    /// Allowed CORS origin; repeat for several, `*` allows any
+    #[arg(long = "cors-origin", global = true, value_name = "ORIGIN")]
+    pub cors_origins: Vec<String>,
+
+    /// Root directory for server-side files (screenshots, exports)
+    #[arg(long, global = true, value_name = "DIR")]
+    pub storage_dir: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Operator commands; without one the binary runs the API server.
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Run the API server (the default)
+    Serve,
+    /// Apply, roll back or inspect schema migrations
+    #[command(subcommand)]
+    Migrate(MigrateCommand),
+    /// Manage projects
+    #[command(subcommand)]
+    Project(ProjectCommand),
+    /// Manage sections
+    #[command(subcommand)]
+    Section(SectionCommand),
+    /// Fill the database with demo projects, tasks and activity
+    Seed,
+    /// Write the database contents to a JSON file
+    Dump {
+        /// Output file; `-` writes to stdout
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+    },
+    /// Load a JSON dump produced by `dump`
+    Restore {
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+        /// Delete existing data before restoring
+        #[arg(long)]
+        replace: bool,
+    },
+    /// Delete activity and window activity older than a cutoff
+    PurgeActivity(PurgeArgs),
+}
+
+#[derive(Debug, Subcommand)]
+pub enum MigrateCommand {
+    /// Apply pending migrations
+    Up {
+        /// Number of migrations to apply; all when omitted
+        #[arg(short, long)]
+        steps: Option<u32>,
+    },
+    /// Roll back applied migrations
+    Down {
+        /// Number of migrations to roll back
+        #[arg(short, long, default_value_t = 1)]
+        steps: u32,
+    },
+    /// Show applied and pending migrations
+    Status,
+    /// Run all migrations up, down and up again on a scratch database and
+    /// compare the resulting schema with the entities
+    Check {
+        /// Scratch database name; defaults to `<database>_migrate_check`.
+        /// It is dropped before and after the check.
+        #[arg(long, value_name = "NAME")]
+        scratch_database: Option<String>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ProjectCommand {
+    /// List projects
+    List {
+        /// List archived projects instead
+        #[arg(long)]
+        archived: bool,
+    },
+    /// Create a project
+    Create { name: String },
+    /// Rename a project
+    Rename { id: Uuid, name: String },
+    /// Delete a project with its sections and tasks
+    Delete { id: Uuid },
+    /// Hide a project from listings and make it read-only
+    Archive { id: Uuid },
+    /// Make an archived project active again
+    Unarchive { id: Uuid },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum SectionCommand {
+    /// List sections, optionally for one project
+    List {
+        #[arg(long, value_name = "ID")]
+        project: Option<Uuid>,
+    },
+    /// Create a section in a project
+    Create { project_id: Uuid, name: String },
+    /// Rename a section
+    Rename { id: Uuid, name: String },
+    /// Delete a section with its tasks
+    Delete { id: Uuid },
+}
+
+#[derive(Debug, Args)]
+pub struct PurgeArgs {
+    /// Delete rows older than this many days
+    #[arg(long, value_name = "DAYS")]
+    pub older_than_days: u32,
+    /// Only report how many rows would be deleted
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+impl Cli {
+    /// Apply the CLI layer on top of `settings`.
+    pub fn apply(&self, settings: &mut Settings) {
+        if let Some(bind_address) = self.bind_address {
+            settings.server.bind_address = bind_address;
+        }
+        if let Some(port) = self.port {
+            settings.server.port = port;
+        }
+        apply_tls(settings, self.tls_cert.clone(), self.tls_key.clone());
+        if let Some(url) = &self.database_url {
+            settings.database.url = url.clone();
+        }
+        if let Some(name) = &self.database {
+            settings.database.name = name.clone();
+        }
+        if let Some(max) = self.db_max_connections {
+            settings.database.max_connections = max;
+        }
+        if let Some(min) = self.db_min_connections {
+            settings.database.min_connections = min;
+        }
+        if let Some(secs) = self.db_connect_timeout_secs {
+            settings.database.connect_timeout_secs = secs;
+        }
+        if let Some(secs) = self.db_acquire_timeout_secs {
+            settings.database.acquire_timeout_secs = secs;
+        }
+        if let Some(secs) = self.db_idle_timeout_secs {
+            settings.database.idle_timeout_secs = secs;
+        }
+        if let Some(secs) = self.db_max_lifetime_secs {
+            settings.database.max_lifetime_secs = secs;
+        }
+        if let Some(level) = &self.log_level {
+            settings.log.level = level.clone();
+        }
+        if let Some(format) = self.log_format {
+            settings.log.format = format;
+        }
+        if !self.cors_origins.is_empty() {
+            settings.cors.allowed_origins = self.cors_origins.clone();
+        }
+        if let Some(dir) = &self.storage_dir {
+            settings.storage.root = dir.clone();
+            settings.storage.screenshots = dir.join("screenshots");
+            settings.storage.exports = dir.join("exports");
+        }
+    }
+}
@@ -3,22 +3,53 @@ use sea_orm::{
     Statement,
 };
 use sea_orm_migration::MigratorTrait;
-use std::time::Duration;
 use tracing::info;
 
-use super::env::EnvironmentVariables;
+use super::settings::DatabaseSettings;
 use crate::migrator::Migrator;
 
-pub async fn initialize_database(env: &EnvironmentVariables) -> Result<DatabaseConnection, DbErr> {
-    let mut opt = ConnectOptions::new(env.database_url.clone());
-    opt.max_connections(100)
-        .min_connections(5)
-        .connect_timeout(Duration::from_secs(8))
-        .idle_timeout(Duration::from_secs(8))
-        .max_lifetime(Duration::from_secs(8))
-        .sqlx_logging(true);
+fn connect_options(url: String, settings: &DatabaseSettings) -> ConnectOptions {
+    let mut opt = ConnectOptions::new(url);
+    opt.max_connections(settings.max_connections)
+        .min_connections(settings.min_connections)
+        .connect_timeout(settings.connect_timeout())
+        .acquire_timeout(settings.acquire_timeout())
+        .idle_timeout(settings.idle_timeout())
+        .max_lifetime(settings.max_lifetime())
+        .sqlx_logging(settings.sqlx_logging);
+    opt
+}
+
+/// Short-lived connection to the server itself, used to create or drop the
+/// application database.
+async fn bootstrap(settings: &DatabaseSettings) -> Result<DatabaseConnection, DbErr> {
+    let mut bootstrap = ConnectOptions::new(settings.url.clone());
+    bootstrap
+        .max_connections(1)
+        .min_connections(0)
+        .connect_timeout(settings.connect_timeout())
+        .sqlx_logging(settings.sqlx_logging);
+    Database::connect(bootstrap).await
+}
 
-    let db = Database::connect(opt).await?;
+/// Connect to the application database, creating it if needed, and bring the
+/// schema up to date.
+pub async fn initialize_database(settings: &DatabaseSettings) -> Result<DatabaseConnection, DbErr> {
+    let db = connect(settings).await?;
+
+    if db.get_database_backend() == DatabaseBackend::Postgres {
+        // Run migrations
+        info!("[Migration] Starting fresh migration");
+        Migrator::up(&db, None).await?;
+    }
+
+    Ok(db)
+}
+
+/// Connect to the application database, creating it if needed, without
+/// touching the schema.
+pub async fn connect(settings: &DatabaseSettings) -> Result<DatabaseConnection, DbErr> {
+    let db = bootstrap(settings).await?;
 
     if db.get_database_backend() == DatabaseBackend::Postgres {
         // Check if database exists
@@ -27,7 +58,7 @@ pub async fn initialize_database(env: &EnvironmentVariables) -> Result<DatabaseC
                 db.get_database_backend(),
                 format!(
                     "SELECT 1 FROM pg_database WHERE datname = '{}'",
-                    env.database
+                    settings.name
                 ),
             ))
             .await?;
@@ -36,23 +67,34 @@ pub async fn initialize_database(env: &EnvironmentVariables) -> Result<DatabaseC
         if result.rows_affected() == 0 {
             db.execute(Statement::from_string(
                 db.get_database_backend(),
-                format!("CREATE DATABASE \"{}\"", env.database),
+                format!("CREATE DATABASE \"{}\"", settings.name),
             ))
             .await?;
         }
+        db.close().await?;
 
         // Connect to the specific database
-        let url = format!("{}/{}", env.database_url, env.database);
-        info!("[Database] Connecting to database '{}'", env.database);
-        let db = Database::connect(&url).await?;
-
-        // Run migrations
-        info!("[Migration] Starting fresh migration");
-        Migrator::up(&db, None).await?;
-
-        Ok(db)
+        let url = format!("{}/{}", settings.url, settings.name);
+        info!(
+            "[Database] Connecting to database '{}' (pool {}..{})",
+            settings.name, settings.min_connections, settings.max_connections
+        );
+        Database::connect(connect_options(url, settings)).await
     } else {
-        // For other database backends, just return the initial connection
-        Ok(db)
+        // For other database backends, reconnect with the configured pool
+        db.close().await?;
+        Database::connect(connect_options(settings.url.clone(), settings)).await
     }
 }
+
+/// Drop the configured database, disconnecting any remaining sessions.
+/// Postgres only; used for scratch databases.
+pub async fn drop_database(settings: &DatabaseSettings) -> Result<(), DbErr> {
+    let db = bootstrap(settings).await?;
+    db.execute(Statement::from_string(
+        db.get_database_backend(),
+        format!("DROP DATABASE IF EXISTS \"{}\" WITH (FORCE)", settings.name),
+    ))
+    .await?;
+    db.close().await
+}
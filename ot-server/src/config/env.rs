@@ -1,12 +1,33 @@
-use std::borrow::Cow;
+use std::{net::IpAddr, path::PathBuf, str::FromStr};
 
-use anyhow::bail;
+use anyhow::{anyhow, Context};
 
-#[derive(Clone, Debug)]
+use super::settings::{LogFormat, Settings, TlsSettings};
+
+/// Configuration overrides read from the process environment (and `.env`).
+///
+/// Every variable is optional here; required values are checked once all
+/// layers are merged in [`Settings::validate`].
+#[derive(Clone, Debug, Default)]
 pub struct EnvironmentVariables {
-    pub database_url: Cow<'static, str>,
-    pub database: Cow<'static, str>,
-    pub port: u16,
+    pub config_file: Option<PathBuf>,
+    pub bind_address: Option<IpAddr>,
+    pub port: Option<u16>,
+    pub tls_cert_path: Option<PathBuf>,
+    pub tls_key_path: Option<PathBuf>,
+    pub admin_token: Option<String>,
+    pub database_url: Option<String>,
+    pub database: Option<String>,
+    pub db_max_connections: Option<u32>,
+    pub db_min_connections: Option<u32>,
+    pub db_connect_timeout_secs: Option<u64>,
+    pub db_acquire_timeout_secs: Option<u64>,
+    pub db_idle_timeout_secs: Option<u64>,
+    pub db_max_lifetime_secs: Option<u64>,
+    pub log_level: Option<String>,
+    pub log_format: Option<LogFormat>,
+    pub cors_allowed_origins: Option<Vec<String>>,
+    pub storage_dir: Option<PathBuf>,
 }
 
 impl EnvironmentVariables {
@@ -14,18 +35,132 @@ impl EnvironmentVariables {
         dotenv::dotenv().ok();
 
         Ok(Self {
-            database_url: match dotenv::var("DATABASE_URL") {
-                Ok(url) => url.into(),
-                Err(err) => bail!("missing DATABASE_URL: {err}"),
-            },
-            port: match dotenv::var("PORT") {
-                Ok(port) => port.parse()?,
-                _ => 8000,
-            },
-            database: match dotenv::var("DATABASE") {
-                Ok(name) => name.into(),
-                Err(err) => bail!("missing DATABASE: {err}"),
+            config_file: var("OT_CONFIG").map(PathBuf::from),
+            bind_address: parsed("BIND_ADDRESS")?,
+            port: parsed("PORT")?,
+            tls_cert_path: var("TLS_CERT_PATH").map(PathBuf::from),
+            tls_key_path: var("TLS_KEY_PATH").map(PathBuf::from),
+            admin_token: var("ADMIN_TOKEN"),
+            database_url: var("DATABASE_URL"),
+            database: var("DATABASE"),
+            db_max_connections: parsed("DB_MAX_CONNECTIONS")?,
+            db_min_connections: parsed("DB_MIN_CONNECTIONS")?,
+            db_connect_timeout_secs: parsed("DB_CONNECT_TIMEOUT_SECS")?,
+            db_acquire_timeout_secs: parsed("DB_ACQUIRE_TIMEOUT_SECS")?,
+            db_idle_timeout_secs: parsed("DB_IDLE_TIMEOUT_SECS")?,
+            db_max_lifetime_secs: parsed("DB_MAX_LIFETIME_SECS")?,
+            log_level: var("LOG_LEVEL").or_else(|| var("RUST_LOG")),
+            log_format: match var("LOG_FORMAT") {
+                Some(format) => Some(
+                    <LogFormat as clap::ValueEnum>::from_str(&format, true).map_err(|_| {
+                        anyhow!("invalid LOG_FORMAT `{format}`: expected pretty or json")
+                    })?,
+                ),
+                None => None,
             },
+            cors_allowed_origins: var("CORS_ALLOWED_ORIGINS").map(|origins| {
+                origins
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|o| !o.is_empty())
+                    .map(String::from)
+                    .collect()
+            }),
+            storage_dir: var("STORAGE_DIR").map(PathBuf::from),
         })
     }
+
+    /// Apply the environment layer on top of `settings`.
+    pub fn apply(&self, settings: &mut Settings) {
+        if let Some(bind_address) = self.bind_address {
+            settings.server.bind_address = bind_address;
+        }
+        if let Some(port) = self.port {
+            settings.server.port = port;
+        }
+        apply_tls(
+            settings,
+            self.tls_cert_path.clone(),
+            self.tls_key_path.clone(),
+        );
+        if let Some(token) = &self.admin_token {
+            settings.server.admin_token = Some(token.clone());
+        }
+        if let Some(url) = &self.database_url {
+            settings.database.url = url.clone();
+        }
+        if let Some(name) = &self.database {
+            settings.database.name = name.clone();
+        }
+        if let Some(max) = self.db_max_connections {
+            settings.database.max_connections = max;
+        }
+        if let Some(min) = self.db_min_connections {
+            settings.database.min_connections = min;
+        }
+        if let Some(secs) = self.db_connect_timeout_secs {
+            settings.database.connect_timeout_secs = secs;
+        }
+        if let Some(secs) = self.db_acquire_timeout_secs {
+            settings.database.acquire_timeout_secs = secs;
+        }
+        if let Some(secs) = self.db_idle_timeout_secs {
+            settings.database.idle_timeout_secs = secs;
+        }
+        if let Some(secs) = self.db_max_lifetime_secs {
+            settings.database.max_lifetime_secs = secs;
+        }
+        if let Some(level) = &self.log_level {
+            settings.log.level = level.clone();
+        }
+        if let Some(format) = self.log_format {
+            settings.log.format = format;
+        }
+        if let Some(origins) = &self.cors_allowed_origins {
+            settings.cors.allowed_origins = origins.clone();
+        }
+        if let Some(dir) = &self.storage_dir {
+            settings.storage.root = dir.clone();
+            settings.storage.screenshots = dir.join("screenshots");
+            settings.storage.exports = dir.join("exports");
+        }
+    }
+}
+
+/// Merge a cert/key override into the TLS section. A lone cert or key keeps
+/// the other half from the lower layer so validation can point at what's missing.
+pub(super) fn apply_tls(settings: &mut Settings, cert: Option<PathBuf>, key: Option<PathBuf>) {
+    if cert.is_none() && key.is_none() {
+        return;
+    }
+    let current = settings.server.tls.take();
+    settings.server.tls = Some(TlsSettings {
+        cert_path: cert
+            .or_else(|| current.as_ref().map(|tls| tls.cert_path.clone()))
+            .unwrap_or_default(),
+        key_path: key
+            .or_else(|| current.as_ref().map(|tls| tls.key_path.clone()))
+            .unwrap_or_default(),
+    });
+}
+
+fn var(name: &str) -> Option<String> {
+    dotenv::var(name)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+}
+
+fn parsed<T>(name: &str) -> anyhow::Result<Option<T>>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    var(name)
+        .map(|value| {
+            value
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid {name} `{value}`"))
+        })
+        .transpose()
 }
@@ -0,0 +1,21 @@
+use tracing_subscriber::{fmt::time::time, EnvFilter};
+
+use super::settings::{LogFormat, LogSettings};
+
+/// Install the global tracing subscriber according to the log settings.
+pub fn init(settings: &LogSettings) {
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::new(&settings.level))
+        .with_timer(time())
+        .with_thread_names(true)
+        .with_target(true)
+        .with_file(true)
+        .with_line_number(true)
+        .with_thread_ids(true)
+        .with_level(true);
+
+    match settings.format {
+        LogFormat::Pretty => builder.init(),
+        LogFormat::Json => builder.json().flatten_event(true).init(),
+    }
+}
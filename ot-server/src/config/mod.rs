@@ -1,2 +1,5 @@
+pub mod cli;
 pub mod database;
 pub mod env;
+pub mod logging;
+pub mod settings;
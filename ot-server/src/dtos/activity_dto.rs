@@ -0,0 +1,68 @@
+use chrono::{DateTime, FixedOffset};
+use sea_orm::prelude::Uuid;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct ActivityDataCreateDto {
+    /// Generated by the desktop, so an upload retried after a lost
+    /// response is stored once
+    #[serde(default)]
+    pub id: Option<Uuid>,
+    pub screenshots: Vec<String>,
+    #[validate(range(min = 0.0, max = 100.0))]
+    pub keyboard_activity_percent: f32,
+    #[validate(range(min = 0.0, max = 100.0))]
+    pub mouse_activity_percent: f32,
+    #[validate(range(min = 0.0, max = 100.0))]
+    pub total_percent: f32,
+    pub track_interval: i32,
+    pub start_time: DateTime<FixedOffset>,
+    pub end_time: DateTime<FixedOffset>,
+    /// Task the time was tracked on
+    pub task_id: Option<Uuid>,
+    /// Who tracked the time, for person-specific billing rates
+    #[validate(length(min = 1, max = 100))]
+    pub person: Option<String>,
+}
+
+/// One window kept in focus, as the desktop saw it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Validate)]
+pub struct WindowSpanDto {
+    /// Application owning the window, e.g. its class on X11
+    #[validate(length(max = 200))]
+    pub app_name: String,
+    #[validate(length(max = 1000))]
+    pub title: String,
+    pub pid: Option<u32>,
+    pub start_time: DateTime<FixedOffset>,
+    pub end_time: DateTime<FixedOffset>,
+    /// Time the window had focus
+    #[validate(range(min = 0))]
+    pub focus_seconds: i32,
+}
+
+/// The windows in focus over one tracked interval; stored as
+/// `window_activity_data.active_window_data`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Validate)]
+pub struct WindowActivityDto {
+    /// Task the time was tracked on
+    pub task_id: Option<Uuid>,
+    pub start_time: DateTime<FixedOffset>,
+    pub end_time: DateTime<FixedOffset>,
+    /// In order of focus
+    #[validate(nested)]
+    pub windows: Vec<WindowSpanDto>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct WindowActivityDataCreateDto {
+    /// Generated by the desktop, so an upload retried after a lost
+    /// response is stored once
+    pub id: Uuid,
+    /// Screenshots of the active window
+    #[serde(default)]
+    pub active_window_ss: Vec<String>,
+    #[validate(nested)]
+    pub active_window_data: WindowActivityDto,
+}
@@ -0,0 +1,63 @@
+use chrono::{DateTime, FixedOffset};
+use sea_orm::prelude::Uuid;
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+/// Hourly rate for tracked time. Which time it applies to depends on the ids
+/// set: a person on a project, a project, a client, or neither for the
+/// default rate. The most specific rate wins, in that order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BillingRateDto {
+    pub id: Uuid,
+    pub client_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+    pub person: Option<String>,
+    /// Per hour, in the currency's minor unit (e.g. cents)
+    pub hourly_rate: i64,
+    /// ISO 4217 code such as `EUR`
+    pub currency: String,
+    pub created_at: DateTime<FixedOffset>,
+    pub updated_at: DateTime<FixedOffset>,
+}
+
+/// `client_id` and `project_id` cannot both be set, and `person` needs a
+/// `project_id`.
+#[derive(Debug, Deserialize, Validate)]
+pub struct BillingRateCreateDto {
+    pub client_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+    #[validate(length(min = 1, max = 100))]
+    pub person: Option<String>,
+    #[validate(range(min = 0))]
+    pub hourly_rate: i64,
+    #[validate(custom(function = "validate_currency"))]
+    pub currency: String,
+}
+
+/// What a rate applies to cannot change; omitted fields are left unchanged.
+#[derive(Debug, Deserialize, Validate)]
+pub struct BillingRateUpdateDto {
+    #[validate(range(min = 0))]
+    pub hourly_rate: Option<i64>,
+    #[validate(custom(function = "validate_currency"))]
+    pub currency: Option<String>,
+}
+
+/// Query string filters for `/api/rate/all`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct BillingRateFilterParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<Uuid>,
+}
+
+/// Currencies are upper-case ISO 4217 codes.
+pub fn validate_currency(currency: &str) -> Result<(), ValidationError> {
+    if currency.len() == 3 && currency.chars().all(|c| c.is_ascii_uppercase()) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("currency")
+            .with_message("Currency must be a three letter code such as EUR".into()))
+    }
+}
@@ -0,0 +1,94 @@
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use sea_orm::{prelude::Uuid, sea_query::StringLen, DeriveActiveEnum, EnumIter};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetAlertKind {
+    /// Tracked time against the project's time budget
+    #[sea_orm(string_value = "time")]
+    Time,
+    /// Priced billable time against the project's money budget
+    #[sea_orm(string_value = "money")]
+    Money,
+    /// Tracked time against a task's estimate
+    #[sea_orm(string_value = "estimate")]
+    Estimate,
+}
+
+/// How much of a budget is used and when, at the recent pace, it runs out.
+/// Time is in seconds; money in the currency's minor unit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetProgressDto {
+    pub budget: i64,
+    pub used: i64,
+    /// Negative once the budget is overrun
+    pub remaining: i64,
+    pub percent_used: f64,
+    /// Average use per day over the burn rate window
+    pub burn_rate_per_day: f64,
+    /// Day the budget runs out at the current burn rate; `null` when it is
+    /// already overrun or nothing was used recently
+    pub projected_overrun_on: Option<NaiveDate>,
+    /// Only set for money budgets
+    pub currency: Option<String>,
+}
+
+/// Tracked time on a task with an estimate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskEstimateDto {
+    pub task_id: Uuid,
+    pub name: String,
+    pub estimated_seconds: i64,
+    pub used_seconds: i64,
+    pub percent_used: f64,
+}
+
+/// Response of `/api/project/{id}/budget`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectBudgetDto {
+    pub project_id: Uuid,
+    /// `null` when the project has no time budget
+    pub time: Option<BudgetProgressDto>,
+    /// `null` when the project has no money budget
+    pub money: Option<BudgetProgressDto>,
+    /// Billable time that counts towards no money budget because no rate
+    /// applies or it is priced in another currency
+    pub unpriced_seconds: i64,
+    /// Sum of the estimates of the project's tasks
+    pub estimated_seconds: i64,
+    pub tasks: Vec<TaskEstimateDto>,
+}
+
+/// Raised once when use of a budget or estimate reaches a threshold.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetAlertDto {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub project_name: String,
+    pub task_id: Option<Uuid>,
+    pub task_name: Option<String>,
+    pub kind: BudgetAlertKind,
+    /// Percentage of the budget that was reached
+    pub threshold: i32,
+    /// Use and budget when the alert was raised, in the units of
+    /// [`BudgetProgressDto`]
+    pub used: i64,
+    pub budget: i64,
+    pub currency: Option<String>,
+    pub created_at: DateTime<FixedOffset>,
+    pub acknowledged_at: Option<DateTime<FixedOffset>>,
+}
+
+/// Query string filters for `/api/budget/alerts`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct BudgetAlertFilterParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<Uuid>,
+    /// Alerts raised after this time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<DateTime<FixedOffset>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub acknowledged: Option<bool>,
+}
@@ -0,0 +1,42 @@
+use chrono::{DateTime, FixedOffset};
+use sea_orm::prelude::Uuid;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::task_dto::double_option;
+
+/// Customer that projects are done for and billed to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClientDto {
+    pub id: Uuid,
+    pub name: String,
+    pub email: Option<String>,
+    pub created_at: DateTime<FixedOffset>,
+    pub updated_at: DateTime<FixedOffset>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ClientCreateDto {
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Client name must be between 1 and 100 characters"
+    ))]
+    pub name: String,
+    #[validate(email)]
+    pub email: Option<String>,
+}
+
+/// Omitted fields are left unchanged; `null` clears the email.
+#[derive(Debug, Deserialize, Validate)]
+pub struct ClientUpdateDto {
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Client name must be between 1 and 100 characters"
+    ))]
+    pub name: Option<String>,
+    #[serde(default, deserialize_with = "double_option")]
+    #[validate(email)]
+    pub email: Option<Option<String>>,
+}
@@ -0,0 +1,80 @@
+use chrono::{DateTime, FixedOffset};
+use sea_orm::prelude::Uuid;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentDto {
+    pub id: Uuid,
+    pub task_id: Option<Uuid>,
+    pub sub_task_id: Option<Uuid>,
+    pub author: String,
+    /// Markdown
+    pub body: String,
+    /// Names mentioned as `@name` in the body
+    pub mentions: Vec<String>,
+    /// Number of edits; earlier bodies are listed by the history route
+    pub version: i32,
+    pub created_at: DateTime<FixedOffset>,
+    pub updated_at: DateTime<FixedOffset>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CommentCreateDto {
+    /// Exactly one of `task_id` and `sub_task_id` must be set
+    pub task_id: Option<Uuid>,
+    pub sub_task_id: Option<Uuid>,
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Author must be between 1 and 100 characters"
+    ))]
+    pub author: String,
+    #[validate(length(
+        min = 1,
+        max = 10000,
+        message = "Comment must be between 1 and 10000 characters"
+    ))]
+    pub body: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CommentUpdateDto {
+    #[validate(length(
+        min = 1,
+        max = 10000,
+        message = "Comment must be between 1 and 10000 characters"
+    ))]
+    pub body: String,
+    pub version: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentRevisionDto {
+    pub body: String,
+    /// Comment version this body belonged to
+    pub version: i32,
+    /// When it was replaced
+    pub created_at: DateTime<FixedOffset>,
+}
+
+/// A recorded change to a task or sub task.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskEventDto {
+    pub id: Uuid,
+    pub task_id: Option<Uuid>,
+    pub sub_task_id: Option<Uuid>,
+    /// `created`, `updated` or `moved`
+    pub kind: String,
+    /// `{ "field": { "from": .., "to": .. } }`
+    pub changes: serde_json::Value,
+    pub created_at: DateTime<FixedOffset>,
+}
+
+/// One entry of a task's activity feed, oldest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FeedItemDto {
+    Comment(CommentDto),
+    Change(TaskEventDto),
+}
@@ -0,0 +1,37 @@
+use chrono::{DateTime, FixedOffset};
+use sea_orm::prelude::Uuid;
+use serde::{Deserialize, Serialize};
+
+use super::task_dto::TaskDto;
+
+/// `blocking_task_id` has to be done before `blocked_task_id` can start.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskDependencyDto {
+    pub blocking_task_id: Uuid,
+    pub blocked_task_id: Uuid,
+    pub created_at: DateTime<FixedOffset>,
+}
+
+/// Both tasks must belong to the same project.
+#[derive(Debug, Deserialize)]
+pub struct TaskDependencyCreateDto {
+    pub blocking_task_id: Uuid,
+    pub blocked_task_id: Uuid,
+}
+
+/// The direct dependencies of one task.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TaskDependenciesDto {
+    /// Tasks that have to be done first
+    pub blocked_by: Vec<TaskDto>,
+    /// Tasks waiting on this one
+    pub blocks: Vec<TaskDto>,
+}
+
+/// Every task of a project that takes part in a dependency, and the
+/// dependencies between them.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DependencyGraphDto {
+    pub nodes: Vec<TaskDto>,
+    pub edges: Vec<TaskDependencyDto>,
+}
@@ -0,0 +1,122 @@
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use sea_orm::{prelude::Uuid, sea_query::StringLen, DeriveActiveEnum, EnumIter};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::{billing_dto::validate_currency, task_dto::double_option};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+#[serde(rename_all = "snake_case")]
+pub enum InvoiceStatus {
+    /// Lines and details can still be edited
+    #[sea_orm(string_value = "draft")]
+    Draft,
+    /// Numbered and issued; can no longer change
+    #[sea_orm(string_value = "finalized")]
+    Finalized,
+}
+
+/// Invoice to a client for billable time tracked over a period.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvoiceDto {
+    pub id: Uuid,
+    pub client_id: Uuid,
+    /// Sequential number, set when the invoice is finalized
+    pub number: Option<i32>,
+    pub status: InvoiceStatus,
+    pub currency: String,
+    pub period_start: DateTime<FixedOffset>,
+    pub period_end: DateTime<FixedOffset>,
+    pub issued_on: Option<NaiveDate>,
+    pub due_on: Option<NaiveDate>,
+    pub notes: Option<String>,
+    /// Sum of the line amounts, in the currency's minor unit
+    pub total: i64,
+    pub lines: Vec<InvoiceLineDto>,
+    pub created_at: DateTime<FixedOffset>,
+    pub updated_at: DateTime<FixedOffset>,
+}
+
+/// Time billed on an invoice. Lines drafted from tracked time keep the
+/// project and task it was tracked on; lines added by hand have neither.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvoiceLineDto {
+    pub id: Uuid,
+    pub invoice_id: Uuid,
+    pub project_id: Option<Uuid>,
+    pub task_id: Option<Uuid>,
+    pub description: String,
+    pub seconds: i64,
+    /// Per hour, in the currency's minor unit
+    pub hourly_rate: i64,
+    /// `seconds` at `hourly_rate`, rounded to the nearest minor unit
+    pub amount: i64,
+    pub position: i32,
+    pub created_at: DateTime<FixedOffset>,
+    pub updated_at: DateTime<FixedOffset>,
+}
+
+/// Draft an invoice for the client's billable time tracked in
+/// `[from, to)` that is not on an invoice yet.
+#[derive(Debug, Deserialize, Validate)]
+pub struct InvoiceCreateDto {
+    pub client_id: Uuid,
+    pub from: DateTime<FixedOffset>,
+    pub to: DateTime<FixedOffset>,
+    /// Only time priced in this currency is invoiced; required when the
+    /// client's time is priced in more than one
+    #[validate(custom(function = "validate_currency"))]
+    pub currency: Option<String>,
+    pub due_on: Option<NaiveDate>,
+    #[validate(length(max = 10000))]
+    pub notes: Option<String>,
+}
+
+/// Omitted fields are left unchanged; `null` clears them.
+#[derive(Debug, Deserialize, Validate)]
+pub struct InvoiceUpdateDto {
+    #[serde(default, deserialize_with = "double_option")]
+    pub due_on: Option<Option<NaiveDate>>,
+    #[serde(default, deserialize_with = "double_option")]
+    #[validate(length(max = 10000))]
+    pub notes: Option<Option<String>>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct InvoiceFinalizeDto {
+    /// Defaults to today
+    pub issued_on: Option<NaiveDate>,
+}
+
+/// A line added by hand, e.g. for work that was not tracked.
+#[derive(Debug, Deserialize, Validate)]
+pub struct InvoiceLineCreateDto {
+    #[validate(length(min = 1, max = 255))]
+    pub description: String,
+    #[validate(range(min = 0))]
+    pub seconds: i64,
+    #[validate(range(min = 0))]
+    pub hourly_rate: i64,
+}
+
+/// Omitted fields are left unchanged; the amount follows `seconds` and
+/// `hourly_rate`.
+#[derive(Debug, Deserialize, Validate)]
+pub struct InvoiceLineUpdateDto {
+    #[validate(length(min = 1, max = 255))]
+    pub description: Option<String>,
+    #[validate(range(min = 0))]
+    pub seconds: Option<i64>,
+    #[validate(range(min = 0))]
+    pub hourly_rate: Option<i64>,
+}
+
+/// Query string filters for `/api/invoice/all`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct InvoiceFilterParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<InvoiceStatus>,
+}
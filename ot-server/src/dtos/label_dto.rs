@@ -0,0 +1,105 @@
+use chrono::{DateTime, FixedOffset};
+use sea_orm::prelude::Uuid;
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LabelDto {
+    pub id: Uuid,
+    pub name: String,
+    /// `#rrggbb`
+    pub color: String,
+    pub created_at: DateTime<FixedOffset>,
+    pub updated_at: DateTime<FixedOffset>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct LabelCreateDto {
+    #[validate(custom(function = "validate_label_name"))]
+    pub name: String,
+    #[validate(custom(function = "validate_color"))]
+    pub color: String,
+}
+
+/// Omitted fields are left unchanged.
+#[derive(Debug, Deserialize, Validate)]
+pub struct LabelUpdateDto {
+    #[validate(custom(function = "validate_label_name"))]
+    pub name: Option<String>,
+    #[validate(custom(function = "validate_color"))]
+    pub color: Option<String>,
+}
+
+/// What a label is attached to or detached from; exactly one id must be set.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct LabelTargetDto {
+    pub project_id: Option<Uuid>,
+    pub task_id: Option<Uuid>,
+    pub sub_task_id: Option<Uuid>,
+}
+
+/// Names in a comma separated `label` query parameter; `None` when it is
+/// missing or blank. Items match when they carry any of the names.
+pub fn label_names(label: Option<&str>) -> Option<Vec<String>> {
+    let names = label?
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    (!names.is_empty()).then_some(names)
+}
+
+/// Label names are filtered on as a comma separated list, so they cannot
+/// contain commas.
+pub fn validate_label_name(name: &str) -> Result<(), ValidationError> {
+    let valid =
+        name.trim() == name && (1..=50).contains(&name.chars().count()) && !name.contains(',');
+    if valid {
+        Ok(())
+    } else {
+        Err(ValidationError::new("label_name").with_message(
+            "Label name must be 1 to 50 characters without commas or surrounding spaces".into(),
+        ))
+    }
+}
+
+pub fn validate_color(color: &str) -> Result<(), ValidationError> {
+    let valid = color.len() == 7
+        && color.starts_with('#')
+        && color[1..].chars().all(|c| c.is_ascii_hexdigit());
+    if valid {
+        Ok(())
+    } else {
+        Err(ValidationError::new("color")
+            .with_message("Color must be a hex code such as #1f6feb".into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{extract::Query, http::Uri};
+
+    use super::label_names;
+    use crate::dtos::time_dto::TimeFilterParams;
+
+    #[test]
+    fn label_names_skip_blank_entries() {
+        assert_eq!(
+            label_names(Some(" bugfix, ,meetings ")),
+            Some(vec!["bugfix".to_string(), "meetings".to_string()])
+        );
+        assert_eq!(label_names(Some(" , ")), None);
+        assert_eq!(label_names(None), None);
+    }
+
+    #[test]
+    fn time_filter_takes_label_names() {
+        let uri: Uri = "/api/time/entries?label=bugfix,meetings".parse().unwrap();
+        let Query(filter) = Query::<TimeFilterParams>::try_from_uri(&uri).unwrap();
+        assert_eq!(
+            label_names(filter.label.as_deref()),
+            Some(vec!["bugfix".to_string(), "meetings".to_string()])
+        );
+    }
+}
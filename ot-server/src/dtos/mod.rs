@@ -1,4 +1,17 @@
+pub mod activity_dto;
+pub mod billing_dto;
+pub mod budget_dto;
+pub mod client_dto;
+pub mod comment_dto;
+pub mod dependency_dto;
+pub mod invoice_dto;
+pub mod label_dto;
 pub mod project_dto;
+pub mod recurrence_dto;
+pub mod search_dto;
 pub mod section_dto;
 pub mod sub_task_dto;
 pub mod task_dto;
+pub mod time_dto;
+pub mod timer_dto;
+pub mod tracking_policy_dto;
@@ -4,16 +4,30 @@ use sea_orm::prelude::Uuid;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
-#[derive(Debug, Deserialize, Validate)]
+use super::{billing_dto::validate_currency, task_dto::double_option};
+
+#[derive(Debug, Default, Deserialize, Validate)]
 pub struct ProjectCreateDto {
     #[validate(length(
         min = 1,
-        max = 5,
+        max = 100,
         message = "Project name must be between 1 and 100 characters"
     ))]
     pub name: String,
+    pub client_id: Option<Uuid>,
+    /// Defaults to `true`
+    pub billable: Option<bool>,
+    #[validate(range(min = 1))]
+    pub budget_minutes: Option<i32>,
+    /// In the minor unit of `budget_currency`, which it needs
+    #[validate(range(min = 1))]
+    pub budget_amount: Option<i64>,
+    #[validate(custom(function = "validate_currency"))]
+    pub budget_currency: Option<String>,
 }
 
+/// Omitted fields other than `name` are left unchanged; `null` detaches the
+/// project from its client or removes a budget.
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 pub struct ProjectUpdateDto {
     #[validate(length(
@@ -23,6 +37,18 @@ pub struct ProjectUpdateDto {
     ))]
     pub name: String,
     pub version: i32,
+    #[serde(default, deserialize_with = "double_option")]
+    pub client_id: Option<Option<Uuid>>,
+    pub billable: Option<bool>,
+    #[serde(default, deserialize_with = "double_option")]
+    #[validate(range(min = 1))]
+    pub budget_minutes: Option<Option<i32>>,
+    #[serde(default, deserialize_with = "double_option")]
+    #[validate(range(min = 1))]
+    pub budget_amount: Option<Option<i64>>,
+    #[serde(default, deserialize_with = "double_option")]
+    #[validate(custom(function = "validate_currency"))]
+    pub budget_currency: Option<Option<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,4 +58,27 @@ pub struct ProjectDto {
     pub created_at: DateTime<FixedOffset>,
     pub updated_at: DateTime<FixedOffset>,
     pub version: i32,
+    pub client_id: Option<Uuid>,
+    /// Whether time tracked on the project's tasks is billed
+    pub billable: bool,
+    pub budget_minutes: Option<i32>,
+    /// In the currency's minor unit
+    pub budget_amount: Option<i64>,
+    pub budget_currency: Option<String>,
+    /// Set while the project is archived and read-only
+    pub archived_at: Option<DateTime<FixedOffset>>,
+}
+
+/// Query string filters for `/api/project/all`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ProjectFilterParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<Uuid>,
+    /// Comma separated label names, see
+    /// [`label_names`](super::label_dto::label_names)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// `true` lists only archived projects; they are left out otherwise
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived: Option<bool>,
 }
@@ -0,0 +1,49 @@
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use sea_orm::{prelude::Uuid, sea_query::StringLen, DeriveActiveEnum, EnumIter};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+#[serde(rename_all = "snake_case")]
+pub enum RecurrenceFrequency {
+    #[sea_orm(string_value = "daily")]
+    Daily,
+    #[sea_orm(string_value = "weekly")]
+    Weekly,
+    #[sea_orm(string_value = "monthly")]
+    Monthly,
+}
+
+/// How a task repeats. The task itself is the occurrence on `starts_on`;
+/// later ones are generated as copies of it in the same section.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TaskRecurrenceDto {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    /// The rule as an RRULE, e.g. `FREQ=WEEKLY;INTERVAL=2;COUNT=10`
+    pub rule: String,
+    pub frequency: RecurrenceFrequency,
+    pub interval: i32,
+    pub starts_on: NaiveDate,
+    /// Inclusive
+    pub until: Option<NaiveDate>,
+    /// Number of occurrences, the task itself included
+    pub count: Option<i32>,
+    /// Date of the next occurrence to generate; `None` once the series ended
+    pub next_occurrence: Option<NaiveDate>,
+    pub created_at: DateTime<FixedOffset>,
+    pub updated_at: DateTime<FixedOffset>,
+}
+
+/// Setting a rule restarts the series; dates that already have a generated
+/// task are not generated again.
+#[derive(Debug, Deserialize, Validate)]
+pub struct TaskRecurrenceSetDto {
+    /// RRULE subset: `FREQ` of `DAILY`, `WEEKLY` or `MONTHLY`, optional
+    /// `INTERVAL` and either `UNTIL` or `COUNT`
+    #[validate(length(min = 1, max = 200))]
+    pub rule: String,
+    /// Defaults to the task's due date, or today when it has none
+    pub starts_on: Option<NaiveDate>,
+}
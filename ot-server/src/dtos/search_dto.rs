@@ -0,0 +1,49 @@
+use sea_orm::prelude::Uuid;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct SearchParams {
+    #[validate(length(
+        min = 1,
+        max = 200,
+        message = "Search query must be between 1 and 200 characters"
+    ))]
+    pub q: String,
+    pub project_id: Option<Uuid>,
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<u64>,
+    /// Also search archived projects; defaults to `false`
+    pub include_archived: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchHitKind {
+    Project,
+    Section,
+    Task,
+    SubTask,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BreadcrumbDto {
+    pub kind: SearchHitKind,
+    pub id: Uuid,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHitDto {
+    pub kind: SearchHitKind,
+    pub id: Uuid,
+    pub name: String,
+    pub project_id: Uuid,
+    /// Containing section, or the hit itself for sections
+    pub section_id: Option<Uuid>,
+    /// Containing task, or the hit itself for tasks
+    pub task_id: Option<Uuid>,
+    /// Ancestors from the project down, e.g. project › section › task
+    pub breadcrumbs: Vec<BreadcrumbDto>,
+    pub rank: f32,
+}
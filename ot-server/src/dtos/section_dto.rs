@@ -7,6 +7,9 @@ use validator::Validate;
 pub struct SectionDto {
     pub id: Uuid,
     pub name: String,
+    pub project_id: Uuid,
+    /// Ordering key within the project; sort ascending, byte-wise
+    pub position: String,
     pub created_at: DateTime<FixedOffset>,
     pub updated_at: DateTime<FixedOffset>,
 }
@@ -21,3 +24,11 @@ pub struct SectionCreateDto {
 pub struct SectionUpdateDto {
     pub name: String,
 }
+
+#[derive(Debug, Deserialize)]
+pub struct SectionMoveDto {
+    /// Same or another project
+    pub project_id: Uuid,
+    /// Sibling to place the section after; `None` moves it to the top
+    pub after_id: Option<Uuid>,
+}
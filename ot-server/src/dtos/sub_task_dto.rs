@@ -1,29 +1,76 @@
-use chrono::{DateTime, FixedOffset};
+use chrono::{DateTime, FixedOffset, NaiveDate};
 use sea_orm::prelude::Uuid;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
+use super::task_dto::{double_option, validate_status, TaskPriority};
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SubTaskDto {
     pub name: String,
     pub section_id: Uuid,
+    pub task_id: Option<Uuid>,
     pub id: Uuid,
+    /// Ordering key within the task, or the section for sub tasks without
+    /// one; sort ascending, byte-wise
+    pub position: String,
+    /// Markdown
+    pub description: Option<String>,
+    pub status: String,
+    pub priority: TaskPriority,
+    pub due_date: Option<NaiveDate>,
+    pub estimated_minutes: Option<i32>,
+    pub assignee: Option<String>,
     pub created_at: DateTime<FixedOffset>,
     pub updated_at: DateTime<FixedOffset>,
 }
 
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Default, Deserialize, Validate)]
 pub struct SubTaskCreateDto {
     #[validate(length(min = 1))]
     pub name: String,
     pub section_id: Uuid,
     pub task_id: Option<Uuid>,
+    #[validate(length(max = 10000))]
+    pub description: Option<String>,
+    #[validate(custom(function = "validate_status"))]
+    pub status: Option<String>,
+    pub priority: Option<TaskPriority>,
+    pub due_date: Option<NaiveDate>,
+    #[validate(range(min = 0, max = 100000))]
+    pub estimated_minutes: Option<i32>,
+    #[validate(length(min = 1, max = 100))]
+    pub assignee: Option<String>,
 }
 
+/// Omitted fields are left unchanged; `null` clears the optional ones.
 #[derive(Debug, Deserialize, Validate)]
 pub struct SubTaskUpdateDto {
     #[validate(length(min = 1))]
     pub name: String,
     pub section_id: Uuid,
     pub task_id: Option<Uuid>,
+    #[serde(default, deserialize_with = "double_option")]
+    #[validate(length(max = 10000))]
+    pub description: Option<Option<String>>,
+    #[validate(custom(function = "validate_status"))]
+    pub status: Option<String>,
+    pub priority: Option<TaskPriority>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub due_date: Option<Option<NaiveDate>>,
+    #[serde(default, deserialize_with = "double_option")]
+    #[validate(range(min = 0, max = 100000))]
+    pub estimated_minutes: Option<Option<i32>>,
+    #[serde(default, deserialize_with = "double_option")]
+    #[validate(length(min = 1, max = 100))]
+    pub assignee: Option<Option<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SubTaskMoveDto {
+    pub section_id: Uuid,
+    /// Must belong to `section_id`; `None` attaches it to the section directly
+    pub task_id: Option<Uuid>,
+    /// Sibling to place the sub task after; `None` moves it to the top
+    pub after_id: Option<Uuid>,
 }
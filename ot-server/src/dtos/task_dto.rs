@@ -1,27 +1,148 @@
-use chrono::{DateTime, FixedOffset};
-use sea_orm::prelude::Uuid;
-use serde::{Deserialize, Serialize};
-use validator::Validate;
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use sea_orm::{prelude::Uuid, sea_query::StringLen, DeriveActiveEnum, EnumIter};
+use serde::{Deserialize, Deserializer, Serialize};
+use validator::{Validate, ValidationError};
+
+/// Status given to new tasks and sub tasks. `in_progress` and `done` are the
+/// other built-in ones; any lower-case slug is accepted as a custom status.
+pub const DEFAULT_STATUS: &str = "todo";
+
+/// Status of a finished task; a task stops blocking others once it has it.
+pub const DONE_STATUS: &str = "done";
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+#[serde(rename_all = "snake_case")]
+pub enum TaskPriority {
+    #[sea_orm(string_value = "low")]
+    Low,
+    #[default]
+    #[sea_orm(string_value = "medium")]
+    Medium,
+    #[sea_orm(string_value = "high")]
+    High,
+    #[sea_orm(string_value = "urgent")]
+    Urgent,
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TaskDto {
     pub name: String,
     pub section_id: Uuid,
     pub id: Uuid,
+    /// Ordering key within the section; sort ascending, byte-wise
+    pub position: String,
+    /// Markdown
+    pub description: Option<String>,
+    pub status: String,
+    pub priority: TaskPriority,
+    pub due_date: Option<NaiveDate>,
+    pub estimated_minutes: Option<i32>,
+    pub assignee: Option<String>,
+    /// Whether a task blocking this one is not done yet
+    pub blocked: bool,
+    /// Whether time tracked on the task is billed, provided its project is
+    pub billable: bool,
     pub created_at: DateTime<FixedOffset>,
     pub updated_at: DateTime<FixedOffset>,
 }
 
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Default, Deserialize, Validate)]
 pub struct TaskCreateDto {
     #[validate(length(min = 1))]
     pub name: String,
     pub section_id: Uuid,
+    #[validate(length(max = 10000))]
+    pub description: Option<String>,
+    #[validate(custom(function = "validate_status"))]
+    pub status: Option<String>,
+    pub priority: Option<TaskPriority>,
+    pub due_date: Option<NaiveDate>,
+    #[validate(range(min = 0, max = 100000))]
+    pub estimated_minutes: Option<i32>,
+    #[validate(length(min = 1, max = 100))]
+    pub assignee: Option<String>,
+    /// Defaults to `true`
+    pub billable: Option<bool>,
 }
 
+/// Omitted fields are left unchanged; `null` clears the optional ones.
 #[derive(Debug, Deserialize, Validate)]
 pub struct TaskUpdateDto {
     #[validate(length(min = 1))]
     pub name: String,
     pub section_id: Uuid,
+    #[serde(default, deserialize_with = "double_option")]
+    #[validate(length(max = 10000))]
+    pub description: Option<Option<String>>,
+    #[validate(custom(function = "validate_status"))]
+    pub status: Option<String>,
+    pub priority: Option<TaskPriority>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub due_date: Option<Option<NaiveDate>>,
+    #[serde(default, deserialize_with = "double_option")]
+    #[validate(range(min = 0, max = 100000))]
+    pub estimated_minutes: Option<Option<i32>>,
+    #[serde(default, deserialize_with = "double_option")]
+    #[validate(length(min = 1, max = 100))]
+    pub assignee: Option<Option<String>>,
+    pub billable: Option<bool>,
+}
+
+/// Moving a task to another section, possibly in another project, takes
+/// its sub tasks along.
+#[derive(Debug, Deserialize)]
+pub struct TaskMoveDto {
+    pub section_id: Uuid,
+    /// Sibling to place the task after; `None` moves it to the top
+    pub after_id: Option<Uuid>,
+}
+
+/// Query string filters for the `/all` and `/by-section-id` routes of tasks
+/// and sub tasks.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Validate)]
+pub struct TaskFilterParams {
+    #[validate(custom(function = "validate_status"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<TaskPriority>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    /// Inclusive
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_before: Option<NaiveDate>,
+    /// Inclusive
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_after: Option<NaiveDate>,
+    /// Comma separated label names, see
+    /// [`label_names`](super::label_dto::label_names)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// Statuses are lower-case slugs such as `todo`, `in_progress` or `done`.
+pub fn validate_status(status: &str) -> Result<(), ValidationError> {
+    let valid = (1..=32).contains(&status.len())
+        && status
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(ValidationError::new("status").with_message(
+            "Status must be 1 to 32 lower-case letters, digits or underscores".into(),
+        ))
+    }
+}
+
+/// Tells an explicit `null` (`Some(None)`) apart from a missing field (`None`).
+pub fn double_option<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
 }
@@ -0,0 +1,76 @@
+use chrono::{DateTime, FixedOffset};
+use sea_orm::prelude::Uuid;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// A tracked interval with what it is billed at.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeEntryDto {
+    /// Id of the activity row the time was tracked in
+    pub id: Uuid,
+    pub task_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+    pub client_id: Option<Uuid>,
+    pub person: Option<String>,
+    pub start_time: DateTime<FixedOffset>,
+    pub end_time: DateTime<FixedOffset>,
+    pub duration_seconds: i64,
+    /// Time is billable when it is tracked on a task, and both the task and
+    /// its project are billable
+    pub billable: bool,
+    /// Per hour, in the currency's minor unit; `None` when not billable or
+    /// when no rate applies
+    pub hourly_rate: Option<i64>,
+    pub currency: Option<String>,
+    /// In the currency's minor unit
+    pub amount: Option<i64>,
+    /// Invoice the time is on, draft or finalized
+    pub invoice_id: Option<Uuid>,
+}
+
+/// Billable amount in one currency.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BillableTotalDto {
+    pub currency: String,
+    pub billable_seconds: i64,
+    /// In the currency's minor unit
+    pub amount: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeSummaryDto {
+    pub total_seconds: i64,
+    pub billable_seconds: i64,
+    /// Billable time no rate applies to, left out of `totals`
+    pub unpriced_seconds: i64,
+    pub totals: Vec<BillableTotalDto>,
+}
+
+/// Query string filters for the `/api/time` routes.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Validate)]
+pub struct TimeFilterParams {
+    /// Intervals starting at or after this time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<DateTime<FixedOffset>>,
+    /// Intervals starting before this time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<DateTime<FixedOffset>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(length(min = 1, max = 100))]
+    pub person: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub billable: Option<bool>,
+    /// Whether the time is on an invoice, draft or finalized
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invoiced: Option<bool>,
+    /// Comma separated label names of the task the time was tracked on, see
+    /// [`label_names`](super::label_dto::label_names)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
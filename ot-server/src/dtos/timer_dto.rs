@@ -0,0 +1,61 @@
+use chrono::{DateTime, FixedOffset};
+use sea_orm::prelude::Uuid;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// What a desktop timer is about to track.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimerTargetCheckDto {
+    pub task_id: Uuid,
+    /// Must belong to `task_id`
+    pub sub_task_id: Option<Uuid>,
+}
+
+/// Response of `/api/timer/target`: a task time can be tracked on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimerTargetDto {
+    pub task_id: Uuid,
+    pub task_name: String,
+    pub sub_task_id: Option<Uuid>,
+    pub sub_task_name: Option<String>,
+    pub project_id: Uuid,
+    pub project_name: String,
+    /// Whether the tracked time is billed
+    pub billable: bool,
+    /// A task this one depends on is not done yet
+    pub blocked: bool,
+}
+
+/// A finished desktop timer session. The desktop generates `id`, so an
+/// upload retried after a lost response is stored once.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct TimerSessionCreateDto {
+    pub id: Uuid,
+    pub task_id: Option<Uuid>,
+    pub sub_task_id: Option<Uuid>,
+    pub start_time: DateTime<FixedOffset>,
+    pub end_time: DateTime<FixedOffset>,
+    /// Tracked time, without pauses
+    #[validate(range(min = 0))]
+    pub elapsed_seconds: i32,
+    #[validate(length(min = 1, max = 100))]
+    pub person: Option<String>,
+    /// Why idle time in the session was kept or reassigned
+    #[serde(default)]
+    #[validate(length(max = 2000))]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimerSessionDto {
+    pub id: Uuid,
+    /// `None` once the task is deleted
+    pub task_id: Option<Uuid>,
+    pub sub_task_id: Option<Uuid>,
+    pub start_time: DateTime<FixedOffset>,
+    pub end_time: DateTime<FixedOffset>,
+    pub elapsed_seconds: i32,
+    pub person: Option<String>,
+    pub note: Option<String>,
+    pub created_at: DateTime<FixedOffset>,
+}
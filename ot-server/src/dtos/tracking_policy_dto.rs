@@ -0,0 +1,126 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, FixedOffset};
+use sea_orm::prelude::Uuid;
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+/// Desktop tracking settings a policy can set, named after the desktop's
+/// config fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrackingSetting {
+    EnableScreenShots,
+    /// Seconds
+    ScreenShotsInterval,
+    EnableWindowSs,
+    BrowserWindowSs,
+    EnableKeyboardActivity,
+    EnableMouseActivity,
+    EnableIdleTime,
+    /// Seconds
+    IdleTimeThreshold,
+    AskForIdleReason,
+    EnableWindowActivity,
+    EnableBrowserActivity,
+}
+
+impl TrackingSetting {
+    /// Shortest and longest duration allowed, in seconds, for the settings
+    /// that are durations.
+    pub fn seconds_range(self) -> Option<(u64, u64)> {
+        match self {
+            Self::ScreenShotsInterval => Some((10, 24 * 3600)),
+            Self::IdleTimeThreshold => Some((60, 24 * 3600)),
+            _ => None,
+        }
+    }
+}
+
+/// Value of a setting: a switch, or a duration in seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TrackingValue {
+    Enabled(bool),
+    Seconds(u64),
+}
+
+/// Values a policy sets; settings left out are up to each desktop.
+pub type TrackingSettings = BTreeMap<TrackingSetting, TrackingValue>;
+
+/// Request body of `/api/tracking-policy/set` and
+/// `/api/tracking-policy/set/{project_id}`; replaces the policy.
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct TrackingPolicyUpdateDto {
+    #[serde(default)]
+    #[validate(custom(function = "validate_tracking_settings"))]
+    pub settings: TrackingSettings,
+    /// Settings employees cannot change; each needs a value in `settings`
+    #[serde(default)]
+    pub locked: Vec<TrackingSetting>,
+}
+
+/// A stored policy, for all projects or for one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrackingPolicyDto {
+    pub id: Uuid,
+    /// `null` for the global policy
+    pub project_id: Option<Uuid>,
+    pub settings: TrackingSettings,
+    pub locked: Vec<TrackingSetting>,
+    pub updated_at: DateTime<FixedOffset>,
+}
+
+/// What applies to time tracked on a project, or anywhere when
+/// `project_id` is `null`: the project's policy over the global one.
+/// Response of `/api/tracking-policy/effective`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EffectiveTrackingPolicyDto {
+    pub project_id: Option<Uuid>,
+    pub settings: TrackingSettings,
+    /// Sorted
+    pub locked: Vec<TrackingSetting>,
+    /// Latest change to the policies it is made of; `null` when there are
+    /// none
+    pub updated_at: Option<DateTime<FixedOffset>>,
+}
+
+/// Query string of `/api/tracking-policy/effective`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct EffectiveTrackingPolicyParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<Uuid>,
+}
+
+/// Switches are booleans and durations are seconds within the setting's
+/// range.
+pub fn validate_tracking_settings(settings: &TrackingSettings) -> Result<(), ValidationError> {
+    for (&setting, &value) in settings {
+        let valid = match (setting.seconds_range(), value) {
+            (None, TrackingValue::Enabled(_)) => true,
+            (Some((min, max)), TrackingValue::Seconds(seconds)) => (min..=max).contains(&seconds),
+            _ => false,
+        };
+        if !valid {
+            let message = match setting.seconds_range() {
+                Some((min, max)) => format!(
+                    "{} must be between {} and {} seconds",
+                    setting_name(setting),
+                    min,
+                    max
+                ),
+                None => format!("{} must be true or false", setting_name(setting)),
+            };
+            return Err(ValidationError::new("tracking_setting").with_message(message.into()));
+        }
+    }
+    Ok(())
+}
+
+/// Name of a setting as it appears in JSON.
+pub fn setting_name(setting: TrackingSetting) -> String {
+    serde_json::to_value(setting)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_else(|| format!("{:?}", setting))
+}
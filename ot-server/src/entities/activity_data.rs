@@ -1,8 +1,9 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.4
 
 use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "activity_data")]
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
@@ -19,9 +20,41 @@ pub struct Model {
     pub end_time: DateTimeWithTimeZone,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
+    pub task_id: Option<Uuid>,
+    pub person: Option<String>,
+    pub invoice_line_id: Option<Uuid>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::invoice_line::Entity",
+        from = "Column::InvoiceLineId",
+        to = "super::invoice_line::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    InvoiceLine,
+    #[sea_orm(
+        belongs_to = "super::task::Entity",
+        from = "Column::TaskId",
+        to = "super::task::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    Task,
+}
+
+impl Related<super::invoice_line::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::InvoiceLine.def()
+    }
+}
+
+impl Related<super::task::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Task.def()
+    }
+}
 
 impl ActiveModelBehavior for ActiveModel {}
@@ -3,8 +3,25 @@
 pub mod prelude;
 
 pub mod activity_data;
+pub mod billing_rate;
+pub mod budget_alert;
+pub mod client;
+pub mod comment;
+pub mod comment_revision;
+pub mod invoice;
+pub mod invoice_line;
+pub mod label;
 pub mod project;
+pub mod project_label;
 pub mod section;
 pub mod sub_task;
+pub mod sub_task_label;
 pub mod task;
+pub mod task_dependency;
+pub mod task_event;
+pub mod task_label;
+pub mod task_occurrence;
+pub mod task_recurrence;
+pub mod timer_session;
+pub mod tracking_policy;
 pub mod window_activity_data;
@@ -1,8 +1,25 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.4
 
 pub use super::activity_data::Entity as ActivityData;
+pub use super::billing_rate::Entity as BillingRate;
+pub use super::budget_alert::Entity as BudgetAlert;
+pub use super::client::Entity as Client;
+pub use super::comment::Entity as Comment;
+pub use super::comment_revision::Entity as CommentRevision;
+pub use super::invoice::Entity as Invoice;
+pub use super::invoice_line::Entity as InvoiceLine;
+pub use super::label::Entity as Label;
 pub use super::project::Entity as Project;
+pub use super::project_label::Entity as ProjectLabel;
 pub use super::section::Entity as Section;
 pub use super::sub_task::Entity as SubTask;
+pub use super::sub_task_label::Entity as SubTaskLabel;
 pub use super::task::Entity as Task;
+pub use super::task_dependency::Entity as TaskDependency;
+pub use super::task_event::Entity as TaskEvent;
+pub use super::task_label::Entity as TaskLabel;
+pub use super::task_occurrence::Entity as TaskOccurrence;
+pub use super::task_recurrence::Entity as TaskRecurrence;
+pub use super::timer_session::Entity as TimerSession;
+pub use super::tracking_policy::Entity as TrackingPolicy;
 pub use super::window_activity_data::Entity as WindowActivityData;
@@ -1,8 +1,9 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.4
 
 use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
 #[sea_orm(table_name = "project")]
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
@@ -10,14 +11,53 @@ pub struct Model {
     pub name: String,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
-    #[sea_orm(default_value = 1)]
     pub version: i32,
+    pub client_id: Option<Uuid>,
+    pub billable: bool,
+    pub budget_minutes: Option<i32>,
+    pub budget_amount: Option<i64>,
+    pub budget_currency: Option<String>,
+    pub archived_at: Option<DateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::billing_rate::Entity")]
+    BillingRate,
+    #[sea_orm(has_many = "super::budget_alert::Entity")]
+    BudgetAlert,
+    #[sea_orm(
+        belongs_to = "super::client::Entity",
+        from = "Column::ClientId",
+        to = "super::client::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    Client,
     #[sea_orm(has_many = "super::section::Entity")]
     Section,
+    #[sea_orm(has_many = "super::project_label::Entity")]
+    ProjectLabel,
+    #[sea_orm(has_many = "super::tracking_policy::Entity")]
+    TrackingPolicy,
+}
+
+impl Related<super::billing_rate::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::BillingRate.def()
+    }
+}
+
+impl Related<super::budget_alert::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::BudgetAlert.def()
+    }
+}
+
+impl Related<super::client::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Client.def()
+    }
 }
 
 impl Related<super::section::Entity> for Entity {
@@ -26,4 +66,25 @@ impl Related<super::section::Entity> for Entity {
     }
 }
 
+impl Related<super::project_label::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProjectLabel.def()
+    }
+}
+
+impl Related<super::tracking_policy::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TrackingPolicy.def()
+    }
+}
+
+impl Related<super::label::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::project_label::Relation::Label.def()
+    }
+    fn via() -> Option<RelationDef> {
+        Some(super::project_label::Relation::Project.def().rev())
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
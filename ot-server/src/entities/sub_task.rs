@@ -1,8 +1,11 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.4
 
 use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+use crate::dtos::task_dto::TaskPriority;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
 #[sea_orm(table_name = "sub_task")]
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
@@ -10,6 +13,15 @@ pub struct Model {
     pub name: String,
     pub section_id: Uuid,
     pub task_id: Option<Uuid>,
+    pub version: i32,
+    pub position: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub description: Option<String>,
+    pub status: String,
+    pub priority: TaskPriority,
+    pub due_date: Option<Date>,
+    pub estimated_minutes: Option<i32>,
+    pub assignee: Option<String>,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
 }
@@ -32,6 +44,14 @@ pub enum Relation {
         on_delete = "SetNull"
     )]
     Task,
+    #[sea_orm(has_many = "super::comment::Entity")]
+    Comment,
+    #[sea_orm(has_many = "super::task_event::Entity")]
+    TaskEvent,
+    #[sea_orm(has_many = "super::sub_task_label::Entity")]
+    SubTaskLabel,
+    #[sea_orm(has_many = "super::timer_session::Entity")]
+    TimerSession,
 }
 
 impl Related<super::section::Entity> for Entity {
@@ -46,4 +66,37 @@ impl Related<super::task::Entity> for Entity {
     }
 }
 
+impl Related<super::comment::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Comment.def()
+    }
+}
+
+impl Related<super::task_event::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TaskEvent.def()
+    }
+}
+
+impl Related<super::sub_task_label::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SubTaskLabel.def()
+    }
+}
+
+impl Related<super::timer_session::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TimerSession.def()
+    }
+}
+
+impl Related<super::label::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::sub_task_label::Relation::Label.def()
+    }
+    fn via() -> Option<RelationDef> {
+        Some(super::sub_task_label::Relation::SubTask.def().rev())
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
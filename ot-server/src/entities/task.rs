@@ -1,20 +1,37 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.4
 
 use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+use crate::dtos::task_dto::TaskPriority;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
 #[sea_orm(table_name = "task")]
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
     pub id: Uuid,
     pub name: String,
     pub section_id: Uuid,
+    pub version: i32,
+    pub position: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub description: Option<String>,
+    pub status: String,
+    pub priority: TaskPriority,
+    pub due_date: Option<Date>,
+    pub estimated_minutes: Option<i32>,
+    pub assignee: Option<String>,
+    pub billable: bool,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::activity_data::Entity")]
+    ActivityData,
+    #[sea_orm(has_many = "super::budget_alert::Entity")]
+    BudgetAlert,
     #[sea_orm(
         belongs_to = "super::section::Entity",
         from = "Column::SectionId",
@@ -23,8 +40,32 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     Section,
+    #[sea_orm(has_many = "super::comment::Entity")]
+    Comment,
     #[sea_orm(has_many = "super::sub_task::Entity")]
     SubTask,
+    #[sea_orm(has_many = "super::task_event::Entity")]
+    TaskEvent,
+    #[sea_orm(has_many = "super::task_label::Entity")]
+    TaskLabel,
+    #[sea_orm(has_many = "super::task_occurrence::Entity")]
+    TaskOccurrence,
+    #[sea_orm(has_one = "super::task_recurrence::Entity")]
+    TaskRecurrence,
+    #[sea_orm(has_many = "super::timer_session::Entity")]
+    TimerSession,
+}
+
+impl Related<super::activity_data::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ActivityData.def()
+    }
+}
+
+impl Related<super::budget_alert::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::BudgetAlert.def()
+    }
 }
 
 impl Related<super::section::Entity> for Entity {
@@ -39,4 +80,49 @@ impl Related<super::sub_task::Entity> for Entity {
     }
 }
 
+impl Related<super::comment::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Comment.def()
+    }
+}
+
+impl Related<super::task_event::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TaskEvent.def()
+    }
+}
+
+impl Related<super::task_label::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TaskLabel.def()
+    }
+}
+
+impl Related<super::task_occurrence::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TaskOccurrence.def()
+    }
+}
+
+impl Related<super::task_recurrence::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TaskRecurrence.def()
+    }
+}
+
+impl Related<super::timer_session::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TimerSession.def()
+    }
+}
+
+impl Related<super::label::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::task_label::Relation::Label.def()
+    }
+    fn via() -> Option<RelationDef> {
+        Some(super::task_label::Relation::Task.def().rev())
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,166 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use axum_valid::Valid;
+use sea_orm::prelude::Uuid;
+
+use crate::{
+    dtos::{
+        billing_dto::{
+            BillingRateCreateDto, BillingRateDto, BillingRateFilterParams, BillingRateUpdateDto,
+        },
+        time_dto::{BillableTotalDto, TimeEntryDto, TimeFilterParams, TimeSummaryDto},
+    },
+    entities::billing_rate,
+    services::billing_service::{BillingService, PricedTime, TimeSummary},
+    utils::error::AppError,
+    AppState,
+};
+
+pub struct BillingRateMutationHandlers;
+pub struct BillingRateQueryHandlers;
+pub struct TimeQueryHandlers;
+
+impl BillingRateMutationHandlers {
+    pub async fn create_rate_handler(
+        state: State<AppState>,
+        Valid(Json(payload)): Valid<Json<BillingRateCreateDto>>,
+    ) -> impl IntoResponse {
+        match BillingService::create_rate(&state.db, payload).await {
+            Ok(rate) => (StatusCode::CREATED, Json(rate_dto(rate))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn update_rate_handler(
+        state: State<AppState>,
+        Path(id): Path<Uuid>,
+        Valid(Json(payload)): Valid<Json<BillingRateUpdateDto>>,
+    ) -> impl IntoResponse {
+        match BillingService::update_rate(&state.db, id, payload).await {
+            Ok(rate) => (StatusCode::OK, Json(rate_dto(rate))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn delete_rate_handler(
+        state: State<AppState>,
+        Path(id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        match BillingService::delete_rate(&state.db, id).await {
+            Ok(0) => AppError::NotFound(format!("Rate with id {} not found", id)).into_response(),
+            Ok(_) => (StatusCode::OK, format!("Rate with id {} deleted", id)).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+impl BillingRateQueryHandlers {
+    pub async fn get_rates_handler(
+        state: State<AppState>,
+        Query(filter): Query<BillingRateFilterParams>,
+    ) -> impl IntoResponse {
+        match BillingService::get_rates(&state.db, filter).await {
+            Ok(rates) => (
+                StatusCode::OK,
+                Json(rates.into_iter().map(rate_dto).collect::<Vec<_>>()),
+            )
+                .into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn get_rate_by_id_handler(
+        state: State<AppState>,
+        Path(id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        match BillingService::get_rate_by_id(&state.db, id).await {
+            Ok(Some(rate)) => (StatusCode::OK, Json(rate_dto(rate))).into_response(),
+            Ok(None) => {
+                AppError::NotFound(format!("Rate with id {} not found", id)).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+impl TimeQueryHandlers {
+    pub async fn get_time_entries_handler(
+        state: State<AppState>,
+        Valid(Query(filter)): Valid<Query<TimeFilterParams>>,
+    ) -> impl IntoResponse {
+        match BillingService::get_time_entries(&state.db, filter).await {
+            Ok(entries) => (
+                StatusCode::OK,
+                Json(entries.into_iter().map(time_entry_dto).collect::<Vec<_>>()),
+            )
+                .into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn get_time_summary_handler(
+        state: State<AppState>,
+        Valid(Query(filter)): Valid<Query<TimeFilterParams>>,
+    ) -> impl IntoResponse {
+        match BillingService::get_time_summary(&state.db, filter).await {
+            Ok(summary) => (StatusCode::OK, Json(time_summary_dto(summary))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+fn rate_dto(rate: billing_rate::Model) -> BillingRateDto {
+    BillingRateDto {
+        id: rate.id,
+        client_id: rate.client_id,
+        project_id: rate.project_id,
+        person: rate.person,
+        hourly_rate: rate.hourly_rate,
+        currency: rate.currency,
+        created_at: rate.created_at,
+        updated_at: rate.updated_at,
+    }
+}
+
+fn time_entry_dto(entry: PricedTime) -> TimeEntryDto {
+    let (hourly_rate, currency) = entry
+        .rate
+        .map(|rate| (rate.hourly_rate, rate.currency))
+        .unzip();
+    TimeEntryDto {
+        id: entry.time.id,
+        task_id: entry.time.task_id,
+        project_id: entry.time.project_id,
+        client_id: entry.time.client_id,
+        person: entry.time.person,
+        start_time: entry.time.start_time,
+        end_time: entry.time.end_time,
+        duration_seconds: entry.duration_seconds,
+        billable: entry.billable,
+        hourly_rate,
+        currency,
+        amount: entry.amount,
+        invoice_id: entry.time.invoice_id,
+    }
+}
+
+fn time_summary_dto(summary: TimeSummary) -> TimeSummaryDto {
+    TimeSummaryDto {
+        total_seconds: summary.total_seconds,
+        billable_seconds: summary.billable_seconds,
+        unpriced_seconds: summary.unpriced_seconds,
+        totals: summary
+            .totals
+            .into_iter()
+            .map(|(currency, (billable_seconds, amount))| BillableTotalDto {
+                currency,
+                billable_seconds,
+                amount,
+            })
+            .collect(),
+    }
+}
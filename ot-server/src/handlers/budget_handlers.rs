@@ -0,0 +1,117 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use sea_orm::prelude::Uuid;
+
+use crate::{
+    dtos::budget_dto::{
+        BudgetAlertDto, BudgetAlertFilterParams, BudgetProgressDto, ProjectBudgetDto,
+        TaskEstimateDto,
+    },
+    repository::budget_alert_repository::BudgetAlertRow,
+    services::budget_service::{estimate_seconds, BudgetService, ProjectBudget},
+    utils::budget::{percent, Progress},
+    AppState,
+};
+
+pub struct BudgetMutationHandlers;
+pub struct BudgetQueryHandlers;
+
+impl BudgetMutationHandlers {
+    pub async fn acknowledge_alert_handler(
+        state: State<AppState>,
+        Path(id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        match BudgetService::acknowledge_alert(&state.db, id).await {
+            Ok(alert) => (StatusCode::OK, Json(alert_dto(alert))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+impl BudgetQueryHandlers {
+    pub async fn get_project_budget_handler(
+        state: State<AppState>,
+        Path(id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        match BudgetService::get_project_budget(&state.db, id, &state.settings.budget).await {
+            Ok(budget) => (StatusCode::OK, Json(budget_dto(budget))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn get_alerts_handler(
+        state: State<AppState>,
+        Query(filter): Query<BudgetAlertFilterParams>,
+    ) -> impl IntoResponse {
+        match BudgetService::get_alerts(&state.db, filter).await {
+            Ok(alerts) => (
+                StatusCode::OK,
+                Json(alerts.into_iter().map(alert_dto).collect::<Vec<_>>()),
+            )
+                .into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+fn budget_dto(budget: ProjectBudget) -> ProjectBudgetDto {
+    let currency = budget.project.budget_currency;
+    ProjectBudgetDto {
+        project_id: budget.project.id,
+        time: budget.time.map(|time| progress_dto(time, None)),
+        money: budget.money.map(|money| progress_dto(money, currency)),
+        unpriced_seconds: budget.unpriced_seconds,
+        estimated_seconds: budget
+            .tasks
+            .iter()
+            .map(|(task, _)| estimate_seconds(task))
+            .sum(),
+        tasks: budget
+            .tasks
+            .into_iter()
+            .map(|(task, used_seconds)| {
+                let estimated_seconds = estimate_seconds(&task);
+                TaskEstimateDto {
+                    task_id: task.id,
+                    name: task.name,
+                    estimated_seconds,
+                    used_seconds,
+                    percent_used: percent(used_seconds, estimated_seconds),
+                }
+            })
+            .collect(),
+    }
+}
+
+fn progress_dto(progress: Progress, currency: Option<String>) -> BudgetProgressDto {
+    BudgetProgressDto {
+        budget: progress.budget,
+        used: progress.used,
+        remaining: progress.remaining,
+        percent_used: progress.percent_used,
+        burn_rate_per_day: progress.burn_rate_per_day,
+        projected_overrun_on: progress.projected_overrun_on,
+        currency,
+    }
+}
+
+fn alert_dto(alert: BudgetAlertRow) -> BudgetAlertDto {
+    BudgetAlertDto {
+        id: alert.id,
+        project_id: alert.project_id,
+        project_name: alert.project_name,
+        task_id: alert.task_id,
+        task_name: alert.task_name,
+        kind: alert.kind,
+        threshold: alert.threshold,
+        used: alert.used,
+        budget: alert.budget,
+        currency: alert.currency,
+        created_at: alert.created_at,
+        acknowledged_at: alert.acknowledged_at,
+    }
+}
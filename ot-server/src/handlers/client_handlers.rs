@@ -0,0 +1,89 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use axum_valid::Valid;
+use sea_orm::prelude::Uuid;
+
+use crate::{
+    dtos::client_dto::{ClientCreateDto, ClientDto, ClientUpdateDto},
+    entities::client,
+    services::client_service::ClientService,
+    utils::error::AppError,
+    AppState,
+};
+
+pub struct ClientMutationHandlers;
+pub struct ClientQueryHandlers;
+
+impl ClientMutationHandlers {
+    pub async fn create_client_handler(
+        state: State<AppState>,
+        Valid(Json(payload)): Valid<Json<ClientCreateDto>>,
+    ) -> impl IntoResponse {
+        match ClientService::create_client(&state.db, payload).await {
+            Ok(client) => (StatusCode::CREATED, Json(client_dto(client))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn update_client_handler(
+        state: State<AppState>,
+        Path(id): Path<Uuid>,
+        Valid(Json(payload)): Valid<Json<ClientUpdateDto>>,
+    ) -> impl IntoResponse {
+        match ClientService::update_client(&state.db, id, payload).await {
+            Ok(client) => (StatusCode::OK, Json(client_dto(client))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn delete_client_handler(
+        state: State<AppState>,
+        Path(id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        match ClientService::delete_client(&state.db, id).await {
+            Ok(0) => AppError::NotFound(format!("Client with id {} not found", id)).into_response(),
+            Ok(_) => (StatusCode::OK, format!("Client with id {} deleted", id)).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+impl ClientQueryHandlers {
+    pub async fn get_clients_handler(state: State<AppState>) -> impl IntoResponse {
+        match ClientService::get_clients(&state.db).await {
+            Ok(clients) => (
+                StatusCode::OK,
+                Json(clients.into_iter().map(client_dto).collect::<Vec<_>>()),
+            )
+                .into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn get_client_by_id_handler(
+        state: State<AppState>,
+        Path(id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        match ClientService::get_client_by_id(&state.db, id).await {
+            Ok(Some(client)) => (StatusCode::OK, Json(client_dto(client))).into_response(),
+            Ok(None) => {
+                AppError::NotFound(format!("Client with id {} not found", id)).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+fn client_dto(client: client::Model) -> ClientDto {
+    ClientDto {
+        id: client.id,
+        name: client.name,
+        email: client.email,
+        created_at: client.created_at,
+        updated_at: client.updated_at,
+    }
+}
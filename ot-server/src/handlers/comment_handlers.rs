@@ -0,0 +1,173 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use axum_valid::Valid;
+use sea_orm::prelude::Uuid;
+
+use crate::{
+    dtos::comment_dto::{
+        CommentCreateDto, CommentDto, CommentRevisionDto, CommentUpdateDto, FeedItemDto,
+        TaskEventDto,
+    },
+    entities::{comment, comment_revision, task_event},
+    services::comment_service::{CommentService, FeedItem},
+    utils::error::AppError,
+    AppState,
+};
+
+pub struct CommentMutationHandlers;
+pub struct CommentQueryHandlers;
+
+impl CommentMutationHandlers {
+    pub async fn create_comment_handler(
+        state: State<AppState>,
+        Valid(Json(payload)): Valid<Json<CommentCreateDto>>,
+    ) -> impl IntoResponse {
+        match CommentService::create_comment(&state.db, payload).await {
+            Ok(comment) => (StatusCode::CREATED, Json(comment_dto(comment))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn update_comment_handler(
+        state: State<AppState>,
+        Path(id): Path<Uuid>,
+        Valid(Json(payload)): Valid<Json<CommentUpdateDto>>,
+    ) -> impl IntoResponse {
+        match CommentService::update_comment(&state.db, id, payload).await {
+            Ok(comment) => (StatusCode::OK, Json(comment_dto(comment))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn delete_comment_handler(
+        state: State<AppState>,
+        Path(id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        match CommentService::delete_comment(&state.db, id).await {
+            Ok(0) => {
+                AppError::NotFound(format!("Comment with id {} not found", id)).into_response()
+            }
+            Ok(_) => (StatusCode::OK, format!("Comment with id {} deleted", id)).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+impl CommentQueryHandlers {
+    pub async fn get_comment_by_id_handler(
+        state: State<AppState>,
+        Path(id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        match CommentService::get_comment_by_id(&state.db, id).await {
+            Ok(Some(comment)) => (StatusCode::OK, Json(comment_dto(comment))).into_response(),
+            Ok(None) => {
+                AppError::NotFound(format!("Comment with id {} not found", id)).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn get_comments_by_task_id_handler(
+        state: State<AppState>,
+        Path(task_id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        match CommentService::get_comments_by_task_id(&state.db, task_id).await {
+            Ok(comments) => {
+                let result_data = comments
+                    .into_iter()
+                    .map(comment_dto)
+                    .collect::<Vec<CommentDto>>();
+                (StatusCode::OK, Json(result_data)).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn get_comments_by_sub_task_id_handler(
+        state: State<AppState>,
+        Path(sub_task_id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        match CommentService::get_comments_by_sub_task_id(&state.db, sub_task_id).await {
+            Ok(comments) => {
+                let result_data = comments
+                    .into_iter()
+                    .map(comment_dto)
+                    .collect::<Vec<CommentDto>>();
+                (StatusCode::OK, Json(result_data)).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn get_comment_history_handler(
+        state: State<AppState>,
+        Path(id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        match CommentService::get_comment_history(&state.db, id).await {
+            Ok(revisions) => {
+                let result_data = revisions
+                    .into_iter()
+                    .map(revision_dto)
+                    .collect::<Vec<CommentRevisionDto>>();
+                (StatusCode::OK, Json(result_data)).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn get_task_feed_handler(
+        state: State<AppState>,
+        Path(task_id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        match CommentService::get_task_feed(&state.db, task_id).await {
+            Ok(feed) => {
+                let result_data = feed
+                    .into_iter()
+                    .map(|item| match item {
+                        FeedItem::Comment(comment) => FeedItemDto::Comment(comment_dto(comment)),
+                        FeedItem::Change(event) => FeedItemDto::Change(task_event_dto(event)),
+                    })
+                    .collect::<Vec<FeedItemDto>>();
+                (StatusCode::OK, Json(result_data)).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+fn comment_dto(comment: comment::Model) -> CommentDto {
+    CommentDto {
+        id: comment.id,
+        task_id: comment.task_id,
+        sub_task_id: comment.sub_task_id,
+        author: comment.author,
+        body: comment.body,
+        mentions: comment.mentions,
+        version: comment.version,
+        created_at: comment.created_at,
+        updated_at: comment.updated_at,
+    }
+}
+
+fn revision_dto(revision: comment_revision::Model) -> CommentRevisionDto {
+    CommentRevisionDto {
+        body: revision.body,
+        version: revision.version,
+        created_at: revision.created_at,
+    }
+}
+
+fn task_event_dto(event: task_event::Model) -> TaskEventDto {
+    TaskEventDto {
+        id: event.id,
+        task_id: event.task_id,
+        sub_task_id: event.sub_task_id,
+        kind: event.kind,
+        changes: event.changes,
+        created_at: event.created_at,
+    }
+}
@@ -0,0 +1,103 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use sea_orm::prelude::Uuid;
+
+use crate::{
+    dtos::dependency_dto::{
+        DependencyGraphDto, TaskDependenciesDto, TaskDependencyCreateDto, TaskDependencyDto,
+    },
+    entities::task_dependency,
+    handlers::task_handlers::task_dto,
+    services::dependency_service::DependencyService,
+    utils::error::AppError,
+    AppState,
+};
+
+pub struct DependencyMutationHandlers;
+pub struct DependencyQueryHandlers;
+
+impl DependencyMutationHandlers {
+    pub async fn create_dependency_handler(
+        state: State<AppState>,
+        Json(payload): Json<TaskDependencyCreateDto>,
+    ) -> impl IntoResponse {
+        match DependencyService::create_dependency(&state.db, payload).await {
+            Ok(dependency) => {
+                (StatusCode::CREATED, Json(dependency_dto(dependency))).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn delete_dependency_handler(
+        state: State<AppState>,
+        Path((blocking_task_id, blocked_task_id)): Path<(Uuid, Uuid)>,
+    ) -> impl IntoResponse {
+        match DependencyService::delete_dependency(&state.db, blocking_task_id, blocked_task_id)
+            .await
+        {
+            Ok(0) => AppError::NotFound(format!(
+                "Task {} does not block task {}",
+                blocking_task_id, blocked_task_id
+            ))
+            .into_response(),
+            Ok(_) => (
+                StatusCode::OK,
+                format!(
+                    "Task {} no longer blocks task {}",
+                    blocking_task_id, blocked_task_id
+                ),
+            )
+                .into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+impl DependencyQueryHandlers {
+    pub async fn get_dependencies_by_task_id_handler(
+        state: State<AppState>,
+        Path(task_id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        match DependencyService::get_task_dependencies(&state.db, task_id).await {
+            Ok((blocked_by, blocks)) => (
+                StatusCode::OK,
+                Json(TaskDependenciesDto {
+                    blocked_by: blocked_by.into_iter().map(task_dto).collect(),
+                    blocks: blocks.into_iter().map(task_dto).collect(),
+                }),
+            )
+                .into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn get_project_graph_handler(
+        state: State<AppState>,
+        Path(project_id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        match DependencyService::get_project_graph(&state.db, project_id).await {
+            Ok((tasks, edges)) => (
+                StatusCode::OK,
+                Json(DependencyGraphDto {
+                    nodes: tasks.into_iter().map(task_dto).collect(),
+                    edges: edges.into_iter().map(dependency_dto).collect(),
+                }),
+            )
+                .into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+fn dependency_dto(dependency: task_dependency::Model) -> TaskDependencyDto {
+    TaskDependencyDto {
+        blocking_task_id: dependency.blocking_task_id,
+        blocked_task_id: dependency.blocked_task_id,
+        created_at: dependency.created_at,
+    }
+}
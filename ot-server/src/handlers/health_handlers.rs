@@ -0,0 +1,40 @@
+use axum::{
+    extract::State,
+    http::{header, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use serde_json::json;
+
+use crate::{services::health_service::HealthService, AppState};
+
+pub struct HealthHandlers;
+
+impl HealthHandlers {
+    /// Liveness: the process is up and serving requests.
+    pub async fn live_handler() -> impl IntoResponse {
+        (StatusCode::OK, Json(json!({ "status": "ok" })))
+    }
+
+    /// Readiness: database reachable, migrations applied and storage writable.
+    pub async fn ready_handler(state: State<AppState>) -> impl IntoResponse {
+        let report = HealthService::readiness(&state.db, &state.settings.storage).await;
+        let status = if report.ready {
+            StatusCode::OK
+        } else {
+            StatusCode::SERVICE_UNAVAILABLE
+        };
+        (status, Json(report))
+    }
+
+    pub async fn metrics_handler(state: State<AppState>) -> impl IntoResponse {
+        let body = state
+            .metrics
+            .render(&state.db, state.settings.database.max_connections);
+        (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+            body,
+        )
+    }
+}
@@ -0,0 +1,195 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use axum_valid::Valid;
+use sea_orm::prelude::Uuid;
+
+use crate::{
+    dtos::invoice_dto::{
+        InvoiceCreateDto, InvoiceDto, InvoiceFilterParams, InvoiceFinalizeDto,
+        InvoiceLineCreateDto, InvoiceLineDto, InvoiceLineUpdateDto, InvoiceUpdateDto,
+    },
+    entities::invoice_line,
+    services::invoice_service::{InvoiceService, InvoiceWithLines},
+    utils::error::AppError,
+    AppState,
+};
+
+pub struct InvoiceMutationHandlers;
+pub struct InvoiceQueryHandlers;
+
+impl InvoiceMutationHandlers {
+    pub async fn create_invoice_handler(
+        state: State<AppState>,
+        Valid(Json(payload)): Valid<Json<InvoiceCreateDto>>,
+    ) -> impl IntoResponse {
+        match InvoiceService::create_invoice(&state.db, payload).await {
+            Ok(invoice) => (StatusCode::CREATED, Json(invoice_dto(invoice))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn update_invoice_handler(
+        state: State<AppState>,
+        Path(id): Path<Uuid>,
+        Valid(Json(payload)): Valid<Json<InvoiceUpdateDto>>,
+    ) -> impl IntoResponse {
+        match InvoiceService::update_invoice(&state.db, id, payload).await {
+            Ok(invoice) => (StatusCode::OK, Json(invoice_dto(invoice))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn finalize_invoice_handler(
+        state: State<AppState>,
+        Path(id): Path<Uuid>,
+        Valid(Json(payload)): Valid<Json<InvoiceFinalizeDto>>,
+    ) -> impl IntoResponse {
+        match InvoiceService::finalize_invoice(&state.db, id, payload, &state.settings.invoice)
+            .await
+        {
+            Ok(invoice) => (StatusCode::OK, Json(invoice_dto(invoice))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn delete_invoice_handler(
+        state: State<AppState>,
+        Path(id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        match InvoiceService::delete_invoice(&state.db, id).await {
+            Ok(()) => (StatusCode::OK, format!("Invoice with id {} deleted", id)).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn create_line_handler(
+        state: State<AppState>,
+        Path(invoice_id): Path<Uuid>,
+        Valid(Json(payload)): Valid<Json<InvoiceLineCreateDto>>,
+    ) -> impl IntoResponse {
+        match InvoiceService::create_line(&state.db, invoice_id, payload).await {
+            Ok(line) => (StatusCode::CREATED, Json(line_dto(line))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn update_line_handler(
+        state: State<AppState>,
+        Path(id): Path<Uuid>,
+        Valid(Json(payload)): Valid<Json<InvoiceLineUpdateDto>>,
+    ) -> impl IntoResponse {
+        match InvoiceService::update_line(&state.db, id, payload).await {
+            Ok(line) => (StatusCode::OK, Json(line_dto(line))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn delete_line_handler(
+        state: State<AppState>,
+        Path(id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        match InvoiceService::delete_line(&state.db, id).await {
+            Ok(()) => (
+                StatusCode::OK,
+                format!("Invoice line with id {} deleted", id),
+            )
+                .into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+impl InvoiceQueryHandlers {
+    pub async fn get_invoices_handler(
+        state: State<AppState>,
+        Query(filter): Query<InvoiceFilterParams>,
+    ) -> impl IntoResponse {
+        match InvoiceService::get_invoices(&state.db, filter).await {
+            Ok(invoices) => (
+                StatusCode::OK,
+                Json(invoices.into_iter().map(invoice_dto).collect::<Vec<_>>()),
+            )
+                .into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn get_invoice_by_id_handler(
+        state: State<AppState>,
+        Path(id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        match InvoiceService::get_invoice_by_id(&state.db, id).await {
+            Ok(Some(invoice)) => (StatusCode::OK, Json(invoice_dto(invoice))).into_response(),
+            Ok(None) => {
+                AppError::NotFound(format!("Invoice with id {} not found", id)).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn get_invoice_pdf_handler(
+        state: State<AppState>,
+        Path(id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        match InvoiceService::render_pdf(&state.db, id, &state.settings.invoice).await {
+            Ok((invoice, pdf)) => {
+                let file_name = match invoice.number {
+                    Some(number) => format!("invoice-{}.pdf", number),
+                    None => format!("invoice-draft-{}.pdf", invoice.id),
+                };
+                (
+                    StatusCode::OK,
+                    [
+                        (header::CONTENT_TYPE, "application/pdf".to_string()),
+                        (
+                            header::CONTENT_DISPOSITION,
+                            format!("inline; filename=\"{}\"", file_name),
+                        ),
+                    ],
+                    pdf,
+                )
+                    .into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+fn invoice_dto((invoice, lines): InvoiceWithLines) -> InvoiceDto {
+    InvoiceDto {
+        id: invoice.id,
+        client_id: invoice.client_id,
+        number: invoice.number,
+        status: invoice.status,
+        currency: invoice.currency,
+        period_start: invoice.period_start,
+        period_end: invoice.period_end,
+        issued_on: invoice.issued_on,
+        due_on: invoice.due_on,
+        notes: invoice.notes,
+        total: lines.iter().map(|line| line.amount).sum(),
+        lines: lines.into_iter().map(line_dto).collect(),
+        created_at: invoice.created_at,
+        updated_at: invoice.updated_at,
+    }
+}
+
+fn line_dto(line: invoice_line::Model) -> InvoiceLineDto {
+    InvoiceLineDto {
+        id: line.id,
+        invoice_id: line.invoice_id,
+        project_id: line.project_id,
+        task_id: line.task_id,
+        description: line.description,
+        seconds: line.seconds,
+        hourly_rate: line.hourly_rate,
+        amount: line.amount,
+        position: line.position,
+        created_at: line.created_at,
+        updated_at: line.updated_at,
+    }
+}
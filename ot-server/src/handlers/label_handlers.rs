@@ -0,0 +1,140 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use axum_valid::Valid;
+use sea_orm::prelude::Uuid;
+
+use crate::{
+    dtos::label_dto::{LabelCreateDto, LabelDto, LabelTargetDto, LabelUpdateDto},
+    entities::label,
+    repository::label_repository::LabelTarget,
+    services::label_service::LabelService,
+    utils::error::AppError,
+    AppState,
+};
+
+pub struct LabelMutationHandlers;
+pub struct LabelQueryHandlers;
+
+impl LabelMutationHandlers {
+    pub async fn create_label_handler(
+        state: State<AppState>,
+        Valid(Json(payload)): Valid<Json<LabelCreateDto>>,
+    ) -> impl IntoResponse {
+        match LabelService::create_label(&state.db, payload).await {
+            Ok(label) => (StatusCode::CREATED, Json(label_dto(label))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn update_label_handler(
+        state: State<AppState>,
+        Path(id): Path<Uuid>,
+        Valid(Json(payload)): Valid<Json<LabelUpdateDto>>,
+    ) -> impl IntoResponse {
+        match LabelService::update_label(&state.db, id, payload).await {
+            Ok(label) => (StatusCode::OK, Json(label_dto(label))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn delete_label_handler(
+        state: State<AppState>,
+        Path(id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        match LabelService::delete_label(&state.db, id).await {
+            Ok(0) => AppError::NotFound(format!("Label with id {} not found", id)).into_response(),
+            Ok(_) => (StatusCode::OK, format!("Label with id {} deleted", id)).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn assign_label_handler(
+        state: State<AppState>,
+        Path(id): Path<Uuid>,
+        Json(payload): Json<LabelTargetDto>,
+    ) -> impl IntoResponse {
+        match LabelService::assign_label(&state.db, id, payload).await {
+            Ok(labels) => (StatusCode::OK, Json(label_dtos(labels))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn unassign_label_handler(
+        state: State<AppState>,
+        Path(id): Path<Uuid>,
+        Json(payload): Json<LabelTargetDto>,
+    ) -> impl IntoResponse {
+        match LabelService::unassign_label(&state.db, id, payload).await {
+            Ok(labels) => (StatusCode::OK, Json(label_dtos(labels))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+impl LabelQueryHandlers {
+    pub async fn get_labels_handler(state: State<AppState>) -> impl IntoResponse {
+        match LabelService::get_labels(&state.db).await {
+            Ok(labels) => (StatusCode::OK, Json(label_dtos(labels))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn get_label_by_id_handler(
+        state: State<AppState>,
+        Path(id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        match LabelService::get_label_by_id(&state.db, id).await {
+            Ok(Some(label)) => (StatusCode::OK, Json(label_dto(label))).into_response(),
+            Ok(None) => {
+                AppError::NotFound(format!("Label with id {} not found", id)).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn get_labels_by_project_id_handler(
+        state: State<AppState>,
+        Path(project_id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        Self::labels_of(&state, LabelTarget::Project(project_id)).await
+    }
+
+    pub async fn get_labels_by_task_id_handler(
+        state: State<AppState>,
+        Path(task_id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        Self::labels_of(&state, LabelTarget::Task(task_id)).await
+    }
+
+    pub async fn get_labels_by_sub_task_id_handler(
+        state: State<AppState>,
+        Path(sub_task_id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        Self::labels_of(&state, LabelTarget::SubTask(sub_task_id)).await
+    }
+
+    async fn labels_of(state: &AppState, target: LabelTarget) -> axum::response::Response {
+        match LabelService::get_labels_by_target(&state.db, target).await {
+            Ok(labels) => (StatusCode::OK, Json(label_dtos(labels))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+fn label_dto(label: label::Model) -> LabelDto {
+    LabelDto {
+        id: label.id,
+        name: label.name,
+        color: label.color,
+        created_at: label.created_at,
+        updated_at: label.updated_at,
+    }
+}
+
+fn label_dtos(labels: Vec<label::Model>) -> Vec<LabelDto> {
+    labels.into_iter().map(label_dto).collect()
+}
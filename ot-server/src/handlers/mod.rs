@@ -1,4 +1,16 @@
+pub mod billing_handlers;
+pub mod budget_handlers;
+pub mod client_handlers;
+pub mod comment_handlers;
+pub mod dependency_handlers;
+pub mod health_handlers;
+pub mod invoice_handlers;
+pub mod label_handlers;
 pub mod project_handlers;
+pub mod recurrence_handlers;
+pub mod search_handlers;
 pub mod section_handlers;
 pub mod sub_task_handlers;
 pub mod task_handlers;
+pub mod timer_handlers;
+pub mod tracking_policy_handlers;
@@ -1,15 +1,16 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
     response::{IntoResponse, Json},
 };
 use axum_valid::Valid;
 use sea_orm::prelude::Uuid;
 
 use crate::{
-    dtos::project_dto::{ProjectCreateDto, ProjectDto, ProjectUpdateDto},
+    dtos::project_dto::{ProjectCreateDto, ProjectDto, ProjectFilterParams, ProjectUpdateDto},
+    entities::project,
     services::project_service::ProjectService,
-    utils::error::AppError,
+    utils::{admin::require_admin, error::AppError},
     AppState,
 };
 pub struct ProjectQueryHandlers;
@@ -33,13 +34,7 @@ impl ProjectMutationHandlers {
         match ProjectService::create_project(&state.db, payload).await {
             Ok(project) => (
                 StatusCode::CREATED,
-                Json(ProjectDto {
-                    id: project.id,
-                    name: project.name,
-                    version: project.version,
-                    created_at: project.created_at,
-                    updated_at: project.updated_at,
-                }),
+                Json(project_dto(project)),
             )
                 .into_response(),
             Err(e) => e.into_response(),
@@ -49,24 +44,43 @@ impl ProjectMutationHandlers {
     pub async fn project_update_handler(
         state: State<AppState>,
         Path(id): Path<Uuid>,
-        Json(payload): Json<ProjectUpdateDto>,
+        Valid(Json(payload)): Valid<Json<ProjectUpdateDto>>,
     ) -> impl IntoResponse {
         match ProjectService::update_project(&state.db, id, payload).await {
             Ok(project) => (
                 StatusCode::OK,
-                Json(ProjectDto {
-                    id: project.id,
-                    name: project.name,
-                    version: project.version,
-                    created_at: project.created_at,
-                    updated_at: project.updated_at,
-                }),
+                Json(project_dto(project)),
             )
                 .into_response(),
             Err(e) => e.into_response(),
         }
     }
 
+    pub async fn project_archive_handler(
+        state: State<AppState>,
+        Path(id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        match ProjectService::archive_project(&state.db, id).await {
+            Ok(project) => (StatusCode::OK, Json(project_dto(project))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Only for holders of the admin token.
+    pub async fn project_unarchive_handler(
+        state: State<AppState>,
+        headers: HeaderMap,
+        Path(id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        if let Err(e) = require_admin(&headers, &state.settings.server) {
+            return e.into_response();
+        }
+        match ProjectService::unarchive_project(&state.db, id).await {
+            Ok(project) => (StatusCode::OK, Json(project_dto(project))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
     pub async fn project_delete_handler(
         state: State<AppState>,
         Path(id): Path<Uuid>,
@@ -79,18 +93,15 @@ impl ProjectMutationHandlers {
 }
 
 impl ProjectQueryHandlers {
-    pub async fn project_list_handler(state: State<AppState>) -> impl IntoResponse {
-        match ProjectService::get_projects(&state.db).await {
+    pub async fn project_list_handler(
+        state: State<AppState>,
+        Query(filter): Query<ProjectFilterParams>,
+    ) -> impl IntoResponse {
+        match ProjectService::get_projects(&state.db, filter).await {
             Ok(projects) => {
                 let projects = projects
                     .into_iter()
-                    .map(|project| ProjectDto {
-                        id: project.id,
-                        name: project.name,
-                        version: project.version,
-                        created_at: project.created_at,
-                        updated_at: project.updated_at,
-                    })
+                    .map(project_dto)
                     .collect::<Vec<_>>();
                 (StatusCode::OK, Json(projects)).into_response()
             }
@@ -104,13 +115,7 @@ impl ProjectQueryHandlers {
         match ProjectService::get_project_by_id(&state.db, id).await {
             Ok(Some(project)) => (
                 StatusCode::OK,
-                Json(ProjectDto {
-                    id: project.id,
-                    name: project.name,
-                    version: project.version,
-                    created_at: project.created_at,
-                    updated_at: project.updated_at,
-                }),
+                Json(project_dto(project)),
             )
                 .into_response(),
             Ok(None) => {
@@ -129,3 +134,63 @@ impl ProjectQueryHandlers {
     //     }
     // }
 }
+
+fn project_dto(project: project::Model) -> ProjectDto {
+    ProjectDto {
+        id: project.id,
+        name: project.name,
+        version: project.version,
+        client_id: project.client_id,
+        billable: project.billable,
+        budget_minutes: project.budget_minutes,
+        budget_amount: project.budget_amount,
+        budget_currency: project.budget_currency,
+        archived_at: project.archived_at,
+        created_at: project.created_at,
+        updated_at: project.updated_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{
+        extract::{Path, State},
+        http::{header::AUTHORIZATION, HeaderMap, StatusCode},
+        response::IntoResponse,
+    };
+    use sea_orm::{prelude::Uuid, DatabaseConnection};
+
+    use super::ProjectMutationHandlers as PMH;
+    use crate::{config::settings::Settings, utils::metrics::Metrics, AppState};
+
+    /// The admin check comes before any query, so no database is needed.
+    fn state() -> AppState {
+        let mut settings = Settings::default();
+        settings.server.admin_token = Some("secret".to_string());
+        AppState {
+            db: Arc::new(DatabaseConnection::Disconnected),
+            settings: Arc::new(settings),
+            metrics: Arc::new(Metrics::new()),
+            client: reqwest::Client::new(),
+            is_standalone: true,
+        }
+    }
+
+    async fn unarchive(headers: HeaderMap) -> StatusCode {
+        PMH::project_unarchive_handler(State(state()), headers, Path(Uuid::nil()))
+            .await
+            .into_response()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn unarchive_needs_the_admin_token() {
+        assert_eq!(unarchive(HeaderMap::new()).await, StatusCode::FORBIDDEN);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, "Bearer guess".parse().unwrap());
+        assert_eq!(unarchive(headers).await, StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,90 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use axum_valid::Valid;
+use sea_orm::prelude::Uuid;
+
+use crate::{
+    dtos::{
+        recurrence_dto::{TaskRecurrenceDto, TaskRecurrenceSetDto},
+        task_dto::TaskDto,
+    },
+    entities::task_recurrence,
+    handlers::task_handlers::task_dto,
+    repository::recurrence_repository::recurrence_rule,
+    services::recurrence_service::RecurrenceService,
+    utils::error::AppError,
+    AppState,
+};
+
+pub struct RecurrenceMutationHandlers;
+pub struct RecurrenceQueryHandlers;
+
+impl RecurrenceMutationHandlers {
+    pub async fn set_recurrence_handler(
+        state: State<AppState>,
+        Path(task_id): Path<Uuid>,
+        Valid(Json(payload)): Valid<Json<TaskRecurrenceSetDto>>,
+    ) -> impl IntoResponse {
+        match RecurrenceService::set_recurrence(&state.db, task_id, payload).await {
+            Ok(recurrence) => (StatusCode::OK, Json(recurrence_dto(recurrence))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn delete_recurrence_handler(
+        state: State<AppState>,
+        Path(task_id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        match RecurrenceService::delete_recurrence(&state.db, task_id).await {
+            Ok(0) => AppError::NotFound(format!("Task {} does not recur", task_id)).into_response(),
+            Ok(_) => (StatusCode::OK, format!("Task {} no longer recurs", task_id)).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+impl RecurrenceQueryHandlers {
+    pub async fn get_recurrence_by_task_id_handler(
+        state: State<AppState>,
+        Path(task_id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        match RecurrenceService::get_recurrence(&state.db, task_id).await {
+            Ok(recurrence) => (StatusCode::OK, Json(recurrence_dto(recurrence))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn get_occurrences_by_task_id_handler(
+        state: State<AppState>,
+        Path(task_id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        match RecurrenceService::get_occurrences(&state.db, task_id).await {
+            Ok(tasks) => (
+                StatusCode::OK,
+                Json(tasks.into_iter().map(task_dto).collect::<Vec<TaskDto>>()),
+            )
+                .into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+fn recurrence_dto(recurrence: task_recurrence::Model) -> TaskRecurrenceDto {
+    TaskRecurrenceDto {
+        rule: recurrence_rule(&recurrence).to_string(),
+        id: recurrence.id,
+        task_id: recurrence.task_id,
+        frequency: recurrence.frequency,
+        interval: recurrence.interval,
+        starts_on: recurrence.starts_on,
+        until: recurrence.until,
+        count: recurrence.count,
+        next_occurrence: recurrence.next_occurrence,
+        created_at: recurrence.created_at,
+        updated_at: recurrence.updated_at,
+    }
+}
@@ -0,0 +1,80 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use axum_valid::Valid;
+
+use crate::{
+    dtos::search_dto::{BreadcrumbDto, SearchHitDto, SearchHitKind, SearchParams},
+    repository::search_repository::SearchRow,
+    services::search_service::SearchService,
+    AppState,
+};
+
+pub struct SearchQueryHandlers;
+
+impl SearchQueryHandlers {
+    pub async fn search_handler(
+        state: State<AppState>,
+        Valid(Query(params)): Valid<Query<SearchParams>>,
+    ) -> impl IntoResponse {
+        match SearchService::search(&state.db, params).await {
+            Ok(rows) => {
+                let hits = rows.into_iter().filter_map(search_hit).collect::<Vec<_>>();
+                (StatusCode::OK, Json(hits)).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+fn search_hit(row: SearchRow) -> Option<SearchHitDto> {
+    let kind = match row.kind.as_str() {
+        "project" => SearchHitKind::Project,
+        "section" => SearchHitKind::Section,
+        "task" => SearchHitKind::Task,
+        "sub_task" => SearchHitKind::SubTask,
+        _ => return None,
+    };
+
+    // Ancestors only; the row repeats its own id in the column for its kind
+    let mut breadcrumbs = Vec::new();
+    if kind != SearchHitKind::Project {
+        breadcrumbs.push(BreadcrumbDto {
+            kind: SearchHitKind::Project,
+            id: row.project_id,
+            name: row.project_name,
+        });
+    }
+    if matches!(kind, SearchHitKind::Task | SearchHitKind::SubTask) {
+        if let (Some(id), Some(name)) = (row.section_id, row.section_name) {
+            breadcrumbs.push(BreadcrumbDto {
+                kind: SearchHitKind::Section,
+                id,
+                name,
+            });
+        }
+    }
+    if kind == SearchHitKind::SubTask {
+        if let (Some(id), Some(name)) = (row.task_id, row.task_name) {
+            breadcrumbs.push(BreadcrumbDto {
+                kind: SearchHitKind::Task,
+                id,
+                name,
+            });
+        }
+    }
+
+    Some(SearchHitDto {
+        kind,
+        id: row.id,
+        name: row.name,
+        project_id: row.project_id,
+        section_id: row.section_id,
+        task_id: row.task_id,
+        breadcrumbs,
+        rank: row.rank,
+    })
+}
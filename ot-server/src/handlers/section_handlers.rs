@@ -8,7 +8,8 @@ use reqwest::StatusCode;
 use sea_orm::prelude::Uuid;
 
 use crate::{
-    dtos::section_dto::{SectionCreateDto, SectionDto, SectionUpdateDto},
+    dtos::section_dto::{SectionCreateDto, SectionDto, SectionMoveDto, SectionUpdateDto},
+    entities::section,
     services::section_service::SectionService,
     utils::error::AppError,
     AppState,
@@ -25,12 +26,7 @@ impl SectionMutationHandlers {
         match SectionService::create_section(&state.db, payload).await {
             Ok(section) => (
                 StatusCode::OK,
-                Json(SectionDto {
-                    id: section.id,
-                    name: section.name,
-                    created_at: section.created_at,
-                    updated_at: section.updated_at,
-                }),
+                Json(section_dto(section)),
             )
                 .into_response(),
             Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
@@ -45,18 +41,24 @@ impl SectionMutationHandlers {
         match SectionService::update_section(&state.db, id, payload).await {
             Ok(section) => (
                 StatusCode::OK,
-                Json(SectionDto {
-                    id: section.id,
-                    name: section.name,
-                    created_at: section.created_at,
-                    updated_at: section.updated_at,
-                }),
+                Json(section_dto(section)),
             )
                 .into_response(),
             Err(e) => e.into_response(),
         }
     }
 
+    pub async fn move_section_handler(
+        state: State<AppState>,
+        Path(id): Path<Uuid>,
+        Json(payload): Json<SectionMoveDto>,
+    ) -> impl IntoResponse {
+        match SectionService::move_section(&state.db, id, payload).await {
+            Ok(section) => (StatusCode::OK, Json(section_dto(section))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
     pub async fn delete_section_handler(
         state: State<AppState>,
         Path(section_id): Path<Uuid>,
@@ -80,12 +82,7 @@ impl SectionQueryHandlers {
         match SectionService::get_section_by_id(&state.db, id).await {
             Ok(Some(section)) => (
                 StatusCode::OK,
-                Json(SectionDto {
-                    id: section.id,
-                    name: section.name,
-                    created_at: section.created_at,
-                    updated_at: section.updated_at,
-                }),
+                Json(section_dto(section)),
             )
                 .into_response(),
             Ok(None) => {
@@ -104,13 +101,8 @@ impl SectionQueryHandlers {
                 StatusCode::OK,
                 Json(
                     sections
-                        .iter()
-                        .map(|s| SectionDto {
-                            id: s.id,
-                            name: s.name.clone(),
-                            created_at: s.created_at,
-                            updated_at: s.updated_at,
-                        })
+                        .into_iter()
+                        .map(section_dto)
                         .collect::<Vec<SectionDto>>(),
                 ),
             )
@@ -125,13 +117,8 @@ impl SectionQueryHandlers {
                 StatusCode::OK,
                 Json(
                     sections
-                        .iter()
-                        .map(|s| SectionDto {
-                            id: s.id,
-                            name: s.name.clone(),
-                            created_at: s.created_at,
-                            updated_at: s.updated_at,
-                        })
+                        .into_iter()
+                        .map(section_dto)
                         .collect::<Vec<SectionDto>>(),
                 ),
             )
@@ -140,3 +127,14 @@ impl SectionQueryHandlers {
         }
     }
 }
+
+fn section_dto(section: section::Model) -> SectionDto {
+    SectionDto {
+        id: section.id,
+        name: section.name,
+        project_id: section.project_id,
+        position: section.position,
+        created_at: section.created_at,
+        updated_at: section.updated_at,
+    }
+}
@@ -1,18 +1,22 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
     Json,
 };
+use axum_valid::Valid;
 use sea_orm::prelude::Uuid;
 use sea_orm::DatabaseConnection;
 
 use crate::{
-    dtos::sub_task_dto::{SubTaskCreateDto, SubTaskUpdateDto},
+    dtos::sub_task_dto::{SubTaskCreateDto, SubTaskMoveDto, SubTaskUpdateDto},
     AppState,
 };
 use crate::{
-    dtos::{sub_task_dto::SubTaskDto, task_dto::TaskDto},
+    dtos::{
+        sub_task_dto::SubTaskDto,
+        task_dto::{TaskDto, TaskFilterParams},
+    },
     utils::error::AppError,
 };
 use crate::{entities::sub_task, services::sub_task_service::SubTaskService};
@@ -26,18 +30,15 @@ pub struct CreateSubTaskPath {
 pub struct SubTaskHandlers;
 
 impl SubTaskHandlers {
-    pub async fn get_sub_tasks_handler(State(state): State<AppState>) -> impl IntoResponse {
-        match SubTaskService::get_sub_tasks(&state.db).await {
+    pub async fn get_sub_tasks_handler(
+        State(state): State<AppState>,
+        Valid(Query(filter)): Valid<Query<TaskFilterParams>>,
+    ) -> impl IntoResponse {
+        match SubTaskService::get_sub_tasks(&state.db, filter).await {
             Ok(tasks) => {
                 let result_data = tasks
-                    .iter()
-                    .map(|t| SubTaskDto {
-                        id: t.id,
-                        name: t.name.clone(),
-                        section_id: t.section_id,
-                        created_at: t.created_at,
-                        updated_at: t.updated_at,
-                    })
+                    .into_iter()
+                    .map(sub_task_dto)
                     .collect::<Vec<SubTaskDto>>();
                 (StatusCode::OK, Json(result_data)).into_response()
             }
@@ -53,13 +54,7 @@ impl SubTaskHandlers {
             Ok(task) => match task {
                 Some(task) => (
                     StatusCode::OK,
-                    Json(SubTaskDto {
-                        id: task.id,
-                        name: task.name.clone(),
-                        section_id: task.section_id,
-                        created_at: task.created_at,
-                        updated_at: task.updated_at,
-                    }),
+                    Json(sub_task_dto(task)),
                 )
                     .into_response(),
                 None => (StatusCode::NOT_FOUND, "SubTask not found").into_response(),
@@ -71,18 +66,13 @@ impl SubTaskHandlers {
     pub async fn get_sub_tasks_by_section_id_handler(
         State(state): State<AppState>,
         Path(section_id): Path<Uuid>,
+        Valid(Query(filter)): Valid<Query<TaskFilterParams>>,
     ) -> impl IntoResponse {
-        match SubTaskService::get_sub_tasks_by_section_id(&state.db, section_id).await {
+        match SubTaskService::get_sub_tasks_by_section_id(&state.db, section_id, filter).await {
             Ok(tasks) => {
                 let result_data = tasks
-                    .iter()
-                    .map(|t| SubTaskDto {
-                        id: t.id,
-                        name: t.name.clone(),
-                        section_id: t.section_id,
-                        created_at: t.created_at,
-                        updated_at: t.updated_at,
-                    })
+                    .into_iter()
+                    .map(sub_task_dto)
                     .collect::<Vec<SubTaskDto>>();
                 (StatusCode::OK, Json(result_data)).into_response()
             }
@@ -97,14 +87,8 @@ impl SubTaskHandlers {
         match SubTaskService::get_sub_tasks_by_task_id(&state.db, task_id).await {
             Ok(tasks) => {
                 let result_data = tasks
-                    .iter()
-                    .map(|t| SubTaskDto {
-                        id: t.id,
-                        name: t.name.clone(),
-                        section_id: t.section_id,
-                        created_at: t.created_at,
-                        updated_at: t.updated_at,
-                    })
+                    .into_iter()
+                    .map(sub_task_dto)
                     .collect::<Vec<SubTaskDto>>();
                 (StatusCode::OK, Json(result_data)).into_response()
             }
@@ -115,7 +99,7 @@ impl SubTaskHandlers {
     pub async fn create_sub_task_handler(
         State(state): State<AppState>,
         Path(path_params): Path<CreateSubTaskPath>,
-        Json(data): Json<SubTaskCreateDto>,
+        Valid(Json(data)): Valid<Json<SubTaskCreateDto>>,
     ) -> impl IntoResponse {
         match SubTaskService::create_sub_task_for_task_and_section(
             &state.db,
@@ -127,13 +111,7 @@ impl SubTaskHandlers {
         {
             Ok(sub_task) => (
                 StatusCode::CREATED,
-                Json(SubTaskDto {
-                    id: sub_task.id,
-                    name: sub_task.name.clone(),
-                    section_id: sub_task.section_id,
-                    created_at: sub_task.created_at,
-                    updated_at: sub_task.updated_at,
-                }),
+                Json(sub_task_dto(sub_task)),
             )
                 .into_response(),
             Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
@@ -143,24 +121,29 @@ impl SubTaskHandlers {
     pub async fn update_sub_task_handler(
         State(state): State<AppState>,
         Path(id): Path<Uuid>,
-        Json(data): Json<SubTaskUpdateDto>,
+        Valid(Json(data)): Valid<Json<SubTaskUpdateDto>>,
     ) -> impl IntoResponse {
         match SubTaskService::update_sub_task(&state.db, id, data).await {
             Ok(task) => (
                 StatusCode::OK,
-                Json(SubTaskDto {
-                    id: task.id,
-                    name: task.name.clone(),
-                    section_id: task.section_id,
-                    created_at: task.created_at,
-                    updated_at: task.updated_at,
-                }),
+                Json(sub_task_dto(task)),
             )
                 .into_response(),
             Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
         }
     }
 
+    pub async fn move_sub_task_handler(
+        State(state): State<AppState>,
+        Path(id): Path<Uuid>,
+        Json(data): Json<SubTaskMoveDto>,
+    ) -> impl IntoResponse {
+        match SubTaskService::move_sub_task(&state.db, id, data).await {
+            Ok(sub_task) => (StatusCode::OK, Json(sub_task_dto(sub_task))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
     pub async fn delete_sub_task_handler(
         State(state): State<AppState>,
         Path(id): Path<Uuid>,
@@ -171,3 +154,21 @@ impl SubTaskHandlers {
         }
     }
 }
+
+fn sub_task_dto(sub_task: sub_task::Model) -> SubTaskDto {
+    SubTaskDto {
+        id: sub_task.id,
+        name: sub_task.name,
+        section_id: sub_task.section_id,
+        task_id: sub_task.task_id,
+        position: sub_task.position,
+        description: sub_task.description,
+        status: sub_task.status,
+        priority: sub_task.priority,
+        due_date: sub_task.due_date,
+        estimated_minutes: sub_task.estimated_minutes,
+        assignee: sub_task.assignee,
+        created_at: sub_task.created_at,
+        updated_at: sub_task.updated_at,
+    }
+}
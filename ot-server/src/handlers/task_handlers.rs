@@ -1,5 +1,5 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     response::IntoResponse,
     Json,
 };
@@ -8,8 +8,8 @@ use reqwest::StatusCode;
 use sea_orm::prelude::Uuid;
 
 use crate::{
-    dtos::task_dto::{TaskCreateDto, TaskDto, TaskUpdateDto},
-    services::task_service::TaskService,
+    dtos::task_dto::{TaskCreateDto, TaskDto, TaskFilterParams, TaskMoveDto, TaskUpdateDto},
+    services::task_service::{TaskService, TaskWithBlocked},
     utils::error::AppError,
     AppState,
 };
@@ -26,13 +26,7 @@ impl TaskMutationHandlers {
         match TaskService::create_task(&state.db, payload, section_id).await {
             Ok(task) => (
                 StatusCode::OK,
-                Json(TaskDto {
-                    id: task.id,
-                    name: task.name,
-                    section_id: task.section_id,
-                    created_at: task.created_at,
-                    updated_at: task.updated_at,
-                }),
+                Json(task_dto((task, false))),
             )
                 .into_response(),
             Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
@@ -47,19 +41,24 @@ impl TaskMutationHandlers {
         match TaskService::update_task(&state.db, id, payload).await {
             Ok(task) => (
                 StatusCode::OK,
-                Json(TaskDto {
-                    id: task.id,
-                    name: task.name,
-                    section_id: task.section_id,
-                    created_at: task.created_at,
-                    updated_at: task.updated_at,
-                }),
+                Json(task_dto(task)),
             )
                 .into_response(),
             Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
         }
     }
 
+    pub async fn move_task_handler(
+        state: State<AppState>,
+        Path(id): Path<Uuid>,
+        Json(payload): Json<TaskMoveDto>,
+    ) -> impl IntoResponse {
+        match TaskService::move_task(&state.db, id, payload).await {
+            Ok(task) => (StatusCode::OK, Json(task_dto(task))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
     pub async fn delete_task_handler(
         state: State<AppState>,
         Path(task_id): Path<Uuid>,
@@ -74,18 +73,15 @@ impl TaskMutationHandlers {
 }
 
 impl TaskQueryHandlers {
-    pub async fn get_tasks_handler(state: State<AppState>) -> impl IntoResponse {
-        match TaskService::get_tasks(&state.db).await {
+    pub async fn get_tasks_handler(
+        state: State<AppState>,
+        Valid(Query(filter)): Valid<Query<TaskFilterParams>>,
+    ) -> impl IntoResponse {
+        match TaskService::get_tasks(&state.db, filter).await {
             Ok(tasks) => {
                 let result_data = tasks
-                    .iter()
-                    .map(|t| TaskDto {
-                        id: t.id,
-                        name: t.name.clone(),
-                        section_id: t.section_id,
-                        created_at: t.created_at,
-                        updated_at: t.updated_at,
-                    })
+                    .into_iter()
+                    .map(task_dto)
                     .collect::<Vec<TaskDto>>();
                 (StatusCode::OK, Json(result_data)).into_response()
             }
@@ -100,13 +96,7 @@ impl TaskQueryHandlers {
         match TaskService::get_task_by_id(&state.db, id).await {
             Ok(Some(task)) => (
                 StatusCode::OK,
-                Json(TaskDto {
-                    id: task.id,
-                    name: task.name,
-                    section_id: task.section_id,
-                    created_at: task.created_at,
-                    updated_at: task.updated_at,
-                }),
+                Json(task_dto(task)),
             )
                 .into_response(),
             Ok(None) => {
@@ -119,18 +109,13 @@ impl TaskQueryHandlers {
     pub async fn get_tasks_by_section_id_handler(
         state: State<AppState>,
         Path(section_id): Path<Uuid>,
+        Valid(Query(filter)): Valid<Query<TaskFilterParams>>,
     ) -> impl IntoResponse {
-        match TaskService::get_tasks_by_section_id(&state.db, section_id).await {
+        match TaskService::get_tasks_by_section_id(&state.db, section_id, filter).await {
             Ok(tasks) => {
                 let result_data = tasks
-                    .iter()
-                    .map(|t| TaskDto {
-                        id: t.id,
-                        name: t.name.clone(),
-                        section_id: t.section_id,
-                        created_at: t.created_at,
-                        updated_at: t.updated_at,
-                    })
+                    .into_iter()
+                    .map(task_dto)
                     .collect::<Vec<TaskDto>>();
                 (StatusCode::OK, Json(result_data)).into_response()
             }
@@ -138,3 +123,22 @@ impl TaskQueryHandlers {
         }
     }
 }
+
+pub(crate) fn task_dto((task, blocked): TaskWithBlocked) -> TaskDto {
+    TaskDto {
+        id: task.id,
+        name: task.name,
+        section_id: task.section_id,
+        position: task.position,
+        description: task.description,
+        status: task.status,
+        priority: task.priority,
+        due_date: task.due_date,
+        estimated_minutes: task.estimated_minutes,
+        assignee: task.assignee,
+        blocked,
+        billable: task.billable,
+        created_at: task.created_at,
+        updated_at: task.updated_at,
+    }
+}
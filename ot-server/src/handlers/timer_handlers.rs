@@ -0,0 +1,131 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use axum_valid::Valid;
+use sea_orm::prelude::Uuid;
+use serde_json::json;
+
+use crate::{
+    dtos::{
+        activity_dto::{ActivityDataCreateDto, WindowActivityDataCreateDto},
+        timer_dto::{TimerSessionCreateDto, TimerSessionDto, TimerTargetCheckDto, TimerTargetDto},
+    },
+    entities::timer_session,
+    services::timer_service::{TimerService, TimerTarget},
+    AppState,
+};
+
+pub struct TimerMutationHandlers;
+pub struct TimerQueryHandlers;
+
+impl TimerMutationHandlers {
+    pub async fn upload_session_handler(
+        state: State<AppState>,
+        Valid(Json(payload)): Valid<Json<TimerSessionCreateDto>>,
+    ) -> impl IntoResponse {
+        match TimerService::upload_session(&state.db, payload).await {
+            Ok((session, created)) => {
+                // Retried uploads of a session already stored are not counted
+                if created {
+                    state.metrics.timers_started.inc();
+                }
+                (upload_status(created), Json(session_dto(session))).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn upload_activity_handler(
+        state: State<AppState>,
+        Valid(Json(payload)): Valid<Json<ActivityDataCreateDto>>,
+    ) -> impl IntoResponse {
+        let id = payload.id;
+        let screenshots = payload.screenshots.len() as u64;
+        match TimerService::upload_activity(&state.db, payload).await {
+            Ok(created) => {
+                if created {
+                    state.metrics.screenshots_stored.inc_by(screenshots);
+                }
+                (upload_status(created), Json(json!({ "id": id }))).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn upload_window_activity_handler(
+        state: State<AppState>,
+        Valid(Json(payload)): Valid<Json<WindowActivityDataCreateDto>>,
+    ) -> impl IntoResponse {
+        let id = payload.id;
+        match TimerService::upload_window_activity(&state.db, payload).await {
+            Ok(created) => (upload_status(created), Json(json!({ "id": id }))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+impl TimerQueryHandlers {
+    /// Asked by the desktop before it starts or switches a timer.
+    pub async fn check_target_handler(
+        state: State<AppState>,
+        Json(payload): Json<TimerTargetCheckDto>,
+    ) -> impl IntoResponse {
+        match TimerService::check_target(&state.db, payload.task_id, payload.sub_task_id).await {
+            Ok(target) => (StatusCode::OK, Json(target_dto(target))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn get_sessions_by_task_id_handler(
+        state: State<AppState>,
+        Path(task_id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        match TimerService::get_sessions_by_task_id(&state.db, task_id).await {
+            Ok(sessions) => (
+                StatusCode::OK,
+                Json(sessions.into_iter().map(session_dto).collect::<Vec<_>>()),
+            )
+                .into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+fn session_dto(session: timer_session::Model) -> TimerSessionDto {
+    TimerSessionDto {
+        id: session.id,
+        task_id: session.task_id,
+        sub_task_id: session.sub_task_id,
+        start_time: session.start_time,
+        end_time: session.end_time,
+        elapsed_seconds: session.elapsed_seconds,
+        person: session.person,
+        note: session.note,
+        created_at: session.created_at,
+    }
+}
+
+fn target_dto(target: TimerTarget) -> TimerTargetDto {
+    TimerTargetDto {
+        task_id: target.task.id,
+        task_name: target.task.name,
+        sub_task_id: target.sub_task.as_ref().map(|sub_task| sub_task.id),
+        sub_task_name: target.sub_task.map(|sub_task| sub_task.name),
+        project_id: target.project.id,
+        project_name: target.project.name,
+        billable: target.project.billable && target.task.billable,
+        blocked: target.blocked,
+    }
+}
+
+/// `201 Created` for a new upload, `200 OK` for one already stored.
+fn upload_status(created: bool) -> StatusCode {
+    if created {
+        StatusCode::CREATED
+    } else {
+        StatusCode::OK
+    }
+}
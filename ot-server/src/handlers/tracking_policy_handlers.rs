@@ -0,0 +1,186 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use axum_valid::Valid;
+use sea_orm::prelude::Uuid;
+
+use crate::{
+    dtos::tracking_policy_dto::{
+        EffectiveTrackingPolicyDto, EffectiveTrackingPolicyParams, TrackingPolicyDto,
+        TrackingPolicyUpdateDto,
+    },
+    services::tracking_policy_service::{EffectivePolicy, TrackingPolicy, TrackingPolicyService},
+    utils::{admin::require_admin, error::AppError},
+    AppState,
+};
+
+pub struct TrackingPolicyMutationHandlers;
+pub struct TrackingPolicyQueryHandlers;
+
+impl TrackingPolicyMutationHandlers {
+    /// Only for holders of the admin token.
+    pub async fn save_global_policy_handler(
+        state: State<AppState>,
+        headers: HeaderMap,
+        Valid(Json(payload)): Valid<Json<TrackingPolicyUpdateDto>>,
+    ) -> impl IntoResponse {
+        if let Err(e) = require_admin(&headers, &state.settings.server) {
+            return e.into_response();
+        }
+        match TrackingPolicyService::save_policy(&state.db, None, payload).await {
+            Ok(policy) => (StatusCode::OK, Json(policy_dto(policy))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Only for holders of the admin token.
+    pub async fn save_project_policy_handler(
+        state: State<AppState>,
+        headers: HeaderMap,
+        Path(project_id): Path<Uuid>,
+        Valid(Json(payload)): Valid<Json<TrackingPolicyUpdateDto>>,
+    ) -> impl IntoResponse {
+        if let Err(e) = require_admin(&headers, &state.settings.server) {
+            return e.into_response();
+        }
+        match TrackingPolicyService::save_policy(&state.db, Some(project_id), payload).await {
+            Ok(policy) => (StatusCode::OK, Json(policy_dto(policy))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Only for holders of the admin token. Only the global policy applies
+    /// to the project afterwards.
+    pub async fn delete_project_policy_handler(
+        state: State<AppState>,
+        headers: HeaderMap,
+        Path(project_id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        if let Err(e) = require_admin(&headers, &state.settings.server) {
+            return e.into_response();
+        }
+        match TrackingPolicyService::delete_policy(&state.db, Some(project_id)).await {
+            Ok(0) => AppError::NotFound(format!(
+                "Project with id {} has no tracking policy",
+                project_id
+            ))
+            .into_response(),
+            Ok(_) => (
+                StatusCode::OK,
+                format!("Tracking policy of project {} deleted", project_id),
+            )
+                .into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+impl TrackingPolicyQueryHandlers {
+    pub async fn get_global_policy_handler(state: State<AppState>) -> impl IntoResponse {
+        match TrackingPolicyService::get_policy(&state.db, None).await {
+            Ok(Some(policy)) => (StatusCode::OK, Json(policy_dto(policy))).into_response(),
+            Ok(None) => AppError::NotFound("No global tracking policy".to_string()).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn get_project_policy_handler(
+        state: State<AppState>,
+        Path(project_id): Path<Uuid>,
+    ) -> impl IntoResponse {
+        match TrackingPolicyService::get_policy(&state.db, Some(project_id)).await {
+            Ok(Some(policy)) => (StatusCode::OK, Json(policy_dto(policy))).into_response(),
+            Ok(None) => AppError::NotFound(format!(
+                "Project with id {} has no tracking policy",
+                project_id
+            ))
+            .into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    pub async fn get_effective_policy_handler(
+        state: State<AppState>,
+        Query(params): Query<EffectiveTrackingPolicyParams>,
+    ) -> impl IntoResponse {
+        match TrackingPolicyService::get_effective_policy(&state.db, params.project_id).await {
+            Ok(policy) => (StatusCode::OK, Json(effective_dto(policy))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+fn policy_dto(policy: TrackingPolicy) -> TrackingPolicyDto {
+    TrackingPolicyDto {
+        id: policy.id,
+        project_id: policy.project_id,
+        settings: policy.policy.settings,
+        locked: policy.policy.locked,
+        updated_at: policy.updated_at,
+    }
+}
+
+fn effective_dto(policy: EffectivePolicy) -> EffectiveTrackingPolicyDto {
+    EffectiveTrackingPolicyDto {
+        project_id: policy.project_id,
+        settings: policy.policy.settings,
+        locked: policy.policy.locked,
+        updated_at: policy.updated_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{
+        extract::State,
+        http::{header::AUTHORIZATION, HeaderMap, StatusCode},
+        response::IntoResponse,
+        Json,
+    };
+    use axum_valid::Valid;
+    use sea_orm::DatabaseConnection;
+
+    use super::TrackingPolicyMutationHandlers as TPMH;
+    use crate::{
+        config::settings::Settings, dtos::tracking_policy_dto::TrackingPolicyUpdateDto,
+        utils::metrics::Metrics, AppState,
+    };
+
+    /// The admin check comes before any query, so no database is needed.
+    fn state() -> AppState {
+        let mut settings = Settings::default();
+        settings.server.admin_token = Some("secret".to_string());
+        AppState {
+            db: Arc::new(DatabaseConnection::Disconnected),
+            settings: Arc::new(settings),
+            metrics: Arc::new(Metrics::new()),
+            client: reqwest::Client::new(),
+            is_standalone: true,
+        }
+    }
+
+    async fn set_global(headers: HeaderMap) -> StatusCode {
+        let payload = TrackingPolicyUpdateDto {
+            settings: Default::default(),
+            locked: Vec::new(),
+        };
+        TPMH::save_global_policy_handler(State(state()), headers, Valid(Json(payload)))
+            .await
+            .into_response()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn set_needs_the_admin_token() {
+        assert_eq!(set_global(HeaderMap::new()).await, StatusCode::FORBIDDEN);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, "Bearer guess".parse().unwrap());
+        assert_eq!(set_global(headers).await, StatusCode::FORBIDDEN);
+    }
+}
@@ -1,25 +1,46 @@
 #![deny(unsafe_code)]
 #![allow(unused_imports)]
-use axum::Router;
+use anyhow::Context;
+use axum::{middleware, Router};
+use axum_server::tls_rustls::RustlsConfig;
 use chrono::Local;
-use config::env::EnvironmentVariables;
+use clap::Parser;
+use config::{
+    cli::{Cli, Command},
+    settings::Settings,
+};
 use routes::{
-    project_route::project_routes, section_route::section_routes, sub_task_route::sub_task_routes,
+    billing_route::{rate_routes, time_routes},
+    budget_route::budget_routes,
+    client_route::client_routes,
+    comment_route::comment_routes,
+    dependency_route::dependency_routes,
+    health_route::{health_routes, metrics_routes},
+    invoice_route::invoice_routes,
+    label_route::label_routes,
+    project_route::project_routes,
+    recurrence_route::recurrence_routes,
+    search_route::search_routes,
+    section_route::section_routes,
+    sub_task_route::sub_task_routes,
     task_route::task_routes,
+    timer_route::timer_routes,
+    tracking_policy_route::tracking_policy_routes,
 };
 
 use sea_orm::{DatabaseConnection, DbErr};
 use std::{
-    error::Error,
     panic,
     sync::{Arc, RwLock},
     time::Duration,
 };
 use tokio::time::sleep;
 use tower_http::trace::TraceLayer;
+use services::{budget_service::BudgetService, recurrence_service::RecurrenceService};
 use tracing::{error, info, warn};
-use tracing_subscriber::{fmt::time::time, EnvFilter};
+use utils::metrics::{track_metrics, Metrics};
 
+mod admin;
 mod config;
 mod dtos;
 mod entities;
@@ -34,7 +55,8 @@ mod utils;
 #[derive(Clone)]
 pub struct AppState {
     pub db: Arc<DatabaseConnection>,
-    pub env: Arc<EnvironmentVariables>,
+    pub settings: Arc<Settings>,
+    pub metrics: Arc<Metrics>,
     pub client: reqwest::Client,
     pub is_standalone: bool,
 }
@@ -42,16 +64,14 @@ pub struct AppState {
 type ApplicationState = Arc<RwLock<AppState>>;
 
 impl AppState {
-    pub async fn from_env() -> anyhow::Result<Self> {
-        let env = EnvironmentVariables::from_env()?;
-
+    pub async fn from_settings(settings: Settings) -> anyhow::Result<Self> {
         // Implement connection retry logic
         let mut retry_count = 0;
         let max_retries = 5;
         let mut db = None;
 
         while retry_count < max_retries {
-            match config::database::initialize_database(&env).await {
+            match config::database::initialize_database(&settings.database).await {
                 Ok(connection) => {
                     db = Some(connection);
                     break;
@@ -76,27 +96,88 @@ impl AppState {
         })?;
         info!("Successfully connected to database");
 
+        let metrics = Metrics::new();
+        metrics.database_up.set(1);
+
         Ok(Self {
             db: Arc::new(db),
-            env: Arc::new(env),
+            settings: Arc::new(settings),
+            metrics: Arc::new(metrics),
             client: reqwest::Client::new(),
             is_standalone: true,
         })
     }
 
-    // Helper method to check database connection health
-    pub async fn check_db_health(&self) -> bool {
-        match self.db.ping().await {
-            Ok(_) => true,
-            Err(e) => {
-                error!("Database health check failed: {}", e);
-                false
+    /// Watch database connectivity without taking the server down. A failed
+    /// ping marks the database as down (readiness reports it too) and is
+    /// retried with backoff while the pool re-establishes its connections.
+    pub async fn monitor_database(self) {
+        const HEALTHY_INTERVAL: Duration = Duration::from_secs(30);
+        const MIN_BACKOFF: Duration = Duration::from_secs(2);
+        const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+        let mut backoff = MIN_BACKOFF;
+        loop {
+            match self.db.ping().await {
+                Ok(_) => {
+                    if self.metrics.database_up.get() == 0 {
+                        info!("Database connection restored, server ready again");
+                    }
+                    self.metrics.database_up.set(1);
+                    backoff = MIN_BACKOFF;
+                    sleep(HEALTHY_INTERVAL).await;
+                }
+                Err(e) => {
+                    self.metrics.database_up.set(0);
+                    error!(
+                        "Database health check failed: {}. Readiness degraded, retrying in {} seconds",
+                        e,
+                        backoff.as_secs()
+                    );
+                    sleep(backoff).await;
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                }
+            }
+        }
+    }
+
+    /// Generate upcoming occurrences of recurring tasks. Progress is kept in
+    /// the database under row locks, so restarts and several servers sharing
+    /// a database never generate an occurrence twice.
+    pub async fn schedule_recurrences(self) {
+        let scheduler = &self.settings.scheduler;
+        loop {
+            match RecurrenceService::generate_due_occurrences(
+                &self.db,
+                scheduler.recurrence_lookahead_days,
+            )
+            .await
+            {
+                Ok(0) => {}
+                Ok(created) => info!("Generated {} recurring task occurrence(s)", created),
+                Err(e) => error!("Recurring task scheduler failed: {}", e),
             }
+            sleep(scheduler.recurrence_interval()).await;
+        }
+    }
+
+    /// Raise alerts for budgets and estimates that reached a threshold.
+    /// Alerts are unique per threshold, so servers sharing a database never
+    /// raise one twice.
+    pub async fn check_budgets(self) {
+        let budget = &self.settings.budget;
+        loop {
+            match BudgetService::check_alerts(&self.db, budget).await {
+                Ok(0) => {}
+                Ok(raised) => info!("Raised {} budget alert(s)", raised),
+                Err(e) => error!("Budget check failed: {}", e),
+            }
+            sleep(budget.check_interval()).await;
         }
     }
 }
 
-async fn shutdown_signal(rx: tokio::sync::oneshot::Receiver<()>) {
+async fn shutdown_signal() {
     let ctrl_c = async {
         tokio::signal::ctrl_c()
             .await
@@ -114,54 +195,68 @@ async fn shutdown_signal(rx: tokio::sync::oneshot::Receiver<()>) {
     #[cfg(not(unix))]
     let terminate = std::future::pending::<()>();
 
-    let rx = async {
-        rx.await.ok();
-    };
-
     tokio::select! {
         _ = ctrl_c => {},
         _ = terminate => {},
-        _ = rx => {},
     }
 
     println!("[Backend] Shutdown signal received, starting graceful shutdown...");
 }
 
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn Error>> {
+async fn main() -> anyhow::Result<()> {
     // Set up panic hook for crash reporting
     panic::set_hook(Box::new(|panic_info| {
         error!("Server panic occurred: {}", panic_info);
         // You could add additional crash reporting here (e.g., to a monitoring service)
     }));
 
-    // Initialize logging first
-    // Initialize more verbose logging
-    tracing_subscriber::fmt()
-        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| {
-            EnvFilter::new("ot_server=debug,tower_http=debug,axum::rejection=trace,sea_orm=debug")
-        }))
-        .with_timer(time())
-        .with_thread_names(true)
-        .with_target(true)
-        .with_file(true)
-        .with_line_number(true)
-        .with_thread_ids(true)
-        .with_level(true)
-        .init();
+    // Resolve configuration before anything else so bad settings fail fast
+    let mut cli = Cli::parse();
+    let mut settings = Settings::load(&cli)?;
+
+    let command = cli.command.take().unwrap_or(Command::Serve);
+    if !matches!(command, Command::Serve) {
+        // Operator commands print their own output; keep the log quiet unless asked
+        if cli.log_level.is_none() {
+            settings.log.level = "warn".to_string();
+            settings.database.sqlx_logging = false;
+        }
+        config::logging::init(&settings.log);
+        return admin::run(command, &settings).await;
+    }
+
+    config::logging::init(&settings.log);
 
     info!("Starting server initialization");
+    settings.storage.ensure_dirs()?;
+
+    let addr = settings.server.socket_addr();
+    let tls = settings.server.tls.clone();
+    let cors = settings.cors.layer();
 
     // Initialize application state with retry logic
-    let state = AppState::from_env().await?;
+    let state = AppState::from_settings(settings).await?;
 
     // Database is already initialized in AppState
 
     let api_routes = Router::new()
+        .nest("/client", client_routes())
         .nest("/project", project_routes())
         .nest("/section", section_routes())
         .nest("/task", task_routes())
         .nest("/sub-task", sub_task_routes())
+        .nest("/comment", comment_routes())
+        .nest("/dependency", dependency_routes())
+        .nest("/recurrence", recurrence_routes())
+        .nest("/label", label_routes())
+        .nest("/search", search_routes())
+        .nest("/rate", rate_routes())
+        .nest("/time", time_routes())
+        .nest("/invoice", invoice_routes())
+        .nest("/budget", budget_routes())
+        .nest("/timer", timer_routes())
+        .nest("/tracking-policy", tracking_policy_routes())
         .layer(
             TraceLayer::new_for_http()
                 .make_span_with(
@@ -176,38 +271,63 @@ async fn main() -> Result<(), Box<dyn Error>> {
                 ),
         );
 
-    let app = Router::new()
+    let mut app = Router::new()
         .nest("/api", api_routes)
+        .nest("/health", health_routes())
+        .merge(metrics_routes())
+        .layer(middleware::from_fn_with_state(state.clone(), track_metrics))
         .with_state(state.clone());
+    if let Some(cors) = cors {
+        app = app.layer(cors);
+    }
 
-    let listener = tokio::net::TcpListener::bind("0.0.0.0:4000").await?;
-    info!("Server listening on port 3000");
+    // Spawn database health monitor
+    tokio::spawn(state.clone().monitor_database());
+    tokio::spawn(state.clone().schedule_recurrences());
+    tokio::spawn(state.clone().check_budgets());
 
-    // Set up graceful shutdown
-    let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+    let result = match tls {
+        Some(tls) => {
+            // rustls needs a process-wide crypto provider; ignore the error if
+            // another dependency already installed one.
+            let _ = rustls::crypto::ring::default_provider().install_default();
+            let tls_config = RustlsConfig::from_pem_file(&tls.cert_path, &tls.key_path)
+                .await
+                .with_context(|| {
+                    format!(
+                        "failed to load TLS certificate {} / key {}",
+                        tls.cert_path.display(),
+                        tls.key_path.display()
+                    )
+                })?;
 
-    // Clone state for health check
-    let state_clone = state.clone();
+            let handle = axum_server::Handle::new();
+            let shutdown_handle = handle.clone();
+            tokio::spawn(async move {
+                shutdown_signal().await;
+                shutdown_handle.graceful_shutdown(Some(Duration::from_secs(30)));
+            });
 
-    // Spawn health check task
-    tokio::spawn(async move {
-        let mut interval = tokio::time::interval(Duration::from_secs(30));
-        loop {
-            interval.tick().await;
-            if !state_clone.check_db_health().await {
-                error!("Database health check failed. Initiating graceful shutdown...");
-                let _ = tx.send(());
-                break;
-            }
+            info!("Server listening on https://{}", addr);
+            info!("Server initialization complete. Press Ctrl+C to stop");
+            axum_server::bind_rustls(addr, tls_config)
+                .handle(handle)
+                .serve(app.into_make_service())
+                .await
         }
-    });
-
-    let server =
-        axum::serve(listener, app.into_make_service()).with_graceful_shutdown(shutdown_signal(rx));
-
-    info!("Server initialization complete. Press Ctrl+C to stop");
+        None => {
+            let listener = tokio::net::TcpListener::bind(addr)
+                .await
+                .with_context(|| format!("failed to bind {}", addr))?;
+            info!("Server listening on http://{}", addr);
+            info!("Server initialization complete. Press Ctrl+C to stop");
+            axum::serve(listener, app.into_make_service())
+                .with_graceful_shutdown(shutdown_signal())
+                .await
+        }
+    };
 
-    match server.await {
+    match result {
         Ok(_) => info!("Server shutdown completed successfully"),
         Err(e) => error!("Server error: {}", e),
     }
@@ -0,0 +1,55 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Tables searched by `/api/search`, each indexed on its `name` column.
+const SEARCHABLE_TABLES: [&str; 4] = ["project", "section", "task", "sub_task"];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != sea_orm::DatabaseBackend::Postgres {
+            return Ok(());
+        }
+
+        let db = manager.get_connection();
+        db.execute_unprepared("CREATE EXTENSION IF NOT EXISTS pg_trgm;")
+            .await?;
+
+        for table in SEARCHABLE_TABLES {
+            // Full-text index; the expression must match the one used by the
+            // search query for the planner to pick it up.
+            db.execute_unprepared(&format!(
+                "CREATE INDEX IF NOT EXISTS idx_{table}_name_fts ON {table} \
+                 USING GIN (to_tsvector('simple', name));"
+            ))
+            .await?;
+            // Trigram index for fuzzy matching of misspelled names
+            db.execute_unprepared(&format!(
+                "CREATE INDEX IF NOT EXISTS idx_{table}_name_trgm ON {table} \
+                 USING GIN (name gin_trgm_ops);"
+            ))
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != sea_orm::DatabaseBackend::Postgres {
+            return Ok(());
+        }
+
+        let db = manager.get_connection();
+        for table in SEARCHABLE_TABLES {
+            db.execute_unprepared(&format!(
+                "DROP INDEX IF EXISTS idx_{table}_name_fts; \
+                 DROP INDEX IF EXISTS idx_{table}_name_trgm;"
+            ))
+            .await?;
+        }
+        db.execute_unprepared("DROP EXTENSION IF EXISTS pg_trgm;")
+            .await?;
+        Ok(())
+    }
+}
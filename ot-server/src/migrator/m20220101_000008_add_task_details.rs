@@ -0,0 +1,73 @@
+use super::{
+    m20220101_000005_create_task_table::Task, m20220101_000006_create_sub_task_table::SubTask,
+};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in [Task::Table.into_iden(), SubTask::Table.into_iden()] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .add_column(ColumnDef::new(TaskDetails::Description).text().null())
+                        .add_column(
+                            ColumnDef::new(TaskDetails::Status)
+                                .string_len(32)
+                                .default("todo")
+                                .not_null(),
+                        )
+                        .add_column(
+                            ColumnDef::new(TaskDetails::Priority)
+                                .string_len(16)
+                                .default("medium")
+                                .not_null(),
+                        )
+                        .add_column(ColumnDef::new(TaskDetails::DueDate).date().null())
+                        .add_column(
+                            ColumnDef::new(TaskDetails::EstimatedMinutes)
+                                .integer()
+                                .null(),
+                        )
+                        .add_column(ColumnDef::new(TaskDetails::Assignee).string().null())
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in [Task::Table.into_iden(), SubTask::Table.into_iden()] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .drop_column(TaskDetails::Description)
+                        .drop_column(TaskDetails::Status)
+                        .drop_column(TaskDetails::Priority)
+                        .drop_column(TaskDetails::DueDate)
+                        .drop_column(TaskDetails::EstimatedMinutes)
+                        .drop_column(TaskDetails::Assignee)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+/// Columns shared by the `task` and `sub_task` tables.
+#[derive(DeriveIden)]
+enum TaskDetails {
+    Description,
+    Status,
+    Priority,
+    DueDate,
+    EstimatedMinutes,
+    Assignee,
+}
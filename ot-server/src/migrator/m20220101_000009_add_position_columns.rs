@@ -0,0 +1,69 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Ordered tables and the columns that make up their parent.
+const ORDERED_TABLES: [(&str, &str); 3] = [
+    ("section", "project_id"),
+    ("task", "section_id"),
+    ("sub_task", "section_id, task_id"),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        for (table, parent) in ORDERED_TABLES {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        // Keys are compared byte-wise, which only the C
+                        // collation guarantees
+                        .add_column(ColumnDef::new(Position).string().extra("COLLATE \"C\""))
+                        .to_owned(),
+                )
+                .await?;
+
+            // Existing rows keep their creation order. Zero-padded counters
+            // ending in a non-zero digit are valid keys from utils::ordering.
+            db.execute_unprepared(&format!(
+                "UPDATE {table} SET position = ordered.position FROM ( \
+                     SELECT id, lpad(row_number() OVER ( \
+                         PARTITION BY {parent} ORDER BY created_at, id)::text, 8, '0') || 'V' \
+                         AS position \
+                     FROM {table}) ordered \
+                 WHERE {table}.id = ordered.id;"
+            ))
+            .await?;
+
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .modify_column(ColumnDef::new(Position).not_null())
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for (table, _) in ORDERED_TABLES {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .drop_column(Position)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+struct Position;
@@ -0,0 +1,217 @@
+use super::{
+    m20220101_000005_create_task_table::Task, m20220101_000006_create_sub_task_table::SubTask,
+};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Comment::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Comment::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key()
+                            .default(Expr::cust("uuid_generate_v4()")),
+                    )
+                    .col(ColumnDef::new(Comment::TaskId).uuid().null())
+                    .col(ColumnDef::new(Comment::SubTaskId).uuid().null())
+                    .col(ColumnDef::new(Comment::Author).string().not_null())
+                    .col(ColumnDef::new(Comment::Body).text().not_null())
+                    .col(
+                        ColumnDef::new(Comment::Mentions)
+                            .array(ColumnType::String(StringLen::Max))
+                            .default(Expr::cust("'{}'"))
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Comment::Version)
+                            .integer()
+                            .default(0)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Comment::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Comment::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    // Attached to exactly one of a task or a sub task
+                    .check(Expr::cust("(task_id IS NULL) <> (sub_task_id IS NULL)"))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_comment_task")
+                            .from(Comment::Table, Comment::TaskId)
+                            .to(Task::Table, Task::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_comment_sub_task")
+                            .from(Comment::Table, Comment::SubTaskId)
+                            .to(SubTask::Table, SubTask::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(CommentRevision::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(CommentRevision::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key()
+                            .default(Expr::cust("uuid_generate_v4()")),
+                    )
+                    .col(ColumnDef::new(CommentRevision::CommentId).uuid().not_null())
+                    .col(ColumnDef::new(CommentRevision::Body).text().not_null())
+                    .col(ColumnDef::new(CommentRevision::Version).integer().not_null())
+                    .col(
+                        ColumnDef::new(CommentRevision::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_comment_revision_comment")
+                            .from(CommentRevision::Table, CommentRevision::CommentId)
+                            .to(Comment::Table, Comment::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TaskEvent::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TaskEvent::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key()
+                            .default(Expr::cust("uuid_generate_v4()")),
+                    )
+                    .col(ColumnDef::new(TaskEvent::TaskId).uuid().null())
+                    .col(ColumnDef::new(TaskEvent::SubTaskId).uuid().null())
+                    .col(ColumnDef::new(TaskEvent::Kind).string_len(16).not_null())
+                    .col(ColumnDef::new(TaskEvent::Changes).json_binary().not_null())
+                    .col(
+                        ColumnDef::new(TaskEvent::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .check(Expr::cust("(task_id IS NULL) <> (sub_task_id IS NULL)"))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_task_event_task")
+                            .from(TaskEvent::Table, TaskEvent::TaskId)
+                            .to(Task::Table, Task::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_task_event_sub_task")
+                            .from(TaskEvent::Table, TaskEvent::SubTaskId)
+                            .to(SubTask::Table, SubTask::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        for (index, table, column) in [
+            ("idx_comment_task_id", "comment", "task_id"),
+            ("idx_comment_sub_task_id", "comment", "sub_task_id"),
+            ("idx_task_event_task_id", "task_event", "task_id"),
+            ("idx_task_event_sub_task_id", "task_event", "sub_task_id"),
+        ] {
+            manager
+                .create_index(
+                    Index::create()
+                        .if_not_exists()
+                        .name(index)
+                        .table(Alias::new(table))
+                        .col(Alias::new(column))
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TaskEvent::Table).if_exists().to_owned())
+            .await?;
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(CommentRevision::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Comment::Table).if_exists().to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Comment {
+    Table,
+    Id,
+    TaskId,
+    SubTaskId,
+    Author,
+    Body,
+    Mentions,
+    Version,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum CommentRevision {
+    Table,
+    Id,
+    CommentId,
+    Body,
+    Version,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum TaskEvent {
+    Table,
+    Id,
+    TaskId,
+    SubTaskId,
+    Kind,
+    Changes,
+    CreatedAt,
+}
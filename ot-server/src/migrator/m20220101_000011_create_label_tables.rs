@@ -0,0 +1,140 @@
+use super::{
+    m20220101_000003_create_project_table::Project, m20220101_000005_create_task_table::Task,
+    m20220101_000006_create_sub_task_table::SubTask,
+};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Label::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Label::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key()
+                            .default(Expr::cust("uuid_generate_v4()")),
+                    )
+                    .col(
+                        ColumnDef::new(Label::Name)
+                            .string_len(50)
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(Label::Color).string_len(7).not_null())
+                    .col(
+                        ColumnDef::new(Label::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Label::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // One join table per labelled entity, keyed on the pair so a label is
+        // attached at most once
+        for (table, target_table, target_column) in [
+            ("project_label", Project::Table.into_iden(), "project_id"),
+            ("task_label", Task::Table.into_iden(), "task_id"),
+            ("sub_task_label", SubTask::Table.into_iden(), "sub_task_id"),
+        ] {
+            manager
+                .create_table(
+                    Table::create()
+                        .table(Alias::new(table))
+                        .if_not_exists()
+                        .col(ColumnDef::new(Alias::new(target_column)).uuid().not_null())
+                        .col(ColumnDef::new(LabelAssignment::LabelId).uuid().not_null())
+                        .col(
+                            ColumnDef::new(LabelAssignment::CreatedAt)
+                                .timestamp_with_time_zone()
+                                .default(Expr::current_timestamp())
+                                .not_null(),
+                        )
+                        .primary_key(
+                            Index::create()
+                                .col(Alias::new(target_column))
+                                .col(LabelAssignment::LabelId),
+                        )
+                        .foreign_key(
+                            ForeignKey::create()
+                                .name(format!("fk_{}_{}", table, target_column))
+                                .from(Alias::new(table), Alias::new(target_column))
+                                .to(target_table, Alias::new("id"))
+                                .on_delete(ForeignKeyAction::Cascade),
+                        )
+                        .foreign_key(
+                            ForeignKey::create()
+                                .name(format!("fk_{}_label", table))
+                                .from(Alias::new(table), LabelAssignment::LabelId)
+                                .to(Label::Table, Label::Id)
+                                .on_delete(ForeignKeyAction::Cascade),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+
+            // Label filters look assignments up by label
+            manager
+                .create_index(
+                    Index::create()
+                        .if_not_exists()
+                        .name(format!("idx_{}_label_id", table))
+                        .table(Alias::new(table))
+                        .col(LabelAssignment::LabelId)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in ["sub_task_label", "task_label", "project_label"] {
+            manager
+                .drop_table(
+                    Table::drop()
+                        .table(Alias::new(table))
+                        .if_exists()
+                        .to_owned(),
+                )
+                .await?;
+        }
+        manager
+            .drop_table(Table::drop().table(Label::Table).if_exists().to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Label {
+    Table,
+    Id,
+    Name,
+    Color,
+    CreatedAt,
+    UpdatedAt,
+}
+
+/// Columns shared by the `project_label`, `task_label` and `sub_task_label`
+/// join tables.
+#[derive(DeriveIden)]
+enum LabelAssignment {
+    LabelId,
+    CreatedAt,
+}
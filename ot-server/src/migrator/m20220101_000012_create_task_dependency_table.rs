@@ -0,0 +1,88 @@
+use super::m20220101_000005_create_task_table::Task;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TaskDependency::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TaskDependency::BlockingTaskId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TaskDependency::BlockedTaskId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TaskDependency::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(TaskDependency::BlockingTaskId)
+                            .col(TaskDependency::BlockedTaskId),
+                    )
+                    .check(Expr::cust("blocking_task_id <> blocked_task_id"))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_task_dependency_blocking_task")
+                            .from(TaskDependency::Table, TaskDependency::BlockingTaskId)
+                            .to(Task::Table, Task::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_task_dependency_blocked_task")
+                            .from(TaskDependency::Table, TaskDependency::BlockedTaskId)
+                            .to(Task::Table, Task::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // The blocked flag looks dependencies up by the blocked task
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_task_dependency_blocked_task_id")
+                    .table(TaskDependency::Table)
+                    .col(TaskDependency::BlockedTaskId)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(TaskDependency::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum TaskDependency {
+    Table,
+    BlockingTaskId,
+    BlockedTaskId,
+    CreatedAt,
+}
@@ -0,0 +1,179 @@
+use super::m20220101_000005_create_task_table::Task;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TaskRecurrence::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TaskRecurrence::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key()
+                            .default(Expr::cust("uuid_generate_v4()")),
+                    )
+                    .col(
+                        ColumnDef::new(TaskRecurrence::TaskId)
+                            .uuid()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(TaskRecurrence::Frequency)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TaskRecurrence::Interval)
+                            .integer()
+                            .default(1)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(TaskRecurrence::StartsOn).date().not_null())
+                    .col(ColumnDef::new(TaskRecurrence::Until).date().null())
+                    .col(ColumnDef::new(TaskRecurrence::Count).integer().null())
+                    .col(
+                        ColumnDef::new(TaskRecurrence::OccurrenceCount)
+                            .integer()
+                            .default(1)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(TaskRecurrence::NextOccurrence).date().null())
+                    .col(
+                        ColumnDef::new(TaskRecurrence::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TaskRecurrence::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .check(Expr::cust("\"interval\" > 0"))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_task_recurrence_task")
+                            .from(TaskRecurrence::Table, TaskRecurrence::TaskId)
+                            .to(Task::Table, Task::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // The scheduler looks for rules whose next occurrence is coming up
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_task_recurrence_next_occurrence")
+                    .table(TaskRecurrence::Table)
+                    .col(TaskRecurrence::NextOccurrence)
+                    .to_owned(),
+            )
+            .await?;
+
+        // One row per generated task, keyed on the date so that an
+        // occurrence is never generated twice
+        manager
+            .create_table(
+                Table::create()
+                    .table(TaskOccurrence::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TaskOccurrence::RecurrenceId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(TaskOccurrence::OccursOn).date().not_null())
+                    .col(
+                        ColumnDef::new(TaskOccurrence::TaskId)
+                            .uuid()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(TaskOccurrence::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(TaskOccurrence::RecurrenceId)
+                            .col(TaskOccurrence::OccursOn),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_task_occurrence_recurrence")
+                            .from(TaskOccurrence::Table, TaskOccurrence::RecurrenceId)
+                            .to(TaskRecurrence::Table, TaskRecurrence::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_task_occurrence_task")
+                            .from(TaskOccurrence::Table, TaskOccurrence::TaskId)
+                            .to(Task::Table, Task::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(TaskOccurrence::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(TaskRecurrence::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum TaskRecurrence {
+    Table,
+    Id,
+    TaskId,
+    Frequency,
+    Interval,
+    StartsOn,
+    Until,
+    Count,
+    OccurrenceCount,
+    NextOccurrence,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum TaskOccurrence {
+    Table,
+    RecurrenceId,
+    OccursOn,
+    TaskId,
+    CreatedAt,
+}
@@ -0,0 +1,269 @@
+use super::{
+    m20220101_000001_create_activity_data_table::ActivityData,
+    m20220101_000003_create_project_table::Project, m20220101_000005_create_task_table::Task,
+};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Client::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Client::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key()
+                            .default(Expr::cust("uuid_generate_v4()")),
+                    )
+                    .col(
+                        ColumnDef::new(Client::Name)
+                            .string_len(100)
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(Client::Email).string_len(255).null())
+                    .col(
+                        ColumnDef::new(Client::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Client::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Project::Table)
+                    .add_column(ColumnDef::new(Billing::ClientId).uuid().null())
+                    .add_column(
+                        ColumnDef::new(Billing::Billable)
+                            .boolean()
+                            .default(true)
+                            .not_null(),
+                    )
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_project_client")
+                            .from_tbl(Project::Table)
+                            .from_col(Billing::ClientId)
+                            .to_tbl(Client::Table)
+                            .to_col(Client::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Task::Table)
+                    .add_column(
+                        ColumnDef::new(Billing::Billable)
+                            .boolean()
+                            .default(true)
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Tracked time is attributed to the task and person it was spent on
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ActivityData::Table)
+                    .add_column(ColumnDef::new(Billing::TaskId).uuid().null())
+                    .add_column(ColumnDef::new(Billing::Person).string_len(100).null())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_activity_data_task")
+                            .from_tbl(ActivityData::Table)
+                            .from_col(Billing::TaskId)
+                            .to_tbl(Task::Table)
+                            .to_col(Task::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_activity_data_task_id")
+                    .table(ActivityData::Table)
+                    .col(Billing::TaskId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(BillingRate::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(BillingRate::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key()
+                            .default(Expr::cust("uuid_generate_v4()")),
+                    )
+                    .col(ColumnDef::new(BillingRate::ClientId).uuid().null())
+                    .col(ColumnDef::new(BillingRate::ProjectId).uuid().null())
+                    .col(ColumnDef::new(BillingRate::Person).string_len(100).null())
+                    .col(
+                        ColumnDef::new(BillingRate::HourlyRate)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(BillingRate::Currency)
+                            .string_len(3)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(BillingRate::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(BillingRate::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .check(Expr::cust("hourly_rate >= 0"))
+                    // A rate is the default, or for a client, a project or a
+                    // person on a project
+                    .check(Expr::cust(
+                        "(client_id IS NULL OR project_id IS NULL) AND (person IS NULL OR project_id IS NOT NULL)",
+                    ))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_billing_rate_client")
+                            .from(BillingRate::Table, BillingRate::ClientId)
+                            .to(Client::Table, Client::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_billing_rate_project")
+                            .from(BillingRate::Table, BillingRate::ProjectId)
+                            .to(Project::Table, Project::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // At most one rate per scope, the single default rate included
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_billing_rate_scope")
+                    .table(BillingRate::Table)
+                    .col(BillingRate::ClientId)
+                    .col(BillingRate::ProjectId)
+                    .col(BillingRate::Person)
+                    .unique()
+                    .nulls_not_distinct()
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(BillingRate::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ActivityData::Table)
+                    .drop_foreign_key(Alias::new("fk_activity_data_task"))
+                    .drop_column(Billing::TaskId)
+                    .drop_column(Billing::Person)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Task::Table)
+                    .drop_column(Billing::Billable)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Project::Table)
+                    .drop_foreign_key(Alias::new("fk_project_client"))
+                    .drop_column(Billing::ClientId)
+                    .drop_column(Billing::Billable)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Client::Table).if_exists().to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Client {
+    Table,
+    Id,
+    Name,
+    Email,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum BillingRate {
+    Table,
+    Id,
+    ClientId,
+    ProjectId,
+    Person,
+    HourlyRate,
+    Currency,
+    CreatedAt,
+    UpdatedAt,
+}
+
+/// Columns added to the `project`, `task` and `activity_data` tables.
+#[derive(DeriveIden)]
+enum Billing {
+    ClientId,
+    Billable,
+    TaskId,
+    Person,
+}
@@ -0,0 +1,265 @@
+use super::{
+    m20220101_000001_create_activity_data_table::ActivityData,
+    m20220101_000003_create_project_table::Project, m20220101_000005_create_task_table::Task,
+    m20220101_000014_create_billing_tables::Client,
+};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Invoice::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Invoice::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key()
+                            .default(Expr::cust("uuid_generate_v4()")),
+                    )
+                    .col(ColumnDef::new(Invoice::ClientId).uuid().not_null())
+                    // Assigned when the invoice is finalized
+                    .col(
+                        ColumnDef::new(Invoice::Number)
+                            .integer()
+                            .null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(Invoice::Status)
+                            .string_len(16)
+                            .not_null()
+                            .default("draft"),
+                    )
+                    .col(ColumnDef::new(Invoice::Currency).string_len(3).not_null())
+                    .col(
+                        ColumnDef::new(Invoice::PeriodStart)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Invoice::PeriodEnd)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Invoice::IssuedOn).date().null())
+                    .col(ColumnDef::new(Invoice::DueOn).date().null())
+                    .col(ColumnDef::new(Invoice::Notes).text().null())
+                    .col(
+                        ColumnDef::new(Invoice::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Invoice::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .check(Expr::cust(
+                        "(status = 'draft') = (number IS NULL AND issued_on IS NULL)",
+                    ))
+                    // Invoices are kept for the books, so a client that was
+                    // invoiced cannot be deleted
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_invoice_client")
+                            .from(Invoice::Table, Invoice::ClientId)
+                            .to(Client::Table, Client::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_invoice_client_id")
+                    .table(Invoice::Table)
+                    .col(Invoice::ClientId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(InvoiceLine::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(InvoiceLine::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key()
+                            .default(Expr::cust("uuid_generate_v4()")),
+                    )
+                    .col(ColumnDef::new(InvoiceLine::InvoiceId).uuid().not_null())
+                    .col(ColumnDef::new(InvoiceLine::ProjectId).uuid().null())
+                    .col(ColumnDef::new(InvoiceLine::TaskId).uuid().null())
+                    .col(
+                        ColumnDef::new(InvoiceLine::Description)
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(InvoiceLine::Seconds)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(InvoiceLine::HourlyRate)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(InvoiceLine::Amount).big_integer().not_null())
+                    .col(ColumnDef::new(InvoiceLine::Position).integer().not_null())
+                    .col(
+                        ColumnDef::new(InvoiceLine::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(InvoiceLine::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .check(Expr::cust("seconds >= 0 AND hourly_rate >= 0"))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_invoice_line_invoice")
+                            .from(InvoiceLine::Table, InvoiceLine::InvoiceId)
+                            .to(Invoice::Table, Invoice::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_invoice_line_project")
+                            .from(InvoiceLine::Table, InvoiceLine::ProjectId)
+                            .to(Project::Table, Project::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_invoice_line_task")
+                            .from(InvoiceLine::Table, InvoiceLine::TaskId)
+                            .to(Task::Table, Task::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_invoice_line_invoice_id")
+                    .table(InvoiceLine::Table)
+                    .col(InvoiceLine::InvoiceId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Time on an invoice line is taken; removing the line frees it again
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ActivityData::Table)
+                    .add_column(ColumnDef::new(Invoiced::InvoiceLineId).uuid().null())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_activity_data_invoice_line")
+                            .from_tbl(ActivityData::Table)
+                            .from_col(Invoiced::InvoiceLineId)
+                            .to_tbl(InvoiceLine::Table)
+                            .to_col(InvoiceLine::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_activity_data_invoice_line_id")
+                    .table(ActivityData::Table)
+                    .col(Invoiced::InvoiceLineId)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ActivityData::Table)
+                    .drop_foreign_key(Alias::new("fk_activity_data_invoice_line"))
+                    .drop_column(Invoiced::InvoiceLineId)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(InvoiceLine::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Invoice::Table).if_exists().to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Invoice {
+    Table,
+    Id,
+    ClientId,
+    Number,
+    Status,
+    Currency,
+    PeriodStart,
+    PeriodEnd,
+    IssuedOn,
+    DueOn,
+    Notes,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum InvoiceLine {
+    Table,
+    Id,
+    InvoiceId,
+    ProjectId,
+    TaskId,
+    Description,
+    Seconds,
+    HourlyRate,
+    Amount,
+    Position,
+    CreatedAt,
+    UpdatedAt,
+}
+
+/// Column added to the `activity_data` table.
+#[derive(DeriveIden)]
+enum Invoiced {
+    InvoiceLineId,
+}
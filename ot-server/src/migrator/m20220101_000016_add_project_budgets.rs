@@ -0,0 +1,157 @@
+use super::{
+    m20220101_000003_create_project_table::Project, m20220101_000005_create_task_table::Task,
+};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Project::Table)
+                    .add_column(ColumnDef::new(Budget::BudgetMinutes).integer().null())
+                    .add_column(ColumnDef::new(Budget::BudgetAmount).big_integer().null())
+                    .add_column(ColumnDef::new(Budget::BudgetCurrency).string_len(3).null())
+                    .to_owned(),
+            )
+            .await?;
+        // A money budget needs its currency
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE project ADD CONSTRAINT project_budget_check CHECK (\
+                 (budget_minutes IS NULL OR budget_minutes > 0) \
+                 AND (budget_amount IS NULL OR budget_amount > 0) \
+                 AND (budget_amount IS NULL) = (budget_currency IS NULL))",
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(BudgetAlert::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(BudgetAlert::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key()
+                            .default(Expr::cust("uuid_generate_v4()")),
+                    )
+                    .col(ColumnDef::new(BudgetAlert::ProjectId).uuid().not_null())
+                    // Set for alerts on a task's estimate
+                    .col(ColumnDef::new(BudgetAlert::TaskId).uuid().null())
+                    .col(ColumnDef::new(BudgetAlert::Kind).string_len(16).not_null())
+                    .col(ColumnDef::new(BudgetAlert::Threshold).integer().not_null())
+                    .col(ColumnDef::new(BudgetAlert::Used).big_integer().not_null())
+                    .col(ColumnDef::new(BudgetAlert::Budget).big_integer().not_null())
+                    .col(ColumnDef::new(BudgetAlert::Currency).string_len(3).null())
+                    .col(
+                        ColumnDef::new(BudgetAlert::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(BudgetAlert::AcknowledgedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .check(Expr::cust("(kind = 'estimate') = (task_id IS NOT NULL)"))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_budget_alert_project")
+                            .from(BudgetAlert::Table, BudgetAlert::ProjectId)
+                            .to(Project::Table, Project::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_budget_alert_task")
+                            .from(BudgetAlert::Table, BudgetAlert::TaskId)
+                            .to(Task::Table, Task::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        // Each threshold is raised once per budget; alerts are cleared when
+        // the budget changes so they are raised again against the new one
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_budget_alert_unique")
+                    .table(BudgetAlert::Table)
+                    .col(BudgetAlert::ProjectId)
+                    .col(BudgetAlert::TaskId)
+                    .col(BudgetAlert::Kind)
+                    .col(BudgetAlert::Threshold)
+                    .unique()
+                    .nulls_not_distinct()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_budget_alert_created_at")
+                    .table(BudgetAlert::Table)
+                    .col(BudgetAlert::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(BudgetAlert::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Project::Table)
+                    .drop_column(Budget::BudgetMinutes)
+                    .drop_column(Budget::BudgetAmount)
+                    .drop_column(Budget::BudgetCurrency)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum BudgetAlert {
+    Table,
+    Id,
+    ProjectId,
+    TaskId,
+    Kind,
+    Threshold,
+    Used,
+    Budget,
+    Currency,
+    CreatedAt,
+    AcknowledgedAt,
+}
+
+/// Columns added to the `project` table.
+#[derive(DeriveIden)]
+#[allow(clippy::enum_variant_names)]
+enum Budget {
+    BudgetMinutes,
+    BudgetAmount,
+    BudgetCurrency,
+}
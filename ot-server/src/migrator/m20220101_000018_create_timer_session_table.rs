@@ -0,0 +1,107 @@
+use super::{
+    m20220101_000005_create_task_table::Task, m20220101_000006_create_sub_task_table::SubTask,
+};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TimerSession::Table)
+                    .if_not_exists()
+                    // Generated by the desktop, so a session uploaded twice
+                    // is only stored once
+                    .col(
+                        ColumnDef::new(TimerSession::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(TimerSession::TaskId).uuid().null())
+                    .col(ColumnDef::new(TimerSession::SubTaskId).uuid().null())
+                    .col(
+                        ColumnDef::new(TimerSession::StartTime)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TimerSession::EndTime)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TimerSession::ElapsedSeconds)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(TimerSession::Person).string_len(100).null())
+                    .col(
+                        ColumnDef::new(TimerSession::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .check(Expr::cust(
+                        "elapsed_seconds >= 0 AND end_time >= start_time",
+                    ))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_timer_session_task")
+                            .from(TimerSession::Table, TimerSession::TaskId)
+                            .to(Task::Table, Task::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_timer_session_sub_task")
+                            .from(TimerSession::Table, TimerSession::SubTaskId)
+                            .to(SubTask::Table, SubTask::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_timer_session_task_id")
+                    .table(TimerSession::Table)
+                    .col(TimerSession::TaskId)
+                    .col(TimerSession::StartTime)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(TimerSession::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum TimerSession {
+    Table,
+    Id,
+    TaskId,
+    SubTaskId,
+    StartTime,
+    EndTime,
+    ElapsedSeconds,
+    Person,
+    CreatedAt,
+}
@@ -0,0 +1,88 @@
+use super::m20220101_000003_create_project_table::Project;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TrackingPolicy::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TrackingPolicy::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key()
+                            .default(Expr::cust("uuid_generate_v4()")),
+                    )
+                    // Null for the global policy
+                    .col(ColumnDef::new(TrackingPolicy::ProjectId).uuid().null())
+                    .col(
+                        ColumnDef::new(TrackingPolicy::Settings)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TrackingPolicy::Locked)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TrackingPolicy::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_tracking_policy_project")
+                            .from(TrackingPolicy::Table, TrackingPolicy::ProjectId)
+                            .to(Project::Table, Project::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // One policy per project, and a single global one
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_tracking_policy_project")
+                    .table(TrackingPolicy::Table)
+                    .col(TrackingPolicy::ProjectId)
+                    .unique()
+                    .nulls_not_distinct()
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(TrackingPolicy::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum TrackingPolicy {
+    Table,
+    Id,
+    ProjectId,
+    Settings,
+    Locked,
+    UpdatedAt,
+}
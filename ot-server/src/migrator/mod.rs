@@ -9,6 +9,20 @@ mod m20220101_000003_create_project_table;
 mod m20220101_000004_create_section_table;
 mod m20220101_000005_create_task_table;
 mod m20220101_000006_create_sub_task_table;
+mod m20220101_000007_create_search_indexes;
+mod m20220101_000008_add_task_details;
+mod m20220101_000009_add_position_columns;
+mod m20220101_000010_create_comment_tables;
+mod m20220101_000011_create_label_tables;
+mod m20220101_000012_create_task_dependency_table;
+mod m20220101_000013_create_task_recurrence_tables;
+mod m20220101_000014_create_billing_tables;
+mod m20220101_000015_create_invoice_tables;
+mod m20220101_000016_add_project_budgets;
+mod m20220101_000017_add_project_archival;
+mod m20220101_000018_create_timer_session_table;
+mod m20220101_000019_add_timer_session_note;
+mod m20220101_000020_create_tracking_policy_table;
 
 pub struct Migrator;
 
@@ -23,6 +37,20 @@ impl MigratorTrait for Migrator {
             Box::new(m20220101_000004_create_section_table::Migration),
             Box::new(m20220101_000005_create_task_table::Migration),
             Box::new(m20220101_000006_create_sub_task_table::Migration),
+            Box::new(m20220101_000007_create_search_indexes::Migration),
+            Box::new(m20220101_000008_add_task_details::Migration),
+            Box::new(m20220101_000009_add_position_columns::Migration),
+            Box::new(m20220101_000010_create_comment_tables::Migration),
+            Box::new(m20220101_000011_create_label_tables::Migration),
+            Box::new(m20220101_000012_create_task_dependency_table::Migration),
+            Box::new(m20220101_000013_create_task_recurrence_tables::Migration),
+            Box::new(m20220101_000014_create_billing_tables::Migration),
+            Box::new(m20220101_000015_create_invoice_tables::Migration),
+            Box::new(m20220101_000016_add_project_budgets::Migration),
+            Box::new(m20220101_000017_add_project_archival::Migration),
+            Box::new(m20220101_000018_create_timer_session_table::Migration),
+            Box::new(m20220101_000019_add_timer_session_note::Migration),
+            Box::new(m20220101_000020_create_tracking_policy_table::Migration),
         ]
     }
 }
@@ -0,0 +1,348 @@
+use sea_orm::{
+    prelude::{DateTimeWithTimeZone, Uuid},
+    sea_query::OnConflict,
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+    FromQueryResult, IntoActiveModel, JoinType, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, QueryTrait, RelationTrait, Select, TryInsertResult,
+};
+
+use crate::{
+    dtos::{
+        activity_dto::{ActivityDataCreateDto, WindowActivityDataCreateDto},
+        label_dto::label_names,
+        time_dto::TimeFilterParams,
+    },
+    entities::{prelude::*, *},
+    repository::label_repository::LabelQuery,
+    utils::error::AppError,
+};
+
+pub struct ActivityQuery;
+pub struct ActivityMutation;
+
+/// A tracked interval with the task, project and client it was spent on;
+/// those are `None` for time not tracked on a task.
+#[derive(Debug, Clone, FromQueryResult)]
+pub struct TrackedTime {
+    pub id: Uuid,
+    pub task_id: Option<Uuid>,
+    pub person: Option<String>,
+    pub start_time: DateTimeWithTimeZone,
+    pub end_time: DateTimeWithTimeZone,
+    pub task_name: Option<String>,
+    pub task_billable: Option<bool>,
+    pub project_id: Option<Uuid>,
+    pub project_name: Option<String>,
+    pub project_billable: Option<bool>,
+    pub client_id: Option<Uuid>,
+    pub invoice_id: Option<Uuid>,
+}
+
+impl ActivityQuery {
+    pub async fn get_activity_data(
+        db: &DatabaseConnection,
+    ) -> Result<Vec<activity_data::Model>, AppError> {
+        ActivityData::find().all(db).await.map_err(|e| {
+            AppError::InternalServerError(format!("Failed to get activity data: {}", e))
+        })
+    }
+
+    pub async fn get_window_activity_data(
+        db: &DatabaseConnection,
+    ) -> Result<Vec<window_activity_data::Model>, AppError> {
+        WindowActivityData::find().all(db).await.map_err(|e| {
+            AppError::InternalServerError(format!("Failed to get window activity data: {}", e))
+        })
+    }
+
+    /// Tracked intervals matching `filter`, by start time. The `billable`
+    /// filter is left to the caller.
+    pub async fn get_tracked_time(
+        db: &DatabaseConnection,
+        filter: &TimeFilterParams,
+    ) -> Result<Vec<TrackedTime>, AppError> {
+        tracked_time_query(filter)
+            .into_model::<TrackedTime>()
+            .all(db)
+            .await
+            .map_err(|e| {
+                AppError::InternalServerError(format!("Failed to get tracked time: {}", e))
+            })
+    }
+
+    /// Count rows that [`ActivityMutation::purge_before`] would delete.
+    pub async fn count_before(
+        db: &DatabaseConnection,
+        cutoff: DateTimeWithTimeZone,
+    ) -> Result<(u64, u64), AppError> {
+        let activity = ActivityData::find()
+            .filter(activity_data::Column::EndTime.lt(cutoff))
+            .count(db)
+            .await
+            .map_err(|e| {
+                AppError::InternalServerError(format!("Failed to count activity data: {}", e))
+            })?;
+        let window_activity = WindowActivityData::find()
+            .filter(window_activity_data::Column::CreatedAt.lt(cutoff))
+            .count(db)
+            .await
+            .map_err(|e| {
+                AppError::InternalServerError(format!(
+                    "Failed to count window activity data: {}",
+                    e
+                ))
+            })?;
+        Ok((activity, window_activity))
+    }
+}
+
+/// Tracked intervals matching `filter` with what they were tracked on.
+fn tracked_time_query(filter: &TimeFilterParams) -> Select<ActivityData> {
+    ActivityData::find()
+        .select_only()
+        .columns([
+            activity_data::Column::Id,
+            activity_data::Column::TaskId,
+            activity_data::Column::Person,
+            activity_data::Column::StartTime,
+            activity_data::Column::EndTime,
+        ])
+        .column_as(task::Column::Name, "task_name")
+        .column_as(task::Column::Billable, "task_billable")
+        .column(section::Column::ProjectId)
+        .column_as(project::Column::Name, "project_name")
+        .column_as(project::Column::Billable, "project_billable")
+        .column(project::Column::ClientId)
+        .column(invoice_line::Column::InvoiceId)
+        .join(JoinType::LeftJoin, activity_data::Relation::Task.def())
+        .join(JoinType::LeftJoin, task::Relation::Section.def())
+        .join(JoinType::LeftJoin, section::Relation::Project.def())
+        .join(
+            JoinType::LeftJoin,
+            activity_data::Relation::InvoiceLine.def(),
+        )
+        .apply_if(filter.from, |q, from| {
+            q.filter(activity_data::Column::StartTime.gte(from))
+        })
+        .apply_if(filter.to, |q, to| {
+            q.filter(activity_data::Column::StartTime.lt(to))
+        })
+        .apply_if(filter.client_id, |q, client_id| {
+            q.filter(project::Column::ClientId.eq(client_id))
+        })
+        .apply_if(filter.project_id, |q, project_id| {
+            q.filter(section::Column::ProjectId.eq(project_id))
+        })
+        .apply_if(filter.task_id, |q, task_id| {
+            q.filter(activity_data::Column::TaskId.eq(task_id))
+        })
+        .apply_if(filter.person.clone(), |q, person| {
+            q.filter(activity_data::Column::Person.eq(person))
+        })
+        .apply_if(filter.invoiced, |q, invoiced| {
+            q.filter(if invoiced {
+                activity_data::Column::InvoiceLineId.is_not_null()
+            } else {
+                activity_data::Column::InvoiceLineId.is_null()
+            })
+        })
+        .apply_if(label_names(filter.label.as_deref()), |q, names| {
+            q.filter(
+                activity_data::Column::TaskId.in_subquery(LabelQuery::task_ids_labelled(names)),
+            )
+        })
+        .order_by_asc(activity_data::Column::StartTime)
+}
+
+impl ActivityMutation {
+    pub async fn create_activity_data(
+        db: &DatabaseConnection,
+        data: ActivityDataCreateDto,
+    ) -> Result<activity_data::Model, AppError> {
+        let activity = activity_model(data);
+        ActivityData::insert(activity)
+            .exec_with_returning(db)
+            .await
+            .map_err(|e| {
+                AppError::InternalServerError(format!("Failed to create activity data: {}", e))
+            })
+    }
+
+    /// Insert activity uploaded by the desktop, leaving a row with the same
+    /// id as it is; returns whether the row was new.
+    pub async fn upload_activity_data(
+        db: &DatabaseConnection,
+        data: ActivityDataCreateDto,
+    ) -> Result<bool, AppError> {
+        let activity = activity_model(data);
+        let result = ActivityData::insert(activity)
+            .on_conflict(
+                OnConflict::column(activity_data::Column::Id)
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .do_nothing()
+            .exec_without_returning(db)
+            .await
+            .map_err(|e| {
+                AppError::InternalServerError(format!("Failed to upload activity data: {}", e))
+            })?;
+        Ok(matches!(result, TryInsertResult::Inserted(count) if count > 0))
+    }
+
+    /// Insert window activity uploaded by the desktop, leaving a row with
+    /// the same id as it is; returns whether the row was new.
+    pub async fn upload_window_activity_data(
+        db: &DatabaseConnection,
+        data: WindowActivityDataCreateDto,
+    ) -> Result<bool, AppError> {
+        let active_window_data = serde_json::to_value(data.active_window_data).map_err(|e| {
+            AppError::InternalServerError(format!("Failed to encode window activity: {}", e))
+        })?;
+        let window_activity = window_activity_data::ActiveModel {
+            id: ActiveValue::set(data.id),
+            active_window_ss: ActiveValue::set(data.active_window_ss),
+            active_window_data: ActiveValue::set(active_window_data),
+            ..Default::default()
+        };
+        let result = WindowActivityData::insert(window_activity)
+            .on_conflict(
+                OnConflict::column(window_activity_data::Column::Id)
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .do_nothing()
+            .exec_without_returning(db)
+            .await
+            .map_err(|e| {
+                AppError::InternalServerError(format!(
+                    "Failed to upload window activity data: {}",
+                    e
+                ))
+            })?;
+        Ok(matches!(result, TryInsertResult::Inserted(count) if count > 0))
+    }
+
+    /// Delete activity rows that ended, and window activity recorded, before `cutoff`.
+    pub async fn purge_before<C: ConnectionTrait>(
+        db: &C,
+        cutoff: DateTimeWithTimeZone,
+    ) -> Result<(u64, u64), AppError> {
+        let activity = ActivityData::delete_many()
+            .filter(activity_data::Column::EndTime.lt(cutoff))
+            .exec(db)
+            .await
+            .map_err(|e| {
+                AppError::InternalServerError(format!("Failed to purge activity data: {}", e))
+            })?;
+        let window_activity = WindowActivityData::delete_many()
+            .filter(window_activity_data::Column::CreatedAt.lt(cutoff))
+            .exec(db)
+            .await
+            .map_err(|e| {
+                AppError::InternalServerError(format!(
+                    "Failed to purge window activity data: {}",
+                    e
+                ))
+            })?;
+        Ok((activity.rows_affected, window_activity.rows_affected))
+    }
+
+    pub async fn delete_all<C: ConnectionTrait>(db: &C) -> Result<(), AppError> {
+        ActivityData::delete_many().exec(db).await.map_err(|e| {
+            AppError::InternalServerError(format!("Failed to delete activity data: {}", e))
+        })?;
+        WindowActivityData::delete_many()
+            .exec(db)
+            .await
+            .map_err(|e| {
+                AppError::InternalServerError(format!(
+                    "Failed to delete window activity data: {}",
+                    e
+                ))
+            })?;
+        Ok(())
+    }
+
+    /// Insert activity rows as-is, keeping their ids and timestamps.
+    pub async fn import_activity_data<C: ConnectionTrait>(
+        db: &C,
+        rows: Vec<activity_data::Model>,
+    ) -> Result<u64, AppError> {
+        let count = rows.len() as u64;
+        for row in rows {
+            row.into_active_model()
+                .reset_all()
+                .insert(db)
+                .await
+                .map_err(|e| {
+                    AppError::InternalServerError(format!("Failed to import activity data: {}", e))
+                })?;
+        }
+        Ok(count)
+    }
+
+    pub async fn import_window_activity_data<C: ConnectionTrait>(
+        db: &C,
+        rows: Vec<window_activity_data::Model>,
+    ) -> Result<u64, AppError> {
+        let count = rows.len() as u64;
+        for row in rows {
+            row.into_active_model()
+                .reset_all()
+                .insert(db)
+                .await
+                .map_err(|e| {
+                    AppError::InternalServerError(format!(
+                        "Failed to import window activity data: {}",
+                        e
+                    ))
+                })?;
+        }
+        Ok(count)
+    }
+}
+
+fn activity_model(data: ActivityDataCreateDto) -> activity_data::ActiveModel {
+    activity_data::ActiveModel {
+        id: data.id.map_or(ActiveValue::NotSet, ActiveValue::set),
+        screenshots: ActiveValue::set(data.screenshots),
+        keyboard_activity_percent: ActiveValue::set(data.keyboard_activity_percent),
+        mouse_activity_percent: ActiveValue::set(data.mouse_activity_percent),
+        total_percent: ActiveValue::set(data.total_percent),
+        track_interval: ActiveValue::set(data.track_interval),
+        start_time: ActiveValue::set(data.start_time),
+        end_time: ActiveValue::set(data.end_time),
+        task_id: ActiveValue::set(data.task_id),
+        person: ActiveValue::set(data.person),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::{DbBackend, QueryTrait};
+
+    use super::tracked_time_query;
+    use crate::dtos::time_dto::TimeFilterParams;
+
+    #[test]
+    fn tracked_time_filters_by_task_labels() {
+        let filter = TimeFilterParams {
+            label: Some("bugfix,meetings".to_string()),
+            ..Default::default()
+        };
+        let sql = tracked_time_query(&filter)
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert!(sql.contains(
+            r#""activity_data"."task_id" IN (SELECT "task_label"."task_id" FROM "task_label" INNER JOIN "label""#
+        ));
+        assert!(sql.contains(r#"WHERE "label"."name" IN ('bugfix', 'meetings'))"#));
+
+        let sql = tracked_time_query(&TimeFilterParams::default())
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert!(!sql.contains("task_label"));
+    }
+}
@@ -0,0 +1,129 @@
+use chrono::Utc;
+use sea_orm::{
+    prelude::Uuid, ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DatabaseConnection,
+    DbErr, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder, QueryTrait, SqlErr,
+};
+
+use crate::{
+    dtos::billing_dto::{BillingRateCreateDto, BillingRateFilterParams, BillingRateUpdateDto},
+    entities::{prelude::*, *},
+    utils::error::AppError,
+};
+
+pub struct BillingRateQuery;
+pub struct BillingRateMutation;
+
+/// There is one rate per scope; report a second one as a conflict.
+fn rate_err(action: &str, e: DbErr) -> AppError {
+    match e.sql_err() {
+        Some(SqlErr::UniqueConstraintViolation(_)) => AppError::Conflict(
+            "A rate for this client, project or person already exists".to_string(),
+        ),
+        _ => AppError::InternalServerError(format!("Failed to {} rate: {}", action, e)),
+    }
+}
+
+impl BillingRateMutation {
+    pub async fn create_rate(
+        db: &DatabaseConnection,
+        data: BillingRateCreateDto,
+    ) -> Result<billing_rate::Model, AppError> {
+        let rate = billing_rate::ActiveModel {
+            client_id: ActiveValue::set(data.client_id),
+            project_id: ActiveValue::set(data.project_id),
+            person: ActiveValue::set(data.person),
+            hourly_rate: ActiveValue::set(data.hourly_rate),
+            currency: ActiveValue::set(data.currency),
+            ..Default::default()
+        };
+        BillingRate::insert(rate)
+            .exec_with_returning(db)
+            .await
+            .map_err(|e| rate_err("create", e))
+    }
+
+    pub async fn update_rate(
+        db: &DatabaseConnection,
+        id: Uuid,
+        data: BillingRateUpdateDto,
+    ) -> Result<billing_rate::Model, AppError> {
+        let rate = billing_rate::ActiveModel {
+            id: ActiveValue::set(id),
+            hourly_rate: data
+                .hourly_rate
+                .map_or(ActiveValue::NotSet, ActiveValue::set),
+            currency: data.currency.map_or(ActiveValue::NotSet, ActiveValue::set),
+            updated_at: ActiveValue::set(Utc::now().fixed_offset()),
+            ..Default::default()
+        };
+        rate.update(db).await.map_err(|e| match e {
+            DbErr::RecordNotUpdated => AppError::NotFound(format!("Rate with id {} not found", id)),
+            e => rate_err("update", e),
+        })
+    }
+
+    pub async fn delete_rate(db: &DatabaseConnection, id: Uuid) -> Result<u64, AppError> {
+        BillingRate::delete_by_id(id)
+            .exec(db)
+            .await
+            .map(|res| res.rows_affected)
+            .map_err(|e| rate_err("delete", e))
+    }
+
+    /// Insert rates as-is, keeping their ids and timestamps.
+    pub async fn import_rates<C: ConnectionTrait>(
+        db: &C,
+        rows: Vec<billing_rate::Model>,
+    ) -> Result<u64, AppError> {
+        let count = rows.len() as u64;
+        for row in rows {
+            row.into_active_model()
+                .reset_all()
+                .insert(db)
+                .await
+                .map_err(|e| {
+                    AppError::InternalServerError(format!("Failed to import rate: {}", e))
+                })?;
+        }
+        Ok(count)
+    }
+
+    /// The default rate belongs to neither a client nor a project, so
+    /// restoring with `--replace` clears rates separately.
+    pub async fn delete_all_rates<C: ConnectionTrait>(db: &C) -> Result<u64, AppError> {
+        BillingRate::delete_many()
+            .exec(db)
+            .await
+            .map(|res| res.rows_affected)
+            .map_err(|e| AppError::InternalServerError(format!("Failed to delete rates: {}", e)))
+    }
+}
+
+impl BillingRateQuery {
+    pub async fn get_rates(
+        db: &DatabaseConnection,
+        filter: BillingRateFilterParams,
+    ) -> Result<Vec<billing_rate::Model>, AppError> {
+        BillingRate::find()
+            .apply_if(filter.client_id, |q, client_id| {
+                q.filter(billing_rate::Column::ClientId.eq(client_id))
+            })
+            .apply_if(filter.project_id, |q, project_id| {
+                q.filter(billing_rate::Column::ProjectId.eq(project_id))
+            })
+            .order_by_asc(billing_rate::Column::CreatedAt)
+            .all(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get rates: {}", e)))
+    }
+
+    pub async fn get_rate_by_id(
+        db: &DatabaseConnection,
+        id: Uuid,
+    ) -> Result<Option<billing_rate::Model>, AppError> {
+        BillingRate::find_by_id(id)
+            .one(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get rate: {}", e)))
+    }
+}
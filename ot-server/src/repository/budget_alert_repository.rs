@@ -0,0 +1,179 @@
+use chrono::Utc;
+use sea_orm::{
+    prelude::{DateTimeWithTimeZone, Expr, Uuid},
+    sea_query::OnConflict,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait,
+    FromQueryResult, IntoActiveModel, JoinType, QueryFilter, QueryOrder, QuerySelect, QueryTrait,
+    RelationTrait, Select, TryInsertResult,
+};
+
+use crate::{
+    dtos::budget_dto::{BudgetAlertFilterParams, BudgetAlertKind},
+    entities::{prelude::*, *},
+    utils::error::AppError,
+};
+
+pub struct BudgetAlertQuery;
+pub struct BudgetAlertMutation;
+
+/// An alert with the names of the project and task it is about.
+#[derive(Debug, Clone, FromQueryResult)]
+pub struct BudgetAlertRow {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub project_name: String,
+    pub task_id: Option<Uuid>,
+    pub task_name: Option<String>,
+    pub kind: BudgetAlertKind,
+    pub threshold: i32,
+    pub used: i64,
+    pub budget: i64,
+    pub currency: Option<String>,
+    pub created_at: DateTimeWithTimeZone,
+    pub acknowledged_at: Option<DateTimeWithTimeZone>,
+}
+
+impl BudgetAlertMutation {
+    /// Insert the alerts not raised before; returns how many were new.
+    pub async fn raise_alerts(
+        db: &DatabaseConnection,
+        alerts: Vec<budget_alert::ActiveModel>,
+    ) -> Result<u64, AppError> {
+        if alerts.is_empty() {
+            return Ok(0);
+        }
+        let result = BudgetAlert::insert_many(alerts)
+            .on_conflict(OnConflict::new().do_nothing().to_owned())
+            .do_nothing()
+            .exec_without_returning(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to raise alerts: {}", e)))?;
+        Ok(match result {
+            TryInsertResult::Inserted(count) => count,
+            TryInsertResult::Empty | TryInsertResult::Conflicted => 0,
+        })
+    }
+
+    /// Forget the alerts of a project budget so they are raised again
+    /// against its new value.
+    pub async fn clear_project_alerts<C: ConnectionTrait>(
+        db: &C,
+        project_id: Uuid,
+        kind: BudgetAlertKind,
+    ) -> Result<u64, DbErr> {
+        BudgetAlert::delete_many()
+            .filter(budget_alert::Column::ProjectId.eq(project_id))
+            .filter(budget_alert::Column::TaskId.is_null())
+            .filter(budget_alert::Column::Kind.eq(kind))
+            .exec(db)
+            .await
+            .map(|res| res.rows_affected)
+    }
+
+    /// Forget the alerts of a task's estimate.
+    pub async fn clear_task_alerts<C: ConnectionTrait>(
+        db: &C,
+        task_id: Uuid,
+    ) -> Result<u64, DbErr> {
+        BudgetAlert::delete_many()
+            .filter(budget_alert::Column::TaskId.eq(task_id))
+            .exec(db)
+            .await
+            .map(|res| res.rows_affected)
+    }
+
+    /// Mark an alert as seen; acknowledging it again keeps the first time.
+    pub async fn acknowledge_alert(db: &DatabaseConnection, id: Uuid) -> Result<u64, AppError> {
+        BudgetAlert::update_many()
+            .col_expr(
+                budget_alert::Column::AcknowledgedAt,
+                Expr::value(Utc::now().fixed_offset()),
+            )
+            .filter(budget_alert::Column::Id.eq(id))
+            .filter(budget_alert::Column::AcknowledgedAt.is_null())
+            .exec(db)
+            .await
+            .map(|res| res.rows_affected)
+            .map_err(|e| {
+                AppError::InternalServerError(format!("Failed to acknowledge alert: {}", e))
+            })
+    }
+
+    /// Insert alerts as-is, keeping their ids and timestamps.
+    pub async fn import_budget_alerts<C: ConnectionTrait>(
+        db: &C,
+        rows: Vec<budget_alert::Model>,
+    ) -> Result<u64, AppError> {
+        let count = rows.len() as u64;
+        for row in rows {
+            row.into_active_model()
+                .reset_all()
+                .insert(db)
+                .await
+                .map_err(|e| {
+                    AppError::InternalServerError(format!("Failed to import alert: {}", e))
+                })?;
+        }
+        Ok(count)
+    }
+}
+
+impl BudgetAlertQuery {
+    /// Alerts matching `filter`, newest first.
+    pub async fn get_alerts(
+        db: &DatabaseConnection,
+        filter: BudgetAlertFilterParams,
+    ) -> Result<Vec<BudgetAlertRow>, AppError> {
+        Self::with_names()
+            .apply_if(filter.project_id, |q, project_id| {
+                q.filter(budget_alert::Column::ProjectId.eq(project_id))
+            })
+            .apply_if(filter.since, |q, since| {
+                q.filter(budget_alert::Column::CreatedAt.gt(since))
+            })
+            .apply_if(filter.acknowledged, |q, acknowledged| {
+                if acknowledged {
+                    q.filter(budget_alert::Column::AcknowledgedAt.is_not_null())
+                } else {
+                    q.filter(budget_alert::Column::AcknowledgedAt.is_null())
+                }
+            })
+            .order_by_desc(budget_alert::Column::CreatedAt)
+            .order_by_desc(budget_alert::Column::Threshold)
+            .order_by_asc(budget_alert::Column::Id)
+            .into_model::<BudgetAlertRow>()
+            .all(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get alerts: {}", e)))
+    }
+
+    pub async fn get_alert_by_id(
+        db: &DatabaseConnection,
+        id: Uuid,
+    ) -> Result<Option<BudgetAlertRow>, AppError> {
+        Self::with_names()
+            .filter(budget_alert::Column::Id.eq(id))
+            .into_model::<BudgetAlertRow>()
+            .one(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get alert: {}", e)))
+    }
+
+    pub async fn get_all_alerts(
+        db: &DatabaseConnection,
+    ) -> Result<Vec<budget_alert::Model>, AppError> {
+        BudgetAlert::find()
+            .order_by_asc(budget_alert::Column::CreatedAt)
+            .all(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get alerts: {}", e)))
+    }
+
+    fn with_names() -> Select<BudgetAlert> {
+        BudgetAlert::find()
+            .column_as(project::Column::Name, "project_name")
+            .column_as(task::Column::Name, "task_name")
+            .join(JoinType::InnerJoin, budget_alert::Relation::Project.def())
+            .join(JoinType::LeftJoin, budget_alert::Relation::Task.def())
+    }
+}
@@ -0,0 +1,123 @@
+use chrono::Utc;
+use sea_orm::{
+    prelude::Uuid, ActiveModelTrait, ActiveValue, ConnectionTrait, DatabaseConnection, DbErr,
+    EntityTrait, IntoActiveModel, QueryOrder, SqlErr,
+};
+
+use crate::{
+    dtos::client_dto::{ClientCreateDto, ClientUpdateDto},
+    entities::{prelude::*, *},
+    utils::error::AppError,
+};
+
+pub struct ClientQuery;
+pub struct ClientMutation;
+
+/// Client names are unique, and invoiced clients are kept; report either as
+/// a conflict rather than a failure.
+fn client_err(action: &str, e: DbErr) -> AppError {
+    match e.sql_err() {
+        Some(SqlErr::UniqueConstraintViolation(_)) => {
+            AppError::Conflict("A client with this name already exists".to_string())
+        }
+        Some(SqlErr::ForeignKeyConstraintViolation(_)) => {
+            AppError::Conflict("A client that was invoiced cannot be deleted".to_string())
+        }
+        _ => AppError::InternalServerError(format!("Failed to {} client: {}", action, e)),
+    }
+}
+
+impl ClientMutation {
+    pub async fn create_client(
+        db: &DatabaseConnection,
+        data: ClientCreateDto,
+    ) -> Result<client::Model, AppError> {
+        let client = client::ActiveModel {
+            name: ActiveValue::set(data.name),
+            email: ActiveValue::set(data.email),
+            ..Default::default()
+        };
+        Client::insert(client)
+            .exec_with_returning(db)
+            .await
+            .map_err(|e| client_err("create", e))
+    }
+
+    pub async fn update_client(
+        db: &DatabaseConnection,
+        id: Uuid,
+        data: ClientUpdateDto,
+    ) -> Result<client::Model, AppError> {
+        let client = client::ActiveModel {
+            id: ActiveValue::set(id),
+            name: data.name.map_or(ActiveValue::NotSet, ActiveValue::set),
+            email: data.email.map_or(ActiveValue::NotSet, ActiveValue::set),
+            updated_at: ActiveValue::set(Utc::now().fixed_offset()),
+            ..Default::default()
+        };
+        client.update(db).await.map_err(|e| match e {
+            DbErr::RecordNotUpdated => {
+                AppError::NotFound(format!("Client with id {} not found", id))
+            }
+            e => client_err("update", e),
+        })
+    }
+
+    /// Delete a client along with its rates; its projects are kept without
+    /// a client. Clients with invoices cannot be deleted.
+    pub async fn delete_client(db: &DatabaseConnection, id: Uuid) -> Result<u64, AppError> {
+        Client::delete_by_id(id)
+            .exec(db)
+            .await
+            .map(|res| res.rows_affected)
+            .map_err(|e| client_err("delete", e))
+    }
+
+    /// Insert clients as-is, keeping their ids and timestamps.
+    pub async fn import_clients<C: ConnectionTrait>(
+        db: &C,
+        rows: Vec<client::Model>,
+    ) -> Result<u64, AppError> {
+        let count = rows.len() as u64;
+        for row in rows {
+            row.into_active_model()
+                .reset_all()
+                .insert(db)
+                .await
+                .map_err(|e| {
+                    AppError::InternalServerError(format!("Failed to import client: {}", e))
+                })?;
+        }
+        Ok(count)
+    }
+
+    /// Clients are not owned by a project, so restoring with `--replace`
+    /// clears them separately; their rates cascade.
+    pub async fn delete_all_clients<C: ConnectionTrait>(db: &C) -> Result<u64, AppError> {
+        Client::delete_many()
+            .exec(db)
+            .await
+            .map(|res| res.rows_affected)
+            .map_err(|e| AppError::InternalServerError(format!("Failed to delete clients: {}", e)))
+    }
+}
+
+impl ClientQuery {
+    pub async fn get_clients(db: &DatabaseConnection) -> Result<Vec<client::Model>, AppError> {
+        Client::find()
+            .order_by_asc(client::Column::Name)
+            .all(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get clients: {}", e)))
+    }
+
+    pub async fn get_client_by_id(
+        db: &DatabaseConnection,
+        id: Uuid,
+    ) -> Result<Option<client::Model>, AppError> {
+        Client::find_by_id(id)
+            .one(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get client: {}", e)))
+    }
+}
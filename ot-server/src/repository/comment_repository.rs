@@ -0,0 +1,210 @@
+use chrono::Utc;
+use sea_orm::{
+    prelude::Uuid, ActiveModelTrait, ActiveValue, ColumnTrait, Condition, ConnectionTrait,
+    DatabaseConnection, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder, TransactionTrait,
+};
+
+use crate::{
+    dtos::comment_dto::{CommentCreateDto, CommentUpdateDto},
+    entities::{prelude::*, *},
+    utils::error::AppError,
+};
+
+pub struct CommentQuery;
+pub struct CommentMutation;
+
+impl CommentMutation {
+    pub async fn create_comment(
+        db: &DatabaseConnection,
+        data: CommentCreateDto,
+        mentions: Vec<String>,
+    ) -> Result<comment::Model, AppError> {
+        let comment = comment::ActiveModel {
+            task_id: ActiveValue::set(data.task_id),
+            sub_task_id: ActiveValue::set(data.sub_task_id),
+            author: ActiveValue::set(data.author),
+            body: ActiveValue::set(data.body),
+            mentions: ActiveValue::set(mentions),
+            ..Default::default()
+        };
+        Comment::insert(comment)
+            .exec_with_returning(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to create comment: {}", e)))
+    }
+
+    /// Replace the body, keeping the previous one as a revision. Fails with
+    /// a conflict when `data.version` is not the current version.
+    pub async fn update_comment(
+        db: &DatabaseConnection,
+        id: Uuid,
+        data: CommentUpdateDto,
+        mentions: Vec<String>,
+    ) -> Result<comment::Model, AppError> {
+        let map_err = |e| AppError::InternalServerError(format!("Failed to update comment: {}", e));
+        let txn = db.begin().await.map_err(map_err)?;
+        let current = Comment::find_by_id(id)
+            .one(&txn)
+            .await
+            .map_err(map_err)?
+            .ok_or_else(|| AppError::NotFound(format!("Comment with id {} not found", id)))?;
+        if current.version != data.version {
+            return Err(AppError::Conflict(
+                "Comment was modified by another user. Please refresh and try again.".to_string(),
+            ));
+        }
+
+        comment_revision::ActiveModel {
+            comment_id: ActiveValue::set(id),
+            body: ActiveValue::set(current.body),
+            version: ActiveValue::set(current.version),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .map_err(map_err)?;
+
+        let comment = comment::ActiveModel {
+            id: ActiveValue::set(id),
+            body: ActiveValue::set(data.body),
+            mentions: ActiveValue::set(mentions),
+            version: ActiveValue::set(current.version + 1),
+            updated_at: ActiveValue::set(Utc::now().fixed_offset()),
+            ..Default::default()
+        };
+        let comment = comment.update(&txn).await.map_err(map_err)?;
+        txn.commit().await.map_err(map_err)?;
+        Ok(comment)
+    }
+
+    pub async fn delete_comment(db: &DatabaseConnection, id: Uuid) -> Result<u64, AppError> {
+        Comment::delete_by_id(id)
+            .exec(db)
+            .await
+            .map(|res| res.rows_affected)
+            .map_err(|e| AppError::InternalServerError(format!("Failed to delete comment: {}", e)))
+    }
+
+    /// Insert comments as-is, keeping their ids and timestamps.
+    pub async fn import_comments<C: ConnectionTrait>(
+        db: &C,
+        rows: Vec<comment::Model>,
+    ) -> Result<u64, AppError> {
+        let count = rows.len() as u64;
+        for row in rows {
+            row.into_active_model()
+                .reset_all()
+                .insert(db)
+                .await
+                .map_err(|e| {
+                    AppError::InternalServerError(format!("Failed to import comment: {}", e))
+                })?;
+        }
+        Ok(count)
+    }
+
+    /// Insert comment revisions as-is, keeping their ids and timestamps.
+    pub async fn import_comment_revisions<C: ConnectionTrait>(
+        db: &C,
+        rows: Vec<comment_revision::Model>,
+    ) -> Result<u64, AppError> {
+        let count = rows.len() as u64;
+        for row in rows {
+            row.into_active_model()
+                .reset_all()
+                .insert(db)
+                .await
+                .map_err(|e| {
+                    AppError::InternalServerError(format!(
+                        "Failed to import comment revision: {}",
+                        e
+                    ))
+                })?;
+        }
+        Ok(count)
+    }
+}
+
+impl CommentQuery {
+    pub async fn get_comments(db: &DatabaseConnection) -> Result<Vec<comment::Model>, AppError> {
+        Comment::find()
+            .all(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get comments: {}", e)))
+    }
+
+    pub async fn get_comment_by_id(
+        db: &DatabaseConnection,
+        id: Uuid,
+    ) -> Result<Option<comment::Model>, AppError> {
+        Comment::find_by_id(id)
+            .one(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get comment: {}", e)))
+    }
+
+    pub async fn get_comments_by_task_id(
+        db: &DatabaseConnection,
+        task_id: Uuid,
+    ) -> Result<Vec<comment::Model>, AppError> {
+        Comment::find()
+            .filter(comment::Column::TaskId.eq(task_id))
+            .order_by_asc(comment::Column::CreatedAt)
+            .all(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get comments: {}", e)))
+    }
+
+    pub async fn get_comments_by_sub_task_id(
+        db: &DatabaseConnection,
+        sub_task_id: Uuid,
+    ) -> Result<Vec<comment::Model>, AppError> {
+        Comment::find()
+            .filter(comment::Column::SubTaskId.eq(sub_task_id))
+            .order_by_asc(comment::Column::CreatedAt)
+            .all(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get comments: {}", e)))
+    }
+
+    /// Comments on a task and on the given sub tasks, oldest first.
+    pub async fn get_comments_for_task(
+        db: &DatabaseConnection,
+        task_id: Uuid,
+        sub_task_ids: Vec<Uuid>,
+    ) -> Result<Vec<comment::Model>, AppError> {
+        Comment::find()
+            .filter(
+                Condition::any()
+                    .add(comment::Column::TaskId.eq(task_id))
+                    .add(comment::Column::SubTaskId.is_in(sub_task_ids)),
+            )
+            .order_by_asc(comment::Column::CreatedAt)
+            .all(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get comments: {}", e)))
+    }
+
+    pub async fn get_comment_revisions(
+        db: &DatabaseConnection,
+    ) -> Result<Vec<comment_revision::Model>, AppError> {
+        CommentRevision::find().all(db).await.map_err(|e| {
+            AppError::InternalServerError(format!("Failed to get comment revisions: {}", e))
+        })
+    }
+
+    /// Earlier bodies of a comment, oldest first.
+    pub async fn get_revisions_by_comment_id(
+        db: &DatabaseConnection,
+        comment_id: Uuid,
+    ) -> Result<Vec<comment_revision::Model>, AppError> {
+        CommentRevision::find()
+            .filter(comment_revision::Column::CommentId.eq(comment_id))
+            .order_by_asc(comment_revision::Column::Version)
+            .all(db)
+            .await
+            .map_err(|e| {
+                AppError::InternalServerError(format!("Failed to get comment revisions: {}", e))
+            })
+    }
+}
@@ -0,0 +1,398 @@
+use chrono::{NaiveDate, Utc};
+use sea_orm::{
+    prelude::{DateTimeWithTimeZone, Expr, Uuid},
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr,
+    EntityTrait, IntoActiveModel, QueryFilter, QueryOrder, QuerySelect, QueryTrait, SqlErr,
+    TransactionTrait,
+};
+
+use crate::{
+    dtos::invoice_dto::{
+        InvoiceFilterParams, InvoiceLineCreateDto, InvoiceLineUpdateDto, InvoiceStatus,
+        InvoiceUpdateDto,
+    },
+    entities::{prelude::*, *},
+    utils::{billing::billable_amount, error::AppError},
+};
+
+pub struct InvoiceQuery;
+pub struct InvoiceMutation;
+
+/// A line of a new draft with the tracked time it bills.
+#[derive(Debug, Clone)]
+pub struct DraftLine {
+    pub project_id: Option<Uuid>,
+    pub task_id: Option<Uuid>,
+    pub description: String,
+    pub seconds: i64,
+    pub hourly_rate: i64,
+    pub activity_ids: Vec<Uuid>,
+}
+
+/// The period and details of a new draft.
+#[derive(Debug, Clone)]
+pub struct DraftInvoice {
+    pub client_id: Uuid,
+    pub currency: String,
+    pub period_start: DateTimeWithTimeZone,
+    pub period_end: DateTimeWithTimeZone,
+    pub due_on: Option<NaiveDate>,
+    pub notes: Option<String>,
+}
+
+impl InvoiceMutation {
+    /// Create a draft with `lines`, taking their tracked time. Fails with a
+    /// conflict, creating nothing, if any of the time was taken by another
+    /// invoice in the meantime.
+    pub async fn create_draft(
+        db: &DatabaseConnection,
+        draft: DraftInvoice,
+        lines: Vec<DraftLine>,
+    ) -> Result<invoice::Model, AppError> {
+        let map_err = |e| AppError::InternalServerError(format!("Failed to create invoice: {}", e));
+        let txn = db.begin().await.map_err(map_err)?;
+        let invoice = Invoice::insert(invoice::ActiveModel {
+            client_id: ActiveValue::set(draft.client_id),
+            status: ActiveValue::set(InvoiceStatus::Draft),
+            currency: ActiveValue::set(draft.currency),
+            period_start: ActiveValue::set(draft.period_start),
+            period_end: ActiveValue::set(draft.period_end),
+            due_on: ActiveValue::set(draft.due_on),
+            notes: ActiveValue::set(draft.notes),
+            ..Default::default()
+        })
+        .exec_with_returning(&txn)
+        .await
+        .map_err(map_err)?;
+
+        for (position, line) in lines.into_iter().enumerate() {
+            let created = InvoiceLine::insert(invoice_line::ActiveModel {
+                invoice_id: ActiveValue::set(invoice.id),
+                project_id: ActiveValue::set(line.project_id),
+                task_id: ActiveValue::set(line.task_id),
+                description: ActiveValue::set(line.description),
+                seconds: ActiveValue::set(line.seconds),
+                hourly_rate: ActiveValue::set(line.hourly_rate),
+                amount: ActiveValue::set(billable_amount(line.seconds, line.hourly_rate)),
+                position: ActiveValue::set(position as i32),
+                ..Default::default()
+            })
+            .exec_with_returning(&txn)
+            .await
+            .map_err(map_err)?;
+
+            let claimed = ActivityData::update_many()
+                .col_expr(
+                    activity_data::Column::InvoiceLineId,
+                    Expr::value(created.id),
+                )
+                .filter(activity_data::Column::Id.is_in(line.activity_ids.clone()))
+                .filter(activity_data::Column::InvoiceLineId.is_null())
+                .exec(&txn)
+                .await
+                .map_err(map_err)?;
+            if claimed.rows_affected != line.activity_ids.len() as u64 {
+                return Err(AppError::Conflict(
+                    "Some of the time was invoiced at the same time; try again".to_string(),
+                ));
+            }
+        }
+        txn.commit().await.map_err(map_err)?;
+        Ok(invoice)
+    }
+
+    pub async fn update_invoice(
+        db: &DatabaseConnection,
+        id: Uuid,
+        data: InvoiceUpdateDto,
+    ) -> Result<invoice::Model, AppError> {
+        let map_err = |e| AppError::InternalServerError(format!("Failed to update invoice: {}", e));
+        let txn = db.begin().await.map_err(map_err)?;
+        Self::lock_draft(&txn, id).await?;
+        let invoice = invoice::ActiveModel {
+            id: ActiveValue::set(id),
+            due_on: data.due_on.map_or(ActiveValue::NotSet, ActiveValue::set),
+            notes: data.notes.map_or(ActiveValue::NotSet, ActiveValue::set),
+            updated_at: ActiveValue::set(Utc::now().fixed_offset()),
+            ..Default::default()
+        }
+        .update(&txn)
+        .await
+        .map_err(map_err)?;
+        txn.commit().await.map_err(map_err)?;
+        Ok(invoice)
+    }
+
+    /// Give a draft the next invoice number and issue it. From then on it
+    /// and the time on it can no longer change.
+    pub async fn finalize_invoice(
+        db: &DatabaseConnection,
+        id: Uuid,
+        issued_on: NaiveDate,
+        due_on: NaiveDate,
+    ) -> Result<invoice::Model, AppError> {
+        let map_err =
+            |e| AppError::InternalServerError(format!("Failed to finalize invoice: {}", e));
+        let txn = db.begin().await.map_err(map_err)?;
+        let draft = Self::lock_draft(&txn, id).await?;
+        let lines = InvoiceQuery::get_lines(&txn, &[id]).await?;
+        if lines.is_empty() {
+            return Err(AppError::BadRequest(
+                "An invoice needs at least one line to be finalized".to_string(),
+            ));
+        }
+
+        let last_number: Option<i32> = Invoice::find()
+            .select_only()
+            .column_as(invoice::Column::Number.max(), "number")
+            .into_tuple()
+            .one(&txn)
+            .await
+            .map_err(map_err)?
+            .flatten();
+        let invoice = invoice::ActiveModel {
+            id: ActiveValue::set(id),
+            number: ActiveValue::set(Some(last_number.unwrap_or(0) + 1)),
+            status: ActiveValue::set(InvoiceStatus::Finalized),
+            issued_on: ActiveValue::set(Some(issued_on)),
+            due_on: ActiveValue::set(draft.due_on.or(Some(due_on))),
+            updated_at: ActiveValue::set(Utc::now().fixed_offset()),
+            ..Default::default()
+        }
+        .update(&txn)
+        .await
+        .map_err(|e| match e.sql_err() {
+            // Another invoice took the number first
+            Some(SqlErr::UniqueConstraintViolation(_)) => AppError::Conflict(
+                "Another invoice was finalized at the same time; try again".to_string(),
+            ),
+            _ => map_err(e),
+        })?;
+        txn.commit().await.map_err(map_err)?;
+        Ok(invoice)
+    }
+
+    /// Delete a draft; its time can be invoiced again.
+    pub async fn delete_invoice(db: &DatabaseConnection, id: Uuid) -> Result<(), AppError> {
+        let map_err = |e| AppError::InternalServerError(format!("Failed to delete invoice: {}", e));
+        let txn = db.begin().await.map_err(map_err)?;
+        Self::lock_draft(&txn, id).await?;
+        Invoice::delete_by_id(id)
+            .exec(&txn)
+            .await
+            .map_err(map_err)?;
+        txn.commit().await.map_err(map_err)?;
+        Ok(())
+    }
+
+    /// Add a line to the end of a draft.
+    pub async fn create_line(
+        db: &DatabaseConnection,
+        invoice_id: Uuid,
+        data: InvoiceLineCreateDto,
+    ) -> Result<invoice_line::Model, AppError> {
+        let map_err =
+            |e| AppError::InternalServerError(format!("Failed to create invoice line: {}", e));
+        let txn = db.begin().await.map_err(map_err)?;
+        Self::lock_draft(&txn, invoice_id).await?;
+        let last_position: Option<i32> = InvoiceLine::find()
+            .select_only()
+            .column_as(invoice_line::Column::Position.max(), "position")
+            .filter(invoice_line::Column::InvoiceId.eq(invoice_id))
+            .into_tuple()
+            .one(&txn)
+            .await
+            .map_err(map_err)?
+            .flatten();
+        let line = InvoiceLine::insert(invoice_line::ActiveModel {
+            invoice_id: ActiveValue::set(invoice_id),
+            description: ActiveValue::set(data.description),
+            seconds: ActiveValue::set(data.seconds),
+            hourly_rate: ActiveValue::set(data.hourly_rate),
+            amount: ActiveValue::set(billable_amount(data.seconds, data.hourly_rate)),
+            position: ActiveValue::set(last_position.map_or(0, |position| position + 1)),
+            ..Default::default()
+        })
+        .exec_with_returning(&txn)
+        .await
+        .map_err(map_err)?;
+        txn.commit().await.map_err(map_err)?;
+        Ok(line)
+    }
+
+    /// Edit a line of a draft; its amount follows the hours and rate.
+    pub async fn update_line(
+        db: &DatabaseConnection,
+        id: Uuid,
+        data: InvoiceLineUpdateDto,
+    ) -> Result<invoice_line::Model, AppError> {
+        let map_err =
+            |e| AppError::InternalServerError(format!("Failed to update invoice line: {}", e));
+        let txn = db.begin().await.map_err(map_err)?;
+        let current = Self::lock_line(&txn, id).await?;
+        let seconds = data.seconds.unwrap_or(current.seconds);
+        let hourly_rate = data.hourly_rate.unwrap_or(current.hourly_rate);
+        let line = invoice_line::ActiveModel {
+            id: ActiveValue::set(id),
+            description: data
+                .description
+                .map_or(ActiveValue::NotSet, ActiveValue::set),
+            seconds: ActiveValue::set(seconds),
+            hourly_rate: ActiveValue::set(hourly_rate),
+            amount: ActiveValue::set(billable_amount(seconds, hourly_rate)),
+            updated_at: ActiveValue::set(Utc::now().fixed_offset()),
+            ..Default::default()
+        }
+        .update(&txn)
+        .await
+        .map_err(map_err)?;
+        txn.commit().await.map_err(map_err)?;
+        Ok(line)
+    }
+
+    /// Remove a line from a draft; its time can be invoiced again.
+    pub async fn delete_line(db: &DatabaseConnection, id: Uuid) -> Result<(), AppError> {
+        let map_err =
+            |e| AppError::InternalServerError(format!("Failed to delete invoice line: {}", e));
+        let txn = db.begin().await.map_err(map_err)?;
+        Self::lock_line(&txn, id).await?;
+        InvoiceLine::delete_by_id(id)
+            .exec(&txn)
+            .await
+            .map_err(map_err)?;
+        txn.commit().await.map_err(map_err)?;
+        Ok(())
+    }
+
+    /// Lock an invoice for the rest of the transaction, failing unless it
+    /// is a draft, so that it cannot be finalized while it is being edited.
+    async fn lock_draft<C: ConnectionTrait>(db: &C, id: Uuid) -> Result<invoice::Model, AppError> {
+        let invoice = Invoice::find_by_id(id)
+            .lock_exclusive()
+            .one(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get invoice: {}", e)))?
+            .ok_or_else(|| AppError::NotFound(format!("Invoice with id {} not found", id)))?;
+        match (invoice.status, invoice.number) {
+            (InvoiceStatus::Draft, _) => Ok(invoice),
+            (InvoiceStatus::Finalized, number) => Err(AppError::Conflict(format!(
+                "Invoice {} is finalized and can no longer change",
+                number.unwrap_or_default()
+            ))),
+        }
+    }
+
+    /// The line with id `id`, after locking its invoice as a draft.
+    async fn lock_line<C: ConnectionTrait>(
+        db: &C,
+        id: Uuid,
+    ) -> Result<invoice_line::Model, AppError> {
+        let line = InvoiceLine::find_by_id(id)
+            .one(db)
+            .await
+            .map_err(|e| {
+                AppError::InternalServerError(format!("Failed to get invoice line: {}", e))
+            })?
+            .ok_or_else(|| AppError::NotFound(format!("Invoice line with id {} not found", id)))?;
+        Self::lock_draft(db, line.invoice_id).await?;
+        Ok(line)
+    }
+
+    /// Insert invoices as-is, keeping their ids, numbers and timestamps.
+    pub async fn import_invoices<C: ConnectionTrait>(
+        db: &C,
+        rows: Vec<invoice::Model>,
+    ) -> Result<u64, AppError> {
+        let count = rows.len() as u64;
+        for row in rows {
+            row.into_active_model()
+                .reset_all()
+                .insert(db)
+                .await
+                .map_err(|e| {
+                    AppError::InternalServerError(format!("Failed to import invoice: {}", e))
+                })?;
+        }
+        Ok(count)
+    }
+
+    pub async fn import_invoice_lines<C: ConnectionTrait>(
+        db: &C,
+        rows: Vec<invoice_line::Model>,
+    ) -> Result<u64, AppError> {
+        let count = rows.len() as u64;
+        for row in rows {
+            row.into_active_model()
+                .reset_all()
+                .insert(db)
+                .await
+                .map_err(|e| {
+                    AppError::InternalServerError(format!("Failed to import invoice line: {}", e))
+                })?;
+        }
+        Ok(count)
+    }
+
+    /// Invoices keep their client from being deleted, so restoring with
+    /// `--replace` clears them first; their lines cascade.
+    pub async fn delete_all_invoices<C: ConnectionTrait>(db: &C) -> Result<u64, AppError> {
+        Invoice::delete_many()
+            .exec(db)
+            .await
+            .map(|res| res.rows_affected)
+            .map_err(|e| AppError::InternalServerError(format!("Failed to delete invoices: {}", e)))
+    }
+}
+
+impl InvoiceQuery {
+    /// Invoices matching `filter`, newest first.
+    pub async fn get_invoices(
+        db: &DatabaseConnection,
+        filter: InvoiceFilterParams,
+    ) -> Result<Vec<invoice::Model>, AppError> {
+        Invoice::find()
+            .apply_if(filter.client_id, |q, client_id| {
+                q.filter(invoice::Column::ClientId.eq(client_id))
+            })
+            .apply_if(filter.status, |q, status| {
+                q.filter(invoice::Column::Status.eq(status))
+            })
+            .order_by_desc(invoice::Column::CreatedAt)
+            .all(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get invoices: {}", e)))
+    }
+
+    pub async fn get_invoice_by_id(
+        db: &DatabaseConnection,
+        id: Uuid,
+    ) -> Result<Option<invoice::Model>, AppError> {
+        Invoice::find_by_id(id)
+            .one(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get invoice: {}", e)))
+    }
+
+    /// Lines of the given invoices, in order.
+    pub async fn get_lines<C: ConnectionTrait>(
+        db: &C,
+        invoice_ids: &[Uuid],
+    ) -> Result<Vec<invoice_line::Model>, AppError> {
+        InvoiceLine::find()
+            .filter(invoice_line::Column::InvoiceId.is_in(invoice_ids.iter().copied()))
+            .order_by_asc(invoice_line::Column::Position)
+            .all(db)
+            .await
+            .map_err(|e| {
+                AppError::InternalServerError(format!("Failed to get invoice lines: {}", e))
+            })
+    }
+
+    pub async fn get_all_lines(
+        db: &DatabaseConnection,
+    ) -> Result<Vec<invoice_line::Model>, AppError> {
+        InvoiceLine::find().all(db).await.map_err(|e| {
+            AppError::InternalServerError(format!("Failed to get invoice lines: {}", e))
+        })
+    }
+}
@@ -0,0 +1,346 @@
+use chrono::Utc;
+use sea_orm::{
+    prelude::Uuid,
+    sea_query::{OnConflict, SelectStatement},
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr,
+    EntityTrait, IntoActiveModel, QueryFilter, QueryOrder, QuerySelect, QueryTrait, SqlErr,
+};
+
+use crate::{
+    dtos::label_dto::{LabelCreateDto, LabelUpdateDto},
+    entities::{prelude::*, *},
+    utils::error::AppError,
+};
+
+/// Something a label can be attached to.
+#[derive(Debug, Clone, Copy)]
+pub enum LabelTarget {
+    Project(Uuid),
+    Task(Uuid),
+    SubTask(Uuid),
+}
+
+pub struct LabelQuery;
+pub struct LabelMutation;
+
+/// Label names are unique; report a clash as a conflict rather than a failure.
+fn label_err(action: &str, e: DbErr) -> AppError {
+    match e.sql_err() {
+        Some(SqlErr::UniqueConstraintViolation(_)) => {
+            AppError::Conflict("A label with this name already exists".to_string())
+        }
+        _ => AppError::InternalServerError(format!("Failed to {} label: {}", action, e)),
+    }
+}
+
+impl LabelMutation {
+    pub async fn create_label(
+        db: &DatabaseConnection,
+        data: LabelCreateDto,
+    ) -> Result<label::Model, AppError> {
+        let label = label::ActiveModel {
+            name: ActiveValue::set(data.name),
+            color: ActiveValue::set(data.color.to_lowercase()),
+            ..Default::default()
+        };
+        Label::insert(label)
+            .exec_with_returning(db)
+            .await
+            .map_err(|e| label_err("create", e))
+    }
+
+    pub async fn update_label(
+        db: &DatabaseConnection,
+        id: Uuid,
+        data: LabelUpdateDto,
+    ) -> Result<label::Model, AppError> {
+        let label = label::ActiveModel {
+            id: ActiveValue::set(id),
+            name: data.name.map_or(ActiveValue::NotSet, ActiveValue::set),
+            color: data.color.map_or(ActiveValue::NotSet, |color| {
+                ActiveValue::set(color.to_lowercase())
+            }),
+            updated_at: ActiveValue::set(Utc::now().fixed_offset()),
+            ..Default::default()
+        };
+        label.update(db).await.map_err(|e| match e {
+            DbErr::RecordNotUpdated => {
+                AppError::NotFound(format!("Label with id {} not found", id))
+            }
+            e => label_err("update", e),
+        })
+    }
+
+    /// Delete a label, detaching it from everything it was attached to.
+    pub async fn delete_label(db: &DatabaseConnection, id: Uuid) -> Result<u64, AppError> {
+        Label::delete_by_id(id)
+            .exec(db)
+            .await
+            .map(|res| res.rows_affected)
+            .map_err(|e| label_err("delete", e))
+    }
+
+    /// Attach a label; attaching it again is a no-op.
+    pub async fn assign_label(
+        db: &DatabaseConnection,
+        label_id: Uuid,
+        target: LabelTarget,
+    ) -> Result<(), AppError> {
+        let result = match target {
+            LabelTarget::Project(project_id) => ProjectLabel::insert(project_label::ActiveModel {
+                project_id: ActiveValue::set(project_id),
+                label_id: ActiveValue::set(label_id),
+                ..Default::default()
+            })
+            .on_conflict(
+                OnConflict::columns([
+                    project_label::Column::ProjectId,
+                    project_label::Column::LabelId,
+                ])
+                .do_nothing()
+                .to_owned(),
+            )
+            .do_nothing()
+            .exec(db)
+            .await
+            .map(|_| ()),
+            LabelTarget::Task(task_id) => TaskLabel::insert(task_label::ActiveModel {
+                task_id: ActiveValue::set(task_id),
+                label_id: ActiveValue::set(label_id),
+                ..Default::default()
+            })
+            .on_conflict(
+                OnConflict::columns([task_label::Column::TaskId, task_label::Column::LabelId])
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .do_nothing()
+            .exec(db)
+            .await
+            .map(|_| ()),
+            LabelTarget::SubTask(sub_task_id) => {
+                SubTaskLabel::insert(sub_task_label::ActiveModel {
+                    sub_task_id: ActiveValue::set(sub_task_id),
+                    label_id: ActiveValue::set(label_id),
+                    ..Default::default()
+                })
+                .on_conflict(
+                    OnConflict::columns([
+                        sub_task_label::Column::SubTaskId,
+                        sub_task_label::Column::LabelId,
+                    ])
+                    .do_nothing()
+                    .to_owned(),
+                )
+                .do_nothing()
+                .exec(db)
+                .await
+                .map(|_| ())
+            }
+        };
+        result.map_err(|e| AppError::InternalServerError(format!("Failed to assign label: {}", e)))
+    }
+
+    /// Detach a label; returns the number of assignments removed.
+    pub async fn unassign_label(
+        db: &DatabaseConnection,
+        label_id: Uuid,
+        target: LabelTarget,
+    ) -> Result<u64, AppError> {
+        let result = match target {
+            LabelTarget::Project(project_id) => {
+                ProjectLabel::delete_by_id((project_id, label_id))
+                    .exec(db)
+                    .await
+            }
+            LabelTarget::Task(task_id) => {
+                TaskLabel::delete_by_id((task_id, label_id)).exec(db).await
+            }
+            LabelTarget::SubTask(sub_task_id) => {
+                SubTaskLabel::delete_by_id((sub_task_id, label_id))
+                    .exec(db)
+                    .await
+            }
+        };
+        result
+            .map(|res| res.rows_affected)
+            .map_err(|e| AppError::InternalServerError(format!("Failed to unassign label: {}", e)))
+    }
+
+    /// Insert labels and their assignments as-is, keeping ids and timestamps.
+    pub async fn import_labels<C: ConnectionTrait>(
+        db: &C,
+        rows: Vec<label::Model>,
+    ) -> Result<u64, AppError> {
+        let count = rows.len() as u64;
+        for row in rows {
+            row.into_active_model()
+                .reset_all()
+                .insert(db)
+                .await
+                .map_err(|e| {
+                    AppError::InternalServerError(format!("Failed to import label: {}", e))
+                })?;
+        }
+        Ok(count)
+    }
+
+    pub async fn import_project_labels<C: ConnectionTrait>(
+        db: &C,
+        rows: Vec<project_label::Model>,
+    ) -> Result<u64, AppError> {
+        let count = rows.len() as u64;
+        for row in rows {
+            row.into_active_model()
+                .reset_all()
+                .insert(db)
+                .await
+                .map_err(|e| {
+                    AppError::InternalServerError(format!("Failed to import project label: {}", e))
+                })?;
+        }
+        Ok(count)
+    }
+
+    pub async fn import_task_labels<C: ConnectionTrait>(
+        db: &C,
+        rows: Vec<task_label::Model>,
+    ) -> Result<u64, AppError> {
+        let count = rows.len() as u64;
+        for row in rows {
+            row.into_active_model()
+                .reset_all()
+                .insert(db)
+                .await
+                .map_err(|e| {
+                    AppError::InternalServerError(format!("Failed to import task label: {}", e))
+                })?;
+        }
+        Ok(count)
+    }
+
+    pub async fn import_sub_task_labels<C: ConnectionTrait>(
+        db: &C,
+        rows: Vec<sub_task_label::Model>,
+    ) -> Result<u64, AppError> {
+        let count = rows.len() as u64;
+        for row in rows {
+            row.into_active_model()
+                .reset_all()
+                .insert(db)
+                .await
+                .map_err(|e| {
+                    AppError::InternalServerError(format!("Failed to import sub task label: {}", e))
+                })?;
+        }
+        Ok(count)
+    }
+
+    /// Labels are not owned by a project, so restoring with `--replace`
+    /// clears them separately; their assignments cascade.
+    pub async fn delete_all_labels<C: ConnectionTrait>(db: &C) -> Result<u64, AppError> {
+        Label::delete_many()
+            .exec(db)
+            .await
+            .map(|res| res.rows_affected)
+            .map_err(|e| AppError::InternalServerError(format!("Failed to delete labels: {}", e)))
+    }
+}
+
+impl LabelQuery {
+    pub async fn get_labels(db: &DatabaseConnection) -> Result<Vec<label::Model>, AppError> {
+        Label::find()
+            .order_by_asc(label::Column::Name)
+            .all(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get labels: {}", e)))
+    }
+
+    pub async fn get_label_by_id(
+        db: &DatabaseConnection,
+        id: Uuid,
+    ) -> Result<Option<label::Model>, AppError> {
+        Label::find_by_id(id)
+            .one(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get label: {}", e)))
+    }
+
+    /// Labels attached to `target`, by name.
+    pub async fn get_labels_by_target(
+        db: &DatabaseConnection,
+        target: LabelTarget,
+    ) -> Result<Vec<label::Model>, AppError> {
+        let query = match target {
+            LabelTarget::Project(id) => Label::find()
+                .inner_join(ProjectLabel)
+                .filter(project_label::Column::ProjectId.eq(id)),
+            LabelTarget::Task(id) => Label::find()
+                .inner_join(TaskLabel)
+                .filter(task_label::Column::TaskId.eq(id)),
+            LabelTarget::SubTask(id) => Label::find()
+                .inner_join(SubTaskLabel)
+                .filter(sub_task_label::Column::SubTaskId.eq(id)),
+        };
+        query
+            .order_by_asc(label::Column::Name)
+            .all(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get labels: {}", e)))
+    }
+
+    pub async fn get_project_labels(
+        db: &DatabaseConnection,
+    ) -> Result<Vec<project_label::Model>, AppError> {
+        ProjectLabel::find().all(db).await.map_err(|e| {
+            AppError::InternalServerError(format!("Failed to get project labels: {}", e))
+        })
+    }
+
+    pub async fn get_task_labels(
+        db: &DatabaseConnection,
+    ) -> Result<Vec<task_label::Model>, AppError> {
+        TaskLabel::find()
+            .all(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get task labels: {}", e)))
+    }
+
+    pub async fn get_sub_task_labels(
+        db: &DatabaseConnection,
+    ) -> Result<Vec<sub_task_label::Model>, AppError> {
+        SubTaskLabel::find().all(db).await.map_err(|e| {
+            AppError::InternalServerError(format!("Failed to get sub task labels: {}", e))
+        })
+    }
+
+    /// Subquery selecting the ids of projects carrying any of `names`.
+    pub fn project_ids_labelled(names: Vec<String>) -> SelectStatement {
+        ProjectLabel::find()
+            .select_only()
+            .column(project_label::Column::ProjectId)
+            .inner_join(Label)
+            .filter(label::Column::Name.is_in(names))
+            .into_query()
+    }
+
+    /// Subquery selecting the ids of tasks carrying any of `names`.
+    pub fn task_ids_labelled(names: Vec<String>) -> SelectStatement {
+        TaskLabel::find()
+            .select_only()
+            .column(task_label::Column::TaskId)
+            .inner_join(Label)
+            .filter(label::Column::Name.is_in(names))
+            .into_query()
+    }
+
+    /// Subquery selecting the ids of sub tasks carrying any of `names`.
+    pub fn sub_task_ids_labelled(names: Vec<String>) -> SelectStatement {
+        SubTaskLabel::find()
+            .select_only()
+            .column(sub_task_label::Column::SubTaskId)
+            .inner_join(Label)
+            .filter(label::Column::Name.is_in(names))
+            .into_query()
+    }
+}
@@ -1,4 +1,17 @@
+pub mod activity_repository;
+pub mod billing_rate_repository;
+pub mod budget_alert_repository;
+pub mod client_repository;
+pub mod comment_repository;
+pub mod invoice_repository;
+pub mod label_repository;
 pub mod project_repository;
+pub mod recurrence_repository;
+pub mod search_repository;
 pub mod section_repository;
 pub mod sub_task_repository;
+pub mod task_dependency_repository;
+pub mod task_event_repository;
 pub mod task_repository;
+pub mod timer_session_repository;
+pub mod tracking_policy_repository;
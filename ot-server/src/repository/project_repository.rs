@@ -1,10 +1,19 @@
 use std::sync::Arc;
 
-use sea_orm::{prelude::Uuid, ActiveModelTrait, ActiveValue, DatabaseConnection, EntityTrait};
+use sea_orm::{
+    prelude::{DateTimeWithTimeZone, Expr, Uuid},
+    sea_query::SelectStatement,
+    ActiveModelTrait, ActiveValue, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection,
+    EntityTrait, IntoActiveModel, JoinType, QueryFilter, QuerySelect, QueryTrait, RelationTrait,
+};
 
 use crate::{
-    dtos::project_dto::{ProjectCreateDto, ProjectUpdateDto},
+    dtos::{
+        label_dto::label_names,
+        project_dto::{ProjectCreateDto, ProjectFilterParams, ProjectUpdateDto},
+    },
     entities::{prelude::*, *},
+    repository::label_repository::LabelQuery,
     utils::error::AppError,
 };
 
@@ -12,6 +21,15 @@ pub struct ProjectQuery;
 
 pub struct ProjectMutation;
 
+/// Something that lives in a project.
+#[derive(Debug, Clone, Copy)]
+pub enum ProjectItem {
+    Project(Uuid),
+    Section(Uuid),
+    Task(Uuid),
+    SubTask(Uuid),
+}
+
 impl ProjectMutation {
     pub async fn create_project(
         db: &DatabaseConnection,
@@ -19,6 +37,11 @@ impl ProjectMutation {
     ) -> Result<project::Model, AppError> {
         let project = project::ActiveModel {
             name: ActiveValue::set(data.name),
+            client_id: ActiveValue::set(data.client_id),
+            billable: ActiveValue::set(data.billable.unwrap_or(true)),
+            budget_minutes: ActiveValue::set(data.budget_minutes),
+            budget_amount: ActiveValue::set(data.budget_amount),
+            budget_currency: ActiveValue::set(data.budget_currency),
             ..Default::default()
         };
         Project::insert(project)
@@ -38,6 +61,17 @@ impl ProjectMutation {
             id: ActiveValue::set(id),
             name: ActiveValue::set(data.name),
             version: ActiveValue::set(data.version + 1),
+            client_id: data.client_id.map_or(ActiveValue::NotSet, ActiveValue::set),
+            billable: data.billable.map_or(ActiveValue::NotSet, ActiveValue::set),
+            budget_minutes: data
+                .budget_minutes
+                .map_or(ActiveValue::NotSet, ActiveValue::set),
+            budget_amount: data
+                .budget_amount
+                .map_or(ActiveValue::NotSet, ActiveValue::set),
+            budget_currency: data
+                .budget_currency
+                .map_or(ActiveValue::NotSet, ActiveValue::set),
             ..Default::default()
         };
 
@@ -68,6 +102,32 @@ impl ProjectMutation {
             .ok_or_else(|| AppError::NotFound(format!("Project with id {} not found", id)))
     }
 
+    /// Archive the project at `archived_at`, or unarchive it with `None`.
+    /// Bumps the version so edits based on the old state conflict.
+    pub async fn set_archived(
+        db: &DatabaseConnection,
+        id: Uuid,
+        archived_at: Option<DateTimeWithTimeZone>,
+    ) -> Result<project::Model, AppError> {
+        Project::update_many()
+            .col_expr(project::Column::ArchivedAt, Expr::value(archived_at))
+            .col_expr(
+                project::Column::Version,
+                Expr::col(project::Column::Version).add(1),
+            )
+            .filter(project::Column::Id.eq(id))
+            .exec(db)
+            .await
+            .map_err(|e| {
+                AppError::InternalServerError(format!("Failed to archive project: {}", e))
+            })?;
+        Project::find_by_id(id)
+            .one(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to fetch project: {}", e)))?
+            .ok_or_else(|| AppError::NotFound(format!("Project with id {} not found", id)))
+    }
+
     pub async fn delete_project(db: &DatabaseConnection, id: Uuid) -> Result<u64, AppError> {
         Project::delete_by_id(id)
             .exec(db)
@@ -75,11 +135,135 @@ impl ProjectMutation {
             .map(|res| res.rows_affected)
             .map_err(|e| AppError::InternalServerError(format!("Failed to delete project: {}", e)))
     }
+
+    /// Insert projects as-is, keeping their ids and timestamps.
+    pub async fn import_projects<C: ConnectionTrait>(
+        db: &C,
+        rows: Vec<project::Model>,
+    ) -> Result<u64, AppError> {
+        let count = rows.len() as u64;
+        for row in rows {
+            row.into_active_model()
+                .reset_all()
+                .insert(db)
+                .await
+                .map_err(|e| {
+                    AppError::InternalServerError(format!("Failed to import project: {}", e))
+                })?;
+        }
+        Ok(count)
+    }
+
+    /// Delete every project; sections, tasks and sub tasks go with them.
+    pub async fn delete_all_projects<C: ConnectionTrait>(db: &C) -> Result<u64, AppError> {
+        Project::delete_many()
+            .exec(db)
+            .await
+            .map(|res| res.rows_affected)
+            .map_err(|e| AppError::InternalServerError(format!("Failed to delete projects: {}", e)))
+    }
 }
 
 impl ProjectQuery {
-    pub async fn get_projects(db: &DatabaseConnection) -> Result<Vec<project::Model>, AppError> {
+    pub async fn get_projects(
+        db: &DatabaseConnection,
+        filter: ProjectFilterParams,
+    ) -> Result<Vec<project::Model>, AppError> {
+        // Archived projects are only listed when asked for
+        let archived = match filter.archived {
+            Some(true) => project::Column::ArchivedAt.is_not_null(),
+            _ => project::Column::ArchivedAt.is_null(),
+        };
+        Project::find()
+            .filter(archived)
+            .apply_if(filter.client_id, |q, client_id| {
+                q.filter(project::Column::ClientId.eq(client_id))
+            })
+            .apply_if(label_names(filter.label.as_deref()), |q, names| {
+                q.filter(project::Column::Id.in_subquery(LabelQuery::project_ids_labelled(names)))
+            })
+            .all(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to fetch projects: {}", e)))
+    }
+
+    /// Every project, archived or not.
+    pub async fn get_all_projects(
+        db: &DatabaseConnection,
+    ) -> Result<Vec<project::Model>, AppError> {
+        Project::find()
+            .all(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to fetch projects: {}", e)))
+    }
+
+    /// The project `item` lives in, if that project is archived.
+    pub async fn get_archived_project(
+        db: &DatabaseConnection,
+        item: ProjectItem,
+    ) -> Result<Option<project::Model>, AppError> {
+        let section_project = Section::find()
+            .select_only()
+            .column(section::Column::ProjectId);
+        let in_project = match item {
+            ProjectItem::Project(id) => project::Column::Id.eq(id),
+            ProjectItem::Section(id) => project::Column::Id.in_subquery(
+                section_project
+                    .filter(section::Column::Id.eq(id))
+                    .into_query(),
+            ),
+            ProjectItem::Task(id) => project::Column::Id.in_subquery(
+                section_project
+                    .join(JoinType::InnerJoin, section::Relation::Task.def())
+                    .filter(task::Column::Id.eq(id))
+                    .into_query(),
+            ),
+            ProjectItem::SubTask(id) => project::Column::Id.in_subquery(
+                section_project
+                    .join(JoinType::InnerJoin, section::Relation::SubTask.def())
+                    .filter(sub_task::Column::Id.eq(id))
+                    .into_query(),
+            ),
+        };
+        Project::find()
+            .filter(project::Column::ArchivedAt.is_not_null())
+            .filter(in_project)
+            .one(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to fetch project: {}", e)))
+    }
+
+    /// Ids of the tasks of archived projects, for use as a subquery.
+    pub fn archived_task_ids() -> SelectStatement {
+        Task::find()
+            .select_only()
+            .column(task::Column::Id)
+            .join(JoinType::InnerJoin, task::Relation::Section.def())
+            .join(JoinType::InnerJoin, section::Relation::Project.def())
+            .filter(project::Column::ArchivedAt.is_not_null())
+            .into_query()
+    }
+
+    /// Active projects with a time or money budget, or a task with an
+    /// estimate.
+    pub async fn get_budgeted_project_ids(db: &DatabaseConnection) -> Result<Vec<Uuid>, AppError> {
+        let estimated = Section::find()
+            .select_only()
+            .column(section::Column::ProjectId)
+            .join(JoinType::InnerJoin, section::Relation::Task.def())
+            .filter(task::Column::EstimatedMinutes.is_not_null())
+            .into_query();
         Project::find()
+            .select_only()
+            .column(project::Column::Id)
+            .filter(project::Column::ArchivedAt.is_null())
+            .filter(
+                Condition::any()
+                    .add(project::Column::BudgetMinutes.is_not_null())
+                    .add(project::Column::BudgetAmount.is_not_null())
+                    .add(project::Column::Id.in_subquery(estimated)),
+            )
+            .into_tuple()
             .all(db)
             .await
             .map_err(|e| AppError::InternalServerError(format!("Failed to fetch projects: {}", e)))
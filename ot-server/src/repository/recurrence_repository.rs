@@ -0,0 +1,293 @@
+use chrono::{NaiveDate, Utc};
+use sea_orm::{
+    prelude::Uuid,
+    sea_query::{LockBehavior, LockType, OnConflict},
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr,
+    EntityTrait, IntoActiveModel, QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
+
+use crate::{
+    dtos::task_dto::DEFAULT_STATUS,
+    entities::{prelude::*, *},
+    repository::{
+        project_repository::ProjectQuery,
+        task_event_repository::{EventTarget, TaskEventMutation},
+        task_repository::TaskQuery,
+    },
+    utils::{error::AppError, recurrence::RecurrenceRule},
+};
+
+pub struct RecurrenceQuery;
+pub struct RecurrenceMutation;
+
+/// The rule a stored recurrence follows.
+pub fn recurrence_rule(recurrence: &task_recurrence::Model) -> RecurrenceRule {
+    RecurrenceRule {
+        frequency: recurrence.frequency,
+        interval: recurrence.interval as u32,
+        until: recurrence.until,
+        count: recurrence.count.map(|count| count as u32),
+    }
+}
+
+impl RecurrenceMutation {
+    /// Give a task a recurrence rule, replacing the one it had. The series
+    /// restarts at `starts_on`, the task itself being its first occurrence.
+    pub async fn set_recurrence(
+        db: &DatabaseConnection,
+        task_id: Uuid,
+        rule: RecurrenceRule,
+        starts_on: NaiveDate,
+    ) -> Result<task_recurrence::Model, AppError> {
+        let recurrence = task_recurrence::ActiveModel {
+            task_id: ActiveValue::set(task_id),
+            frequency: ActiveValue::set(rule.frequency),
+            interval: ActiveValue::set(rule.interval as i32),
+            starts_on: ActiveValue::set(starts_on),
+            until: ActiveValue::set(rule.until),
+            count: ActiveValue::set(rule.count.map(|count| count as i32)),
+            occurrence_count: ActiveValue::set(1),
+            next_occurrence: ActiveValue::set(rule.occurrence(starts_on, 1)),
+            updated_at: ActiveValue::set(Utc::now().fixed_offset()),
+            ..Default::default()
+        };
+        TaskRecurrence::insert(recurrence)
+            .on_conflict(
+                OnConflict::column(task_recurrence::Column::TaskId)
+                    .update_columns([
+                        task_recurrence::Column::Frequency,
+                        task_recurrence::Column::Interval,
+                        task_recurrence::Column::StartsOn,
+                        task_recurrence::Column::Until,
+                        task_recurrence::Column::Count,
+                        task_recurrence::Column::OccurrenceCount,
+                        task_recurrence::Column::NextOccurrence,
+                        task_recurrence::Column::UpdatedAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec_with_returning(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to set recurrence: {}", e)))
+    }
+
+    /// Stop a task from recurring; tasks already generated are kept.
+    pub async fn delete_recurrence(
+        db: &DatabaseConnection,
+        task_id: Uuid,
+    ) -> Result<u64, AppError> {
+        TaskRecurrence::delete_many()
+            .filter(task_recurrence::Column::TaskId.eq(task_id))
+            .exec(db)
+            .await
+            .map(|res| res.rows_affected)
+            .map_err(|e| {
+                AppError::InternalServerError(format!("Failed to delete recurrence: {}", e))
+            })
+    }
+
+    /// Generate the occurrences of a recurrence dated up to `horizon`, each
+    /// as a copy of the recurring task at the end of its section; returns
+    /// how many were created. Occurrences dated before `today`, missed while
+    /// the server was down, are skipped rather than created late.
+    ///
+    /// The recurrence row stays locked until its progress is committed, so
+    /// concurrent runs skip it, and a date that already has a task is never
+    /// generated again.
+    pub async fn generate_occurrences(
+        db: &DatabaseConnection,
+        id: Uuid,
+        today: NaiveDate,
+        horizon: NaiveDate,
+    ) -> Result<u64, DbErr> {
+        let txn = db.begin().await?;
+        let Some(recurrence) = TaskRecurrence::find_by_id(id)
+            .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked)
+            .one(&txn)
+            .await?
+        else {
+            return Ok(0);
+        };
+        let Some(template) = Task::find_by_id(recurrence.task_id).one(&txn).await? else {
+            return Ok(0);
+        };
+
+        let rule = recurrence_rule(&recurrence);
+        let mut index = recurrence.occurrence_count as u32;
+        let mut next = recurrence.next_occurrence;
+        let mut created = 0;
+        while let Some(date) = next.filter(|date| *date <= horizon) {
+            let exists = TaskOccurrence::find_by_id((id, date))
+                .one(&txn)
+                .await?
+                .is_some();
+            if date >= today && !exists {
+                Self::create_occurrence(&txn, &template, id, date).await?;
+                created += 1;
+            }
+            index += 1;
+            next = rule.occurrence(recurrence.starts_on, index);
+        }
+
+        task_recurrence::ActiveModel {
+            id: ActiveValue::set(id),
+            occurrence_count: ActiveValue::set(index as i32),
+            next_occurrence: ActiveValue::set(next),
+            ..Default::default()
+        }
+        .update(&txn)
+        .await?;
+        txn.commit().await?;
+        Ok(created)
+    }
+
+    /// Copy `template` into a new task due on `date`, with the same labels.
+    async fn create_occurrence<C: ConnectionTrait>(
+        db: &C,
+        template: &task::Model,
+        recurrence_id: Uuid,
+        date: NaiveDate,
+    ) -> Result<(), DbErr> {
+        let task = Task::insert(task::ActiveModel {
+            name: ActiveValue::set(template.name.clone()),
+            section_id: ActiveValue::set(template.section_id),
+            position: ActiveValue::set(
+                TaskQuery::get_last_position(db, template.section_id).await?,
+            ),
+            description: ActiveValue::set(template.description.clone()),
+            status: ActiveValue::set(DEFAULT_STATUS.to_string()),
+            priority: ActiveValue::set(template.priority),
+            due_date: ActiveValue::set(Some(date)),
+            estimated_minutes: ActiveValue::set(template.estimated_minutes),
+            assignee: ActiveValue::set(template.assignee.clone()),
+            billable: ActiveValue::set(template.billable),
+            ..Default::default()
+        })
+        .exec_with_returning(db)
+        .await?;
+        TaskEventMutation::record(db, EventTarget::Task(task.id), "created", None, &task).await?;
+
+        task_occurrence::ActiveModel {
+            recurrence_id: ActiveValue::set(recurrence_id),
+            occurs_on: ActiveValue::set(date),
+            task_id: ActiveValue::set(task.id),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+
+        let labels = TaskLabel::find()
+            .filter(task_label::Column::TaskId.eq(template.id))
+            .all(db)
+            .await?;
+        if !labels.is_empty() {
+            TaskLabel::insert_many(labels.into_iter().map(|label| task_label::ActiveModel {
+                task_id: ActiveValue::set(task.id),
+                label_id: ActiveValue::set(label.label_id),
+                ..Default::default()
+            }))
+            .exec(db)
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Insert recurrences as-is, keeping their ids, progress and timestamps.
+    pub async fn import_task_recurrences<C: ConnectionTrait>(
+        db: &C,
+        rows: Vec<task_recurrence::Model>,
+    ) -> Result<u64, AppError> {
+        let count = rows.len() as u64;
+        for row in rows {
+            row.into_active_model()
+                .reset_all()
+                .insert(db)
+                .await
+                .map_err(|e| {
+                    AppError::InternalServerError(format!("Failed to import recurrence: {}", e))
+                })?;
+        }
+        Ok(count)
+    }
+
+    pub async fn import_task_occurrences<C: ConnectionTrait>(
+        db: &C,
+        rows: Vec<task_occurrence::Model>,
+    ) -> Result<u64, AppError> {
+        let count = rows.len() as u64;
+        for row in rows {
+            row.into_active_model()
+                .reset_all()
+                .insert(db)
+                .await
+                .map_err(|e| {
+                    AppError::InternalServerError(format!("Failed to import occurrence: {}", e))
+                })?;
+        }
+        Ok(count)
+    }
+}
+
+impl RecurrenceQuery {
+    pub async fn get_task_recurrences(
+        db: &DatabaseConnection,
+    ) -> Result<Vec<task_recurrence::Model>, AppError> {
+        TaskRecurrence::find()
+            .all(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get recurrences: {}", e)))
+    }
+
+    pub async fn get_task_occurrences(
+        db: &DatabaseConnection,
+    ) -> Result<Vec<task_occurrence::Model>, AppError> {
+        TaskOccurrence::find()
+            .all(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get occurrences: {}", e)))
+    }
+
+    pub async fn get_recurrence_by_task_id(
+        db: &DatabaseConnection,
+        task_id: Uuid,
+    ) -> Result<Option<task_recurrence::Model>, AppError> {
+        TaskRecurrence::find()
+            .filter(task_recurrence::Column::TaskId.eq(task_id))
+            .one(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get recurrence: {}", e)))
+    }
+
+    /// Tasks generated for a recurrence, by date.
+    pub async fn get_occurrence_tasks(
+        db: &DatabaseConnection,
+        recurrence_id: Uuid,
+    ) -> Result<Vec<task::Model>, AppError> {
+        Task::find()
+            .inner_join(TaskOccurrence)
+            .filter(task_occurrence::Column::RecurrenceId.eq(recurrence_id))
+            .order_by_asc(task_occurrence::Column::OccursOn)
+            .all(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get tasks: {}", e)))
+    }
+
+    /// Recurrences with an occurrence dated up to `horizon` still to generate,
+    /// leaving out those of archived projects.
+    pub async fn get_due_recurrence_ids(
+        db: &DatabaseConnection,
+        horizon: NaiveDate,
+    ) -> Result<Vec<Uuid>, DbErr> {
+        TaskRecurrence::find()
+            .select_only()
+            .column(task_recurrence::Column::Id)
+            .filter(task_recurrence::Column::NextOccurrence.lte(horizon))
+            .filter(
+                task_recurrence::Column::TaskId.not_in_subquery(ProjectQuery::archived_task_ids()),
+            )
+            .order_by_asc(task_recurrence::Column::NextOccurrence)
+            .into_tuple()
+            .all(db)
+            .await
+    }
+}
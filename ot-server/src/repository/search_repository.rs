@@ -0,0 +1,257 @@
+use std::collections::HashMap;
+
+use sea_orm::{
+    prelude::Uuid, ColumnTrait, ConnectionTrait, DatabaseBackend, DatabaseConnection, EntityTrait,
+    FromQueryResult, QueryFilter, QueryTrait, Statement,
+};
+
+use crate::{
+    entities::{prelude::*, *},
+    utils::error::AppError,
+};
+
+pub struct SearchQuery;
+
+/// One match with the names of its ancestors, flattened for the UNION query.
+#[derive(Debug, FromQueryResult)]
+pub struct SearchRow {
+    /// `project`, `section`, `task` or `sub_task`
+    pub kind: String,
+    pub id: Uuid,
+    pub name: String,
+    pub project_id: Uuid,
+    pub project_name: String,
+    pub section_id: Option<Uuid>,
+    pub section_name: Option<String>,
+    pub task_id: Option<Uuid>,
+    pub task_name: Option<String>,
+    pub rank: f32,
+}
+
+/// Full-text prefix match or trigram word similarity, ranked by both. The
+/// `to_tsvector('simple', name)` expressions match the indexes created in
+/// `m20220101_000007_create_search_indexes`.
+const POSTGRES_SEARCH: &str = r#"
+WITH q AS (SELECT to_tsquery('simple', $1) AS tsq, $2::text AS term)
+SELECT kind, id, name, project_id, project_name, section_id, section_name, task_id, task_name, rank
+FROM (
+    SELECT 'project' AS kind, p.id, p.name, p.id AS project_id, p.name AS project_name,
+           NULL::uuid AS section_id, NULL::text AS section_name,
+           NULL::uuid AS task_id, NULL::text AS task_name,
+           (ts_rank(to_tsvector('simple', p.name), q.tsq) + word_similarity(q.term, p.name))::real AS rank
+    FROM project p CROSS JOIN q
+    WHERE to_tsvector('simple', p.name) @@ q.tsq OR q.term <% p.name
+    UNION ALL
+    SELECT 'section', s.id, s.name, p.id, p.name, s.id, s.name, NULL, NULL,
+           (ts_rank(to_tsvector('simple', s.name), q.tsq) + word_similarity(q.term, s.name))::real
+    FROM section s JOIN project p ON p.id = s.project_id CROSS JOIN q
+    WHERE to_tsvector('simple', s.name) @@ q.tsq OR q.term <% s.name
+    UNION ALL
+    SELECT 'task', t.id, t.name, p.id, p.name, s.id, s.name, t.id, t.name,
+           (ts_rank(to_tsvector('simple', t.name), q.tsq) + word_similarity(q.term, t.name))::real
+    FROM task t JOIN section s ON s.id = t.section_id JOIN project p ON p.id = s.project_id CROSS JOIN q
+    WHERE to_tsvector('simple', t.name) @@ q.tsq OR q.term <% t.name
+    UNION ALL
+    SELECT 'sub_task', st.id, st.name, p.id, p.name, s.id, s.name, t.id, t.name,
+           (ts_rank(to_tsvector('simple', st.name), q.tsq) + word_similarity(q.term, st.name))::real
+    FROM sub_task st JOIN section s ON s.id = st.section_id JOIN project p ON p.id = s.project_id
+         LEFT JOIN task t ON t.id = st.task_id CROSS JOIN q
+    WHERE to_tsvector('simple', st.name) @@ q.tsq OR q.term <% st.name
+) hits
+WHERE ($3::uuid IS NULL OR project_id = $3::uuid)
+  AND ($5::bool OR project_id IN (SELECT id FROM project WHERE archived_at IS NULL))
+ORDER BY rank DESC, name
+LIMIT $4
+"#;
+
+impl SearchQuery {
+    /// Search names across projects, sections, tasks and sub tasks.
+    /// `terms` are the normalised words of the query; at least one is required.
+    /// Archived projects are left out unless `include_archived` is set.
+    pub async fn search(
+        db: &DatabaseConnection,
+        terms: &[String],
+        project_id: Option<Uuid>,
+        include_archived: bool,
+        limit: u64,
+    ) -> Result<Vec<SearchRow>, AppError> {
+        match db.get_database_backend() {
+            DatabaseBackend::Postgres => {
+                Self::search_postgres(db, terms, project_id, include_archived, limit).await
+            }
+            _ => Self::search_fallback(db, terms, project_id, include_archived, limit).await,
+        }
+    }
+
+    async fn search_postgres(
+        db: &DatabaseConnection,
+        terms: &[String],
+        project_id: Option<Uuid>,
+        include_archived: bool,
+        limit: u64,
+    ) -> Result<Vec<SearchRow>, AppError> {
+        // Every word must match, the last one (still being typed) as a prefix
+        // and the others too, so "web red" finds "Website Redesign".
+        let tsquery = terms
+            .iter()
+            .map(|term| format!("{}:*", term))
+            .collect::<Vec<_>>()
+            .join(" & ");
+
+        SearchRow::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            POSTGRES_SEARCH,
+            [
+                tsquery.into(),
+                terms.join(" ").into(),
+                project_id.into(),
+                (limit as i64).into(),
+                include_archived.into(),
+            ],
+        ))
+        .all(db)
+        .await
+        .map_err(|e| AppError::InternalServerError(format!("Failed to search: {}", e)))
+    }
+
+    /// Case-insensitive substring match for backends without full-text
+    /// search: every term must appear in the name, prefix matches rank first.
+    async fn search_fallback(
+        db: &DatabaseConnection,
+        terms: &[String],
+        project_id: Option<Uuid>,
+        include_archived: bool,
+        limit: u64,
+    ) -> Result<Vec<SearchRow>, AppError> {
+        let map_err = |e| AppError::InternalServerError(format!("Failed to search: {}", e));
+        let first = terms[0].as_str();
+
+        let projects = Project::find()
+            .filter(project::Column::Name.contains(first))
+            .all(db)
+            .await
+            .map_err(map_err)?;
+        let sections = Section::find()
+            .filter(section::Column::Name.contains(first))
+            .all(db)
+            .await
+            .map_err(map_err)?;
+        let tasks = Task::find()
+            .filter(task::Column::Name.contains(first))
+            .all(db)
+            .await
+            .map_err(map_err)?;
+        let sub_tasks = SubTask::find()
+            .filter(sub_task::Column::Name.contains(first))
+            .all(db)
+            .await
+            .map_err(map_err)?;
+
+        // Load the ancestors needed for breadcrumbs
+        let section_ids = tasks
+            .iter()
+            .map(|t| t.section_id)
+            .chain(sub_tasks.iter().map(|st| st.section_id))
+            .chain(sections.iter().map(|s| s.id));
+        let all_sections: HashMap<Uuid, section::Model> = Section::find()
+            .filter(section::Column::Id.is_in(section_ids.collect::<Vec<_>>()))
+            .all(db)
+            .await
+            .map_err(map_err)?
+            .into_iter()
+            .map(|s| (s.id, s))
+            .collect();
+        let project_ids = all_sections
+            .values()
+            .map(|s| s.project_id)
+            .chain(projects.iter().map(|p| p.id));
+        // Hits outside these projects are dropped below
+        let all_projects: HashMap<Uuid, String> = Project::find()
+            .filter(project::Column::Id.is_in(project_ids.collect::<Vec<_>>()))
+            .apply_if((!include_archived).then_some(()), |q, _| {
+                q.filter(project::Column::ArchivedAt.is_null())
+            })
+            .all(db)
+            .await
+            .map_err(map_err)?
+            .into_iter()
+            .map(|p| (p.id, p.name))
+            .collect();
+        let task_ids = sub_tasks.iter().filter_map(|st| st.task_id);
+        let all_tasks: HashMap<Uuid, String> = Task::find()
+            .filter(task::Column::Id.is_in(task_ids.collect::<Vec<_>>()))
+            .all(db)
+            .await
+            .map_err(map_err)?
+            .into_iter()
+            .map(|t| (t.id, t.name))
+            .chain(tasks.iter().map(|t| (t.id, t.name.clone())))
+            .collect();
+
+        let row = |kind: &str,
+                   id: Uuid,
+                   name: String,
+                   section_id: Option<Uuid>,
+                   task_id: Option<Uuid>,
+                   project_id: Uuid| {
+            Some(SearchRow {
+                kind: kind.to_string(),
+                id,
+                project_name: all_projects.get(&project_id)?.clone(),
+                project_id,
+                section_name: section_id.and_then(|id| Some(all_sections.get(&id)?.name.clone())),
+                section_id,
+                task_name: task_id.and_then(|id| all_tasks.get(&id).cloned()),
+                task_id,
+                rank: 0.0,
+                name,
+            })
+        };
+        let section_project = |id: Uuid| all_sections.get(&id).map(|s| s.project_id);
+
+        let mut rows: Vec<SearchRow> = projects
+            .into_iter()
+            .filter_map(|p| row("project", p.id, p.name, None, None, p.id))
+            .chain(
+                sections
+                    .into_iter()
+                    .filter_map(|s| row("section", s.id, s.name, Some(s.id), None, s.project_id)),
+            )
+            .chain(tasks.into_iter().filter_map(|t| {
+                let project_id = section_project(t.section_id)?;
+                row(
+                    "task",
+                    t.id,
+                    t.name,
+                    Some(t.section_id),
+                    Some(t.id),
+                    project_id,
+                )
+            }))
+            .chain(sub_tasks.into_iter().filter_map(|st| {
+                let project_id = section_project(st.section_id)?;
+                row(
+                    "sub_task",
+                    st.id,
+                    st.name,
+                    Some(st.section_id),
+                    st.task_id,
+                    project_id,
+                )
+            }))
+            .filter(|row| project_id.is_none_or(|id| row.project_id == id))
+            .filter_map(|mut row| {
+                let name = row.name.to_lowercase();
+                if !terms.iter().all(|term| name.contains(term.as_str())) {
+                    return None;
+                }
+                row.rank = if name.starts_with(first) { 1.0 } else { 0.5 };
+                Some(row)
+            })
+            .collect();
+
+        rows.sort_by(|a, b| b.rank.total_cmp(&a.rank).then_with(|| a.name.cmp(&b.name)));
+        rows.truncate(limit as usize);
+        Ok(rows)
+    }
+}
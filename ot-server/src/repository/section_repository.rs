@@ -1,15 +1,19 @@
 use futures::future::Select;
 use sea_orm::{
     prelude::{DateTimeWithTimeZone, Uuid},
-    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, ModelTrait,
-    QueryFilter, Related, RelationTrait,
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr,
+    EntityTrait, IntoActiveModel, ModelTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait,
+    Related, RelationTrait, TransactionTrait,
 };
 use sea_orm_migration::seaql_migrations::Column;
 
 use crate::{
-    dtos::section_dto::{SectionCreateDto, SectionUpdateDto},
+    dtos::section_dto::{SectionCreateDto, SectionMoveDto, SectionUpdateDto},
     entities::{prelude::*, *},
-    utils::error::AppError,
+    utils::{
+        error::AppError,
+        ordering::{key_after, key_after_last},
+    },
 };
 
 pub struct SectionQuery;
@@ -20,15 +24,24 @@ impl SectionMutation {
         db: &DatabaseConnection,
         data: SectionCreateDto,
     ) -> Result<section::Model, AppError> {
+        let map_err = |e| AppError::InternalServerError(format!("Failed to create section: {}", e));
+        let txn = db.begin().await.map_err(map_err)?;
+        let position = SectionQuery::get_last_position(&txn, data.project_id)
+            .await
+            .map_err(map_err)?;
+
         let section = section::ActiveModel {
             name: ActiveValue::set(data.name),
             project_id: ActiveValue::set(data.project_id),
+            position: ActiveValue::set(position),
             ..Default::default()
         };
-        Section::insert(section)
-            .exec_with_returning(db)
+        let section = Section::insert(section)
+            .exec_with_returning(&txn)
             .await
-            .map_err(|e| AppError::InternalServerError(format!("Failed to create section: {}", e)))
+            .map_err(map_err)?;
+        txn.commit().await.map_err(map_err)?;
+        Ok(section)
     }
 
     pub async fn update_section(
@@ -46,6 +59,54 @@ impl SectionMutation {
         })
     }
 
+    /// Reorder a section within its project or move it, with its tasks and
+    /// sub tasks, to another project.
+    pub async fn move_section(
+        db: &DatabaseConnection,
+        id: Uuid,
+        data: SectionMoveDto,
+    ) -> Result<section::Model, AppError> {
+        let map_err = |e| AppError::InternalServerError(format!("Failed to move section: {}", e));
+        let txn = db.begin().await.map_err(map_err)?;
+
+        if Project::find_by_id(data.project_id)
+            .one(&txn)
+            .await
+            .map_err(map_err)?
+            .is_none()
+        {
+            return Err(AppError::NotFound(format!(
+                "Project with id {} not found",
+                data.project_id
+            )));
+        }
+        let siblings = SectionQuery::get_positions(&txn, data.project_id, Some(id))
+            .await
+            .map_err(map_err)?;
+        let position = key_after(&siblings, data.after_id).ok_or_else(|| {
+            AppError::BadRequest(format!(
+                "Section {} is not in project {}",
+                data.after_id.unwrap_or_default(),
+                data.project_id
+            ))
+        })?;
+
+        let section = section::ActiveModel {
+            id: ActiveValue::set(id),
+            project_id: ActiveValue::set(data.project_id),
+            position: ActiveValue::set(position),
+            ..Default::default()
+        };
+        let section = section.update(&txn).await.map_err(|e| match e {
+            DbErr::RecordNotUpdated => {
+                AppError::NotFound(format!("Section with id {} not found", id))
+            }
+            e => map_err(e),
+        })?;
+        txn.commit().await.map_err(map_err)?;
+        Ok(section)
+    }
+
     pub async fn delete_section(db: &DatabaseConnection, id: Uuid) -> Result<u64, AppError> {
         Section::delete_by_id(id)
             .exec(db)
@@ -53,6 +114,24 @@ impl SectionMutation {
             .map(|res| res.rows_affected)
             .map_err(|e| AppError::InternalServerError(format!("Failed to delete section: {}", e)))
     }
+
+    /// Insert sections as-is, keeping their ids and timestamps.
+    pub async fn import_sections<C: ConnectionTrait>(
+        db: &C,
+        rows: Vec<section::Model>,
+    ) -> Result<u64, AppError> {
+        let count = rows.len() as u64;
+        for row in rows {
+            row.into_active_model()
+                .reset_all()
+                .insert(db)
+                .await
+                .map_err(|e| {
+                    AppError::InternalServerError(format!("Failed to import section: {}", e))
+                })?;
+        }
+        Ok(count)
+    }
 }
 
 impl SectionQuery {
@@ -78,8 +157,39 @@ impl SectionQuery {
     ) -> Result<Vec<section::Model>, AppError> {
         Section::find()
             .filter(section::Column::ProjectId.eq(project_id))
+            .order_by_asc(section::Column::Position)
+            .order_by_asc(section::Column::Id)
             .all(db)
             .await
             .map_err(|e| AppError::InternalServerError(format!("Failed to get sections: {}", e)))
     }
+
+    /// `(id, position)` of the sections in a project, in order, leaving out
+    /// `exclude`.
+    pub async fn get_positions<C: ConnectionTrait>(
+        db: &C,
+        project_id: Uuid,
+        exclude: Option<Uuid>,
+    ) -> Result<Vec<(Uuid, String)>, DbErr> {
+        Section::find()
+            .select_only()
+            .columns([section::Column::Id, section::Column::Position])
+            .filter(section::Column::ProjectId.eq(project_id))
+            .apply_if(exclude, |q, id| q.filter(section::Column::Id.ne(id)))
+            .order_by_asc(section::Column::Position)
+            .order_by_asc(section::Column::Id)
+            .into_tuple()
+            .all(db)
+            .await
+    }
+
+    /// Position placing a new section after every section in the project.
+    pub async fn get_last_position<C: ConnectionTrait>(
+        db: &C,
+        project_id: Uuid,
+    ) -> Result<String, DbErr> {
+        let positions = Self::get_positions(db, project_id, None).await?;
+        let positions = positions.into_iter().map(|(_, p)| p).collect::<Vec<_>>();
+        Ok(key_after_last(&positions))
+    }
 }
@@ -1,12 +1,24 @@
 use sea_orm::{
-    prelude::Uuid, ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, DbErr,
-    DeleteResult, EntityTrait, QueryFilter,
+    prelude::Uuid, ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DatabaseConnection,
+    DbErr, DeleteResult, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder, QuerySelect,
+    QueryTrait, Select, TransactionTrait,
 };
 
 use crate::{
-    dtos::sub_task_dto::{SubTaskCreateDto, SubTaskUpdateDto},
+    dtos::{
+        label_dto::label_names,
+        sub_task_dto::{SubTaskCreateDto, SubTaskMoveDto, SubTaskUpdateDto},
+        task_dto::{TaskFilterParams, DEFAULT_STATUS},
+    },
     entities::{prelude::*, *},
-    utils::error::AppError,
+    repository::{
+        label_repository::LabelQuery,
+        task_event_repository::{EventTarget, TaskEventMutation},
+    },
+    utils::{
+        error::AppError,
+        ordering::{key_after, key_after_last},
+    },
 };
 
 pub struct SubTaskMutation;
@@ -19,19 +31,43 @@ impl SubTaskMutation {
         section_id: Uuid,
         task_id: Option<Uuid>,
     ) -> Result<sub_task::Model, AppError> {
+        let map_err =
+            |e| AppError::InternalServerError(format!("Failed to create sub task: {:?}", e));
+        let txn = db.begin().await.map_err(map_err)?;
+        Self::ensure_parent(&txn, section_id, task_id).await?;
+        let position = SubTaskQuery::get_last_position(&txn, section_id, task_id)
+            .await
+            .map_err(map_err)?;
+
         let sub_task = sub_task::ActiveModel {
             task_id: ActiveValue::set(task_id),
             section_id: ActiveValue::set(section_id),
+            position: ActiveValue::set(position),
             name: ActiveValue::set(data.name),
+            description: ActiveValue::set(data.description),
+            status: ActiveValue::set(data.status.unwrap_or_else(|| DEFAULT_STATUS.to_string())),
+            priority: ActiveValue::set(data.priority.unwrap_or_default()),
+            due_date: ActiveValue::set(data.due_date),
+            estimated_minutes: ActiveValue::set(data.estimated_minutes),
+            assignee: ActiveValue::set(data.assignee),
             ..Default::default()
         };
 
-        SubTask::insert(sub_task)
-            .exec_with_returning(db)
+        let sub_task = SubTask::insert(sub_task)
+            .exec_with_returning(&txn)
             .await
-            .map_err(|e| {
-                AppError::InternalServerError(format!("Failed to create sub task: {:?}", e))
-            })
+            .map_err(map_err)?;
+        TaskEventMutation::record(
+            &txn,
+            EventTarget::SubTask(sub_task.id),
+            "created",
+            None,
+            &sub_task,
+        )
+        .await
+        .map_err(map_err)?;
+        txn.commit().await.map_err(map_err)?;
+        Ok(sub_task)
     }
 
     pub async fn update_sub_task(
@@ -39,14 +75,141 @@ impl SubTaskMutation {
         id: Uuid,
         data: SubTaskUpdateDto,
     ) -> Result<sub_task::Model, AppError> {
+        let map_err =
+            |e| AppError::InternalServerError(format!("Failed to update sub task: {:?}", e));
+        let txn = db.begin().await.map_err(map_err)?;
+        let current = SubTask::find_by_id(id)
+            .one(&txn)
+            .await
+            .map_err(map_err)?
+            .ok_or_else(|| AppError::NotFound(format!("SubTask with id {} not found", id)))?;
+
+        // A new parent puts the sub task at the end of it
+        let position = if (current.section_id, current.task_id) == (data.section_id, data.task_id)
+        {
+            ActiveValue::NotSet
+        } else {
+            Self::ensure_parent(&txn, data.section_id, data.task_id).await?;
+            ActiveValue::set(
+                SubTaskQuery::get_last_position(&txn, data.section_id, data.task_id)
+                    .await
+                    .map_err(map_err)?,
+            )
+        };
+
         let sub_task = sub_task::ActiveModel {
             id: ActiveValue::set(id),
             name: ActiveValue::set(data.name),
+            section_id: ActiveValue::set(data.section_id),
+            task_id: ActiveValue::set(data.task_id),
+            position,
+            description: data.description.map_or(ActiveValue::NotSet, ActiveValue::set),
+            status: data.status.map_or(ActiveValue::NotSet, ActiveValue::set),
+            priority: data.priority.map_or(ActiveValue::NotSet, ActiveValue::set),
+            due_date: data.due_date.map_or(ActiveValue::NotSet, ActiveValue::set),
+            estimated_minutes: data
+                .estimated_minutes
+                .map_or(ActiveValue::NotSet, ActiveValue::set),
+            assignee: data.assignee.map_or(ActiveValue::NotSet, ActiveValue::set),
             ..Default::default()
         };
-        sub_task.update(db).await.map_err(|e| {
-            AppError::InternalServerError(format!("Failed to update sub task: {:?}", e))
-        })
+        let sub_task = sub_task.update(&txn).await.map_err(map_err)?;
+        TaskEventMutation::record(
+            &txn,
+            EventTarget::SubTask(id),
+            "updated",
+            Some(&current),
+            &sub_task,
+        )
+        .await
+        .map_err(map_err)?;
+        txn.commit().await.map_err(map_err)?;
+        Ok(sub_task)
+    }
+
+    /// Reorder a sub task within its parent or move it to another task or
+    /// section.
+    pub async fn move_sub_task(
+        db: &DatabaseConnection,
+        id: Uuid,
+        data: SubTaskMoveDto,
+    ) -> Result<sub_task::Model, AppError> {
+        let map_err =
+            |e| AppError::InternalServerError(format!("Failed to move sub task: {:?}", e));
+        let txn = db.begin().await.map_err(map_err)?;
+        let current = SubTask::find_by_id(id)
+            .one(&txn)
+            .await
+            .map_err(map_err)?
+            .ok_or_else(|| AppError::NotFound(format!("SubTask with id {} not found", id)))?;
+
+        Self::ensure_parent(&txn, data.section_id, data.task_id).await?;
+        let siblings = SubTaskQuery::get_positions(&txn, data.section_id, data.task_id, Some(id))
+            .await
+            .map_err(map_err)?;
+        let position = key_after(&siblings, data.after_id).ok_or_else(|| {
+            AppError::BadRequest(format!(
+                "SubTask {} is not a sibling in the target task or section",
+                data.after_id.unwrap_or_default()
+            ))
+        })?;
+
+        let sub_task = sub_task::ActiveModel {
+            id: ActiveValue::set(id),
+            section_id: ActiveValue::set(data.section_id),
+            task_id: ActiveValue::set(data.task_id),
+            position: ActiveValue::set(position),
+            ..Default::default()
+        };
+        let sub_task = sub_task.update(&txn).await.map_err(map_err)?;
+        TaskEventMutation::record(
+            &txn,
+            EventTarget::SubTask(id),
+            "moved",
+            Some(&current),
+            &sub_task,
+        )
+        .await
+        .map_err(map_err)?;
+        txn.commit().await.map_err(map_err)?;
+        Ok(sub_task)
+    }
+
+    /// The section must exist and, when given, the task must belong to it so
+    /// a sub task's `section_id` always matches its task's.
+    async fn ensure_parent<C: ConnectionTrait>(
+        db: &C,
+        section_id: Uuid,
+        task_id: Option<Uuid>,
+    ) -> Result<(), AppError> {
+        let map_err = |e| AppError::InternalServerError(format!("Failed to get parent: {:?}", e));
+        match task_id {
+            Some(task_id) => {
+                let task = Task::find_by_id(task_id)
+                    .one(db)
+                    .await
+                    .map_err(map_err)?
+                    .ok_or_else(|| {
+                        AppError::NotFound(format!("Task with id {} not found", task_id))
+                    })?;
+                if task.section_id != section_id {
+                    return Err(AppError::BadRequest(format!(
+                        "Task {} is not in section {}",
+                        task_id, section_id
+                    )));
+                }
+            }
+            None => {
+                Section::find_by_id(section_id)
+                    .one(db)
+                    .await
+                    .map_err(map_err)?
+                    .ok_or_else(|| {
+                        AppError::NotFound(format!("Section with id {} not found", section_id))
+                    })?;
+            }
+        }
+        Ok(())
     }
 
     pub async fn delete_sub_task(
@@ -61,6 +224,24 @@ impl SubTaskMutation {
             AppError::InternalServerError(format!("Failed to delete sub task: {:?}", e))
         })
     }
+
+    /// Insert sub_tasks as-is, keeping their ids and timestamps.
+    pub async fn import_sub_tasks<C: ConnectionTrait>(
+        db: &C,
+        rows: Vec<sub_task::Model>,
+    ) -> Result<u64, AppError> {
+        let count = rows.len() as u64;
+        for row in rows {
+            row.into_active_model()
+                .reset_all()
+                .insert(db)
+                .await
+                .map_err(|e| {
+                    AppError::InternalServerError(format!("Failed to import sub task: {}", e))
+                })?;
+        }
+        Ok(count)
+    }
 }
 
 impl SubTaskQuery {
@@ -68,45 +249,100 @@ impl SubTaskQuery {
         db: &DatabaseConnection,
         id: Uuid,
     ) -> Result<Option<sub_task::Model>, AppError> {
-        let result = SubTask::find_by_id(id)
+        SubTask::find_by_id(id)
             .one(db)
             .await
-            .map_err(|e| AppError::InternalServerError(format!("Failed to get sub task: {:?}", e)));
-        result
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get sub task: {:?}", e)))
     }
 
-    pub async fn get_sub_tasks(db: &DatabaseConnection) -> Result<Vec<sub_task::Model>, AppError> {
-        let result = SubTask::find().all(db).await.map_err(|e| {
-            AppError::InternalServerError(format!("Failed to get sub tasks: {:?}", e))
-        });
-        result
+    pub async fn get_sub_tasks(
+        db: &DatabaseConnection,
+        filter: TaskFilterParams,
+    ) -> Result<Vec<sub_task::Model>, AppError> {
+        Self::filtered(SubTask::find(), filter)
+            .order_by_asc(sub_task::Column::SectionId)
+            .order_by_asc(sub_task::Column::TaskId)
+            .order_by_asc(sub_task::Column::Position)
+            .order_by_asc(sub_task::Column::Id)
+            .all(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get sub tasks: {:?}", e)))
     }
 
     pub async fn get_sub_tasks_by_section_id(
         db: &DatabaseConnection,
         section_id: Uuid,
+        filter: TaskFilterParams,
     ) -> Result<Vec<sub_task::Model>, AppError> {
-        let result = SubTask::find()
+        Self::filtered(SubTask::find(), filter)
             .filter(sub_task::Column::SectionId.eq(section_id))
+            .order_by_asc(sub_task::Column::TaskId)
+            .order_by_asc(sub_task::Column::Position)
+            .order_by_asc(sub_task::Column::Id)
             .all(db)
             .await
-            .map_err(|e| {
-                AppError::InternalServerError(format!("Failed to get sub tasks: {:?}", e))
-            });
-        result
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get sub tasks: {:?}", e)))
+    }
+
+    fn filtered(query: Select<SubTask>, filter: TaskFilterParams) -> Select<SubTask> {
+        let labels = label_names(filter.label.as_deref());
+        query
+            .apply_if(filter.status, |q, v| q.filter(sub_task::Column::Status.eq(v)))
+            .apply_if(filter.priority, |q, v| q.filter(sub_task::Column::Priority.eq(v)))
+            .apply_if(filter.assignee, |q, v| q.filter(sub_task::Column::Assignee.eq(v)))
+            .apply_if(filter.due_before, |q, v| q.filter(sub_task::Column::DueDate.lte(v)))
+            .apply_if(filter.due_after, |q, v| q.filter(sub_task::Column::DueDate.gte(v)))
+            .apply_if(labels, |q, names| {
+                q.filter(sub_task::Column::Id.in_subquery(LabelQuery::sub_task_ids_labelled(names)))
+            })
     }
 
     pub async fn get_sub_tasks_by_task_id(
         db: &DatabaseConnection,
         task_id: Uuid,
     ) -> Result<Vec<sub_task::Model>, AppError> {
-        let result = SubTask::find()
+        SubTask::find()
             .filter(sub_task::Column::TaskId.eq(task_id))
+            .order_by_asc(sub_task::Column::Position)
+            .order_by_asc(sub_task::Column::Id)
             .all(db)
             .await
-            .map_err(|e| {
-                AppError::InternalServerError(format!("Failed to get sub tasks: {:?}", e))
-            });
-        result
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get sub tasks: {:?}", e)))
+    }
+
+    /// `(id, position)` of the sub tasks of a task, or of those directly in
+    /// the section when `task_id` is `None`, in order, leaving out `exclude`.
+    pub async fn get_positions<C: ConnectionTrait>(
+        db: &C,
+        section_id: Uuid,
+        task_id: Option<Uuid>,
+        exclude: Option<Uuid>,
+    ) -> Result<Vec<(Uuid, String)>, DbErr> {
+        let parent = match task_id {
+            Some(task_id) => sub_task::Column::TaskId.eq(task_id),
+            None => sub_task::Column::TaskId.is_null(),
+        };
+        SubTask::find()
+            .select_only()
+            .columns([sub_task::Column::Id, sub_task::Column::Position])
+            .filter(sub_task::Column::SectionId.eq(section_id))
+            .filter(parent)
+            .apply_if(exclude, |q, id| q.filter(sub_task::Column::Id.ne(id)))
+            .order_by_asc(sub_task::Column::Position)
+            .order_by_asc(sub_task::Column::Id)
+            .into_tuple()
+            .all(db)
+            .await
+    }
+
+    /// Position placing a new sub task after every sibling.
+    pub async fn get_last_position<C: ConnectionTrait>(
+        db: &C,
+        section_id: Uuid,
+        task_id: Option<Uuid>,
+    ) -> Result<String, DbErr> {
+        let positions = Self::get_positions(db, section_id, task_id, None).await?;
+        let positions = positions.into_iter().map(|(_, p)| p).collect::<Vec<_>>();
+        Ok(key_after_last(&positions))
     }
 }
@@ -0,0 +1,257 @@
+use std::collections::HashSet;
+
+use sea_orm::{
+    prelude::Uuid, ActiveModelTrait, ActiveValue, ColumnTrait, Condition, ConnectionTrait,
+    DatabaseConnection, DbErr, EntityTrait, IntoActiveModel, JoinType, QueryFilter, QueryOrder,
+    QuerySelect, QueryTrait, RelationTrait, TransactionTrait,
+};
+
+use crate::{
+    dtos::{dependency_dto::TaskDependencyCreateDto, task_dto::DONE_STATUS},
+    entities::{prelude::*, *},
+    utils::{dependency_graph::creates_cycle, error::AppError},
+};
+
+pub struct TaskDependencyQuery;
+pub struct TaskDependencyMutation;
+
+impl TaskDependencyMutation {
+    /// Record that `blocking_task_id` blocks `blocked_task_id`. Both tasks
+    /// must be in the same project and the new edge must not close a cycle;
+    /// recording an existing dependency again returns it unchanged.
+    pub async fn create_dependency(
+        db: &DatabaseConnection,
+        data: TaskDependencyCreateDto,
+    ) -> Result<task_dependency::Model, AppError> {
+        let map_err =
+            |e| AppError::InternalServerError(format!("Failed to create dependency: {}", e));
+        let txn = db.begin().await.map_err(map_err)?;
+
+        let mut project_ids = Vec::new();
+        for task_id in [data.blocking_task_id, data.blocked_task_id] {
+            let project_id = TaskDependencyQuery::get_project_id(&txn, task_id)
+                .await
+                .map_err(map_err)?
+                .ok_or_else(|| AppError::NotFound(format!("Task with id {} not found", task_id)))?;
+            project_ids.push(project_id);
+        }
+        if project_ids[0] != project_ids[1] {
+            return Err(AppError::BadRequest(
+                "Dependencies can only link tasks of the same project".to_string(),
+            ));
+        }
+
+        // Serialize dependency changes per project so that two concurrent
+        // inserts cannot close a cycle between them
+        Project::find_by_id(project_ids[0])
+            .lock_exclusive()
+            .one(&txn)
+            .await
+            .map_err(map_err)?;
+        let edges = TaskDependencyQuery::get_project_dependencies(&txn, project_ids[0])
+            .await
+            .map_err(map_err)?;
+        if let Some(existing) = edges.iter().find(|edge| {
+            edge.blocking_task_id == data.blocking_task_id
+                && edge.blocked_task_id == data.blocked_task_id
+        }) {
+            return Ok(existing.clone());
+        }
+        let pairs = edges
+            .iter()
+            .map(|edge| (edge.blocking_task_id, edge.blocked_task_id))
+            .collect::<Vec<_>>();
+        if creates_cycle(&pairs, data.blocking_task_id, data.blocked_task_id) {
+            return Err(AppError::BadRequest(format!(
+                "Task {} already depends on task {}; this dependency would create a cycle",
+                data.blocking_task_id, data.blocked_task_id
+            )));
+        }
+
+        let dependency = task_dependency::ActiveModel {
+            blocking_task_id: ActiveValue::set(data.blocking_task_id),
+            blocked_task_id: ActiveValue::set(data.blocked_task_id),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .map_err(map_err)?;
+        txn.commit().await.map_err(map_err)?;
+        Ok(dependency)
+    }
+
+    pub async fn delete_dependency(
+        db: &DatabaseConnection,
+        blocking_task_id: Uuid,
+        blocked_task_id: Uuid,
+    ) -> Result<u64, AppError> {
+        TaskDependency::delete_by_id((blocking_task_id, blocked_task_id))
+            .exec(db)
+            .await
+            .map(|res| res.rows_affected)
+            .map_err(|e| {
+                AppError::InternalServerError(format!("Failed to delete dependency: {}", e))
+            })
+    }
+
+    /// Drop the dependencies linking a task to tasks of other projects, as
+    /// happens when the task moves to a section of another project.
+    pub async fn prune_cross_project<C: ConnectionTrait>(
+        db: &C,
+        task_id: Uuid,
+    ) -> Result<(), DbErr> {
+        let Some(project_id) = TaskDependencyQuery::get_project_id(db, task_id).await? else {
+            return Ok(());
+        };
+        let same_project = Task::find()
+            .select_only()
+            .column(task::Column::Id)
+            .inner_join(Section)
+            .filter(section::Column::ProjectId.eq(project_id));
+        TaskDependency::delete_many()
+            .filter(
+                Condition::any()
+                    .add(
+                        Condition::all()
+                            .add(task_dependency::Column::BlockingTaskId.eq(task_id))
+                            .add(
+                                task_dependency::Column::BlockedTaskId
+                                    .not_in_subquery(same_project.clone().into_query()),
+                            ),
+                    )
+                    .add(
+                        Condition::all()
+                            .add(task_dependency::Column::BlockedTaskId.eq(task_id))
+                            .add(
+                                task_dependency::Column::BlockingTaskId
+                                    .not_in_subquery(same_project.into_query()),
+                            ),
+                    ),
+            )
+            .exec(db)
+            .await
+            .map(|_| ())
+    }
+
+    /// Insert dependencies as-is, keeping their timestamps.
+    pub async fn import_task_dependencies<C: ConnectionTrait>(
+        db: &C,
+        rows: Vec<task_dependency::Model>,
+    ) -> Result<u64, AppError> {
+        let count = rows.len() as u64;
+        for row in rows {
+            row.into_active_model()
+                .reset_all()
+                .insert(db)
+                .await
+                .map_err(|e| {
+                    AppError::InternalServerError(format!("Failed to import dependency: {}", e))
+                })?;
+        }
+        Ok(count)
+    }
+}
+
+impl TaskDependencyQuery {
+    pub async fn get_task_dependencies(
+        db: &DatabaseConnection,
+    ) -> Result<Vec<task_dependency::Model>, AppError> {
+        TaskDependency::find().all(db).await.map_err(|e| {
+            AppError::InternalServerError(format!("Failed to get dependencies: {}", e))
+        })
+    }
+
+    /// Dependencies between the tasks of a project.
+    pub async fn get_project_dependencies<C: ConnectionTrait>(
+        db: &C,
+        project_id: Uuid,
+    ) -> Result<Vec<task_dependency::Model>, DbErr> {
+        TaskDependency::find()
+            .join(
+                JoinType::InnerJoin,
+                task_dependency::Relation::BlockedTask.def(),
+            )
+            .join(JoinType::InnerJoin, task::Relation::Section.def())
+            .filter(section::Column::ProjectId.eq(project_id))
+            .order_by_asc(task_dependency::Column::CreatedAt)
+            .all(db)
+            .await
+    }
+
+    /// Tasks that `task_id` waits on.
+    pub async fn get_blocking_tasks(
+        db: &DatabaseConnection,
+        task_id: Uuid,
+    ) -> Result<Vec<task::Model>, AppError> {
+        Task::find()
+            .join(
+                JoinType::InnerJoin,
+                task_dependency::Relation::BlockingTask.def().rev(),
+            )
+            .filter(task_dependency::Column::BlockedTaskId.eq(task_id))
+            .order_by_asc(task::Column::Position)
+            .order_by_asc(task::Column::Id)
+            .all(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get tasks: {}", e)))
+    }
+
+    /// Tasks waiting on `task_id`.
+    pub async fn get_blocked_tasks(
+        db: &DatabaseConnection,
+        task_id: Uuid,
+    ) -> Result<Vec<task::Model>, AppError> {
+        Task::find()
+            .join(
+                JoinType::InnerJoin,
+                task_dependency::Relation::BlockedTask.def().rev(),
+            )
+            .filter(task_dependency::Column::BlockingTaskId.eq(task_id))
+            .order_by_asc(task::Column::Position)
+            .order_by_asc(task::Column::Id)
+            .all(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get tasks: {}", e)))
+    }
+
+    /// Those of `task_ids` waiting on a task that is not done yet.
+    pub async fn get_blocked_ids(
+        db: &DatabaseConnection,
+        task_ids: Vec<Uuid>,
+    ) -> Result<HashSet<Uuid>, AppError> {
+        if task_ids.is_empty() {
+            return Ok(HashSet::new());
+        }
+        TaskDependency::find()
+            .select_only()
+            .column(task_dependency::Column::BlockedTaskId)
+            .distinct()
+            .join(
+                JoinType::InnerJoin,
+                task_dependency::Relation::BlockingTask.def(),
+            )
+            .filter(task_dependency::Column::BlockedTaskId.is_in(task_ids))
+            .filter(task::Column::Status.ne(DONE_STATUS))
+            .into_tuple::<Uuid>()
+            .all(db)
+            .await
+            .map(HashSet::from_iter)
+            .map_err(|e| {
+                AppError::InternalServerError(format!("Failed to get blocked tasks: {}", e))
+            })
+    }
+
+    /// Project of the section a task is in.
+    pub async fn get_project_id<C: ConnectionTrait>(
+        db: &C,
+        task_id: Uuid,
+    ) -> Result<Option<Uuid>, DbErr> {
+        Task::find_by_id(task_id)
+            .select_only()
+            .column(section::Column::ProjectId)
+            .inner_join(Section)
+            .into_tuple()
+            .one(db)
+            .await
+    }
+}
@@ -0,0 +1,118 @@
+use sea_orm::{
+    prelude::Uuid, ActiveModelTrait, ActiveValue, ColumnTrait, Condition, ConnectionTrait,
+    DatabaseConnection, DbErr, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder,
+};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use crate::{
+    entities::{prelude::*, *},
+    utils::error::AppError,
+};
+
+/// Bookkeeping fields left out of recorded changes; reordering within the
+/// same parent is not worth a feed entry either.
+const UNTRACKED_FIELDS: [&str; 5] = ["id", "version", "position", "created_at", "updated_at"];
+
+#[derive(Debug, Clone, Copy)]
+pub enum EventTarget {
+    Task(Uuid),
+    SubTask(Uuid),
+}
+
+pub struct TaskEventMutation;
+pub struct TaskEventQuery;
+
+impl TaskEventMutation {
+    /// Record the fields that differ between `before` and `after`; nothing is
+    /// written when they are the same. `before` is `None` for new records.
+    pub async fn record<C: ConnectionTrait, M: Serialize>(
+        db: &C,
+        target: EventTarget,
+        kind: &str,
+        before: Option<&M>,
+        after: &M,
+    ) -> Result<(), DbErr> {
+        let changes = changes(before, after);
+        if changes.is_empty() {
+            return Ok(());
+        }
+        let (task_id, sub_task_id) = match target {
+            EventTarget::Task(id) => (Some(id), None),
+            EventTarget::SubTask(id) => (None, Some(id)),
+        };
+        task_event::ActiveModel {
+            task_id: ActiveValue::set(task_id),
+            sub_task_id: ActiveValue::set(sub_task_id),
+            kind: ActiveValue::set(kind.to_string()),
+            changes: ActiveValue::set(Value::Object(changes)),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .map(|_| ())
+    }
+
+    /// Insert task events as-is, keeping their ids and timestamps.
+    pub async fn import_task_events<C: ConnectionTrait>(
+        db: &C,
+        rows: Vec<task_event::Model>,
+    ) -> Result<u64, AppError> {
+        let count = rows.len() as u64;
+        for row in rows {
+            row.into_active_model()
+                .reset_all()
+                .insert(db)
+                .await
+                .map_err(|e| {
+                    AppError::InternalServerError(format!("Failed to import task event: {}", e))
+                })?;
+        }
+        Ok(count)
+    }
+}
+
+impl TaskEventQuery {
+    pub async fn get_task_events(
+        db: &DatabaseConnection,
+    ) -> Result<Vec<task_event::Model>, AppError> {
+        TaskEvent::find()
+            .all(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get task events: {}", e)))
+    }
+
+    /// Events of a task and of the given sub tasks, oldest first.
+    pub async fn get_events_for_task(
+        db: &DatabaseConnection,
+        task_id: Uuid,
+        sub_task_ids: Vec<Uuid>,
+    ) -> Result<Vec<task_event::Model>, AppError> {
+        TaskEvent::find()
+            .filter(
+                Condition::any()
+                    .add(task_event::Column::TaskId.eq(task_id))
+                    .add(task_event::Column::SubTaskId.is_in(sub_task_ids)),
+            )
+            .order_by_asc(task_event::Column::CreatedAt)
+            .all(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get task events: {}", e)))
+    }
+}
+
+fn changes<M: Serialize>(before: Option<&M>, after: &M) -> Map<String, Value> {
+    let to_fields = |model: &M| match serde_json::to_value(model) {
+        Ok(Value::Object(fields)) => fields,
+        _ => Map::new(),
+    };
+    let before = before.map(to_fields).unwrap_or_default();
+    to_fields(after)
+        .into_iter()
+        .filter(|(field, _)| !UNTRACKED_FIELDS.contains(&field.as_str()))
+        .filter_map(|(field, to)| {
+            let from = before.get(&field).cloned().unwrap_or(Value::Null);
+            (from != to).then(|| (field, json!({ "from": from, "to": to })))
+        })
+        .collect()
+}
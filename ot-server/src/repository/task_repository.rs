@@ -1,11 +1,24 @@
 use crate::{
-    dtos::task_dto::{TaskCreateDto, TaskUpdateDto},
+    dtos::{
+        label_dto::label_names,
+        task_dto::{TaskCreateDto, TaskFilterParams, TaskMoveDto, TaskUpdateDto, DEFAULT_STATUS},
+    },
     entities::{prelude::*, *},
-    utils::error::AppError,
+    repository::{
+        label_repository::LabelQuery,
+        task_dependency_repository::TaskDependencyMutation,
+        task_event_repository::{EventTarget, TaskEventMutation},
+    },
+    utils::{
+        error::AppError,
+        ordering::{key_after, key_after_last},
+    },
 };
 use sea_orm::{
-    prelude::Uuid, ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, DbErr,
-    DeleteResult, EntityTrait, QueryFilter,
+    prelude::{Expr, Uuid},
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr,
+    DeleteResult, EntityTrait, IntoActiveModel, JoinType, QueryFilter, QueryOrder, QuerySelect,
+    QueryTrait, RelationTrait, Select, TransactionTrait,
 };
 
 pub struct TaskMutation;
@@ -18,15 +31,34 @@ impl TaskMutation {
         data: TaskCreateDto,
         section_id: Uuid,
     ) -> Result<task::Model, AppError> {
+        let map_err = |e| AppError::InternalServerError(format!("Failed to create task: {}", e));
+        let txn = db.begin().await.map_err(map_err)?;
+        let position = TaskQuery::get_last_position(&txn, section_id)
+            .await
+            .map_err(map_err)?;
+
         let task = task::ActiveModel {
             name: ActiveValue::set(data.name),
             section_id: ActiveValue::set(section_id),
+            position: ActiveValue::set(position),
+            description: ActiveValue::set(data.description),
+            status: ActiveValue::set(data.status.unwrap_or_else(|| DEFAULT_STATUS.to_string())),
+            priority: ActiveValue::set(data.priority.unwrap_or_default()),
+            due_date: ActiveValue::set(data.due_date),
+            estimated_minutes: ActiveValue::set(data.estimated_minutes),
+            assignee: ActiveValue::set(data.assignee),
+            billable: ActiveValue::set(data.billable.unwrap_or(true)),
             ..Default::default()
         };
-        Task::insert(task)
-            .exec_with_returning(db)
+        let task = Task::insert(task)
+            .exec_with_returning(&txn)
             .await
-            .map_err(|e| AppError::InternalServerError(format!("Failed to create task: {}", e)))
+            .map_err(map_err)?;
+        TaskEventMutation::record(&txn, EventTarget::Task(task.id), "created", None, &task)
+            .await
+            .map_err(map_err)?;
+        txn.commit().await.map_err(map_err)?;
+        Ok(task)
     }
 
     pub async fn update_task(
@@ -34,15 +66,133 @@ impl TaskMutation {
         id: Uuid,
         data: TaskUpdateDto,
     ) -> Result<task::Model, AppError> {
+        let map_err = |e| AppError::InternalServerError(format!("Failed to update task: {}", e));
+        let txn = db.begin().await.map_err(map_err)?;
+        let current = Task::find_by_id(id)
+            .one(&txn)
+            .await
+            .map_err(map_err)?
+            .ok_or_else(|| AppError::NotFound(format!("Task with id {} not found", id)))?;
+
+        // A new section puts the task at the end of it
+        let position = if current.section_id == data.section_id {
+            ActiveValue::NotSet
+        } else {
+            Self::ensure_section(&txn, data.section_id).await?;
+            Self::carry_sub_tasks(&txn, id, data.section_id)
+                .await
+                .map_err(map_err)?;
+            ActiveValue::set(
+                TaskQuery::get_last_position(&txn, data.section_id)
+                    .await
+                    .map_err(map_err)?,
+            )
+        };
+
         let task = task::ActiveModel {
             id: ActiveValue::set(id),
             name: ActiveValue::set(data.name),
             section_id: ActiveValue::set(data.section_id),
+            position,
+            description: data.description.map_or(ActiveValue::NotSet, ActiveValue::set),
+            status: data.status.map_or(ActiveValue::NotSet, ActiveValue::set),
+            priority: data.priority.map_or(ActiveValue::NotSet, ActiveValue::set),
+            due_date: data.due_date.map_or(ActiveValue::NotSet, ActiveValue::set),
+            estimated_minutes: data
+                .estimated_minutes
+                .map_or(ActiveValue::NotSet, ActiveValue::set),
+            assignee: data.assignee.map_or(ActiveValue::NotSet, ActiveValue::set),
+            billable: data.billable.map_or(ActiveValue::NotSet, ActiveValue::set),
+            ..Default::default()
+        };
+        let task = task.update(&txn).await.map_err(map_err)?;
+        if task.section_id != current.section_id {
+            TaskDependencyMutation::prune_cross_project(&txn, id)
+                .await
+                .map_err(map_err)?;
+        }
+        TaskEventMutation::record(&txn, EventTarget::Task(id), "updated", Some(&current), &task)
+            .await
+            .map_err(map_err)?;
+        txn.commit().await.map_err(map_err)?;
+        Ok(task)
+    }
+
+    /// Reorder a task within its section or move it, with its sub tasks, to
+    /// another section of any project.
+    pub async fn move_task(
+        db: &DatabaseConnection,
+        id: Uuid,
+        data: TaskMoveDto,
+    ) -> Result<task::Model, AppError> {
+        let map_err = |e| AppError::InternalServerError(format!("Failed to move task: {}", e));
+        let txn = db.begin().await.map_err(map_err)?;
+        let current = Task::find_by_id(id)
+            .one(&txn)
+            .await
+            .map_err(map_err)?
+            .ok_or_else(|| AppError::NotFound(format!("Task with id {} not found", id)))?;
+
+        Self::ensure_section(&txn, data.section_id).await?;
+        let siblings = TaskQuery::get_positions(&txn, data.section_id, Some(id))
+            .await
+            .map_err(map_err)?;
+        let position = key_after(&siblings, data.after_id).ok_or_else(|| {
+            AppError::BadRequest(format!(
+                "Task {} is not in section {}",
+                data.after_id.unwrap_or_default(),
+                data.section_id
+            ))
+        })?;
+
+        let task = task::ActiveModel {
+            id: ActiveValue::set(id),
+            section_id: ActiveValue::set(data.section_id),
+            position: ActiveValue::set(position),
             ..Default::default()
         };
-        task.update(db)
+        let task = task.update(&txn).await.map_err(map_err)?;
+        Self::carry_sub_tasks(&txn, id, data.section_id)
+            .await
+            .map_err(map_err)?;
+        if task.section_id != current.section_id {
+            TaskDependencyMutation::prune_cross_project(&txn, id)
+                .await
+                .map_err(map_err)?;
+        }
+        TaskEventMutation::record(&txn, EventTarget::Task(id), "moved", Some(&current), &task)
+            .await
+            .map_err(map_err)?;
+        txn.commit().await.map_err(map_err)?;
+        Ok(task)
+    }
+
+    async fn ensure_section<C: ConnectionTrait>(db: &C, section_id: Uuid) -> Result<(), AppError> {
+        match Section::find_by_id(section_id).one(db).await {
+            Ok(Some(_)) => Ok(()),
+            Ok(None) => Err(AppError::NotFound(format!(
+                "Section with id {} not found",
+                section_id
+            ))),
+            Err(e) => Err(AppError::InternalServerError(format!(
+                "Failed to get section: {}",
+                e
+            ))),
+        }
+    }
+
+    /// Keep the sub tasks of a task in the same section as the task.
+    async fn carry_sub_tasks<C: ConnectionTrait>(
+        db: &C,
+        task_id: Uuid,
+        section_id: Uuid,
+    ) -> Result<(), DbErr> {
+        SubTask::update_many()
+            .col_expr(sub_task::Column::SectionId, Expr::value(section_id))
+            .filter(sub_task::Column::TaskId.eq(task_id))
+            .exec(db)
             .await
-            .map_err(|e| AppError::InternalServerError(format!("Failed to update task: {}", e)))
+            .map(|_| ())
     }
 
     pub async fn delete_task(db: &DatabaseConnection, id: Uuid) -> Result<DeleteResult, AppError> {
@@ -54,11 +204,35 @@ impl TaskMutation {
             .await
             .map_err(|e| AppError::InternalServerError(format!("Failed to delete task: {}", e)))
     }
+
+    /// Insert tasks as-is, keeping their ids and timestamps.
+    pub async fn import_tasks<C: ConnectionTrait>(
+        db: &C,
+        rows: Vec<task::Model>,
+    ) -> Result<u64, AppError> {
+        let count = rows.len() as u64;
+        for row in rows {
+            row.into_active_model()
+                .reset_all()
+                .insert(db)
+                .await
+                .map_err(|e| {
+                    AppError::InternalServerError(format!("Failed to import task: {}", e))
+                })?;
+        }
+        Ok(count)
+    }
 }
 
 impl TaskQuery {
-    pub async fn get_tasks(db: &DatabaseConnection) -> Result<Vec<task::Model>, AppError> {
-        Task::find()
+    pub async fn get_tasks(
+        db: &DatabaseConnection,
+        filter: TaskFilterParams,
+    ) -> Result<Vec<task::Model>, AppError> {
+        Self::filtered(Task::find(), filter)
+            .order_by_asc(task::Column::SectionId)
+            .order_by_asc(task::Column::Position)
+            .order_by_asc(task::Column::Id)
             .all(db)
             .await
             .map_err(|e| AppError::InternalServerError(format!("Failed to get tasks: {}", e)))
@@ -73,14 +247,90 @@ impl TaskQuery {
             .await
             .map_err(|e| AppError::InternalServerError(format!("Failed to get task: {}", e)))
     }
+    pub async fn get_tasks_by_ids(
+        db: &DatabaseConnection,
+        ids: Vec<Uuid>,
+    ) -> Result<Vec<task::Model>, AppError> {
+        Task::find()
+            .filter(task::Column::Id.is_in(ids))
+            .order_by_asc(task::Column::SectionId)
+            .order_by_asc(task::Column::Position)
+            .order_by_asc(task::Column::Id)
+            .all(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get tasks: {}", e)))
+    }
+
     pub async fn get_tasks_by_section_id(
         db: &DatabaseConnection,
         section_id: Uuid,
+        filter: TaskFilterParams,
     ) -> Result<Vec<task::Model>, AppError> {
-        Task::find()
+        Self::filtered(Task::find(), filter)
             .filter(task::Column::SectionId.eq(section_id))
+            .order_by_asc(task::Column::Position)
+            .order_by_asc(task::Column::Id)
             .all(db)
             .await
             .map_err(|e| AppError::InternalServerError(format!("Failed to get tasks: {}", e)))
     }
+
+    /// Tasks of a project that have an estimate.
+    pub async fn get_estimated_tasks(
+        db: &DatabaseConnection,
+        project_id: Uuid,
+    ) -> Result<Vec<task::Model>, AppError> {
+        Task::find()
+            .join(JoinType::InnerJoin, task::Relation::Section.def())
+            .filter(section::Column::ProjectId.eq(project_id))
+            .filter(task::Column::EstimatedMinutes.is_not_null())
+            .order_by_asc(task::Column::SectionId)
+            .order_by_asc(task::Column::Position)
+            .order_by_asc(task::Column::Id)
+            .all(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get tasks: {}", e)))
+    }
+
+    fn filtered(query: Select<Task>, filter: TaskFilterParams) -> Select<Task> {
+        let labels = label_names(filter.label.as_deref());
+        query
+            .apply_if(filter.status, |q, v| q.filter(task::Column::Status.eq(v)))
+            .apply_if(filter.priority, |q, v| q.filter(task::Column::Priority.eq(v)))
+            .apply_if(filter.assignee, |q, v| q.filter(task::Column::Assignee.eq(v)))
+            .apply_if(filter.due_before, |q, v| q.filter(task::Column::DueDate.lte(v)))
+            .apply_if(filter.due_after, |q, v| q.filter(task::Column::DueDate.gte(v)))
+            .apply_if(labels, |q, names| {
+                q.filter(task::Column::Id.in_subquery(LabelQuery::task_ids_labelled(names)))
+            })
+    }
+
+    /// `(id, position)` of the tasks in a section, in order, leaving out
+    /// `exclude`.
+    pub async fn get_positions<C: ConnectionTrait>(
+        db: &C,
+        section_id: Uuid,
+        exclude: Option<Uuid>,
+    ) -> Result<Vec<(Uuid, String)>, DbErr> {
+        Task::find()
+            .select_only()
+            .columns([task::Column::Id, task::Column::Position])
+            .filter(task::Column::SectionId.eq(section_id))
+            .apply_if(exclude, |q, id| q.filter(task::Column::Id.ne(id)))
+            .order_by_asc(task::Column::Position)
+            .order_by_asc(task::Column::Id)
+            .into_tuple()
+            .all(db)
+            .await
+    }
+
+    /// Position placing a new task after every task in the section.
+    pub async fn get_last_position<C: ConnectionTrait>(
+        db: &C,
+        section_id: Uuid,
+    ) -> Result<String, DbErr> {
+        let positions = Self::get_positions(db, section_id, None).await?;
+        let positions = positions.into_iter().map(|(_, p)| p).collect::<Vec<_>>();
+        Ok(key_after_last(&positions))
+    }
 }
@@ -0,0 +1,111 @@
+use sea_orm::{
+    prelude::Uuid, sea_query::OnConflict, ActiveModelTrait, ActiveValue, ColumnTrait,
+    ConnectionTrait, DatabaseConnection, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder,
+    TryInsertResult,
+};
+
+use crate::{
+    dtos::timer_dto::TimerSessionCreateDto,
+    entities::{prelude::*, *},
+    utils::error::AppError,
+};
+
+pub struct TimerSessionQuery;
+pub struct TimerSessionMutation;
+
+impl TimerSessionQuery {
+    pub async fn get_timer_sessions(
+        db: &DatabaseConnection,
+    ) -> Result<Vec<timer_session::Model>, AppError> {
+        TimerSession::find()
+            .order_by_asc(timer_session::Column::StartTime)
+            .all(db)
+            .await
+            .map_err(|e| {
+                AppError::InternalServerError(format!("Failed to get timer sessions: {}", e))
+            })
+    }
+
+    pub async fn get_timer_session_by_id(
+        db: &DatabaseConnection,
+        id: Uuid,
+    ) -> Result<Option<timer_session::Model>, AppError> {
+        TimerSession::find_by_id(id).one(db).await.map_err(|e| {
+            AppError::InternalServerError(format!("Failed to get timer session: {}", e))
+        })
+    }
+
+    /// Sessions tracked on a task, its sub tasks included, by start time.
+    pub async fn get_timer_sessions_by_task_id(
+        db: &DatabaseConnection,
+        task_id: Uuid,
+    ) -> Result<Vec<timer_session::Model>, AppError> {
+        TimerSession::find()
+            .filter(timer_session::Column::TaskId.eq(task_id))
+            .order_by_asc(timer_session::Column::StartTime)
+            .all(db)
+            .await
+            .map_err(|e| {
+                AppError::InternalServerError(format!("Failed to get timer sessions: {}", e))
+            })
+    }
+}
+
+impl TimerSessionMutation {
+    /// Insert a session, leaving one with the same id as it is; returns
+    /// whether the session was new.
+    pub async fn create_timer_session(
+        db: &DatabaseConnection,
+        data: TimerSessionCreateDto,
+    ) -> Result<bool, AppError> {
+        let session = timer_session::ActiveModel {
+            id: ActiveValue::set(data.id),
+            task_id: ActiveValue::set(data.task_id),
+            sub_task_id: ActiveValue::set(data.sub_task_id),
+            start_time: ActiveValue::set(data.start_time),
+            end_time: ActiveValue::set(data.end_time),
+            elapsed_seconds: ActiveValue::set(data.elapsed_seconds),
+            person: ActiveValue::set(data.person),
+            note: ActiveValue::set(data.note),
+            ..Default::default()
+        };
+        let result = TimerSession::insert(session)
+            .on_conflict(
+                OnConflict::column(timer_session::Column::Id)
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .do_nothing()
+            .exec_without_returning(db)
+            .await
+            .map_err(|e| {
+                AppError::InternalServerError(format!("Failed to create timer session: {}", e))
+            })?;
+        Ok(matches!(result, TryInsertResult::Inserted(count) if count > 0))
+    }
+
+    pub async fn delete_all<C: ConnectionTrait>(db: &C) -> Result<(), AppError> {
+        TimerSession::delete_many().exec(db).await.map_err(|e| {
+            AppError::InternalServerError(format!("Failed to delete timer sessions: {}", e))
+        })?;
+        Ok(())
+    }
+
+    /// Insert sessions as-is, keeping their ids and timestamps.
+    pub async fn import_timer_sessions<C: ConnectionTrait>(
+        db: &C,
+        rows: Vec<timer_session::Model>,
+    ) -> Result<u64, AppError> {
+        let count = rows.len() as u64;
+        for row in rows {
+            row.into_active_model()
+                .reset_all()
+                .insert(db)
+                .await
+                .map_err(|e| {
+                    AppError::InternalServerError(format!("Failed to import timer session: {}", e))
+                })?;
+        }
+        Ok(count)
+    }
+}
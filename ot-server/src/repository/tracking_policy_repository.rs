@@ -0,0 +1,118 @@
+use chrono::Utc;
+use sea_orm::{
+    prelude::{Json, Uuid},
+    sea_query::OnConflict,
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+    IntoActiveModel, QueryFilter, QueryOrder,
+};
+
+use crate::{
+    entities::{prelude::*, *},
+    utils::error::AppError,
+};
+
+pub struct TrackingPolicyQuery;
+pub struct TrackingPolicyMutation;
+
+/// Matches the policy of a project, or the global one for `None`.
+fn scope(project_id: Option<Uuid>) -> sea_orm::sea_query::SimpleExpr {
+    match project_id {
+        Some(project_id) => tracking_policy::Column::ProjectId.eq(project_id),
+        None => tracking_policy::Column::ProjectId.is_null(),
+    }
+}
+
+impl TrackingPolicyMutation {
+    /// Create or replace the policy of a project, or the global one.
+    pub async fn save_policy(
+        db: &DatabaseConnection,
+        project_id: Option<Uuid>,
+        settings: Json,
+        locked: Json,
+    ) -> Result<tracking_policy::Model, AppError> {
+        let policy = tracking_policy::ActiveModel {
+            project_id: ActiveValue::set(project_id),
+            settings: ActiveValue::set(settings),
+            locked: ActiveValue::set(locked),
+            updated_at: ActiveValue::set(Utc::now().fixed_offset()),
+            ..Default::default()
+        };
+        TrackingPolicy::insert(policy)
+            .on_conflict(
+                OnConflict::column(tracking_policy::Column::ProjectId)
+                    .update_columns([
+                        tracking_policy::Column::Settings,
+                        tracking_policy::Column::Locked,
+                        tracking_policy::Column::UpdatedAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec_with_returning(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to save policy: {}", e)))
+    }
+
+    pub async fn delete_policy(
+        db: &DatabaseConnection,
+        project_id: Option<Uuid>,
+    ) -> Result<u64, AppError> {
+        TrackingPolicy::delete_many()
+            .filter(scope(project_id))
+            .exec(db)
+            .await
+            .map(|res| res.rows_affected)
+            .map_err(|e| AppError::InternalServerError(format!("Failed to delete policy: {}", e)))
+    }
+
+    /// Insert policies as-is, keeping their ids and timestamps.
+    pub async fn import_policies<C: ConnectionTrait>(
+        db: &C,
+        rows: Vec<tracking_policy::Model>,
+    ) -> Result<u64, AppError> {
+        let count = rows.len() as u64;
+        for row in rows {
+            row.into_active_model()
+                .reset_all()
+                .insert(db)
+                .await
+                .map_err(|e| {
+                    AppError::InternalServerError(format!("Failed to import policy: {}", e))
+                })?;
+        }
+        Ok(count)
+    }
+
+    /// The global policy belongs to no project, so restoring with
+    /// `--replace` clears policies separately.
+    pub async fn delete_all_policies<C: ConnectionTrait>(db: &C) -> Result<u64, AppError> {
+        TrackingPolicy::delete_many()
+            .exec(db)
+            .await
+            .map(|res| res.rows_affected)
+            .map_err(|e| AppError::InternalServerError(format!("Failed to delete policies: {}", e)))
+    }
+}
+
+impl TrackingPolicyQuery {
+    pub async fn get_policies(
+        db: &DatabaseConnection,
+    ) -> Result<Vec<tracking_policy::Model>, AppError> {
+        TrackingPolicy::find()
+            .order_by_asc(tracking_policy::Column::UpdatedAt)
+            .all(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get policies: {}", e)))
+    }
+
+    /// The policy of a project, or the global one for `None`.
+    pub async fn get_policy(
+        db: &DatabaseConnection,
+        project_id: Option<Uuid>,
+    ) -> Result<Option<tracking_policy::Model>, AppError> {
+        TrackingPolicy::find()
+            .filter(scope(project_id))
+            .one(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to get policy: {}", e)))
+    }
+}
@@ -0,0 +1,25 @@
+use axum::{
+    routing::{get, patch, post},
+    Router,
+};
+
+use crate::{
+    handlers::billing_handlers::BillingRateMutationHandlers as BRMH,
+    handlers::billing_handlers::BillingRateQueryHandlers as BRQH,
+    handlers::billing_handlers::TimeQueryHandlers as TQH, AppState,
+};
+
+pub fn rate_routes() -> Router<AppState> {
+    Router::new()
+        .route("/all", get(BRQH::get_rates_handler))
+        .route("/{id}", get(BRQH::get_rate_by_id_handler))
+        .route("/create", post(BRMH::create_rate_handler))
+        .route("/update/{id}", patch(BRMH::update_rate_handler))
+        .route("/delete/{id}", get(BRMH::delete_rate_handler))
+}
+
+pub fn time_routes() -> Router<AppState> {
+    Router::new()
+        .route("/entries", get(TQH::get_time_entries_handler))
+        .route("/summary", get(TQH::get_time_summary_handler))
+}
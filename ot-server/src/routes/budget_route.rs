@@ -0,0 +1,18 @@
+use axum::{
+    routing::{get, post},
+    Router,
+};
+
+use crate::{
+    handlers::budget_handlers::{BudgetMutationHandlers as BMH, BudgetQueryHandlers as BQH},
+    AppState,
+};
+
+pub fn budget_routes() -> Router<AppState> {
+    Router::new()
+        .route("/alerts", get(BQH::get_alerts_handler))
+        .route(
+            "/alerts/acknowledge/{id}",
+            post(BMH::acknowledge_alert_handler),
+        )
+}
@@ -0,0 +1,18 @@
+use axum::{
+    routing::{get, patch, post},
+    Router,
+};
+
+use crate::{
+    handlers::client_handlers::ClientMutationHandlers as CMH,
+    handlers::client_handlers::ClientQueryHandlers as CQH, AppState,
+};
+
+pub fn client_routes() -> Router<AppState> {
+    Router::new()
+        .route("/all", get(CQH::get_clients_handler))
+        .route("/{id}", get(CQH::get_client_by_id_handler))
+        .route("/create", post(CMH::create_client_handler))
+        .route("/update/{id}", patch(CMH::update_client_handler))
+        .route("/delete/{id}", get(CMH::delete_client_handler))
+}
@@ -0,0 +1,27 @@
+use axum::{
+    routing::{get, patch, post},
+    Router,
+};
+
+use crate::{
+    handlers::comment_handlers::CommentMutationHandlers as CMH,
+    handlers::comment_handlers::CommentQueryHandlers as CQH, AppState,
+};
+
+pub fn comment_routes() -> Router<AppState> {
+    Router::new()
+        .route("/{id}", get(CQH::get_comment_by_id_handler))
+        .route("/{id}/history", get(CQH::get_comment_history_handler))
+        .route(
+            "/by-task-id/{task_id}",
+            get(CQH::get_comments_by_task_id_handler),
+        )
+        .route(
+            "/by-sub-task-id/{sub_task_id}",
+            get(CQH::get_comments_by_sub_task_id_handler),
+        )
+        .route("/feed/by-task-id/{task_id}", get(CQH::get_task_feed_handler))
+        .route("/create", post(CMH::create_comment_handler))
+        .route("/update/{id}", patch(CMH::update_comment_handler))
+        .route("/delete/{id}", get(CMH::delete_comment_handler))
+}
@@ -0,0 +1,26 @@
+use axum::{
+    routing::{get, post},
+    Router,
+};
+
+use crate::{
+    handlers::dependency_handlers::DependencyMutationHandlers as DMH,
+    handlers::dependency_handlers::DependencyQueryHandlers as DQH, AppState,
+};
+
+pub fn dependency_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/by-task-id/{task_id}",
+            get(DQH::get_dependencies_by_task_id_handler),
+        )
+        .route(
+            "/graph/by-project-id/{project_id}",
+            get(DQH::get_project_graph_handler),
+        )
+        .route("/create", post(DMH::create_dependency_handler))
+        .route(
+            "/delete/{blocking_task_id}/{blocked_task_id}",
+            get(DMH::delete_dependency_handler),
+        )
+}
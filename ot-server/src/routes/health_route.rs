@@ -0,0 +1,13 @@
+use axum::{routing::get, Router};
+
+use crate::{handlers::health_handlers::HealthHandlers, AppState};
+
+pub fn health_routes() -> Router<AppState> {
+    Router::new()
+        .route("/live", get(HealthHandlers::live_handler))
+        .route("/ready", get(HealthHandlers::ready_handler))
+}
+
+pub fn metrics_routes() -> Router<AppState> {
+    Router::new().route("/metrics", get(HealthHandlers::metrics_handler))
+}
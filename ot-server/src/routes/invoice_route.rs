@@ -0,0 +1,23 @@
+use axum::{
+    routing::{get, patch, post},
+    Router,
+};
+
+use crate::{
+    handlers::invoice_handlers::InvoiceMutationHandlers as IMH,
+    handlers::invoice_handlers::InvoiceQueryHandlers as IQH, AppState,
+};
+
+pub fn invoice_routes() -> Router<AppState> {
+    Router::new()
+        .route("/all", get(IQH::get_invoices_handler))
+        .route("/{id}", get(IQH::get_invoice_by_id_handler))
+        .route("/pdf/{id}", get(IQH::get_invoice_pdf_handler))
+        .route("/create", post(IMH::create_invoice_handler))
+        .route("/update/{id}", patch(IMH::update_invoice_handler))
+        .route("/finalize/{id}", post(IMH::finalize_invoice_handler))
+        .route("/delete/{id}", get(IMH::delete_invoice_handler))
+        .route("/line/create/{invoice_id}", post(IMH::create_line_handler))
+        .route("/line/update/{id}", patch(IMH::update_line_handler))
+        .route("/line/delete/{id}", get(IMH::delete_line_handler))
+}
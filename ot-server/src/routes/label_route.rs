@@ -0,0 +1,32 @@
+use axum::{
+    routing::{get, patch, post},
+    Router,
+};
+
+use crate::{
+    handlers::label_handlers::LabelMutationHandlers as LMH,
+    handlers::label_handlers::LabelQueryHandlers as LQH, AppState,
+};
+
+pub fn label_routes() -> Router<AppState> {
+    Router::new()
+        .route("/all", get(LQH::get_labels_handler))
+        .route("/{id}", get(LQH::get_label_by_id_handler))
+        .route(
+            "/by-project-id/{project_id}",
+            get(LQH::get_labels_by_project_id_handler),
+        )
+        .route(
+            "/by-task-id/{task_id}",
+            get(LQH::get_labels_by_task_id_handler),
+        )
+        .route(
+            "/by-sub-task-id/{sub_task_id}",
+            get(LQH::get_labels_by_sub_task_id_handler),
+        )
+        .route("/create", post(LMH::create_label_handler))
+        .route("/update/{id}", patch(LMH::update_label_handler))
+        .route("/delete/{id}", get(LMH::delete_label_handler))
+        .route("/assign/{id}", post(LMH::assign_label_handler))
+        .route("/unassign/{id}", post(LMH::unassign_label_handler))
+}
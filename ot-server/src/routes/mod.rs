@@ -1,4 +1,16 @@
+pub mod billing_route;
+pub mod budget_route;
+pub mod client_route;
+pub mod comment_route;
+pub mod dependency_route;
+pub mod health_route;
+pub mod invoice_route;
+pub mod label_route;
 pub mod project_route;
+pub mod recurrence_route;
+pub mod search_route;
 pub mod section_route;
 pub mod sub_task_route;
 pub mod task_route;
+pub mod timer_route;
+pub mod tracking_policy_route;
@@ -6,7 +6,10 @@ use axum::{
 };
 
 use crate::{
-    handlers::project_handlers::{ProjectMutationHandlers as PMH, ProjectQueryHandlers as PQH},
+    handlers::{
+        budget_handlers::BudgetQueryHandlers as BQH,
+        project_handlers::{ProjectMutationHandlers as PMH, ProjectQueryHandlers as PQH},
+    },
     AppState,
 };
 
@@ -14,8 +17,11 @@ pub fn project_routes() -> Router<AppState> {
     let project_router = Router::new()
         .route("/all", get(PQH::project_list_handler))
         .route("/{id}", get(PQH::project_by_id_handler))
+        .route("/{id}/budget", get(BQH::get_project_budget_handler))
         .route("/create", post(PMH::project_create_handler))
         .route("/update/{id}", patch(PMH::project_update_handler))
+        .route("/archive/{id}", post(PMH::project_archive_handler))
+        .route("/unarchive/{id}", post(PMH::project_unarchive_handler))
         .route("/delete/{id}", get(PMH::project_delete_handler));
     project_router
 }
@@ -0,0 +1,23 @@
+use axum::{
+    routing::{get, post},
+    Router,
+};
+
+use crate::{
+    handlers::recurrence_handlers::RecurrenceMutationHandlers as RMH,
+    handlers::recurrence_handlers::RecurrenceQueryHandlers as RQH, AppState,
+};
+
+pub fn recurrence_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/by-task-id/{task_id}",
+            get(RQH::get_recurrence_by_task_id_handler),
+        )
+        .route(
+            "/occurrences/by-task-id/{task_id}",
+            get(RQH::get_occurrences_by_task_id_handler),
+        )
+        .route("/set/{task_id}", post(RMH::set_recurrence_handler))
+        .route("/delete/{task_id}", get(RMH::delete_recurrence_handler))
+}
@@ -0,0 +1,7 @@
+use axum::{routing::get, Router};
+
+use crate::{handlers::search_handlers::SearchQueryHandlers as SQH, AppState};
+
+pub fn search_routes() -> Router<AppState> {
+    Router::new().route("/", get(SQH::search_handler))
+}
@@ -18,6 +18,7 @@ pub fn section_routes() -> Router<AppState> {
         .route("/{id}", get(SQH::get_section_by_id_handler))
         .route("/create", post(SMH::create_section_handler))
         .route("/update/{id}", patch(SMH::update_section_handler))
+        .route("/move/{id}", patch(SMH::move_section_handler))
         .route("/delete/{id}", get(SMH::delete_section_handler));
     section_router
 }
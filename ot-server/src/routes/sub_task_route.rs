@@ -25,6 +25,7 @@ pub fn sub_task_routes() -> Router<AppState> {
             "/update/{id}",
             patch(SubTaskHandlers::update_sub_task_handler),
         )
+        .route("/move/{id}", patch(SubTaskHandlers::move_sub_task_handler))
         .route(
             "/delete/{id}",
             get(SubTaskHandlers::delete_sub_task_handler),
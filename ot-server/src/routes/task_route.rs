@@ -18,6 +18,7 @@ pub fn task_routes() -> Router<AppState> {
         )
         .route("/create/{section_id}", post(TMH::create_task_handler))
         .route("/update/{id}", patch(TMH::update_task_handler))
+        .route("/move/{id}", patch(TMH::move_task_handler))
         .route("/delete/{id}", get(TMH::delete_task_handler));
     task_router
 }
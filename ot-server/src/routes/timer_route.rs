@@ -0,0 +1,24 @@
+use axum::{
+    routing::{get, post},
+    Router,
+};
+
+use crate::{
+    handlers::timer_handlers::{TimerMutationHandlers as TMH, TimerQueryHandlers as TQH},
+    AppState,
+};
+
+pub fn timer_routes() -> Router<AppState> {
+    Router::new()
+        .route("/target", post(TQH::check_target_handler))
+        .route("/session", post(TMH::upload_session_handler))
+        .route(
+            "/session/by-task-id/{task_id}",
+            get(TQH::get_sessions_by_task_id_handler),
+        )
+        .route("/activity", post(TMH::upload_activity_handler))
+        .route(
+            "/window-activity",
+            post(TMH::upload_window_activity_handler),
+        )
+}
@@ -0,0 +1,25 @@
+use axum::{
+    routing::{get, post},
+    Router,
+};
+
+use crate::{
+    handlers::tracking_policy_handlers::TrackingPolicyMutationHandlers as TPMH,
+    handlers::tracking_policy_handlers::TrackingPolicyQueryHandlers as TPQH, AppState,
+};
+
+pub fn tracking_policy_routes() -> Router<AppState> {
+    Router::new()
+        .route("/global", get(TPQH::get_global_policy_handler))
+        .route(
+            "/by-project-id/{project_id}",
+            get(TPQH::get_project_policy_handler),
+        )
+        .route("/effective", get(TPQH::get_effective_policy_handler))
+        .route("/set", post(TPMH::save_global_policy_handler))
+        .route("/set/{project_id}", post(TPMH::save_project_policy_handler))
+        .route(
+            "/delete/{project_id}",
+            get(TPMH::delete_project_policy_handler),
+        )
+}
@@ -0,0 +1,167 @@
+use std::collections::BTreeMap;
+
+use sea_orm::{prelude::Uuid, DatabaseConnection};
+
+use crate::{
+    dtos::{
+        billing_dto::{BillingRateCreateDto, BillingRateFilterParams, BillingRateUpdateDto},
+        time_dto::TimeFilterParams,
+    },
+    entities::billing_rate,
+    repository::{
+        activity_repository::{ActivityQuery, TrackedTime},
+        billing_rate_repository::{BillingRateMutation as BRM, BillingRateQuery as BRQ},
+        project_repository::ProjectQuery,
+    },
+    services::client_service::ClientService,
+    utils::{
+        billing::{applicable_rate, billable_amount},
+        error::AppError,
+    },
+};
+
+pub struct BillingService;
+
+/// Tracked time with the rate it is billed at; `rate` and `amount` are
+/// `None` when the time is not billable or no rate applies.
+#[derive(Debug, Clone)]
+pub struct PricedTime {
+    pub time: TrackedTime,
+    pub duration_seconds: i64,
+    pub billable: bool,
+    pub rate: Option<billing_rate::Model>,
+    pub amount: Option<i64>,
+}
+
+/// Totals over a set of [`PricedTime`]. Amounts are per currency, keyed by
+/// currency code, as `(billable seconds, amount)`.
+#[derive(Debug, Clone, Default)]
+pub struct TimeSummary {
+    pub total_seconds: i64,
+    pub billable_seconds: i64,
+    pub unpriced_seconds: i64,
+    pub totals: BTreeMap<String, (i64, i64)>,
+}
+
+impl BillingService {
+    pub async fn get_rates(
+        db: &DatabaseConnection,
+        filter: BillingRateFilterParams,
+    ) -> Result<Vec<billing_rate::Model>, AppError> {
+        BRQ::get_rates(db, filter).await
+    }
+
+    pub async fn get_rate_by_id(
+        db: &DatabaseConnection,
+        id: Uuid,
+    ) -> Result<Option<billing_rate::Model>, AppError> {
+        BRQ::get_rate_by_id(db, id).await
+    }
+
+    pub async fn create_rate(
+        db: &DatabaseConnection,
+        data: BillingRateCreateDto,
+    ) -> Result<billing_rate::Model, AppError> {
+        if data.client_id.is_some() && data.project_id.is_some() {
+            return Err(AppError::BadRequest(
+                "A rate is for a client or for a project, not both".to_string(),
+            ));
+        }
+        if data.person.is_some() && data.project_id.is_none() {
+            return Err(AppError::BadRequest(
+                "A rate for a person needs a project_id".to_string(),
+            ));
+        }
+        if let Some(client_id) = data.client_id {
+            ClientService::ensure_client(db, client_id).await?;
+        }
+        if let Some(project_id) = data.project_id {
+            if ProjectQuery::get_project_by_id(db, project_id)
+                .await?
+                .is_none()
+            {
+                return Err(AppError::NotFound(format!(
+                    "Project with id {} not found",
+                    project_id
+                )));
+            }
+        }
+        BRM::create_rate(db, data).await
+    }
+
+    pub async fn update_rate(
+        db: &DatabaseConnection,
+        id: Uuid,
+        data: BillingRateUpdateDto,
+    ) -> Result<billing_rate::Model, AppError> {
+        BRM::update_rate(db, id, data).await
+    }
+
+    pub async fn delete_rate(db: &DatabaseConnection, id: Uuid) -> Result<u64, AppError> {
+        BRM::delete_rate(db, id).await
+    }
+
+    /// Tracked time matching `filter`, each interval priced at the rate
+    /// that applies to it.
+    pub async fn get_time_entries(
+        db: &DatabaseConnection,
+        filter: TimeFilterParams,
+    ) -> Result<Vec<PricedTime>, AppError> {
+        let tracked = ActivityQuery::get_tracked_time(db, &filter).await?;
+        let rates = BRQ::get_rates(db, Default::default()).await?;
+        Ok(tracked
+            .into_iter()
+            .map(|time| Self::price(&rates, time))
+            .filter(|entry| {
+                filter
+                    .billable
+                    .is_none_or(|billable| entry.billable == billable)
+            })
+            .collect())
+    }
+
+    pub async fn get_time_summary(
+        db: &DatabaseConnection,
+        filter: TimeFilterParams,
+    ) -> Result<TimeSummary, AppError> {
+        let entries = Self::get_time_entries(db, filter).await?;
+        let mut summary = TimeSummary::default();
+        for entry in entries {
+            summary.total_seconds += entry.duration_seconds;
+            if !entry.billable {
+                continue;
+            }
+            summary.billable_seconds += entry.duration_seconds;
+            match (entry.rate, entry.amount) {
+                (Some(rate), Some(amount)) => {
+                    let total = summary.totals.entry(rate.currency).or_default();
+                    total.0 += entry.duration_seconds;
+                    total.1 += amount;
+                }
+                _ => summary.unpriced_seconds += entry.duration_seconds,
+            }
+        }
+        Ok(summary)
+    }
+
+    fn price(rates: &[billing_rate::Model], time: TrackedTime) -> PricedTime {
+        let duration_seconds = (time.end_time - time.start_time).num_seconds().max(0);
+        let billable = time.task_billable == Some(true) && time.project_billable == Some(true);
+        let rate = match time.project_id {
+            Some(project_id) if billable => {
+                applicable_rate(rates, time.client_id, project_id, time.person.as_deref()).cloned()
+            }
+            _ => None,
+        };
+        let amount = rate
+            .as_ref()
+            .map(|rate| billable_amount(duration_seconds, rate.hourly_rate));
+        PricedTime {
+            time,
+            duration_seconds,
+            billable,
+            rate,
+            amount,
+        }
+    }
+}
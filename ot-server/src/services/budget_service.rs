@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+
+use chrono::{Duration, Local, Utc};
+use sea_orm::{prelude::Uuid, ActiveValue, DatabaseConnection};
+
+use crate::{
+    config::settings::BudgetSettings,
+    dtos::{
+        budget_dto::{BudgetAlertFilterParams, BudgetAlertKind},
+        time_dto::TimeFilterParams,
+    },
+    entities::{budget_alert, project, task},
+    repository::{
+        budget_alert_repository::{
+            BudgetAlertMutation as BAM, BudgetAlertQuery as BAQ, BudgetAlertRow,
+        },
+        project_repository::ProjectQuery,
+        task_repository::TaskQuery,
+    },
+    services::billing_service::BillingService,
+    utils::{
+        budget::{progress, reached_thresholds, Progress},
+        error::AppError,
+    },
+};
+
+/// Use of a project's budgets and of its tasks' estimates. Time is in
+/// seconds; money in the minor unit of the budget's currency.
+#[derive(Debug, Clone)]
+pub struct ProjectBudget {
+    pub project: project::Model,
+    pub time: Option<Progress>,
+    pub money: Option<Progress>,
+    pub unpriced_seconds: i64,
+    /// Tasks with an estimate, with the time tracked on them
+    pub tasks: Vec<(task::Model, i64)>,
+}
+
+pub struct BudgetService;
+
+impl BudgetService {
+    pub async fn get_project_budget(
+        db: &DatabaseConnection,
+        id: Uuid,
+        settings: &BudgetSettings,
+    ) -> Result<ProjectBudget, AppError> {
+        let project = ProjectQuery::get_project_by_id(db, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Project with id {} not found", id)))?;
+        Self::budget_of(db, project, settings).await
+    }
+
+    /// Raise an alert for every threshold a budget or estimate reached since
+    /// it was last checked; returns how many were raised.
+    pub async fn check_alerts(
+        db: &DatabaseConnection,
+        settings: &BudgetSettings,
+    ) -> Result<u64, AppError> {
+        let mut raised = 0;
+        for id in ProjectQuery::get_budgeted_project_ids(db).await? {
+            let Some(project) = ProjectQuery::get_project_by_id(db, id).await? else {
+                continue;
+            };
+            let budget = Self::budget_of(db, project, settings).await?;
+            let thresholds = &settings.alert_thresholds;
+
+            let mut alerts = Vec::new();
+            let mut add = |kind, task_id, used, budget, currency: Option<String>| {
+                for threshold in reached_thresholds(used, budget, thresholds) {
+                    alerts.push(budget_alert::ActiveModel {
+                        project_id: ActiveValue::set(id),
+                        task_id: ActiveValue::set(task_id),
+                        kind: ActiveValue::set(kind),
+                        threshold: ActiveValue::set(threshold as i32),
+                        used: ActiveValue::set(used),
+                        budget: ActiveValue::set(budget),
+                        currency: ActiveValue::set(currency.clone()),
+                        ..Default::default()
+                    });
+                }
+            };
+            if let Some(time) = &budget.time {
+                add(BudgetAlertKind::Time, None, time.used, time.budget, None);
+            }
+            if let Some(money) = &budget.money {
+                let currency = budget.project.budget_currency.clone();
+                add(
+                    BudgetAlertKind::Money,
+                    None,
+                    money.used,
+                    money.budget,
+                    currency,
+                );
+            }
+            for (task, used) in &budget.tasks {
+                let estimate = estimate_seconds(task);
+                add(
+                    BudgetAlertKind::Estimate,
+                    Some(task.id),
+                    *used,
+                    estimate,
+                    None,
+                );
+            }
+            raised += BAM::raise_alerts(db, alerts).await?;
+        }
+        Ok(raised)
+    }
+
+    pub async fn get_alerts(
+        db: &DatabaseConnection,
+        filter: BudgetAlertFilterParams,
+    ) -> Result<Vec<BudgetAlertRow>, AppError> {
+        BAQ::get_alerts(db, filter).await
+    }
+
+    pub async fn acknowledge_alert(
+        db: &DatabaseConnection,
+        id: Uuid,
+    ) -> Result<BudgetAlertRow, AppError> {
+        BAM::acknowledge_alert(db, id).await?;
+        BAQ::get_alert_by_id(db, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Alert with id {} not found", id)))
+    }
+
+    /// Clear the alerts of the budgets that changed between `before` and
+    /// `after`, so they are raised again against the new budget.
+    pub async fn budgets_changed(
+        db: &DatabaseConnection,
+        before: &project::Model,
+        after: &project::Model,
+    ) -> Result<(), AppError> {
+        let map_err = |e| AppError::InternalServerError(format!("Failed to clear alerts: {}", e));
+        if before.budget_minutes != after.budget_minutes {
+            BAM::clear_project_alerts(db, after.id, BudgetAlertKind::Time)
+                .await
+                .map_err(map_err)?;
+        }
+        if (&before.budget_amount, &before.budget_currency)
+            != (&after.budget_amount, &after.budget_currency)
+        {
+            BAM::clear_project_alerts(db, after.id, BudgetAlertKind::Money)
+                .await
+                .map_err(map_err)?;
+        }
+        Ok(())
+    }
+
+    /// Clear a task's estimate alerts when its estimate changed.
+    pub async fn estimate_changed(
+        db: &DatabaseConnection,
+        before: &task::Model,
+        after: &task::Model,
+    ) -> Result<(), AppError> {
+        if before.estimated_minutes == after.estimated_minutes {
+            return Ok(());
+        }
+        BAM::clear_task_alerts(db, after.id)
+            .await
+            .map(|_| ())
+            .map_err(|e| AppError::InternalServerError(format!("Failed to clear alerts: {}", e)))
+    }
+
+    async fn budget_of(
+        db: &DatabaseConnection,
+        project: project::Model,
+        settings: &BudgetSettings,
+    ) -> Result<ProjectBudget, AppError> {
+        let filter = TimeFilterParams {
+            project_id: Some(project.id),
+            ..Default::default()
+        };
+        let entries = BillingService::get_time_entries(db, filter).await?;
+        let window_start = Utc::now() - Duration::days(i64::from(settings.burn_rate_window_days));
+
+        // (used, used within the burn rate window)
+        let (mut seconds, mut amount) = ((0, 0), (0, 0));
+        let mut unpriced_seconds = 0;
+        let mut per_task = HashMap::<Uuid, i64>::new();
+        for entry in &entries {
+            let recent = entry.time.start_time >= window_start;
+            seconds.0 += entry.duration_seconds;
+            if recent {
+                seconds.1 += entry.duration_seconds;
+            }
+            if let Some(task_id) = entry.time.task_id {
+                *per_task.entry(task_id).or_default() += entry.duration_seconds;
+            }
+            let Some(currency) = &project.budget_currency else {
+                continue;
+            };
+            match (&entry.rate, entry.amount) {
+                (Some(rate), Some(value)) if &rate.currency == currency => {
+                    amount.0 += value;
+                    if recent {
+                        amount.1 += value;
+                    }
+                }
+                _ if entry.billable => unpriced_seconds += entry.duration_seconds,
+                _ => {}
+            }
+        }
+
+        let today = Local::now().date_naive();
+        let window = settings.burn_rate_window_days;
+        let time = project
+            .budget_minutes
+            .map(|minutes| progress(i64::from(minutes) * 60, seconds.0, seconds.1, window, today));
+        let money = project
+            .budget_amount
+            .map(|budget| progress(budget, amount.0, amount.1, window, today));
+        let tasks = TaskQuery::get_estimated_tasks(db, project.id)
+            .await?
+            .into_iter()
+            .map(|task| {
+                let used = per_task.get(&task.id).copied().unwrap_or_default();
+                (task, used)
+            })
+            .collect();
+        Ok(ProjectBudget {
+            project,
+            time,
+            money,
+            unpriced_seconds,
+            tasks,
+        })
+    }
+}
+
+/// A task's estimate in seconds; zero when it has none.
+pub fn estimate_seconds(task: &task::Model) -> i64 {
+    task.estimated_minutes
+        .map_or(0, |minutes| i64::from(minutes) * 60)
+}
@@ -0,0 +1,53 @@
+use sea_orm::{prelude::Uuid, DatabaseConnection};
+
+use crate::{
+    dtos::client_dto::{ClientCreateDto, ClientUpdateDto},
+    entities::client,
+    repository::client_repository::{ClientMutation as CM, ClientQuery as CQ},
+    utils::error::AppError,
+};
+
+pub struct ClientService;
+
+impl ClientService {
+    pub async fn get_clients(db: &DatabaseConnection) -> Result<Vec<client::Model>, AppError> {
+        CQ::get_clients(db).await
+    }
+
+    pub async fn get_client_by_id(
+        db: &DatabaseConnection,
+        id: Uuid,
+    ) -> Result<Option<client::Model>, AppError> {
+        CQ::get_client_by_id(db, id).await
+    }
+
+    pub async fn create_client(
+        db: &DatabaseConnection,
+        data: ClientCreateDto,
+    ) -> Result<client::Model, AppError> {
+        CM::create_client(db, data).await
+    }
+
+    pub async fn update_client(
+        db: &DatabaseConnection,
+        id: Uuid,
+        data: ClientUpdateDto,
+    ) -> Result<client::Model, AppError> {
+        CM::update_client(db, id, data).await
+    }
+
+    pub async fn delete_client(db: &DatabaseConnection, id: Uuid) -> Result<u64, AppError> {
+        CM::delete_client(db, id).await
+    }
+
+    /// Fail with `NotFound` unless the client exists.
+    pub async fn ensure_client(db: &DatabaseConnection, id: Uuid) -> Result<(), AppError> {
+        match CQ::get_client_by_id(db, id).await? {
+            Some(_) => Ok(()),
+            None => Err(AppError::NotFound(format!(
+                "Client with id {} not found",
+                id
+            ))),
+        }
+    }
+}
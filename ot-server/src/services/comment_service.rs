@@ -0,0 +1,171 @@
+use sea_orm::{prelude::Uuid, DatabaseConnection};
+
+use crate::{
+    dtos::comment_dto::{CommentCreateDto, CommentUpdateDto},
+    entities::{comment, comment_revision, task_event},
+    repository::{
+        comment_repository::{CommentMutation as CM, CommentQuery as CQ},
+        sub_task_repository::SubTaskQuery,
+        task_event_repository::TaskEventQuery,
+        task_repository::TaskQuery,
+    },
+    utils::error::AppError,
+};
+
+/// An entry of a task's activity feed.
+pub enum FeedItem {
+    Comment(comment::Model),
+    Change(task_event::Model),
+}
+
+pub struct CommentService;
+
+impl CommentService {
+    pub async fn get_comment_by_id(
+        db: &DatabaseConnection,
+        id: Uuid,
+    ) -> Result<Option<comment::Model>, AppError> {
+        CQ::get_comment_by_id(db, id).await
+    }
+
+    pub async fn get_comments_by_task_id(
+        db: &DatabaseConnection,
+        task_id: Uuid,
+    ) -> Result<Vec<comment::Model>, AppError> {
+        CQ::get_comments_by_task_id(db, task_id).await
+    }
+
+    pub async fn get_comments_by_sub_task_id(
+        db: &DatabaseConnection,
+        sub_task_id: Uuid,
+    ) -> Result<Vec<comment::Model>, AppError> {
+        CQ::get_comments_by_sub_task_id(db, sub_task_id).await
+    }
+
+    pub async fn get_comment_history(
+        db: &DatabaseConnection,
+        id: Uuid,
+    ) -> Result<Vec<comment_revision::Model>, AppError> {
+        if CQ::get_comment_by_id(db, id).await?.is_none() {
+            return Err(AppError::NotFound(format!("Comment with id {} not found", id)));
+        }
+        CQ::get_revisions_by_comment_id(db, id).await
+    }
+
+    pub async fn create_comment(
+        db: &DatabaseConnection,
+        data: CommentCreateDto,
+    ) -> Result<comment::Model, AppError> {
+        match (data.task_id, data.sub_task_id) {
+            (Some(task_id), None) => {
+                if TaskQuery::get_task_by_id(db, task_id).await?.is_none() {
+                    return Err(AppError::NotFound(format!(
+                        "Task with id {} not found",
+                        task_id
+                    )));
+                }
+            }
+            (None, Some(sub_task_id)) => {
+                if SubTaskQuery::get_sub_task_by_id(db, sub_task_id)
+                    .await?
+                    .is_none()
+                {
+                    return Err(AppError::NotFound(format!(
+                        "SubTask with id {} not found",
+                        sub_task_id
+                    )));
+                }
+            }
+            _ => {
+                return Err(AppError::BadRequest(
+                    "A comment needs exactly one of task_id and sub_task_id".to_string(),
+                ))
+            }
+        }
+
+        let mentions = parse_mentions(&data.body);
+        CM::create_comment(db, data, mentions).await
+    }
+
+    pub async fn update_comment(
+        db: &DatabaseConnection,
+        id: Uuid,
+        data: CommentUpdateDto,
+    ) -> Result<comment::Model, AppError> {
+        let mentions = parse_mentions(&data.body);
+        CM::update_comment(db, id, data, mentions).await
+    }
+
+    pub async fn delete_comment(db: &DatabaseConnection, id: Uuid) -> Result<u64, AppError> {
+        CM::delete_comment(db, id).await
+    }
+
+    /// Comments and recorded changes of a task and its sub tasks, oldest first.
+    pub async fn get_task_feed(
+        db: &DatabaseConnection,
+        task_id: Uuid,
+    ) -> Result<Vec<FeedItem>, AppError> {
+        if TaskQuery::get_task_by_id(db, task_id).await?.is_none() {
+            return Err(AppError::NotFound(format!(
+                "Task with id {} not found",
+                task_id
+            )));
+        }
+        let sub_task_ids = SubTaskQuery::get_sub_tasks_by_task_id(db, task_id)
+            .await?
+            .into_iter()
+            .map(|sub_task| sub_task.id)
+            .collect::<Vec<_>>();
+
+        let comments = CQ::get_comments_for_task(db, task_id, sub_task_ids.clone()).await?;
+        let events = TaskEventQuery::get_events_for_task(db, task_id, sub_task_ids).await?;
+
+        let mut feed = comments
+            .into_iter()
+            .map(FeedItem::Comment)
+            .chain(events.into_iter().map(FeedItem::Change))
+            .collect::<Vec<_>>();
+        feed.sort_by_key(|item| match item {
+            FeedItem::Comment(comment) => comment.created_at,
+            FeedItem::Change(event) => event.created_at,
+        });
+        Ok(feed)
+    }
+}
+
+/// Names mentioned as `@name`, in order of first appearance. An `@` inside a
+/// word, as in an email address, is not a mention.
+fn parse_mentions(body: &str) -> Vec<String> {
+    let is_name_char = |c: char| c.is_alphanumeric() || matches!(c, '_' | '.' | '-');
+    let mut mentions: Vec<String> = Vec::new();
+    let mut previous = None;
+    for (index, c) in body.char_indices() {
+        let starts_mention = c == '@' && !previous.is_some_and(is_name_char);
+        previous = Some(c);
+        if !starts_mention {
+            continue;
+        }
+        let rest = &body[index + 1..];
+        let end = rest.find(|c| !is_name_char(c)).unwrap_or(rest.len());
+        // Sentence punctuation right after a name is not part of it
+        let name = rest[..end].trim_end_matches(['.', '-']);
+        if !name.is_empty() && !mentions.iter().any(|m| m == name) {
+            mentions.push(name.to_string());
+        }
+    }
+    mentions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_mentions;
+
+    #[test]
+    fn parses_mentions_but_not_email_addresses() {
+        assert_eq!(
+            parse_mentions("@sam can you check with @jo.doe? cc @sam, mail jo@example.com."),
+            vec!["sam", "jo.doe"]
+        );
+        assert!(parse_mentions("no mentions @ all").is_empty());
+    }
+}
@@ -0,0 +1,95 @@
+use sea_orm::{prelude::Uuid, DatabaseConnection};
+
+use crate::{
+    dtos::dependency_dto::TaskDependencyCreateDto,
+    entities::task_dependency,
+    repository::{
+        project_repository::{ProjectItem, ProjectQuery},
+        task_dependency_repository::{TaskDependencyMutation as TDM, TaskDependencyQuery as TDQ},
+        task_repository::TaskQuery,
+    },
+    services::{
+        project_service::ProjectService,
+        task_service::{TaskService, TaskWithBlocked},
+    },
+    utils::error::AppError,
+};
+
+pub struct DependencyService;
+
+impl DependencyService {
+    pub async fn create_dependency(
+        db: &DatabaseConnection,
+        data: TaskDependencyCreateDto,
+    ) -> Result<task_dependency::Model, AppError> {
+        if data.blocking_task_id == data.blocked_task_id {
+            return Err(AppError::BadRequest(
+                "A task cannot depend on itself".to_string(),
+            ));
+        }
+        for task_id in [data.blocking_task_id, data.blocked_task_id] {
+            ProjectService::ensure_writable(db, ProjectItem::Task(task_id)).await?;
+        }
+        TDM::create_dependency(db, data).await
+    }
+
+    pub async fn delete_dependency(
+        db: &DatabaseConnection,
+        blocking_task_id: Uuid,
+        blocked_task_id: Uuid,
+    ) -> Result<u64, AppError> {
+        for task_id in [blocking_task_id, blocked_task_id] {
+            ProjectService::ensure_writable(db, ProjectItem::Task(task_id)).await?;
+        }
+        TDM::delete_dependency(db, blocking_task_id, blocked_task_id).await
+    }
+
+    /// Tasks a task waits on, and tasks waiting on it.
+    pub async fn get_task_dependencies(
+        db: &DatabaseConnection,
+        task_id: Uuid,
+    ) -> Result<(Vec<TaskWithBlocked>, Vec<TaskWithBlocked>), AppError> {
+        if TaskQuery::get_task_by_id(db, task_id).await?.is_none() {
+            return Err(AppError::NotFound(format!(
+                "Task with id {} not found",
+                task_id
+            )));
+        }
+        let blocked_by = TDQ::get_blocking_tasks(db, task_id).await?;
+        let blocks = TDQ::get_blocked_tasks(db, task_id).await?;
+        Ok((
+            TaskService::with_blocked(db, blocked_by).await?,
+            TaskService::with_blocked(db, blocks).await?,
+        ))
+    }
+
+    /// The tasks of a project that take part in a dependency, and the
+    /// dependencies between them.
+    pub async fn get_project_graph(
+        db: &DatabaseConnection,
+        project_id: Uuid,
+    ) -> Result<(Vec<TaskWithBlocked>, Vec<task_dependency::Model>), AppError> {
+        if ProjectQuery::get_project_by_id(db, project_id)
+            .await?
+            .is_none()
+        {
+            return Err(AppError::NotFound(format!(
+                "Project with id {} not found",
+                project_id
+            )));
+        }
+        let edges = TDQ::get_project_dependencies(db, project_id)
+            .await
+            .map_err(|e| {
+                AppError::InternalServerError(format!("Failed to get dependencies: {}", e))
+            })?;
+        let mut task_ids = edges
+            .iter()
+            .flat_map(|edge| [edge.blocking_task_id, edge.blocked_task_id])
+            .collect::<Vec<_>>();
+        task_ids.sort_unstable();
+        task_ids.dedup();
+        let tasks = TaskQuery::get_tasks_by_ids(db, task_ids).await?;
+        Ok((TaskService::with_blocked(db, tasks).await?, edges))
+    }
+}
@@ -0,0 +1,103 @@
+use std::{fs, path::Path, time::Duration};
+
+use sea_orm::DatabaseConnection;
+use sea_orm_migration::MigratorTrait;
+use serde::Serialize;
+use tokio::time::timeout;
+
+use crate::{config::settings::StorageSettings, migrator::Migrator};
+
+const CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Serialize)]
+pub struct CheckResult {
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReadinessReport {
+    pub ready: bool,
+    pub database: CheckResult,
+    pub migrations: CheckResult,
+    pub storage: CheckResult,
+}
+
+impl CheckResult {
+    fn ok() -> Self {
+        Self {
+            ok: true,
+            detail: None,
+        }
+    }
+
+    fn failed(detail: impl Into<String>) -> Self {
+        Self {
+            ok: false,
+            detail: Some(detail.into()),
+        }
+    }
+}
+
+pub struct HealthService;
+
+impl HealthService {
+    pub async fn check_database(db: &DatabaseConnection) -> CheckResult {
+        match timeout(CHECK_TIMEOUT, db.ping()).await {
+            Ok(Ok(())) => CheckResult::ok(),
+            Ok(Err(e)) => CheckResult::failed(format!("ping failed: {}", e)),
+            Err(_) => CheckResult::failed("ping timed out"),
+        }
+    }
+
+    pub async fn check_migrations(db: &DatabaseConnection) -> CheckResult {
+        match timeout(CHECK_TIMEOUT, Migrator::get_pending_migrations(db)).await {
+            Ok(Ok(pending)) if pending.is_empty() => CheckResult::ok(),
+            Ok(Ok(pending)) => CheckResult::failed(format!(
+                "{} pending migration(s): {}",
+                pending.len(),
+                pending
+                    .iter()
+                    .map(|m| m.name().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+            Ok(Err(e)) => CheckResult::failed(format!("failed to read migration status: {}", e)),
+            Err(_) => CheckResult::failed("migration status check timed out"),
+        }
+    }
+
+    pub fn check_storage(storage: &StorageSettings) -> CheckResult {
+        for dir in [&storage.root, &storage.screenshots, &storage.exports] {
+            if let Err(e) = probe_writable(dir) {
+                return CheckResult::failed(format!("{} is not writable: {}", dir.display(), e));
+            }
+        }
+        CheckResult::ok()
+    }
+
+    pub async fn readiness(db: &DatabaseConnection, storage: &StorageSettings) -> ReadinessReport {
+        let database = Self::check_database(db).await;
+        // Migration status needs a working connection; don't wait on a second timeout
+        let migrations = if database.ok {
+            Self::check_migrations(db).await
+        } else {
+            CheckResult::failed("skipped: database unavailable")
+        };
+        let storage = Self::check_storage(storage);
+
+        ReadinessReport {
+            ready: database.ok && migrations.ok && storage.ok,
+            database,
+            migrations,
+            storage,
+        }
+    }
+}
+
+fn probe_writable(dir: &Path) -> std::io::Result<()> {
+    let probe = dir.join(".ready-probe");
+    fs::write(&probe, b"ok")?;
+    fs::remove_file(&probe)
+}
@@ -0,0 +1,241 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::{Days, Local};
+use sea_orm::{prelude::Uuid, DatabaseConnection};
+
+use crate::{
+    config::settings::InvoiceSettings,
+    dtos::{
+        invoice_dto::{
+            InvoiceCreateDto, InvoiceFilterParams, InvoiceFinalizeDto, InvoiceLineCreateDto,
+            InvoiceLineUpdateDto, InvoiceUpdateDto,
+        },
+        time_dto::TimeFilterParams,
+    },
+    entities::{invoice, invoice_line},
+    repository::{
+        client_repository::ClientQuery,
+        invoice_repository::{DraftInvoice, DraftLine, InvoiceMutation as IM, InvoiceQuery as IQ},
+    },
+    services::{billing_service::BillingService, client_service::ClientService},
+    utils::{error::AppError, invoice_pdf::render_invoice},
+};
+
+/// An invoice and its lines, in order.
+pub type InvoiceWithLines = (invoice::Model, Vec<invoice_line::Model>);
+
+pub struct InvoiceService;
+
+impl InvoiceService {
+    pub async fn get_invoices(
+        db: &DatabaseConnection,
+        filter: InvoiceFilterParams,
+    ) -> Result<Vec<InvoiceWithLines>, AppError> {
+        let invoices = IQ::get_invoices(db, filter).await?;
+        let ids = invoices
+            .iter()
+            .map(|invoice| invoice.id)
+            .collect::<Vec<_>>();
+        let mut lines = BTreeMap::<Uuid, Vec<invoice_line::Model>>::new();
+        for line in IQ::get_lines(db, &ids).await? {
+            lines.entry(line.invoice_id).or_default().push(line);
+        }
+        Ok(invoices
+            .into_iter()
+            .map(|invoice| {
+                let lines = lines.remove(&invoice.id).unwrap_or_default();
+                (invoice, lines)
+            })
+            .collect())
+    }
+
+    pub async fn get_invoice_by_id(
+        db: &DatabaseConnection,
+        id: Uuid,
+    ) -> Result<Option<InvoiceWithLines>, AppError> {
+        match IQ::get_invoice_by_id(db, id).await? {
+            Some(invoice) => Ok(Some(Self::with_lines(db, invoice).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Draft an invoice from the client's unbilled billable time in the
+    /// period, with a line per task and rate. Time no rate applies to is
+    /// left out, as is time priced in another currency.
+    pub async fn create_invoice(
+        db: &DatabaseConnection,
+        data: InvoiceCreateDto,
+    ) -> Result<InvoiceWithLines, AppError> {
+        if data.from >= data.to {
+            return Err(AppError::BadRequest(
+                "The period must end after it starts".to_string(),
+            ));
+        }
+        ClientService::ensure_client(db, data.client_id).await?;
+
+        let filter = TimeFilterParams {
+            from: Some(data.from),
+            to: Some(data.to),
+            client_id: Some(data.client_id),
+            billable: Some(true),
+            invoiced: Some(false),
+            ..Default::default()
+        };
+        let entries = BillingService::get_time_entries(db, filter)
+            .await?
+            .into_iter()
+            .filter_map(|entry| entry.rate.clone().map(|rate| (entry, rate)))
+            .collect::<Vec<_>>();
+
+        let currencies = entries
+            .iter()
+            .map(|(_, rate)| rate.currency.clone())
+            .collect::<BTreeSet<_>>();
+        let currency = match (data.currency, currencies.len()) {
+            (_, 0) => {
+                return Err(AppError::BadRequest(
+                    "The client has no unbilled billable time in this period".to_string(),
+                ))
+            }
+            (Some(currency), _) if currencies.contains(&currency) => currency,
+            (Some(currency), _) => {
+                return Err(AppError::BadRequest(format!(
+                    "The client has no unbilled time priced in {} in this period",
+                    currency
+                )))
+            }
+            (None, 1) => currencies.into_iter().next().unwrap_or_default(),
+            (None, _) => {
+                return Err(AppError::BadRequest(format!(
+                    "The client's time is priced in {}; choose a currency",
+                    currencies.into_iter().collect::<Vec<_>>().join(", ")
+                )))
+            }
+        };
+
+        // Lines sorted by project and task name, then rate
+        let mut lines = BTreeMap::new();
+        for (entry, rate) in entries {
+            if rate.currency != currency {
+                continue;
+            }
+            let time = entry.time;
+            let line = lines
+                .entry((
+                    time.project_name.clone().unwrap_or_default(),
+                    time.task_name.clone().unwrap_or_default(),
+                    time.project_id,
+                    time.task_id,
+                    rate.hourly_rate,
+                ))
+                .or_insert_with(|| DraftLine {
+                    project_id: time.project_id,
+                    task_id: time.task_id,
+                    description: line_description(
+                        time.project_name.as_deref(),
+                        time.task_name.as_deref(),
+                    ),
+                    seconds: 0,
+                    hourly_rate: rate.hourly_rate,
+                    activity_ids: Vec::new(),
+                });
+            line.seconds += entry.duration_seconds;
+            line.activity_ids.push(time.id);
+        }
+
+        let draft = DraftInvoice {
+            client_id: data.client_id,
+            currency,
+            period_start: data.from,
+            period_end: data.to,
+            due_on: data.due_on,
+            notes: data.notes,
+        };
+        let invoice = IM::create_draft(db, draft, lines.into_values().collect()).await?;
+        Self::with_lines(db, invoice).await
+    }
+
+    pub async fn update_invoice(
+        db: &DatabaseConnection,
+        id: Uuid,
+        data: InvoiceUpdateDto,
+    ) -> Result<InvoiceWithLines, AppError> {
+        let invoice = IM::update_invoice(db, id, data).await?;
+        Self::with_lines(db, invoice).await
+    }
+
+    /// Number and issue a draft; it is due after the configured payment
+    /// terms unless it has a due date.
+    pub async fn finalize_invoice(
+        db: &DatabaseConnection,
+        id: Uuid,
+        data: InvoiceFinalizeDto,
+        settings: &InvoiceSettings,
+    ) -> Result<InvoiceWithLines, AppError> {
+        let issued_on = data.issued_on.unwrap_or_else(|| Local::now().date_naive());
+        let due_on = issued_on
+            .checked_add_days(Days::new(settings.payment_terms_days.into()))
+            .unwrap_or(issued_on);
+        let invoice = IM::finalize_invoice(db, id, issued_on, due_on).await?;
+        Self::with_lines(db, invoice).await
+    }
+
+    pub async fn delete_invoice(db: &DatabaseConnection, id: Uuid) -> Result<(), AppError> {
+        IM::delete_invoice(db, id).await
+    }
+
+    pub async fn create_line(
+        db: &DatabaseConnection,
+        invoice_id: Uuid,
+        data: InvoiceLineCreateDto,
+    ) -> Result<invoice_line::Model, AppError> {
+        IM::create_line(db, invoice_id, data).await
+    }
+
+    pub async fn update_line(
+        db: &DatabaseConnection,
+        id: Uuid,
+        data: InvoiceLineUpdateDto,
+    ) -> Result<invoice_line::Model, AppError> {
+        IM::update_line(db, id, data).await
+    }
+
+    pub async fn delete_line(db: &DatabaseConnection, id: Uuid) -> Result<(), AppError> {
+        IM::delete_line(db, id).await
+    }
+
+    /// The invoice as a PDF document.
+    pub async fn render_pdf(
+        db: &DatabaseConnection,
+        id: Uuid,
+        settings: &InvoiceSettings,
+    ) -> Result<(invoice::Model, Vec<u8>), AppError> {
+        let (invoice, lines) = Self::get_invoice_by_id(db, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Invoice with id {} not found", id)))?;
+        let client = ClientQuery::get_client_by_id(db, invoice.client_id)
+            .await?
+            .ok_or_else(|| {
+                AppError::NotFound(format!("Client with id {} not found", invoice.client_id))
+            })?;
+        let pdf = render_invoice(&invoice, &lines, &client, &settings.issuer);
+        Ok((invoice, pdf))
+    }
+
+    async fn with_lines(
+        db: &DatabaseConnection,
+        invoice: invoice::Model,
+    ) -> Result<InvoiceWithLines, AppError> {
+        let lines = IQ::get_lines(db, &[invoice.id]).await?;
+        Ok((invoice, lines))
+    }
+}
+
+/// `Project / Task`, cut to fit the line description column.
+fn line_description(project: Option<&str>, task: Option<&str>) -> String {
+    let description = match (project, task) {
+        (Some(project), Some(task)) => format!("{} / {}", project, task),
+        (project, task) => project.or(task).unwrap_or("Tracked time").to_string(),
+    };
+    description.chars().take(255).collect()
+}
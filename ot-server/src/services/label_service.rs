@@ -0,0 +1,133 @@
+use sea_orm::{prelude::Uuid, DatabaseConnection};
+
+use crate::{
+    dtos::label_dto::{LabelCreateDto, LabelTargetDto, LabelUpdateDto},
+    entities::label,
+    repository::{
+        label_repository::{LabelMutation as LM, LabelQuery as LQ, LabelTarget},
+        project_repository::ProjectQuery,
+        sub_task_repository::SubTaskQuery,
+        task_repository::TaskQuery,
+    },
+    utils::error::AppError,
+};
+
+pub struct LabelService;
+
+impl LabelService {
+    pub async fn get_labels(db: &DatabaseConnection) -> Result<Vec<label::Model>, AppError> {
+        LQ::get_labels(db).await
+    }
+
+    pub async fn get_label_by_id(
+        db: &DatabaseConnection,
+        id: Uuid,
+    ) -> Result<Option<label::Model>, AppError> {
+        LQ::get_label_by_id(db, id).await
+    }
+
+    pub async fn get_labels_by_target(
+        db: &DatabaseConnection,
+        target: LabelTarget,
+    ) -> Result<Vec<label::Model>, AppError> {
+        LQ::get_labels_by_target(db, target).await
+    }
+
+    pub async fn create_label(
+        db: &DatabaseConnection,
+        data: LabelCreateDto,
+    ) -> Result<label::Model, AppError> {
+        LM::create_label(db, data).await
+    }
+
+    pub async fn update_label(
+        db: &DatabaseConnection,
+        id: Uuid,
+        data: LabelUpdateDto,
+    ) -> Result<label::Model, AppError> {
+        LM::update_label(db, id, data).await
+    }
+
+    pub async fn delete_label(db: &DatabaseConnection, id: Uuid) -> Result<u64, AppError> {
+        LM::delete_label(db, id).await
+    }
+
+    /// Attach a label and return every label of the target.
+    pub async fn assign_label(
+        db: &DatabaseConnection,
+        id: Uuid,
+        data: LabelTargetDto,
+    ) -> Result<Vec<label::Model>, AppError> {
+        let target = Self::resolve_target(db, data).await?;
+        Self::ensure_label(db, id).await?;
+        LM::assign_label(db, id, target).await?;
+        LQ::get_labels_by_target(db, target).await
+    }
+
+    /// Detach a label and return the labels the target still has.
+    pub async fn unassign_label(
+        db: &DatabaseConnection,
+        id: Uuid,
+        data: LabelTargetDto,
+    ) -> Result<Vec<label::Model>, AppError> {
+        let target = Self::resolve_target(db, data).await?;
+        Self::ensure_label(db, id).await?;
+        LM::unassign_label(db, id, target).await?;
+        LQ::get_labels_by_target(db, target).await
+    }
+
+    async fn ensure_label(db: &DatabaseConnection, id: Uuid) -> Result<(), AppError> {
+        match LQ::get_label_by_id(db, id).await? {
+            Some(_) => Ok(()),
+            None => Err(AppError::NotFound(format!(
+                "Label with id {} not found",
+                id
+            ))),
+        }
+    }
+
+    /// The one project, task or sub task named by `data`, which must exist.
+    async fn resolve_target(
+        db: &DatabaseConnection,
+        data: LabelTargetDto,
+    ) -> Result<LabelTarget, AppError> {
+        match (data.project_id, data.task_id, data.sub_task_id) {
+            (Some(project_id), None, None) => {
+                if ProjectQuery::get_project_by_id(db, project_id)
+                    .await?
+                    .is_none()
+                {
+                    return Err(AppError::NotFound(format!(
+                        "Project with id {} not found",
+                        project_id
+                    )));
+                }
+                Ok(LabelTarget::Project(project_id))
+            }
+            (None, Some(task_id), None) => {
+                if TaskQuery::get_task_by_id(db, task_id).await?.is_none() {
+                    return Err(AppError::NotFound(format!(
+                        "Task with id {} not found",
+                        task_id
+                    )));
+                }
+                Ok(LabelTarget::Task(task_id))
+            }
+            (None, None, Some(sub_task_id)) => {
+                if SubTaskQuery::get_sub_task_by_id(db, sub_task_id)
+                    .await?
+                    .is_none()
+                {
+                    return Err(AppError::NotFound(format!(
+                        "SubTask with id {} not found",
+                        sub_task_id
+                    )));
+                }
+                Ok(LabelTarget::SubTask(sub_task_id))
+            }
+            _ => Err(AppError::BadRequest(
+                "Exactly one of project_id, task_id and sub_task_id is required".to_string(),
+            )),
+        }
+    }
+}
@@ -1,4 +1,16 @@
+pub mod billing_service;
+pub mod budget_service;
+pub mod client_service;
+pub mod comment_service;
+pub mod dependency_service;
+pub mod health_service;
+pub mod invoice_service;
+pub mod label_service;
 pub mod project_service;
+pub mod recurrence_service;
+pub mod search_service;
 pub mod section_service;
 pub mod sub_task_service;
 pub mod task_service;
+pub mod timer_service;
+pub mod tracking_policy_service;
@@ -1,9 +1,11 @@
+use chrono::Utc;
 use sea_orm::{prelude::Uuid, DatabaseConnection};
 
 use crate::{
-    dtos::project_dto::{ProjectCreateDto, ProjectUpdateDto},
+    dtos::project_dto::{ProjectCreateDto, ProjectFilterParams, ProjectUpdateDto},
     entities::project,
-    repository::{project_repository::ProjectMutation, project_repository::ProjectQuery},
+    repository::project_repository::{ProjectItem, ProjectMutation, ProjectQuery},
+    services::{budget_service::BudgetService, client_service::ClientService},
     utils::error::AppError,
 };
 
@@ -21,6 +23,10 @@ impl ProjectService {
                 "Project name cannot be empty".to_string(),
             ));
         }
+        if let Some(client_id) = data.client_id {
+            ClientService::ensure_client(db, client_id).await?;
+        }
+        check_money_budget(data.budget_amount, data.budget_currency.as_deref())?;
 
         ProjectMutation::create_project(db, data).await
     }
@@ -31,31 +37,83 @@ impl ProjectService {
         data: ProjectUpdateDto,
     ) -> Result<project::Model, AppError> {
         // Validate if project exists
-        if ProjectQuery::get_project_by_id(db, id).await?.is_none() {
+        let Some(current) = ProjectQuery::get_project_by_id(db, id).await? else {
             return Err(AppError::NotFound(format!(
                 "Project with id {} not found",
                 id
             )));
+        };
+        ensure_active(&current)?;
+        if let Some(Some(client_id)) = data.client_id {
+            ClientService::ensure_client(db, client_id).await?;
         }
+        check_money_budget(
+            data.budget_amount.unwrap_or(current.budget_amount),
+            data.budget_currency
+                .as_ref()
+                .map_or(current.budget_currency.as_deref(), Option::as_deref),
+        )?;
 
-        ProjectMutation::update_project(db, id, data).await
+        let project = ProjectMutation::update_project(db, id, data).await?;
+        BudgetService::budgets_changed(db, &current, &project).await?;
+        Ok(project)
     }
 
     pub async fn delete_project(db: &DatabaseConnection, id: Uuid) -> Result<u64, AppError> {
         // Validate if project exists
-        if ProjectQuery::get_project_by_id(db, id).await?.is_none() {
+        let Some(current) = ProjectQuery::get_project_by_id(db, id).await? else {
             return Err(AppError::NotFound(format!(
                 "Project with id {} not found",
                 id
             )));
-        }
+        };
+        // Archiving is there to keep history; it has to be undone first
+        ensure_active(&current)?;
 
         // Add any cleanup logic here if needed (e.g., deleting related records)
         ProjectMutation::delete_project(db, id).await
     }
 
-    pub async fn get_projects(db: &DatabaseConnection) -> Result<Vec<project::Model>, AppError> {
-        ProjectQuery::get_projects(db).await
+    /// Hide the project from listings and make it read-only. Archiving an
+    /// archived project leaves it as is.
+    pub async fn archive_project(
+        db: &DatabaseConnection,
+        id: Uuid,
+    ) -> Result<project::Model, AppError> {
+        let project = Self::get_project(db, id).await?;
+        if project.archived_at.is_some() {
+            return Ok(project);
+        }
+        ProjectMutation::set_archived(db, id, Some(Utc::now().fixed_offset())).await
+    }
+
+    pub async fn unarchive_project(
+        db: &DatabaseConnection,
+        id: Uuid,
+    ) -> Result<project::Model, AppError> {
+        let project = Self::get_project(db, id).await?;
+        if project.archived_at.is_none() {
+            return Ok(project);
+        }
+        ProjectMutation::set_archived(db, id, None).await
+    }
+
+    /// Fail with a conflict when `item` lives in an archived project.
+    pub async fn ensure_writable(
+        db: &DatabaseConnection,
+        item: ProjectItem,
+    ) -> Result<(), AppError> {
+        match ProjectQuery::get_archived_project(db, item).await? {
+            Some(project) => ensure_active(&project),
+            None => Ok(()),
+        }
+    }
+
+    pub async fn get_projects(
+        db: &DatabaseConnection,
+        filter: ProjectFilterParams,
+    ) -> Result<Vec<project::Model>, AppError> {
+        ProjectQuery::get_projects(db, filter).await
     }
 
     pub async fn get_project_by_id(
@@ -64,4 +122,65 @@ impl ProjectService {
     ) -> Result<Option<project::Model>, AppError> {
         ProjectQuery::get_project_by_id(db, id).await
     }
+
+    async fn get_project(db: &DatabaseConnection, id: Uuid) -> Result<project::Model, AppError> {
+        ProjectQuery::get_project_by_id(db, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Project with id {} not found", id)))
+    }
+}
+
+/// Archived projects are read-only until they are unarchived.
+fn ensure_active(project: &project::Model) -> Result<(), AppError> {
+    if project.archived_at.is_some() {
+        return Err(AppError::Conflict(format!(
+            "Project {} is archived and read-only",
+            project.name
+        )));
+    }
+    Ok(())
+}
+
+/// A money budget is in a currency, and a currency needs a budget.
+fn check_money_budget(amount: Option<i64>, currency: Option<&str>) -> Result<(), AppError> {
+    if amount.is_some() != currency.is_some() {
+        return Err(AppError::BadRequest(
+            "budget_amount and budget_currency are set together".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use sea_orm::prelude::Uuid;
+
+    use super::ensure_active;
+    use crate::{entities::project, utils::error::AppError};
+
+    #[test]
+    fn archived_projects_are_read_only() {
+        let now = Utc::now().fixed_offset();
+        let mut project = project::Model {
+            id: Uuid::nil(),
+            name: "Website".to_string(),
+            created_at: now,
+            updated_at: now,
+            version: 1,
+            client_id: None,
+            billable: true,
+            budget_minutes: None,
+            budget_amount: None,
+            budget_currency: None,
+            archived_at: None,
+        };
+        assert!(ensure_active(&project).is_ok());
+
+        project.archived_at = Some(now);
+        assert!(matches!(
+            ensure_active(&project),
+            Err(AppError::Conflict(message)) if message == "Project Website is archived and read-only"
+        ));
+    }
 }
@@ -0,0 +1,96 @@
+use chrono::{Days, Local};
+use sea_orm::{prelude::Uuid, DatabaseConnection};
+use tracing::error;
+
+use crate::{
+    dtos::recurrence_dto::TaskRecurrenceSetDto,
+    entities::task_recurrence,
+    repository::{
+        project_repository::ProjectItem,
+        recurrence_repository::{RecurrenceMutation as RM, RecurrenceQuery as RQ},
+        task_repository::TaskQuery,
+    },
+    services::{
+        project_service::ProjectService,
+        task_service::{TaskService, TaskWithBlocked},
+    },
+    utils::{error::AppError, recurrence::RecurrenceRule},
+};
+
+pub struct RecurrenceService;
+
+impl RecurrenceService {
+    pub async fn get_recurrence(
+        db: &DatabaseConnection,
+        task_id: Uuid,
+    ) -> Result<task_recurrence::Model, AppError> {
+        RQ::get_recurrence_by_task_id(db, task_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Task {} does not recur", task_id)))
+    }
+
+    pub async fn set_recurrence(
+        db: &DatabaseConnection,
+        task_id: Uuid,
+        data: TaskRecurrenceSetDto,
+    ) -> Result<task_recurrence::Model, AppError> {
+        let rule = RecurrenceRule::parse(&data.rule)
+            .map_err(|e| AppError::BadRequest(format!("Invalid recurrence rule: {}", e)))?;
+        let task = TaskQuery::get_task_by_id(db, task_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Task with id {} not found", task_id)))?;
+        ProjectService::ensure_writable(db, ProjectItem::Task(task_id)).await?;
+        let starts_on = data
+            .starts_on
+            .or(task.due_date)
+            .unwrap_or_else(|| Local::now().date_naive());
+        if rule.until.is_some_and(|until| until < starts_on) {
+            return Err(AppError::BadRequest(format!(
+                "UNTIL cannot be before the start date {}",
+                starts_on
+            )));
+        }
+        RM::set_recurrence(db, task_id, rule, starts_on).await
+    }
+
+    pub async fn delete_recurrence(
+        db: &DatabaseConnection,
+        task_id: Uuid,
+    ) -> Result<u64, AppError> {
+        ProjectService::ensure_writable(db, ProjectItem::Task(task_id)).await?;
+        RM::delete_recurrence(db, task_id).await
+    }
+
+    /// Tasks generated from a recurring task so far.
+    pub async fn get_occurrences(
+        db: &DatabaseConnection,
+        task_id: Uuid,
+    ) -> Result<Vec<TaskWithBlocked>, AppError> {
+        let recurrence = Self::get_recurrence(db, task_id).await?;
+        let tasks = RQ::get_occurrence_tasks(db, recurrence.id).await?;
+        TaskService::with_blocked(db, tasks).await
+    }
+
+    /// Generate every occurrence dated within `lookahead_days` of today.
+    /// A recurrence that fails is logged and retried on the next run
+    /// without holding up the others.
+    pub async fn generate_due_occurrences(
+        db: &DatabaseConnection,
+        lookahead_days: u32,
+    ) -> Result<u64, AppError> {
+        let today = Local::now().date_naive();
+        let horizon = today + Days::new(lookahead_days.into());
+        let ids = RQ::get_due_recurrence_ids(db, horizon).await.map_err(|e| {
+            AppError::InternalServerError(format!("Failed to get recurrences: {}", e))
+        })?;
+
+        let mut created = 0;
+        for id in ids {
+            match RM::generate_occurrences(db, id, today, horizon).await {
+                Ok(count) => created += count,
+                Err(e) => error!("Failed to generate occurrences of recurrence {}: {}", id, e),
+            }
+        }
+        Ok(created)
+    }
+}
@@ -0,0 +1,43 @@
+use sea_orm::DatabaseConnection;
+
+use crate::{
+    dtos::search_dto::SearchParams,
+    repository::search_repository::{SearchQuery, SearchRow},
+    utils::error::AppError,
+};
+
+const DEFAULT_LIMIT: u64 = 20;
+
+pub struct SearchService;
+
+impl SearchService {
+    pub async fn search(
+        db: &DatabaseConnection,
+        params: SearchParams,
+    ) -> Result<Vec<SearchRow>, AppError> {
+        let terms = normalize_terms(&params.q);
+        if terms.is_empty() {
+            return Err(AppError::BadRequest(
+                "Search query must contain at least one letter or digit".to_string(),
+            ));
+        }
+
+        SearchQuery::search(
+            db,
+            &terms,
+            params.project_id,
+            params.include_archived.unwrap_or(false),
+            params.limit.unwrap_or(DEFAULT_LIMIT),
+        )
+        .await
+    }
+}
+
+/// Lower-cased words of the query with punctuation stripped, so user input
+/// can never inject `tsquery` operators.
+fn normalize_terms(q: &str) -> Vec<String> {
+    q.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
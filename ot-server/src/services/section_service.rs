@@ -1,9 +1,13 @@
 use sea_orm::{prelude::Uuid, DatabaseConnection};
 
 use crate::{
-    dtos::section_dto::{SectionCreateDto, SectionUpdateDto},
+    dtos::section_dto::{SectionCreateDto, SectionMoveDto, SectionUpdateDto},
     entities::section,
-    repository::section_repository::{SectionMutation as SM, SectionQuery as SQ},
+    repository::{
+        project_repository::ProjectItem,
+        section_repository::{SectionMutation as SM, SectionQuery as SQ},
+    },
+    services::project_service::ProjectService,
     utils::error::AppError,
 };
 
@@ -32,6 +36,7 @@ impl SectionService {
         db: &DatabaseConnection,
         data: SectionCreateDto,
     ) -> Result<section::Model, AppError> {
+        ProjectService::ensure_writable(db, ProjectItem::Project(data.project_id)).await?;
         SM::create_section(db, data).await
     }
 
@@ -40,10 +45,22 @@ impl SectionService {
         id: Uuid,
         data: SectionUpdateDto,
     ) -> Result<section::Model, AppError> {
+        ProjectService::ensure_writable(db, ProjectItem::Section(id)).await?;
         SM::update_section(db, id, data).await
     }
 
+    pub async fn move_section(
+        db: &DatabaseConnection,
+        id: Uuid,
+        data: SectionMoveDto,
+    ) -> Result<section::Model, AppError> {
+        ProjectService::ensure_writable(db, ProjectItem::Section(id)).await?;
+        ProjectService::ensure_writable(db, ProjectItem::Project(data.project_id)).await?;
+        SM::move_section(db, id, data).await
+    }
+
     pub async fn delete_section(db: &DatabaseConnection, id: Uuid) -> Result<u64, AppError> {
+        ProjectService::ensure_writable(db, ProjectItem::Section(id)).await?;
         SM::delete_section(db, id).await
     }
 }
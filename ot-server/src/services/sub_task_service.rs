@@ -1,17 +1,23 @@
 use sea_orm::prelude::Uuid;
 use sea_orm::{DatabaseConnection, DeleteResult};
 
-use crate::dtos::sub_task_dto::{SubTaskCreateDto, SubTaskUpdateDto};
+use crate::dtos::sub_task_dto::{SubTaskCreateDto, SubTaskMoveDto, SubTaskUpdateDto};
+use crate::dtos::task_dto::TaskFilterParams;
 use crate::entities::{prelude::*, sub_task};
 
+use crate::repository::project_repository::ProjectItem;
 use crate::repository::sub_task_repository::{SubTaskMutation as STM, SubTaskQuery as STQ};
+use crate::services::project_service::ProjectService;
 use crate::utils::error::AppError;
 
 pub struct SubTaskService;
 
 impl SubTaskService {
-    pub async fn get_sub_tasks(db: &DatabaseConnection) -> Result<Vec<sub_task::Model>, AppError> {
-        STQ::get_sub_tasks(db).await
+    pub async fn get_sub_tasks(
+        db: &DatabaseConnection,
+        filter: TaskFilterParams,
+    ) -> Result<Vec<sub_task::Model>, AppError> {
+        STQ::get_sub_tasks(db, filter).await
     }
 
     pub async fn get_sub_task_by_id(
@@ -24,8 +30,9 @@ impl SubTaskService {
     pub async fn get_sub_tasks_by_section_id(
         db: &DatabaseConnection,
         section_id: Uuid,
+        filter: TaskFilterParams,
     ) -> Result<Vec<sub_task::Model>, AppError> {
-        STQ::get_sub_tasks_by_section_id(db, section_id).await
+        STQ::get_sub_tasks_by_section_id(db, section_id, filter).await
     }
 
     pub async fn get_sub_tasks_by_task_id(
@@ -41,6 +48,7 @@ impl SubTaskService {
         section_id: Uuid,
         task_id: Option<Uuid>,
     ) -> Result<sub_task::Model, AppError> {
+        ProjectService::ensure_writable(db, ProjectItem::Section(section_id)).await?;
         STM::create_sub_task_for_task_and_section(db, data, section_id, task_id).await
     }
 
@@ -49,13 +57,26 @@ impl SubTaskService {
         id: Uuid,
         data: SubTaskUpdateDto,
     ) -> Result<sub_task::Model, AppError> {
+        ProjectService::ensure_writable(db, ProjectItem::SubTask(id)).await?;
+        ProjectService::ensure_writable(db, ProjectItem::Section(data.section_id)).await?;
         STM::update_sub_task(db, id, data).await
     }
 
+    pub async fn move_sub_task(
+        db: &DatabaseConnection,
+        id: Uuid,
+        data: SubTaskMoveDto,
+    ) -> Result<sub_task::Model, AppError> {
+        ProjectService::ensure_writable(db, ProjectItem::SubTask(id)).await?;
+        ProjectService::ensure_writable(db, ProjectItem::Section(data.section_id)).await?;
+        STM::move_sub_task(db, id, data).await
+    }
+
     pub async fn delete_sub_task(
         db: &DatabaseConnection,
         id: Uuid,
     ) -> Result<DeleteResult, AppError> {
+        ProjectService::ensure_writable(db, ProjectItem::SubTask(id)).await?;
         STM::delete_sub_task(db, id).await
     }
 }
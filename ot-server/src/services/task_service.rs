@@ -1,38 +1,56 @@
 use sea_orm::prelude::Uuid;
 use sea_orm::{DatabaseConnection, DeleteResult};
 
-use crate::dtos::task_dto::{TaskCreateDto, TaskUpdateDto};
+use crate::dtos::task_dto::{TaskCreateDto, TaskFilterParams, TaskMoveDto, TaskUpdateDto};
 use crate::entities::{prelude::*, task};
 
+use crate::repository::project_repository::ProjectItem;
+use crate::repository::task_dependency_repository::TaskDependencyQuery;
 use crate::repository::task_repository::{TaskMutation as TM, TaskQuery as TQ};
+use crate::services::budget_service::BudgetService;
+use crate::services::project_service::ProjectService;
 use crate::utils::error::AppError;
 
+/// A task and whether a task blocking it is not done yet.
+pub type TaskWithBlocked = (task::Model, bool);
+
 pub struct TaskService;
 
 impl TaskService {
-    pub async fn get_tasks(db: &DatabaseConnection) -> Result<Vec<task::Model>, AppError> {
-        TQ::get_tasks(db).await
+    pub async fn get_tasks(
+        db: &DatabaseConnection,
+        filter: TaskFilterParams,
+    ) -> Result<Vec<TaskWithBlocked>, AppError> {
+        let tasks = TQ::get_tasks(db, filter).await?;
+        Self::with_blocked(db, tasks).await
     }
 
     pub async fn get_task_by_id(
         db: &DatabaseConnection,
         id: Uuid,
-    ) -> Result<Option<task::Model>, AppError> {
-        TQ::get_task_by_id(db, id).await
+    ) -> Result<Option<TaskWithBlocked>, AppError> {
+        match TQ::get_task_by_id(db, id).await? {
+            Some(task) => Self::with_blocked_one(db, task).await.map(Some),
+            None => Ok(None),
+        }
     }
 
     pub async fn get_tasks_by_section_id(
         db: &DatabaseConnection,
         section_id: Uuid,
-    ) -> Result<Vec<task::Model>, AppError> {
-        TQ::get_tasks_by_section_id(db, section_id).await
+        filter: TaskFilterParams,
+    ) -> Result<Vec<TaskWithBlocked>, AppError> {
+        let tasks = TQ::get_tasks_by_section_id(db, section_id, filter).await?;
+        Self::with_blocked(db, tasks).await
     }
 
+    /// New tasks have no dependencies, so they are never blocked.
     pub async fn create_task(
         db: &DatabaseConnection,
         data: TaskCreateDto,
         section_id: Uuid,
     ) -> Result<task::Model, AppError> {
+        ProjectService::ensure_writable(db, ProjectItem::Section(section_id)).await?;
         TM::create_task(db, data, section_id).await
     }
 
@@ -40,11 +58,54 @@ impl TaskService {
         db: &DatabaseConnection,
         id: Uuid,
         data: TaskUpdateDto,
-    ) -> Result<task::Model, AppError> {
-        TM::update_task(db, id, data).await
+    ) -> Result<TaskWithBlocked, AppError> {
+        let current = TQ::get_task_by_id(db, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Task with id {} not found", id)))?;
+        ProjectService::ensure_writable(db, ProjectItem::Task(id)).await?;
+        ProjectService::ensure_writable(db, ProjectItem::Section(data.section_id)).await?;
+        let task = TM::update_task(db, id, data).await?;
+        BudgetService::estimate_changed(db, &current, &task).await?;
+        Self::with_blocked_one(db, task).await
+    }
+
+    pub async fn move_task(
+        db: &DatabaseConnection,
+        id: Uuid,
+        data: TaskMoveDto,
+    ) -> Result<TaskWithBlocked, AppError> {
+        ProjectService::ensure_writable(db, ProjectItem::Task(id)).await?;
+        ProjectService::ensure_writable(db, ProjectItem::Section(data.section_id)).await?;
+        let task = TM::move_task(db, id, data).await?;
+        Self::with_blocked_one(db, task).await
     }
 
     pub async fn delete_task(db: &DatabaseConnection, id: Uuid) -> Result<DeleteResult, AppError> {
+        ProjectService::ensure_writable(db, ProjectItem::Task(id)).await?;
         TM::delete_task(db, id).await
     }
+
+    /// Pair each task with whether it is blocked.
+    pub async fn with_blocked(
+        db: &DatabaseConnection,
+        tasks: Vec<task::Model>,
+    ) -> Result<Vec<TaskWithBlocked>, AppError> {
+        let ids = tasks.iter().map(|task| task.id).collect();
+        let blocked = TaskDependencyQuery::get_blocked_ids(db, ids).await?;
+        Ok(tasks
+            .into_iter()
+            .map(|task| {
+                let is_blocked = blocked.contains(&task.id);
+                (task, is_blocked)
+            })
+            .collect())
+    }
+
+    async fn with_blocked_one(
+        db: &DatabaseConnection,
+        task: task::Model,
+    ) -> Result<TaskWithBlocked, AppError> {
+        let mut tasks = Self::with_blocked(db, vec![task]).await?;
+        Ok(tasks.remove(0))
+    }
 }
@@ -0,0 +1,173 @@
+use sea_orm::{prelude::Uuid, DatabaseConnection};
+
+use crate::{
+    dtos::{
+        activity_dto::{ActivityDataCreateDto, WindowActivityDataCreateDto},
+        timer_dto::TimerSessionCreateDto,
+    },
+    entities::{project, sub_task, task, timer_session},
+    repository::{
+        activity_repository::ActivityMutation,
+        project_repository::{ProjectItem, ProjectQuery},
+        section_repository::SectionQuery,
+        sub_task_repository::SubTaskQuery,
+        task_repository::TaskQuery,
+        timer_session_repository::{TimerSessionMutation, TimerSessionQuery},
+    },
+    services::{project_service::ProjectService, task_service::TaskService},
+    utils::error::AppError,
+};
+
+/// A task a timer can run on, with what the desktop shows about it.
+#[derive(Debug, Clone)]
+pub struct TimerTarget {
+    pub task: task::Model,
+    pub sub_task: Option<sub_task::Model>,
+    pub project: project::Model,
+    pub blocked: bool,
+}
+
+pub struct TimerService;
+
+impl TimerService {
+    /// Check a timer can be started on `task_id`, or on `sub_task_id`
+    /// within it: both exist and the project is not archived.
+    pub async fn check_target(
+        db: &DatabaseConnection,
+        task_id: Uuid,
+        sub_task_id: Option<Uuid>,
+    ) -> Result<TimerTarget, AppError> {
+        let (task, blocked) = TaskService::get_task_by_id(db, task_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Task with id {} not found", task_id)))?;
+        let sub_task = match sub_task_id {
+            Some(id) => {
+                let sub_task =
+                    SubTaskQuery::get_sub_task_by_id(db, id)
+                        .await?
+                        .ok_or_else(|| {
+                            AppError::NotFound(format!("Sub task with id {} not found", id))
+                        })?;
+                if sub_task.task_id != Some(task_id) {
+                    return Err(AppError::BadRequest(format!(
+                        "Sub task {} does not belong to task {}",
+                        id, task_id
+                    )));
+                }
+                Some(sub_task)
+            }
+            None => None,
+        };
+        ProjectService::ensure_writable(db, ProjectItem::Task(task_id)).await?;
+
+        let section = SectionQuery::get_section_by_id(db, task.section_id)
+            .await?
+            .ok_or_else(|| {
+                AppError::NotFound(format!("Section with id {} not found", task.section_id))
+            })?;
+        let project = ProjectQuery::get_project_by_id(db, section.project_id)
+            .await?
+            .ok_or_else(|| {
+                AppError::NotFound(format!("Project with id {} not found", section.project_id))
+            })?;
+        Ok(TimerTarget {
+            task,
+            sub_task,
+            project,
+            blocked,
+        })
+    }
+
+    /// Store a session uploaded by the desktop; returns it and whether it
+    /// was new. The session may have been tracked offline a while ago, so
+    /// a task or sub task deleted or moved since is dropped from it rather
+    /// than the time being rejected.
+    pub async fn upload_session(
+        db: &DatabaseConnection,
+        mut data: TimerSessionCreateDto,
+    ) -> Result<(timer_session::Model, bool), AppError> {
+        if data.end_time < data.start_time {
+            return Err(AppError::BadRequest(
+                "end_time cannot be before start_time".to_string(),
+            ));
+        }
+        data.task_id = Self::existing_task_id(db, data.task_id).await?;
+        data.sub_task_id = match data.sub_task_id {
+            Some(id) => SubTaskQuery::get_sub_task_by_id(db, id)
+                .await?
+                .filter(|sub_task| data.task_id.is_some() && sub_task.task_id == data.task_id)
+                .map(|sub_task| sub_task.id),
+            None => None,
+        };
+        let id = data.id;
+        let created = TimerSessionMutation::create_timer_session(db, data).await?;
+        let session = TimerSessionQuery::get_timer_session_by_id(db, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Timer session {} not found", id)))?;
+        Ok((session, created))
+    }
+
+    pub async fn get_sessions_by_task_id(
+        db: &DatabaseConnection,
+        task_id: Uuid,
+    ) -> Result<Vec<timer_session::Model>, AppError> {
+        TimerSessionQuery::get_timer_sessions_by_task_id(db, task_id).await
+    }
+
+    /// Store an activity sample uploaded by the desktop; returns whether it
+    /// was new. Like sessions, samples are keyed by their client id and keep
+    /// their time when the task is gone.
+    pub async fn upload_activity(
+        db: &DatabaseConnection,
+        mut data: ActivityDataCreateDto,
+    ) -> Result<bool, AppError> {
+        if data.id.is_none() {
+            return Err(AppError::BadRequest(
+                "Uploaded activity needs an id".to_string(),
+            ));
+        }
+        if data.end_time < data.start_time {
+            return Err(AppError::BadRequest(
+                "end_time cannot be before start_time".to_string(),
+            ));
+        }
+        data.task_id = Self::existing_task_id(db, data.task_id).await?;
+        ActivityMutation::upload_activity_data(db, data).await
+    }
+
+    /// Store the windows the desktop saw in focus over an interval; the
+    /// task is cleared if it no longer exists.
+    pub async fn upload_window_activity(
+        db: &DatabaseConnection,
+        mut data: WindowActivityDataCreateDto,
+    ) -> Result<bool, AppError> {
+        let activity = &data.active_window_data;
+        if activity.end_time < activity.start_time {
+            return Err(AppError::BadRequest(
+                "end_time cannot be before start_time".to_string(),
+            ));
+        }
+        if activity.windows.iter().any(|window| {
+            window.start_time < activity.start_time
+                || window.end_time > activity.end_time
+                || window.end_time < window.start_time
+        }) {
+            return Err(AppError::BadRequest(
+                "Windows must be in focus within the interval".to_string(),
+            ));
+        }
+        data.active_window_data.task_id =
+            Self::existing_task_id(db, data.active_window_data.task_id).await?;
+        ActivityMutation::upload_window_activity_data(db, data).await
+    }
+
+    async fn existing_task_id(
+        db: &DatabaseConnection,
+        task_id: Option<Uuid>,
+    ) -> Result<Option<Uuid>, AppError> {
+        match task_id {
+            Some(id) => Ok(TaskQuery::get_task_by_id(db, id).await?.map(|task| task.id)),
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use sea_orm::{
+    prelude::{DateTimeWithTimeZone, Uuid},
+    DatabaseConnection,
+};
+
+use crate::{
+    dtos::tracking_policy_dto::{setting_name, TrackingPolicyUpdateDto},
+    entities::tracking_policy,
+    repository::{
+        project_repository::ProjectQuery,
+        tracking_policy_repository::{TrackingPolicyMutation as TPM, TrackingPolicyQuery as TPQ},
+    },
+    utils::{
+        error::AppError,
+        tracking_policy::{effective_settings, PolicySettings},
+    },
+};
+
+/// A stored policy with its settings read back.
+#[derive(Debug, Clone)]
+pub struct TrackingPolicy {
+    pub id: Uuid,
+    pub project_id: Option<Uuid>,
+    pub policy: PolicySettings,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+/// The policies that apply to time tracked on a project, combined.
+#[derive(Debug, Clone)]
+pub struct EffectivePolicy {
+    pub project_id: Option<Uuid>,
+    pub policy: PolicySettings,
+    pub updated_at: Option<DateTimeWithTimeZone>,
+}
+
+pub struct TrackingPolicyService;
+
+impl TrackingPolicyService {
+    /// The policy of a project, or the global one for `None`.
+    pub async fn get_policy(
+        db: &DatabaseConnection,
+        project_id: Option<Uuid>,
+    ) -> Result<Option<TrackingPolicy>, AppError> {
+        if let Some(project_id) = project_id {
+            ensure_project(db, project_id).await?;
+        }
+        TPQ::get_policy(db, project_id)
+            .await?
+            .map(read_policy)
+            .transpose()
+    }
+
+    /// Replace the policy of a project, or the global one for `None`.
+    pub async fn save_policy(
+        db: &DatabaseConnection,
+        project_id: Option<Uuid>,
+        data: TrackingPolicyUpdateDto,
+    ) -> Result<TrackingPolicy, AppError> {
+        if let Some(project_id) = project_id {
+            ensure_project(db, project_id).await?;
+        }
+        // A locked setting without a value would leave it up to each
+        // desktop while keeping employees from changing it
+        if let Some(&setting) = data
+            .locked
+            .iter()
+            .find(|setting| !data.settings.contains_key(setting))
+        {
+            return Err(AppError::BadRequest(format!(
+                "Locked setting {} needs a value",
+                setting_name(setting)
+            )));
+        }
+        let mut locked = data.locked;
+        locked.sort();
+        locked.dedup();
+
+        let settings = serde_json::to_value(&data.settings)
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+        let locked = serde_json::to_value(&locked)
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+        read_policy(TPM::save_policy(db, project_id, settings, locked).await?)
+    }
+
+    pub async fn delete_policy(
+        db: &DatabaseConnection,
+        project_id: Option<Uuid>,
+    ) -> Result<u64, AppError> {
+        TPM::delete_policy(db, project_id).await
+    }
+
+    /// What applies to time tracked on a project: its policy over the
+    /// global one. Only the global policy applies for `None`.
+    pub async fn get_effective_policy(
+        db: &DatabaseConnection,
+        project_id: Option<Uuid>,
+    ) -> Result<EffectivePolicy, AppError> {
+        let global = Self::get_policy(db, None).await?;
+        let project = match project_id {
+            Some(project_id) => Self::get_policy(db, Some(project_id)).await?,
+            None => None,
+        };
+        let updated_at = global
+            .iter()
+            .chain(project.iter())
+            .map(|policy| policy.updated_at)
+            .max();
+        let policy = effective_settings(
+            global.map(|global| global.policy).unwrap_or_default(),
+            project.map(|project| project.policy).unwrap_or_default(),
+        );
+        Ok(EffectivePolicy {
+            project_id,
+            policy,
+            updated_at,
+        })
+    }
+}
+
+async fn ensure_project(db: &DatabaseConnection, id: Uuid) -> Result<(), AppError> {
+    match ProjectQuery::get_project_by_id(db, id).await? {
+        Some(_) => Ok(()),
+        None => Err(AppError::NotFound(format!(
+            "Project with id {} not found",
+            id
+        ))),
+    }
+}
+
+fn read_policy(model: tracking_policy::Model) -> Result<TrackingPolicy, AppError> {
+    let invalid = |e: serde_json::Error| {
+        AppError::InternalServerError(format!("Invalid policy {}: {}", model.id, e))
+    };
+    let settings = serde_json::from_value(model.settings.clone()).map_err(invalid)?;
+    let locked = serde_json::from_value(model.locked.clone()).map_err(invalid)?;
+    Ok(TrackingPolicy {
+        id: model.id,
+        project_id: model.project_id,
+        policy: PolicySettings { settings, locked },
+        updated_at: model.updated_at,
+    })
+}
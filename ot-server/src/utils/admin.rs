@@ -0,0 +1,31 @@
+//! Gate for administrative requests.
+
+use axum::http::{header::AUTHORIZATION, HeaderMap};
+
+use crate::{config::settings::ServerSettings, utils::error::AppError};
+
+/// Require the configured admin token as a bearer token. Requests are
+/// refused outright when no token is configured.
+pub fn require_admin(headers: &HeaderMap, server: &ServerSettings) -> Result<(), AppError> {
+    let Some(expected) = &server.admin_token else {
+        return Err(AppError::Forbidden(
+            "No admin token is configured on the server".to_string(),
+        ));
+    };
+    let given = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim);
+    match given {
+        Some(token) if tokens_match(token.as_bytes(), expected.as_bytes()) => Ok(()),
+        _ => Err(AppError::Forbidden(
+            "A valid admin token is required".to_string(),
+        )),
+    }
+}
+
+/// Compare without leaking where the tokens differ.
+fn tokens_match(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
@@ -0,0 +1,101 @@
+//! Which billing rate applies to tracked time, and what the time comes to.
+
+use sea_orm::prelude::Uuid;
+
+use crate::entities::billing_rate;
+
+/// The most specific of `rates` for time tracked by `person` on a project of
+/// `client_id`: a rate for the person on the project, then one for the
+/// project, then one for the client, then the default rate.
+pub fn applicable_rate<'a>(
+    rates: &'a [billing_rate::Model],
+    client_id: Option<Uuid>,
+    project_id: Uuid,
+    person: Option<&str>,
+) -> Option<&'a billing_rate::Model> {
+    rates
+        .iter()
+        .filter_map(|rate| {
+            let rank = match (rate.client_id, rate.project_id, rate.person.as_deref()) {
+                (None, Some(project), Some(who))
+                    if project == project_id && person == Some(who) =>
+                {
+                    0
+                }
+                (None, Some(project), None) if project == project_id => 1,
+                (Some(client), None, None) if client_id == Some(client) => 2,
+                (None, None, None) => 3,
+                _ => return None,
+            };
+            Some((rank, rate))
+        })
+        .min_by_key(|(rank, _)| *rank)
+        .map(|(_, rate)| rate)
+}
+
+/// Amount for `seconds` at `hourly_rate`, both amounts in the currency's
+/// minor unit, rounded to the nearest unit.
+pub fn billable_amount(seconds: i64, hourly_rate: i64) -> i64 {
+    let amount = (i128::from(seconds) * i128::from(hourly_rate) + 1800).div_euclid(3600);
+    amount as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use sea_orm::prelude::Uuid;
+
+    use super::{applicable_rate, billable_amount};
+    use crate::entities::billing_rate;
+
+    fn rate(
+        client_id: Option<Uuid>,
+        project_id: Option<Uuid>,
+        person: Option<&str>,
+        hourly_rate: i64,
+    ) -> billing_rate::Model {
+        billing_rate::Model {
+            id: Uuid::nil(),
+            client_id,
+            project_id,
+            person: person.map(str::to_string),
+            hourly_rate,
+            currency: "EUR".to_string(),
+            created_at: Utc::now().fixed_offset(),
+            updated_at: Utc::now().fixed_offset(),
+        }
+    }
+
+    #[test]
+    fn most_specific_rate_wins() {
+        let (client, project, other_project) =
+            (Uuid::from_u128(1), Uuid::from_u128(2), Uuid::from_u128(3));
+        let mut rates = vec![
+            rate(None, None, None, 1000),
+            rate(Some(client), None, None, 2000),
+            rate(None, Some(project), None, 3000),
+            rate(None, Some(project), Some("sam"), 4000),
+            rate(None, Some(other_project), Some("kim"), 5000),
+        ];
+        let hourly = |rates: &[billing_rate::Model], person| {
+            applicable_rate(rates, Some(client), project, person).map(|rate| rate.hourly_rate)
+        };
+
+        assert_eq!(hourly(&rates, Some("sam")), Some(4000));
+        assert_eq!(hourly(&rates, Some("kim")), Some(3000));
+        rates.remove(2);
+        assert_eq!(hourly(&rates, None), Some(2000));
+        rates.remove(1);
+        assert_eq!(hourly(&rates, Some("kim")), Some(1000));
+        rates.remove(0);
+        assert_eq!(hourly(&rates, None), None);
+    }
+
+    #[test]
+    fn amounts_round_to_the_nearest_unit() {
+        assert_eq!(billable_amount(3600, 12000), 12000);
+        assert_eq!(billable_amount(600, 10000), 1667);
+        assert_eq!(billable_amount(1, 1799), 0);
+        assert_eq!(billable_amount(0, 12000), 0);
+    }
+}
@@ -0,0 +1,121 @@
+//! How far along a budget is, and when it runs out at the recent pace.
+
+use chrono::{Days, NaiveDate};
+
+/// Use of a budget, in whatever unit the budget is in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Progress {
+    pub budget: i64,
+    pub used: i64,
+    pub remaining: i64,
+    pub percent_used: f64,
+    pub burn_rate_per_day: f64,
+    pub projected_overrun_on: Option<NaiveDate>,
+}
+
+/// Progress of `budget` with `used` spent overall, of which `recent` was
+/// spent over the last `window_days` days up to `today`. The overrun date
+/// assumes the budget keeps being used at the recent pace.
+pub fn progress(
+    budget: i64,
+    used: i64,
+    recent: i64,
+    window_days: u32,
+    today: NaiveDate,
+) -> Progress {
+    let remaining = budget - used;
+    let burn_rate_per_day = recent as f64 / f64::from(window_days.max(1));
+    let projected_overrun_on = if remaining > 0 && burn_rate_per_day > 0.0 {
+        let days = (remaining as f64 / burn_rate_per_day).ceil();
+        // Far enough out to not matter; also keeps the cast in range
+        (days < 100_000.0)
+            .then(|| today.checked_add_days(Days::new(days as u64)))
+            .flatten()
+    } else {
+        None
+    };
+    Progress {
+        budget,
+        used,
+        remaining,
+        percent_used: percent(used, budget),
+        burn_rate_per_day,
+        projected_overrun_on,
+    }
+}
+
+/// `used` as a percentage of `budget`, to two decimals.
+pub fn percent(used: i64, budget: i64) -> f64 {
+    if budget <= 0 {
+        return 0.0;
+    }
+    (used as f64 * 10_000.0 / budget as f64).round() / 100.0
+}
+
+/// The `thresholds`, in percent, that `used` has reached.
+pub fn reached_thresholds(used: i64, budget: i64, thresholds: &[u32]) -> Vec<u32> {
+    if budget <= 0 {
+        return Vec::new();
+    }
+    let mut reached = thresholds
+        .iter()
+        .copied()
+        .filter(|&threshold| i128::from(used) * 100 >= i128::from(budget) * i128::from(threshold))
+        .collect::<Vec<_>>();
+    reached.sort_unstable();
+    reached.dedup();
+    reached
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::{percent, progress, reached_thresholds};
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 3, d).unwrap()
+    }
+
+    #[test]
+    fn projects_overrun_at_recent_pace() {
+        // 40h of 100h used, 14h over the last week: 2h a day, 30 days left
+        let hour = 3600;
+        let p = progress(100 * hour, 40 * hour, 14 * hour, 7, day(1));
+        assert_eq!(p.remaining, 60 * hour);
+        assert_eq!(p.percent_used, 40.0);
+        assert_eq!(p.burn_rate_per_day, 2.0 * hour as f64);
+        assert_eq!(p.projected_overrun_on, Some(day(31)));
+
+        // A partial day left still counts as a day
+        let p = progress(100, 95, 14, 7, day(1));
+        assert_eq!(p.projected_overrun_on, Some(day(4)));
+    }
+
+    #[test]
+    fn no_projection_when_idle_or_overrun() {
+        let p = progress(100, 40, 0, 14, day(1));
+        assert_eq!(p.burn_rate_per_day, 0.0);
+        assert_eq!(p.projected_overrun_on, None);
+
+        let p = progress(100, 130, 50, 14, day(1));
+        assert_eq!(p.remaining, -30);
+        assert_eq!(p.percent_used, 130.0);
+        assert_eq!(p.projected_overrun_on, None);
+
+        let p = progress(i64::MAX, 1, 1, 366, day(1));
+        assert_eq!(p.projected_overrun_on, None);
+    }
+
+    #[test]
+    fn thresholds_reached() {
+        let thresholds = [100, 80, 80, 50];
+        assert!(reached_thresholds(49, 100, &thresholds).is_empty());
+        assert_eq!(reached_thresholds(80, 100, &thresholds), vec![50, 80]);
+        assert_eq!(reached_thresholds(250, 100, &thresholds), vec![50, 80, 100]);
+        // 79.9% is not 80%
+        assert_eq!(reached_thresholds(799, 1000, &[80]), Vec::<u32>::new());
+        assert!(reached_thresholds(10, 0, &thresholds).is_empty());
+        assert_eq!(percent(1, 3), 33.33);
+    }
+}
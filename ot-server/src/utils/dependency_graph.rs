@@ -0,0 +1,47 @@
+//! Cycle detection for task dependencies.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// Whether adding the edge `blocking -> blocked` to `edges`, given as
+/// `(blocking, blocked)` pairs, would close a cycle: that is, whether
+/// `blocked` already blocks `blocking`, directly or through other tasks.
+pub fn creates_cycle<T: Copy + Eq + Hash>(edges: &[(T, T)], blocking: T, blocked: T) -> bool {
+    if blocking == blocked {
+        return true;
+    }
+    let mut next: HashMap<T, Vec<T>> = HashMap::new();
+    for &(from, to) in edges {
+        next.entry(from).or_default().push(to);
+    }
+
+    let mut seen = HashSet::from([blocked]);
+    let mut stack = vec![blocked];
+    while let Some(task) = stack.pop() {
+        for &following in next.get(&task).into_iter().flatten() {
+            if following == blocking {
+                return true;
+            }
+            if seen.insert(following) {
+                stack.push(following);
+            }
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::creates_cycle;
+
+    #[test]
+    fn detects_direct_and_transitive_cycles() {
+        let edges = [(1, 2), (2, 3), (3, 4), (5, 4)];
+        assert!(creates_cycle(&edges, 2, 1));
+        assert!(creates_cycle(&edges, 4, 1));
+        assert!(creates_cycle(&edges, 1, 1));
+        assert!(!creates_cycle(&edges, 1, 4));
+        assert!(creates_cycle(&edges, 4, 5));
+        assert!(!creates_cycle(&edges, 1, 5));
+    }
+}
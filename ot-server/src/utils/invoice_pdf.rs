@@ -0,0 +1,401 @@
+//! Render an invoice as a plain A4 PDF.
+//!
+//! Only the standard Helvetica and Courier fonts are used, so nothing needs
+//! to be embedded; text outside the Windows-1252 character set they are
+//! encoded with is printed as `?`. Amounts are printed with two decimals.
+
+use chrono::Days;
+use pdf_writer::{Content, Name, Pdf, Rect, Ref, Str, TextStr};
+
+use crate::{
+    dtos::invoice_dto::InvoiceStatus,
+    entities::{client, invoice, invoice_line},
+};
+
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 50.0;
+const ROW_HEIGHT: f32 = 16.0;
+/// Rows stop here, leaving room for the page footer.
+const BOTTOM: f32 = MARGIN + 30.0;
+
+const REGULAR: Name = Name(b"F1");
+const BOLD: Name = Name(b"F2");
+const MONO: Name = Name(b"F3");
+
+/// Right edges of the numeric columns and the widest description that fits.
+const HOURS_RIGHT: f32 = 365.0;
+const RATE_RIGHT: f32 = 455.0;
+const AMOUNT_RIGHT: f32 = PAGE_WIDTH - MARGIN;
+const DESCRIPTION_CHARS: usize = 48;
+
+/// `minor` units of a currency as a decimal amount, e.g. `1,234.50`.
+pub fn format_amount(minor: i64) -> String {
+    let sign = if minor < 0 { "-" } else { "" };
+    let minor = minor.unsigned_abs();
+    let units = (minor / 100).to_string();
+    let mut grouped = String::with_capacity(units.len() + units.len() / 3);
+    for (i, digit) in units.chars().enumerate() {
+        if i > 0 && (units.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    format!("{}{}.{:02}", sign, grouped, minor % 100)
+}
+
+/// `seconds` as hours with two decimals, rounded to the nearest hundredth.
+pub fn format_hours(seconds: i64) -> String {
+    let hundredths = (seconds + 18) / 36;
+    format!("{}.{:02}", hundredths / 100, hundredths % 100)
+}
+
+pub fn render_invoice(
+    invoice: &invoice::Model,
+    lines: &[invoice_line::Model],
+    client: &client::Model,
+    issuer: &[String],
+) -> Vec<u8> {
+    let title = match (invoice.status, invoice.number) {
+        (InvoiceStatus::Finalized, Some(number)) => format!("Invoice {}", number),
+        _ => "Draft invoice".to_string(),
+    };
+
+    let mut pages = vec![Content::new()];
+    let mut y = PAGE_HEIGHT - MARGIN;
+    let page = pages.last_mut().unwrap();
+    text(page, BOLD, 20.0, MARGIN, y - 20.0, &title);
+    let mut right_y = y - 12.0;
+    for line in issuer {
+        text_right(page, REGULAR, 10.0, AMOUNT_RIGHT, right_y, line);
+        right_y -= 13.0;
+    }
+    y -= 56.0;
+
+    // The period ends before `period_end`, so its last day is the one before
+    let last_day = invoice
+        .period_end
+        .date_naive()
+        .checked_sub_days(Days::new(1))
+        .unwrap_or(invoice.period_end.date_naive());
+    let mut details = vec![
+        (
+            "Period",
+            format!("{} to {}", invoice.period_start.date_naive(), last_day),
+        ),
+        ("Currency", invoice.currency.clone()),
+    ];
+    if let Some(issued_on) = invoice.issued_on {
+        details.insert(0, ("Issued", issued_on.to_string()));
+    }
+    if let Some(due_on) = invoice.due_on {
+        details.push(("Due", due_on.to_string()));
+    }
+    let mut bill_to = vec![client.name.clone()];
+    bill_to.extend(client.email.clone());
+
+    text(page, BOLD, 10.0, MARGIN, y, "Bill to");
+    text(page, BOLD, 10.0, 320.0, y, "Details");
+    for (i, name) in bill_to.iter().enumerate() {
+        text(page, REGULAR, 10.0, MARGIN, y - 14.0 * (i + 1) as f32, name);
+    }
+    for (i, (label, value)) in details.iter().enumerate() {
+        let row_y = y - 14.0 * (i + 1) as f32;
+        text(page, REGULAR, 10.0, 320.0, row_y, label);
+        text(page, REGULAR, 10.0, 390.0, row_y, value);
+    }
+    y -= 14.0 * (bill_to.len().max(details.len()) + 2) as f32;
+    y = y.min(right_y - ROW_HEIGHT);
+
+    table_header(page, y, &invoice.currency);
+    y -= ROW_HEIGHT + 4.0;
+    for line in lines {
+        if y < BOTTOM {
+            pages.push(Content::new());
+            y = PAGE_HEIGHT - MARGIN - 10.0;
+            table_header(pages.last_mut().unwrap(), y, &invoice.currency);
+            y -= ROW_HEIGHT + 4.0;
+        }
+        let page = pages.last_mut().unwrap();
+        text(page, REGULAR, 10.0, MARGIN, y, &truncate(&line.description));
+        text_right(
+            page,
+            MONO,
+            10.0,
+            HOURS_RIGHT,
+            y,
+            &format_hours(line.seconds),
+        );
+        text_right(
+            page,
+            MONO,
+            10.0,
+            RATE_RIGHT,
+            y,
+            &format_amount(line.hourly_rate),
+        );
+        text_right(
+            page,
+            MONO,
+            10.0,
+            AMOUNT_RIGHT,
+            y,
+            &format_amount(line.amount),
+        );
+        y -= ROW_HEIGHT;
+    }
+
+    if y - ROW_HEIGHT < BOTTOM {
+        pages.push(Content::new());
+        y = PAGE_HEIGHT - MARGIN - 10.0;
+    }
+    let page = pages.last_mut().unwrap();
+    rule(page, y + ROW_HEIGHT - 4.0);
+    let total: i64 = lines.iter().map(|line| line.amount).sum();
+    text(page, BOLD, 11.0, MARGIN, y - 4.0, "Total");
+    text_right(
+        page,
+        BOLD,
+        11.0,
+        AMOUNT_RIGHT,
+        y - 4.0,
+        &format!("{} {}", format_amount(total), invoice.currency),
+    );
+    y -= ROW_HEIGHT * 2.0 + 14.0;
+
+    if let Some(notes) = &invoice.notes {
+        text(pages.last_mut().unwrap(), BOLD, 10.0, MARGIN, y, "Notes");
+        for line in wrap(notes, 95) {
+            y -= 14.0;
+            if y < BOTTOM {
+                pages.push(Content::new());
+                y = PAGE_HEIGHT - MARGIN - 10.0;
+            }
+            text(pages.last_mut().unwrap(), REGULAR, 10.0, MARGIN, y, &line);
+        }
+    }
+
+    let count = pages.len();
+    for (i, page) in pages.iter_mut().enumerate() {
+        let footer = format!("{} - page {} of {}", title, i + 1, count);
+        text_right(page, REGULAR, 8.0, AMOUNT_RIGHT, MARGIN, &footer);
+    }
+    write_document(&title, pages)
+}
+
+fn write_document(title: &str, pages: Vec<Content>) -> Vec<u8> {
+    let catalog_id = Ref::new(1);
+    let page_tree_id = Ref::new(2);
+    let info_id = Ref::new(3);
+    let font_ids = [Ref::new(4), Ref::new(5), Ref::new(6)];
+    let page_ids = (0..pages.len())
+        .map(|i| Ref::new(7 + 2 * i as i32))
+        .collect::<Vec<_>>();
+
+    let mut pdf = Pdf::new();
+    pdf.catalog(catalog_id).pages(page_tree_id);
+    pdf.pages(page_tree_id)
+        .kids(page_ids.iter().copied())
+        .count(page_ids.len() as i32);
+    pdf.document_info(info_id).title(TextStr(title));
+    for (id, base_font) in font_ids.into_iter().zip([
+        Name(b"Helvetica"),
+        Name(b"Helvetica-Bold"),
+        Name(b"Courier"),
+    ]) {
+        pdf.type1_font(id)
+            .base_font(base_font)
+            .encoding_predefined(Name(b"WinAnsiEncoding"));
+    }
+
+    for (page, page_id) in pages.into_iter().zip(page_ids) {
+        let content_id = Ref::new(page_id.get() + 1);
+        {
+            let mut page_writer = pdf.page(page_id);
+            page_writer
+                .media_box(Rect::new(0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT))
+                .parent(page_tree_id)
+                .contents(content_id);
+            let mut resources = page_writer.resources();
+            let mut fonts = resources.fonts();
+            for (name, id) in [REGULAR, BOLD, MONO].into_iter().zip(font_ids) {
+                fonts.pair(name, id);
+            }
+        }
+        pdf.stream(content_id, &page.finish());
+    }
+    pdf.finish()
+}
+
+fn table_header(page: &mut Content, y: f32, currency: &str) {
+    text(page, BOLD, 10.0, MARGIN, y, "Description");
+    text_right(page, BOLD, 10.0, HOURS_RIGHT, y, "Hours");
+    text_right(
+        page,
+        BOLD,
+        10.0,
+        RATE_RIGHT,
+        y,
+        &format!("Rate ({})", currency),
+    );
+    text_right(page, BOLD, 10.0, AMOUNT_RIGHT, y, "Amount");
+    rule(page, y - 5.0);
+}
+
+fn rule(page: &mut Content, y: f32) {
+    page.set_line_width(0.5)
+        .move_to(MARGIN, y)
+        .line_to(PAGE_WIDTH - MARGIN, y)
+        .stroke();
+}
+
+fn text(page: &mut Content, font: Name, size: f32, x: f32, y: f32, value: &str) {
+    page.begin_text()
+        .set_font(font, size)
+        .next_line(x, y)
+        .show(Str(&win_ansi(value)))
+        .end_text();
+}
+
+/// Text ending at `right`. Widths are estimated: exact for Courier, and
+/// from an average glyph width for Helvetica.
+fn text_right(page: &mut Content, font: Name, size: f32, right: f32, y: f32, value: &str) {
+    let em = if font == MONO { 0.6 } else { 0.55 };
+    let width = value.chars().count() as f32 * em * size;
+    text(page, font, size, right - width, y, value);
+}
+
+fn truncate(value: &str) -> String {
+    if value.chars().count() <= DESCRIPTION_CHARS {
+        return value.to_string();
+    }
+    let mut value = value
+        .chars()
+        .take(DESCRIPTION_CHARS - 1)
+        .collect::<String>();
+    value.push('…');
+    value
+}
+
+/// Split `value` into lines of at most `width` characters at spaces,
+/// keeping its own line breaks.
+fn wrap(value: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in value.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Encode text for the fonts' `WinAnsiEncoding` (Windows-1252).
+fn win_ansi(value: &str) -> Vec<u8> {
+    value
+        .chars()
+        .map(|c| match c {
+            ' '..='~' | '\u{a0}'..='\u{ff}' => c as u8,
+            '€' => 0x80,
+            '…' => 0x85,
+            '‘' => 0x91,
+            '’' => 0x92,
+            '“' => 0x93,
+            '”' => 0x94,
+            '•' => 0x95,
+            '–' => 0x96,
+            '—' => 0x97,
+            c if c.is_whitespace() => b' ',
+            _ => b'?',
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use sea_orm::prelude::Uuid;
+
+    use super::{format_amount, format_hours, render_invoice};
+    use crate::{
+        dtos::invoice_dto::InvoiceStatus,
+        entities::{client, invoice, invoice_line},
+    };
+
+    #[test]
+    fn amounts_and_hours_format_with_two_decimals() {
+        assert_eq!(format_amount(0), "0.00");
+        assert_eq!(format_amount(5), "0.05");
+        assert_eq!(format_amount(123456789), "1,234,567.89");
+        assert_eq!(format_amount(-100050), "-1,000.50");
+        assert_eq!(format_hours(5400), "1.50");
+        assert_eq!(format_hours(17), "0.00");
+        assert_eq!(format_hours(18), "0.01");
+    }
+
+    #[test]
+    fn long_invoices_continue_on_later_pages() {
+        let now = Utc
+            .with_ymd_and_hms(2026, 10, 1, 0, 0, 0)
+            .unwrap()
+            .fixed_offset();
+        let client = client::Model {
+            id: Uuid::nil(),
+            name: "Acme (Europe)".to_string(),
+            email: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let invoice = invoice::Model {
+            id: Uuid::nil(),
+            client_id: client.id,
+            number: Some(7),
+            status: InvoiceStatus::Finalized,
+            currency: "EUR".to_string(),
+            period_start: now,
+            period_end: now,
+            issued_on: Some(now.date_naive()),
+            due_on: None,
+            notes: Some("Thank you".to_string()),
+            created_at: now,
+            updated_at: now,
+        };
+        let line = |position: i32| invoice_line::Model {
+            id: Uuid::nil(),
+            invoice_id: invoice.id,
+            project_id: None,
+            task_id: None,
+            description: format!("Website / Task {}", position),
+            seconds: 3600,
+            hourly_rate: 10000,
+            amount: 10000,
+            position,
+            created_at: now,
+            updated_at: now,
+        };
+
+        let count = |pdf: &[u8], needle: &[u8]| {
+            pdf.windows(needle.len())
+                .filter(|window| *window == needle)
+                .count()
+        };
+        let short = render_invoice(&invoice, &[line(0)], &client, &[]);
+        assert!(short.starts_with(b"%PDF-"));
+        assert_eq!(count(&short, b"/Type /Page\n"), 1);
+        assert_eq!(count(&short, b"(Acme (Europe))"), 1);
+
+        let lines = (0..70).map(line).collect::<Vec<_>>();
+        let long = render_invoice(&invoice, &lines, &client, &["Issuer".to_string()]);
+        assert_eq!(count(&long, b"/Type /Page\n"), 2);
+        assert_eq!(count(&long, b"(Website / Task 69)"), 1);
+        assert_eq!(count(&long, b"(7,000.00 EUR)"), 1);
+        assert_eq!(count(&long, b"(Invoice 7 - page 2 of 2)"), 1);
+    }
+}
@@ -0,0 +1,151 @@
+use std::time::Instant;
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::Response,
+};
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, Opts, Registry,
+    TextEncoder,
+};
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection};
+
+use crate::AppState;
+
+/// Prometheus registry and the collectors exported on `/metrics`.
+pub struct Metrics {
+    registry: Registry,
+    http_requests: IntCounterVec,
+    http_request_duration: HistogramVec,
+    db_pool_connections: IntGauge,
+    db_pool_idle: IntGauge,
+    db_pool_max: IntGauge,
+    pub database_up: IntGauge,
+    pub timers_started: IntCounter,
+    pub screenshots_stored: IntCounter,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        let registry = Registry::new_custom(Some("ot".to_string()), None)
+            .expect("metrics registry prefix is valid");
+
+        let http_requests = IntCounterVec::new(
+            Opts::new("http_requests_total", "HTTP requests handled, by route"),
+            &["method", "route", "status"],
+        )
+        .expect("valid metric");
+        let http_request_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "HTTP request latency, by route",
+            )
+            .buckets(vec![
+                0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+            ]),
+            &["method", "route"],
+        )
+        .expect("valid metric");
+        let db_pool_connections = IntGauge::new(
+            "db_pool_connections",
+            "Open connections in the database pool",
+        )
+        .expect("valid metric");
+        let db_pool_idle = IntGauge::new("db_pool_idle_connections", "Idle pooled connections")
+            .expect("valid metric");
+        let db_pool_max = IntGauge::new("db_pool_max_connections", "Configured pool size limit")
+            .expect("valid metric");
+        let database_up = IntGauge::new(
+            "database_up",
+            "1 if the last database health check succeeded",
+        )
+        .expect("valid metric");
+        let timers_started = IntCounter::new(
+            "timers_started_total",
+            "Tracking sessions started by desktop clients",
+        )
+        .expect("valid metric");
+        let screenshots_stored = IntCounter::new(
+            "screenshots_stored_total",
+            "Screenshots recorded against activity data",
+        )
+        .expect("valid metric");
+
+        for collector in [
+            Box::new(http_requests.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(http_request_duration.clone()),
+            Box::new(db_pool_connections.clone()),
+            Box::new(db_pool_idle.clone()),
+            Box::new(db_pool_max.clone()),
+            Box::new(database_up.clone()),
+            Box::new(timers_started.clone()),
+            Box::new(screenshots_stored.clone()),
+        ] {
+            registry
+                .register(collector)
+                .expect("metric registered once");
+        }
+
+        Self {
+            registry,
+            http_requests,
+            http_request_duration,
+            db_pool_connections,
+            db_pool_idle,
+            db_pool_max,
+            database_up,
+            timers_started,
+            screenshots_stored,
+        }
+    }
+
+    /// Render all metrics in the Prometheus text exposition format,
+    /// sampling the database pool at scrape time.
+    pub fn render(&self, db: &DatabaseConnection, max_connections: u32) -> String {
+        if db.get_database_backend() == DatabaseBackend::Postgres {
+            let pool = db.get_postgres_connection_pool();
+            self.db_pool_connections.set(i64::from(pool.size()));
+            self.db_pool_idle.set(pool.num_idle() as i64);
+        }
+        self.db_pool_max.set(i64::from(max_connections));
+
+        let mut buffer = Vec::new();
+        if let Err(e) = TextEncoder::new().encode(&self.registry.gather(), &mut buffer) {
+            tracing::error!("Failed to encode metrics: {}", e);
+        }
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Middleware recording request counts and latency per matched route.
+pub async fn track_metrics(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_owned())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let method = req.method().to_string();
+    let start = Instant::now();
+
+    let response = next.run(req).await;
+
+    state
+        .metrics
+        .http_request_duration
+        .with_label_values(&[&method, &route])
+        .observe(start.elapsed().as_secs_f64());
+    state
+        .metrics
+        .http_requests
+        .with_label_values(&[&method, &route, response.status().as_str()])
+        .inc();
+
+    response
+}
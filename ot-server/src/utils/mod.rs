@@ -1 +1,10 @@
+pub mod admin;
+pub mod billing;
+pub mod budget;
+pub mod dependency_graph;
 pub mod error;
+pub mod invoice_pdf;
+pub mod metrics;
+pub mod ordering;
+pub mod recurrence;
+pub mod tracking_policy;
@@ -0,0 +1,115 @@
+//! Fractional ordering keys for sections, tasks and sub tasks.
+//!
+//! Keys are base-62 strings compared byte-wise, so an item can be moved by
+//! rewriting only its own key to one that sorts between its new neighbours.
+//! A key never ends in the smallest digit, which guarantees there is always
+//! room for another key before it.
+
+const DIGITS: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// A key sorting strictly between `before` and `after`; `None` stands for the
+/// start or the end of the list. Panics if `before` does not sort before `after`.
+pub fn key_between(before: Option<&str>, after: Option<&str>) -> String {
+    let before = before.unwrap_or("");
+    if let Some(after) = after {
+        assert!(before < after, "{:?} must sort before {:?}", before, after);
+    }
+    midpoint(before.as_bytes(), after.map(str::as_bytes))
+}
+
+/// Key placing an item directly after sibling `after`, or first when `None`.
+/// `siblings` are `(id, key)` pairs sorted by key, without the item itself.
+/// Returns `None` when `after` is not one of the siblings.
+pub fn key_after<T: PartialEq>(siblings: &[(T, String)], after: Option<T>) -> Option<String> {
+    let previous = match after {
+        Some(after) => Some(siblings.iter().find(|(id, _)| *id == after)?.1.as_str()),
+        None => None,
+    };
+    // Siblings sharing a key (concurrent moves) are skipped rather than split
+    let next = siblings
+        .iter()
+        .map(|(_, key)| key.as_str())
+        .find(|key| previous.is_none_or(|previous| *key > previous));
+    Some(key_between(previous, next))
+}
+
+/// Key placing an item after every sibling in `siblings`, sorted by key.
+pub fn key_after_last(siblings: &[String]) -> String {
+    key_between(siblings.last().map(String::as_str), None)
+}
+
+fn digit(key: &[u8], index: usize) -> usize {
+    key.get(index)
+        .map(|c| DIGITS.iter().position(|d| d == c).expect("invalid ordering key"))
+        .unwrap_or(0)
+}
+
+fn midpoint(before: &[u8], after: Option<&[u8]>) -> String {
+    if let Some(after) = after {
+        // Keep the shared prefix, treating a missing digit in `before` as zero
+        let shared = after
+            .iter()
+            .enumerate()
+            .take_while(|&(i, &c)| before.get(i).copied().unwrap_or(DIGITS[0]) == c)
+            .count();
+        if shared > 0 {
+            let rest = midpoint(before.get(shared..).unwrap_or(&[]), Some(&after[shared..]));
+            return String::from_utf8_lossy(&after[..shared]).into_owned() + &rest;
+        }
+    }
+
+    let low = digit(before, 0);
+    let high = after.map_or(DIGITS.len(), |after| digit(after, 0));
+    if high - low > 1 {
+        return (DIGITS[(low + high) / 2] as char).to_string();
+    }
+    match after {
+        // The first digit of `after` on its own already sorts in between
+        Some(after) if after.len() > 1 => (after[0] as char).to_string(),
+        _ => (DIGITS[low] as char).to_string() + &midpoint(before.get(1..).unwrap_or(&[]), None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{key_after, key_between};
+
+    #[test]
+    fn keys_sort_between_their_neighbours() {
+        let first = key_between(None, None);
+        let last = key_between(Some(&first), None);
+        let middle = key_between(Some(&first), Some(&last));
+        let head = key_between(None, Some(&first));
+        assert!(head < first && first < middle && middle < last);
+    }
+
+    #[test]
+    fn repeated_inserts_keep_finding_room() {
+        let mut keys = vec![key_between(None, None)];
+        for _ in 0..200 {
+            keys.push(key_between(keys.last().map(String::as_str), None));
+            keys.insert(0, key_between(None, Some(&keys[0])));
+            let between = key_between(Some(&keys[1]), Some(&keys[2]));
+            keys.insert(2, between);
+        }
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(keys.iter().all(|key| !key.ends_with('0')));
+    }
+
+    #[test]
+    fn key_after_places_between_the_sibling_and_the_next_one() {
+        let siblings = vec![
+            (1, "A".to_string()),
+            (2, "B".to_string()),
+            (3, "B".to_string()),
+            (4, "C".to_string()),
+        ];
+        assert!(key_after(&siblings, None).unwrap().as_str() < "A");
+        let after_first = key_after(&siblings, Some(1)).unwrap();
+        assert!("A" < after_first.as_str() && after_first.as_str() < "B");
+        let after_tie = key_after(&siblings, Some(2)).unwrap();
+        assert!("B" < after_tie.as_str() && after_tie.as_str() < "C");
+        assert!(key_after(&siblings, Some(4)).unwrap().as_str() > "C");
+        assert_eq!(key_after(&siblings, Some(5)), None);
+    }
+}
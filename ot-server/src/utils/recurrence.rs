@@ -0,0 +1,218 @@
+//! The subset of iCalendar recurrence rules (RFC 5545 `RRULE`) tasks repeat by.
+//!
+//! A rule has a `FREQ` of `DAILY`, `WEEKLY` or `MONTHLY`, an optional
+//! `INTERVAL` and at most one of `UNTIL` and `COUNT`. Weekly occurrences fall
+//! on the weekday of the start date and monthly ones on its day of the month,
+//! moved back to the last day of shorter months.
+
+use std::fmt;
+
+use chrono::{Days, Months, NaiveDate};
+
+use crate::dtos::recurrence_dto::RecurrenceFrequency;
+
+const MAX_INTERVAL: u32 = 1000;
+const MAX_COUNT: u32 = 10000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    pub frequency: RecurrenceFrequency,
+    pub interval: u32,
+    /// Last possible date, inclusive
+    pub until: Option<NaiveDate>,
+    /// Number of occurrences, the start date included
+    pub count: Option<u32>,
+}
+
+impl RecurrenceRule {
+    /// Parse a rule such as `FREQ=WEEKLY;INTERVAL=2;UNTIL=20261231`. Keys are
+    /// case-insensitive and an `RRULE:` prefix is accepted.
+    pub fn parse(rule: &str) -> Result<Self, String> {
+        let rule = rule.trim();
+        let rule = match rule.get(..6) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("RRULE:") => &rule[6..],
+            _ => rule,
+        };
+
+        let mut frequency = None;
+        let mut interval = None;
+        let mut until = None;
+        let mut count = None;
+        for part in rule
+            .split(';')
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+        {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("`{}` is not a KEY=VALUE pair", part))?;
+            let key = key.trim().to_ascii_uppercase();
+            let value = value.trim();
+            let duplicate = match key.as_str() {
+                "FREQ" => frequency.replace(parse_frequency(value)?).is_some(),
+                "INTERVAL" => interval
+                    .replace(parse_number(&key, value, MAX_INTERVAL)?)
+                    .is_some(),
+                "UNTIL" => until.replace(parse_until(value)?).is_some(),
+                "COUNT" => count
+                    .replace(parse_number(&key, value, MAX_COUNT)?)
+                    .is_some(),
+                _ => {
+                    return Err(format!(
+                        "{} is not supported; rules may only use FREQ, INTERVAL, UNTIL and COUNT",
+                        key
+                    ))
+                }
+            };
+            if duplicate {
+                return Err(format!("{} is given more than once", key));
+            }
+        }
+
+        let frequency = frequency.ok_or("FREQ is required")?;
+        if until.is_some() && count.is_some() {
+            return Err("UNTIL and COUNT cannot be combined".to_string());
+        }
+        Ok(Self {
+            frequency,
+            interval: interval.unwrap_or(1),
+            until,
+            count,
+        })
+    }
+
+    /// Date of occurrence `index` of a series starting on `starts_on`, where
+    /// 0 is the start date itself; `None` once the series has ended.
+    pub fn occurrence(&self, starts_on: NaiveDate, index: u32) -> Option<NaiveDate> {
+        if self.count.is_some_and(|count| index >= count) {
+            return None;
+        }
+        // Counted from the start date rather than the previous occurrence so
+        // that a monthly series on the 31st returns to it after shorter months
+        let steps = index.checked_mul(self.interval)?;
+        let date = match self.frequency {
+            RecurrenceFrequency::Daily => starts_on.checked_add_days(Days::new(steps.into())),
+            RecurrenceFrequency::Weekly => {
+                starts_on.checked_add_days(Days::new(u64::from(steps) * 7))
+            }
+            RecurrenceFrequency::Monthly => starts_on.checked_add_months(Months::new(steps)),
+        }?;
+        match self.until {
+            Some(until) if date > until => None,
+            _ => Some(date),
+        }
+    }
+}
+
+impl fmt::Display for RecurrenceRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let frequency = match self.frequency {
+            RecurrenceFrequency::Daily => "DAILY",
+            RecurrenceFrequency::Weekly => "WEEKLY",
+            RecurrenceFrequency::Monthly => "MONTHLY",
+        };
+        write!(f, "FREQ={}", frequency)?;
+        if self.interval != 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", until.format("%Y%m%d"))?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={}", count)?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_frequency(value: &str) -> Result<RecurrenceFrequency, String> {
+    match value.to_ascii_uppercase().as_str() {
+        "DAILY" => Ok(RecurrenceFrequency::Daily),
+        "WEEKLY" => Ok(RecurrenceFrequency::Weekly),
+        "MONTHLY" => Ok(RecurrenceFrequency::Monthly),
+        _ => Err(format!(
+            "FREQ `{}` is not supported; use DAILY, WEEKLY or MONTHLY",
+            value
+        )),
+    }
+}
+
+fn parse_number(key: &str, value: &str, max: u32) -> Result<u32, String> {
+    value
+        .parse::<u32>()
+        .ok()
+        .filter(|n| (1..=max).contains(n))
+        .ok_or_else(|| format!("{} must be a whole number from 1 to {}", key, max))
+}
+
+/// `UNTIL` is a date, `YYYYMMDD`; the time part of a date-time is ignored.
+fn parse_until(value: &str) -> Result<NaiveDate, String> {
+    let date = match value.split_once(['T', 't']) {
+        Some((date, _)) => date,
+        None => value,
+    };
+    NaiveDate::parse_from_str(date, "%Y%m%d")
+        .map_err(|_| format!("UNTIL `{}` must be a date such as 20261231", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::{RecurrenceFrequency, RecurrenceRule};
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn rules_parse_and_format() {
+        let rule =
+            RecurrenceRule::parse("RRULE:freq=weekly; INTERVAL=2;UNTIL=20261231T235959Z").unwrap();
+        assert_eq!(rule.frequency, RecurrenceFrequency::Weekly);
+        assert_eq!(rule.to_string(), "FREQ=WEEKLY;INTERVAL=2;UNTIL=20261231");
+        assert_eq!(
+            RecurrenceRule::parse("FREQ=DAILY;INTERVAL=1")
+                .unwrap()
+                .to_string(),
+            "FREQ=DAILY"
+        );
+
+        for invalid in [
+            "INTERVAL=2",
+            "FREQ=YEARLY",
+            "FREQ=DAILY;INTERVAL=0",
+            "FREQ=DAILY;COUNT=3;UNTIL=20261231",
+            "FREQ=DAILY;FREQ=WEEKLY",
+            "FREQ=WEEKLY;BYDAY=MO",
+            "FREQ=DAILY;UNTIL=2026-12-31",
+        ] {
+            assert!(RecurrenceRule::parse(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn occurrences_stop_at_the_end_of_the_series() {
+        let monthly = RecurrenceRule::parse("FREQ=MONTHLY;COUNT=4").unwrap();
+        let dates = (0..5)
+            .map(|i| monthly.occurrence(date(2026, 1, 31), i))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            dates,
+            [
+                Some(date(2026, 1, 31)),
+                Some(date(2026, 2, 28)),
+                Some(date(2026, 3, 31)),
+                Some(date(2026, 4, 30)),
+                None
+            ]
+        );
+
+        let weekly = RecurrenceRule::parse("FREQ=WEEKLY;INTERVAL=2;UNTIL=20261115").unwrap();
+        assert_eq!(
+            weekly.occurrence(date(2026, 10, 19), 1),
+            Some(date(2026, 11, 2))
+        );
+        assert_eq!(weekly.occurrence(date(2026, 10, 19), 2), None);
+    }
+}
@@ -0,0 +1,94 @@
+//! How the global tracking policy and a project's combine.
+
+use crate::dtos::tracking_policy_dto::{TrackingSetting, TrackingSettings};
+
+/// Settings of a policy and those of them it locks.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PolicySettings {
+    pub settings: TrackingSettings,
+    pub locked: Vec<TrackingSetting>,
+}
+
+/// The project's settings over the global ones, except for settings the
+/// global policy locks, which projects cannot override. Settings locked by
+/// either policy stay locked.
+pub fn effective_settings(global: PolicySettings, project: PolicySettings) -> PolicySettings {
+    let mut settings = global.settings;
+    for (setting, value) in project.settings {
+        if !global.locked.contains(&setting) {
+            settings.insert(setting, value);
+        }
+    }
+    let mut locked: Vec<_> = global.locked.into_iter().chain(project.locked).collect();
+    locked.sort();
+    locked.dedup();
+    PolicySettings { settings, locked }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{effective_settings, PolicySettings};
+    use crate::dtos::tracking_policy_dto::{
+        TrackingSetting::{self, *},
+        TrackingValue::{self, *},
+    };
+
+    fn policy(
+        settings: &[(TrackingSetting, TrackingValue)],
+        locked: &[TrackingSetting],
+    ) -> PolicySettings {
+        PolicySettings {
+            settings: settings.iter().copied().collect(),
+            locked: locked.to_vec(),
+        }
+    }
+
+    #[test]
+    fn projects_override_unlocked_settings() {
+        let global = policy(
+            &[
+                (EnableScreenShots, Enabled(true)),
+                (ScreenShotsInterval, Seconds(600)),
+            ],
+            &[EnableScreenShots],
+        );
+        let project = policy(
+            &[
+                (EnableScreenShots, Enabled(false)),
+                (ScreenShotsInterval, Seconds(300)),
+                (EnableIdleTime, Enabled(true)),
+            ],
+            &[EnableIdleTime, EnableScreenShots],
+        );
+
+        let effective = effective_settings(global, project);
+        assert_eq!(
+            effective,
+            policy(
+                &[
+                    // Locked globally, so the project's value is ignored
+                    (EnableScreenShots, Enabled(true)),
+                    (ScreenShotsInterval, Seconds(300)),
+                    (EnableIdleTime, Enabled(true)),
+                ],
+                &[EnableScreenShots, EnableIdleTime],
+            )
+        );
+    }
+
+    #[test]
+    fn missing_policies_add_nothing() {
+        let global = policy(
+            &[(EnableMouseActivity, Enabled(false))],
+            &[EnableMouseActivity],
+        );
+        assert_eq!(
+            effective_settings(global.clone(), PolicySettings::default()),
+            global
+        );
+        assert_eq!(
+            effective_settings(PolicySettings::default(), global.clone()),
+            global
+        );
+    }
+}
@@ -0,0 +1,348 @@
+use std::{
+    collections::HashSet,
+    panic::{self, AssertUnwindSafe},
+    sync::mpsc::{self, RecvTimeoutError, Sender},
+    thread,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use device_query::{DeviceQuery, DeviceState, Keycode};
+use ot_server::activity_dto::ActivityDataCreateDto;
+use uuid::Uuid;
+
+/// How often input is polled.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(200);
+/// Length of the intervals activity is reported for.
+pub const TRACK_INTERVAL: Duration = Duration::from_secs(600);
+
+/// Input state at one poll.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InputState {
+    pub keys: HashSet<Keycode>,
+    pub mouse_position: (i32, i32),
+    pub mouse_pressed: bool,
+}
+
+impl InputState {
+    /// Keys pressed or released since `previous`.
+    pub fn keyboard_active(&self, previous: &InputState) -> bool {
+        self.keys != previous.keys
+    }
+
+    /// The mouse moved since `previous`, or a button is held.
+    pub fn mouse_active(&self, previous: &InputState) -> bool {
+        self.mouse_position != previous.mouse_position || self.mouse_pressed
+    }
+}
+
+/// Where the sampler reads input from; faked in tests.
+pub trait InputSource {
+    fn read(&mut self) -> InputState;
+}
+
+/// Keyboard and mouse of this machine. On Linux it needs an X display.
+pub struct DeviceInput(DeviceState);
+
+impl DeviceInput {
+    pub fn new() -> Self {
+        Self(DeviceState::new())
+    }
+}
+
+impl InputSource for DeviceInput {
+    fn read(&mut self) -> InputState {
+        let mouse = self.0.get_mouse();
+        InputState {
+            keys: self.0.get_keys().into_iter().collect(),
+            mouse_position: mouse.coords,
+            mouse_pressed: mouse.button_pressed.iter().any(|&pressed| pressed),
+        }
+    }
+}
+
+/// What a sampler tracks, taken from the timer and the app config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SamplerConfig {
+    pub task_id: Option<Uuid>,
+    pub keyboard: bool,
+    pub mouse: bool,
+    pub poll_interval: Duration,
+    pub track_interval: Duration,
+}
+
+/// Folds input polls into the share of seconds with keyboard and mouse
+/// activity, one interval at a time.
+#[derive(Debug)]
+pub struct ActivityCounter {
+    config: SamplerConfig,
+    interval_start: DateTime<Utc>,
+    previous: Option<InputState>,
+    /// Seconds into the interval with activity
+    keyboard_seconds: HashSet<i64>,
+    mouse_seconds: HashSet<i64>,
+}
+
+impl ActivityCounter {
+    pub fn new(config: SamplerConfig, start: DateTime<Utc>) -> Self {
+        Self {
+            config,
+            interval_start: start,
+            previous: None,
+            keyboard_seconds: HashSet::new(),
+            mouse_seconds: HashSet::new(),
+        }
+    }
+
+    pub fn interval_start(&self) -> DateTime<Utc> {
+        self.interval_start
+    }
+
+    /// Record input polled at `at`. Activity is a change since the previous
+    /// poll: keys pressed or released, the mouse moved, or a button held.
+    pub fn record(&mut self, at: DateTime<Utc>, state: InputState) {
+        let second = (at - self.interval_start).num_seconds();
+        if let Some(previous) = &self.previous {
+            if self.config.keyboard && state.keyboard_active(previous) {
+                self.keyboard_seconds.insert(second);
+            }
+            if self.config.mouse && state.mouse_active(previous) {
+                self.mouse_seconds.insert(second);
+            }
+        }
+        self.previous = Some(state);
+    }
+
+    /// End the interval at `end` and start the next one there; `None` for
+    /// an interval under a second.
+    pub fn finish(&mut self, end: DateTime<Utc>) -> Option<ActivityDataCreateDto> {
+        let start = std::mem::replace(&mut self.interval_start, end);
+        let keyboard = std::mem::take(&mut self.keyboard_seconds);
+        let mouse = std::mem::take(&mut self.mouse_seconds);
+        let seconds = (end - start).num_seconds();
+        if seconds < 1 {
+            return None;
+        }
+        let percent = |active: usize| (active as f32 * 100.0 / seconds as f32).min(100.0);
+        Some(ActivityDataCreateDto {
+            id: None,
+            screenshots: vec![],
+            keyboard_activity_percent: percent(keyboard.len()),
+            mouse_activity_percent: percent(mouse.len()),
+            total_percent: percent(keyboard.union(&mouse).count()),
+            track_interval: seconds as i32,
+            start_time: start.fixed_offset(),
+            end_time: end.fixed_offset(),
+            task_id: self.config.task_id,
+            person: None,
+        })
+    }
+}
+
+/// Samples input on a dedicated thread while the timer runs, handing a
+/// record to the sink at the end of every interval and when stopped.
+#[derive(Debug, Default)]
+pub struct ActivitySampler {
+    stop: Option<Sender<DateTime<Utc>>>,
+}
+
+impl ActivitySampler {
+    /// Start sampling, stopping a sampler already running. The source is
+    /// made on the sampler thread, as device handles cannot be sent across.
+    pub fn start<S, F, K>(&mut self, config: SamplerConfig, make_source: F, sink: K)
+    where
+        S: InputSource,
+        F: FnOnce() -> S + Send + 'static,
+        K: Fn(ActivityDataCreateDto) + Send + 'static,
+    {
+        self.stop(Utc::now());
+        let (tx, rx) = mpsc::channel();
+        self.stop = Some(tx);
+        let spawned = thread::Builder::new()
+            .name("activity-sampler".into())
+            .spawn(move || {
+                let Ok(mut source) = panic::catch_unwind(AssertUnwindSafe(make_source)) else {
+                    eprintln!("[Activity] No input source, not sampling activity");
+                    return;
+                };
+                let mut counter = ActivityCounter::new(config, Utc::now());
+                let track_interval = chrono::Duration::from_std(config.track_interval)
+                    .expect("Track interval fits a chrono duration");
+                let finish = |counter: &mut ActivityCounter, end| {
+                    if let Some(sample) = counter.finish(end) {
+                        sink(sample);
+                    }
+                };
+                loop {
+                    match rx.recv_timeout(config.poll_interval) {
+                        Ok(end) => break finish(&mut counter, end),
+                        Err(RecvTimeoutError::Disconnected) => {
+                            break finish(&mut counter, Utc::now());
+                        }
+                        Err(RecvTimeoutError::Timeout) => {}
+                    }
+                    let now = Utc::now();
+                    let interval_end = counter.interval_start() + track_interval;
+                    if now >= interval_end {
+                        finish(&mut counter, interval_end);
+                    }
+                    counter.record(now, source.read());
+                }
+            });
+        if let Err(e) = spawned {
+            eprintln!("[Activity] Failed to start sampler: {}", e);
+        }
+    }
+
+    /// Stop sampling, reporting the interval so far as ending at `end`.
+    pub fn stop(&mut self, end: DateTime<Utc>) {
+        if let Some(stop) = self.stop.take() {
+            // The thread is gone already if this fails
+            let _ = stop.send(end);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, sync::mpsc, time::Duration};
+
+    use chrono::{TimeZone, Utc};
+    use device_query::Keycode;
+    use uuid::Uuid;
+
+    use super::{ActivityCounter, ActivitySampler, InputSource, InputState, SamplerConfig};
+
+    fn config() -> SamplerConfig {
+        SamplerConfig {
+            task_id: Some(Uuid::from_u128(1)),
+            keyboard: true,
+            mouse: true,
+            poll_interval: Duration::from_millis(5),
+            track_interval: Duration::from_secs(600),
+        }
+    }
+
+    fn keys(keys: &[Keycode]) -> InputState {
+        InputState {
+            keys: keys.iter().copied().collect(),
+            ..Default::default()
+        }
+    }
+
+    fn mouse(x: i32) -> InputState {
+        InputState {
+            mouse_position: (x, 0),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn counts_seconds_with_activity() {
+        let start = Utc.with_ymd_and_hms(2025, 3, 1, 9, 0, 0).unwrap();
+        let at = |ms: i64| start + chrono::Duration::milliseconds(ms);
+        let mut counter = ActivityCounter::new(config(), start);
+
+        counter.record(at(0), InputState::default());
+        // Typing through second 1: several changes, one active second
+        counter.record(at(1_000), keys(&[Keycode::A]));
+        counter.record(at(1_200), keys(&[]));
+        counter.record(at(1_400), keys(&[Keycode::B]));
+        // Held key, no change
+        counter.record(at(2_000), keys(&[Keycode::B]));
+        // Mouse moves in seconds 3 and 4, typing again in 4
+        counter.record(at(3_000), mouse(10));
+        counter.record(
+            at(4_000),
+            InputState {
+                keys: HashSet::from([Keycode::C]),
+                mouse_position: (20, 0),
+                mouse_pressed: false,
+            },
+        );
+        // Still and idle
+        counter.record(
+            at(5_000),
+            InputState {
+                keys: HashSet::from([Keycode::C]),
+                mouse_position: (20, 0),
+                mouse_pressed: false,
+            },
+        );
+
+        let sample = counter.finish(at(10_000)).unwrap();
+        assert_eq!(sample.track_interval, 10);
+        // Seconds 1, 3 (keys released) and 4
+        assert_eq!(sample.keyboard_activity_percent, 30.0);
+        assert_eq!(sample.mouse_activity_percent, 20.0);
+        // Seconds 1, 3 and 4
+        assert_eq!(sample.total_percent, 30.0);
+        assert_eq!(sample.task_id, Some(Uuid::from_u128(1)));
+        assert_eq!(sample.start_time, start.fixed_offset());
+
+        // The next interval starts where this one ended, from the last
+        // state: releasing the key is activity, the mouse stayed put
+        counter.record(at(10_500), mouse(20));
+        let sample = counter.finish(at(20_000)).unwrap();
+        assert_eq!(sample.start_time, at(10_000).fixed_offset());
+        assert_eq!(sample.keyboard_activity_percent, 10.0);
+        assert_eq!(sample.mouse_activity_percent, 0.0);
+        assert!(counter.finish(at(20_500)).is_none());
+    }
+
+    #[test]
+    fn disabled_inputs_are_not_counted() {
+        let start = Utc::now();
+        let at = |s: i64| start + chrono::Duration::seconds(s);
+        let mut counter = ActivityCounter::new(
+            SamplerConfig {
+                keyboard: false,
+                ..config()
+            },
+            start,
+        );
+        counter.record(at(0), InputState::default());
+        counter.record(at(1), keys(&[Keycode::A]));
+        counter.record(at(2), mouse(5));
+        let sample = counter.finish(at(4)).unwrap();
+        assert_eq!(sample.keyboard_activity_percent, 0.0);
+        assert_eq!(sample.mouse_activity_percent, 25.0);
+        assert_eq!(sample.total_percent, 25.0);
+    }
+
+    /// Moves the mouse on every read.
+    struct Jiggle(i32);
+
+    impl InputSource for Jiggle {
+        fn read(&mut self) -> InputState {
+            self.0 += 1;
+            mouse(self.0)
+        }
+    }
+
+    #[test]
+    fn sampler_reports_intervals_and_the_rest_on_stop() {
+        let (tx, rx) = mpsc::channel();
+        let mut sampler = ActivitySampler::default();
+        sampler.start(
+            SamplerConfig {
+                track_interval: Duration::from_secs(2),
+                ..config()
+            },
+            || Jiggle(0),
+            move |sample| tx.send(sample).unwrap(),
+        );
+
+        let first = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(first.track_interval, 2);
+        assert!(first.mouse_activity_percent > 0.0);
+
+        std::thread::sleep(Duration::from_millis(3300));
+        sampler.stop(Utc::now());
+        let rest: Vec<_> = rx.iter().collect();
+        // A full interval and the second it was stopped in
+        assert_eq!(rest.len(), 2);
+        assert_eq!(rest[0].start_time, first.end_time);
+        assert_eq!(rest[1].start_time, rest[0].end_time);
+    }
+}
@@ -0,0 +1,67 @@
+use std::collections::HashSet;
+
+use ot_server::budget_dto::{BudgetAlertDto, ProjectBudgetDto};
+use tauri::{Emitter, Manager};
+use tokio::time::Duration;
+use uuid::Uuid;
+
+use crate::services::budget::BudgetService;
+
+/// How often the server is asked for new budget alerts.
+const ALERT_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Marks the alert poller as started.
+struct BudgetAlertUpdates;
+
+#[tauri::command]
+pub async fn get_project_budget(project_id: Uuid) -> Result<ProjectBudgetDto, String> {
+    let budget_service = BudgetService::new();
+    budget_service
+        .get_project_budget(project_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Dismiss an alert so it is not shown again, on this or any other desktop.
+#[tauri::command]
+pub async fn acknowledge_budget_alert(id: Uuid) -> Result<BudgetAlertDto, String> {
+    let budget_service = BudgetService::new();
+    budget_service
+        .acknowledge_alert(id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Poll the server for budget alerts and emit each open one once as a
+/// `budget-alert` event, which the UI shows as a notification. Starting
+/// the poller again is a no-op.
+#[tauri::command]
+pub async fn start_budget_alert_updates(app: tauri::AppHandle) -> Result<(), String> {
+    if !app.manage(BudgetAlertUpdates) {
+        return Ok(());
+    }
+
+    tokio::spawn(async move {
+        let budget_service = BudgetService::new();
+        let mut seen = HashSet::new();
+        loop {
+            match budget_service.get_open_alerts().await {
+                Ok(alerts) => {
+                    // Oldest first, so notifications stack in order
+                    for alert in alerts.into_iter().rev() {
+                        if !seen.insert(alert.id) {
+                            continue;
+                        }
+                        if let Err(e) = app.emit("budget-alert", alert) {
+                            eprintln!("Failed to emit budget alert: {:?}", e);
+                        }
+                    }
+                }
+                Err(e) => eprintln!("Failed to fetch budget alerts: {}", e),
+            }
+            tokio::time::sleep(ALERT_POLL_INTERVAL).await;
+        }
+    });
+
+    Ok(())
+}
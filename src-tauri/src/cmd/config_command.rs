@@ -0,0 +1,125 @@
+use std::sync::Mutex;
+
+use ot_server::tracking_policy_dto::{EffectiveTrackingPolicyDto, TrackingSetting, setting_name};
+use serde::Serialize;
+use tauri::Manager;
+use uuid::Uuid;
+
+use crate::{
+    AppState,
+    config_store::{ConfigStore, StoredConfig},
+    services::policy::PolicyService,
+    state::AppConfig,
+    task_timer::restart_tracking,
+};
+
+/// The config and which of its settings the tracking policy locks.
+#[derive(Debug, Clone, Serialize)]
+pub struct AppConfigResponse {
+    pub config: AppConfig,
+    /// Settings the user cannot change
+    pub locked: Vec<TrackingSetting>,
+}
+
+fn config_response(app_state: &AppState) -> AppConfigResponse {
+    AppConfigResponse {
+        config: app_state.app_config.clone(),
+        locked: locked_settings(app_state).to_vec(),
+    }
+}
+
+fn locked_settings(app_state: &AppState) -> &[TrackingSetting] {
+    app_state
+        .policy
+        .as_ref()
+        .map(|policy| policy.locked.as_slice())
+        .unwrap_or_default()
+}
+
+#[tauri::command]
+pub fn get_app_config(
+    state: tauri::State<'_, Mutex<AppState>>,
+) -> Result<AppConfigResponse, String> {
+    let app_state = state.lock().map_err(|e| format!("{:?}", e))?;
+    Ok(config_response(&app_state))
+}
+
+/// Replace the config, unless that changes a setting the tracking policy
+/// locks. A running timer picks the new config up straight away.
+#[tauri::command]
+pub async fn update_app_config(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, Mutex<AppState>>,
+    store: tauri::State<'_, ConfigStore>,
+    config: AppConfig,
+) -> Result<AppConfigResponse, String> {
+    let mut app_state = state.lock().map_err(|e| format!("{:?}", e))?;
+    let changed = app_state
+        .app_config
+        .locked_changes(&config, locked_settings(&app_state));
+    if !changed.is_empty() {
+        let names: Vec<_> = changed.into_iter().map(setting_name).collect();
+        return Err(format!(
+            "Locked by the tracking policy: {}",
+            names.join(", ")
+        ));
+    }
+    app_state.app_config = config;
+    save_config(&store, &app_state);
+    restart_tracking(&app_handle, &mut app_state);
+    Ok(config_response(&app_state))
+}
+
+/// The tracking policy for a project, or the global one for `None`;
+/// `None` when the server cannot be reached, leaving the last one in
+/// place.
+pub async fn fetch_policy(project_id: Option<Uuid>) -> Option<EffectiveTrackingPolicyDto> {
+    PolicyService::new()
+        .get_effective_policy(project_id)
+        .await
+        .map_err(|e| eprintln!("Failed to fetch tracking policy: {}", e))
+        .ok()
+}
+
+/// Apply a tracking policy to the config and save both.
+pub fn apply_policy(
+    app_handle: &tauri::AppHandle,
+    app_state: &mut AppState,
+    policy: EffectiveTrackingPolicyDto,
+) {
+    let previous = app_state.policy.take();
+    app_state
+        .app_config
+        .apply_policy(&policy, previous.as_ref());
+    app_state.policy = Some(policy);
+    save_config(&app_handle.state::<ConfigStore>(), app_state);
+}
+
+/// Write the config to the config file. Called with the state locked, so
+/// writes land in the order of the changes they record.
+fn save_config(store: &ConfigStore, app_state: &AppState) {
+    let stored = StoredConfig {
+        config: app_state.app_config.clone(),
+        policy: app_state.policy.clone(),
+    };
+    if let Err(e) = store.save(&stored) {
+        eprintln!("Failed to save config: {}", e);
+    }
+}
+
+/// Apply the global tracking policy once on startup. A timer started in
+/// the meantime has its project's policy applied already.
+pub fn spawn_policy_sync(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let Some(policy) = fetch_policy(None).await else {
+            return;
+        };
+        let state = app.state::<Mutex<AppState>>();
+        let Ok(mut app_state) = state.lock() else {
+            return;
+        };
+        if !app_state.timer_state.running {
+            apply_policy(&app, &mut app_state, policy);
+        }
+    });
+}
@@ -1,4 +1,9 @@
+pub mod budget_command;
+pub mod config_command;
 pub mod project_command;
+pub mod screenshot_command;
+pub mod search_command;
 pub mod section_command;
 pub mod sub_task_command;
+pub mod sync_command;
 pub mod task_command;
@@ -0,0 +1,154 @@
+use std::{path::PathBuf, sync::Mutex};
+
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use tauri::Manager;
+use uuid::Uuid;
+
+use crate::{
+    AppState,
+    screenshot_privacy::ScreenshotPrivacy,
+    screenshot_store::{
+        self, ScreenshotDeletion, ScreenshotFormat, ScreenshotRecord, kept_screenshots,
+    },
+};
+
+/// A screenshot the user can still delete.
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingScreenshot {
+    #[serde(flatten)]
+    pub record: ScreenshotRecord,
+    /// When it can no longer be deleted
+    pub final_at: DateTime<Utc>,
+}
+
+/// What the review window shows.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScreenshotReview {
+    /// Newest first
+    pub pending: Vec<PendingScreenshot>,
+    /// Set while screenshots are paused
+    pub paused_until: Option<DateTime<Utc>>,
+}
+
+/// The screenshot directory and privacy settings from the app config.
+fn screenshot_config(
+    app_handle: &tauri::AppHandle,
+    state: &tauri::State<'_, Mutex<AppState>>,
+) -> Result<(PathBuf, ScreenshotPrivacy), String> {
+    let app_state = state.lock().map_err(|e| format!("{:?}", e))?;
+    let data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?;
+    Ok((
+        app_state.app_config.screenshot_storage.root(&data_dir),
+        app_state.app_config.screenshot_privacy.clone(),
+    ))
+}
+
+/// Screenshots saved on this desktop and not deleted, oldest first; only
+/// those of one session's folder when `session` is given.
+#[tauri::command]
+pub fn get_screenshots(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, Mutex<AppState>>,
+    session: Option<String>,
+) -> Result<Vec<ScreenshotRecord>, String> {
+    let (root, _) = screenshot_config(&app_handle, &state)?;
+    let mut records = kept_screenshots(&root).map_err(|e| e.to_string())?;
+    if let Some(session) = session {
+        records.retain(|record| record.session == session);
+    }
+    Ok(records)
+}
+
+/// Screenshots still in their review period, for the user to look over.
+#[tauri::command]
+pub fn get_screenshot_review(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, Mutex<AppState>>,
+) -> Result<ScreenshotReview, String> {
+    let (root, privacy) = screenshot_config(&app_handle, &state)?;
+    let now = Utc::now();
+    let mut pending: Vec<_> = kept_screenshots(&root)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|record| PendingScreenshot {
+            final_at: record.captured_at + privacy.review_period(),
+            record,
+        })
+        .filter(|screenshot| screenshot.final_at > now)
+        .collect();
+    pending.reverse();
+    let paused_until = state
+        .lock()
+        .map_err(|e| format!("{:?}", e))?
+        .screenshot_pause
+        .paused_until(now);
+    Ok(ScreenshotReview {
+        pending,
+        paused_until,
+    })
+}
+
+/// A saved screenshot as a data URL, for previews.
+#[tauri::command]
+pub fn get_screenshot_image(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, Mutex<AppState>>,
+    id: Uuid,
+) -> Result<String, String> {
+    let (root, _) = screenshot_config(&app_handle, &state)?;
+    let record = kept_screenshots(&root)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|record| record.id == id)
+        .ok_or("Screenshot not found")?;
+    let bytes = std::fs::read(root.join(&record.path)).map_err(|e| e.to_string())?;
+    let mime = match record.format {
+        ScreenshotFormat::Jpeg => "image/jpeg",
+        ScreenshotFormat::Webp => "image/webp",
+    };
+    Ok(format!("data:{};base64,{}", mime, BASE64.encode(bytes)))
+}
+
+/// Delete a screenshot during its review period. The deletion is kept on
+/// record.
+#[tauri::command]
+pub fn delete_screenshot(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, Mutex<AppState>>,
+    id: Uuid,
+) -> Result<ScreenshotDeletion, String> {
+    let (root, privacy) = screenshot_config(&app_handle, &state)?;
+    screenshot_store::delete_screenshot(&root, id, privacy.review_period(), Utc::now())
+        .map_err(|e| e.to_string())
+}
+
+/// Take no screenshots for the next `minutes`; returns when they resume.
+#[tauri::command]
+pub fn pause_screenshots(
+    state: tauri::State<'_, Mutex<AppState>>,
+    minutes: u32,
+) -> Result<DateTime<Utc>, String> {
+    let until = Utc::now() + Duration::minutes(minutes.into());
+    state
+        .lock()
+        .map_err(|e| format!("{:?}", e))?
+        .screenshot_pause
+        .pause_until(until);
+    Ok(until)
+}
+
+/// Take screenshots again before a pause is over.
+#[tauri::command]
+pub fn resume_screenshots(state: tauri::State<'_, Mutex<AppState>>) -> Result<(), String> {
+    state
+        .lock()
+        .map_err(|e| format!("{:?}", e))?
+        .screenshot_pause
+        .resume();
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use ot_server::search_dto::SearchHitDto;
+use uuid::Uuid;
+
+use crate::services::search::SearchService;
+
+/// Backs the quick-switcher: ranked matches across projects, sections,
+/// tasks and sub tasks with their breadcrumb paths.
+#[tauri::command]
+pub async fn search(
+    q: String,
+    project_id: Option<Uuid>,
+    limit: Option<u64>,
+) -> Result<Vec<SearchHitDto>, String> {
+    let search_service = SearchService::new();
+    search_service
+        .search(&q, project_id, limit)
+        .await
+        .map_err(|e| e.to_string())
+}
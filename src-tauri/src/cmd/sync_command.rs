@@ -0,0 +1,63 @@
+use chrono::Utc;
+use tauri::{Emitter, Manager};
+use tokio::time::Duration;
+
+use crate::{
+    outbox::{Outbox, OutboxStatus},
+    services::sync::{SyncError, SyncService},
+};
+
+/// How often the outbox is checked for uploads that are due.
+const SYNC_INTERVAL: Duration = Duration::from_secs(5);
+
+/// What is still waiting to be uploaded to the server.
+#[tauri::command]
+pub fn get_sync_status(outbox: tauri::State<'_, Outbox>) -> Result<OutboxStatus, String> {
+    outbox.status().map_err(|e| e.to_string())
+}
+
+/// Upload the outbox in the background for as long as the app runs.
+/// Uploads that fail are retried with backoff; a `sync-status` event is
+/// emitted whenever the outbox changes.
+pub fn spawn_outbox_sync(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let sync_service = SyncService::new();
+        loop {
+            let outbox = app.state::<Outbox>();
+            let due = outbox.due(Utc::now()).unwrap_or_else(|e| {
+                eprintln!("Failed to read sync outbox: {}", e);
+                Vec::new()
+            });
+            let changed = !due.is_empty();
+            for item in due {
+                let result = match sync_service.upload(&item.payload).await {
+                    Ok(()) => outbox.remove(&item),
+                    Err(SyncError::Rejected(message)) => {
+                        eprintln!("Server refused outbox item: {}", message);
+                        outbox.reject(item, message)
+                    }
+                    Err(e) => outbox.retry_later(item, e.to_string(), Utc::now()),
+                };
+                if let Err(e) = result {
+                    eprintln!("Failed to update sync outbox: {}", e);
+                }
+            }
+            if changed {
+                notify_sync_status(&app);
+            }
+            tokio::time::sleep(SYNC_INTERVAL).await;
+        }
+    });
+}
+
+/// Emit the outbox status as a `sync-status` event.
+pub fn notify_sync_status(app: &tauri::AppHandle) {
+    match app.state::<Outbox>().status() {
+        Ok(status) => {
+            if let Err(e) = app.emit("sync-status", status) {
+                eprintln!("Failed to emit sync status: {:?}", e);
+            }
+        }
+        Err(e) => eprintln!("Failed to read sync outbox: {}", e),
+    }
+}
@@ -1,13 +1,32 @@
-use ot_server::task_dto::TaskDto;
+use ot_server::task_dto::{DONE_STATUS, TaskDto, TaskFilterParams};
 use uuid::Uuid;
 
 use crate::services::task::TaskService;
 
 #[tauri::command]
-pub async fn get_tasks_by_section_id(section_id: Uuid) -> Result<Vec<TaskDto>, String> {
+pub async fn get_tasks_by_section_id(
+    section_id: Uuid,
+    filter: Option<TaskFilterParams>,
+) -> Result<Vec<TaskDto>, String> {
     let task_service = TaskService::new();
     task_service
-        .get_tasks_by_section_id(section_id)
+        .get_tasks_by_section_id(section_id, &filter.unwrap_or_default())
         .await
         .map_err(|e| e.to_string())
 }
+
+/// Tasks that have to be done before `task_id`, and are not yet. The UI
+/// warns before starting a timer on a task with open blockers.
+#[tauri::command]
+pub async fn get_task_blockers(task_id: Uuid) -> Result<Vec<TaskDto>, String> {
+    let task_service = TaskService::new();
+    let dependencies = task_service
+        .get_dependencies(task_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(dependencies
+        .blocked_by
+        .into_iter()
+        .filter(|task| task.status != DONE_STATUS)
+        .collect())
+}
@@ -0,0 +1,115 @@
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use ot_server::tracking_policy_dto::EffectiveTrackingPolicyDto;
+use serde::{Deserialize, Serialize};
+
+use crate::state::AppConfig;
+
+/// File the config is kept in, inside the app config directory.
+pub const CONFIG_FILE: &str = "config.json";
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("Failed to access config file: {0}")]
+    Io(#[from] io::Error),
+    #[error("Config file is corrupt: {0}")]
+    Format(#[from] serde_json::Error),
+}
+
+/// What the config file holds.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StoredConfig {
+    pub config: AppConfig,
+    /// Tracking policy last fetched, kept so its locks hold offline
+    pub policy: Option<EffectiveTrackingPolicyDto>,
+}
+
+/// The app config on disk, rewritten whenever the user or the tracking
+/// policy changes it.
+#[derive(Debug)]
+pub struct ConfigStore {
+    path: PathBuf,
+}
+
+impl ConfigStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// The stored config; `None` before it was first saved.
+    pub fn load(&self) -> Result<Option<StoredConfig>, ConfigError> {
+        match fs::read(&self.path) {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Write `stored` next to the config file and rename it over it, so a
+    /// crash mid-write leaves the previous config intact.
+    pub fn save(&self, stored: &StoredConfig) -> Result<(), ConfigError> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        let mut file = File::create(&tmp)?;
+        file.write_all(&serde_json::to_vec_pretty(stored)?)?;
+        file.sync_all()?;
+        fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use ot_server::tracking_policy_dto::{
+        EffectiveTrackingPolicyDto, TrackingSetting::EnableScreenShots, TrackingValue::Enabled,
+    };
+    use tokio::time::Duration;
+
+    use super::{CONFIG_FILE, ConfigStore, StoredConfig};
+    use crate::state::AppConfig;
+
+    #[test]
+    fn saves_and_loads() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ConfigStore::new(dir.path().join("nested").join(CONFIG_FILE));
+        assert!(store.load().unwrap().is_none());
+
+        let mut stored = StoredConfig {
+            policy: Some(EffectiveTrackingPolicyDto {
+                settings: [(EnableScreenShots, Enabled(true))].into(),
+                locked: vec![EnableScreenShots],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        stored.config.screen_shots_interval = Duration::from_secs(300);
+        stored.config.screenshot_privacy.excluded_apps = vec!["KeePassXC".to_string()];
+        store.save(&stored).unwrap();
+        assert_eq!(store.load().unwrap(), Some(stored));
+
+        // Durations are plain seconds, and settings left out keep their
+        // defaults
+        fs::write(
+            dir.path().join("nested").join(CONFIG_FILE),
+            r#"{"config": {"idle_time_threshold": 120, "enable_mouse_activity": false}}"#,
+        )
+        .unwrap();
+        let loaded = store.load().unwrap().unwrap();
+        assert_eq!(loaded.config.idle_time_threshold, Duration::from_secs(120));
+        assert!(!loaded.config.enable_mouse_activity);
+        assert_eq!(
+            loaded.config.screen_shots_interval,
+            AppConfig::default().screen_shots_interval
+        );
+        assert_eq!(loaded.policy, None);
+    }
+}
@@ -0,0 +1,219 @@
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::mpsc::{self, RecvTimeoutError, Sender},
+    thread,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::activity_sampler::{InputSource, InputState};
+
+/// Time without any input while the timer ran, reported once input comes
+/// back and held until the user decides what to do with it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IdleSpan {
+    pub id: Uuid,
+    /// Last input before the user went idle
+    pub start_time: DateTime<Utc>,
+    /// First input after
+    pub end_time: DateTime<Utc>,
+    pub idle_seconds: u64,
+    /// Task the timer was on
+    pub task_id: Option<Uuid>,
+    pub sub_task_id: Option<Uuid>,
+    /// Whether the user should be asked why they were away
+    pub ask_for_reason: bool,
+}
+
+/// What to do with an idle span.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdleDecision {
+    /// Count it as time worked on the timer's task
+    Keep,
+    /// Take it out of the session
+    Discard,
+    /// Take it out of the session and track it on another task
+    Reassign {
+        task_id: Uuid,
+        sub_task_id: Option<Uuid>,
+    },
+}
+
+/// Watches input polls for gaps longer than the threshold.
+#[derive(Debug)]
+pub struct IdleTracker {
+    threshold: chrono::Duration,
+    last_input: DateTime<Utc>,
+    previous: Option<InputState>,
+}
+
+impl IdleTracker {
+    pub fn new(threshold: Duration, start: DateTime<Utc>) -> Self {
+        Self {
+            // A threshold too long to represent is never reached
+            threshold: chrono::Duration::from_std(threshold).unwrap_or(chrono::Duration::MAX),
+            last_input: start,
+            previous: None,
+        }
+    }
+
+    /// Whether nothing was input for longer than the threshold by `now`.
+    pub fn is_idle(&self, now: DateTime<Utc>) -> bool {
+        now - self.last_input >= self.threshold
+    }
+
+    /// Record input polled at `at`. Returns the start and end of the idle
+    /// span when this is the first input after one.
+    pub fn record(
+        &mut self,
+        at: DateTime<Utc>,
+        state: InputState,
+    ) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        let active = self.previous.as_ref().is_some_and(|previous| {
+            state.keyboard_active(previous) || state.mouse_active(previous)
+        });
+        self.previous = Some(state);
+        if !active {
+            return None;
+        }
+        let span = self.is_idle(at).then_some((self.last_input, at));
+        self.last_input = at;
+        span
+    }
+}
+
+/// Polls input on a dedicated thread while the timer runs, reporting each
+/// idle span when the user comes back.
+#[derive(Debug, Default)]
+pub struct IdleDetector {
+    stop: Option<Sender<()>>,
+}
+
+impl IdleDetector {
+    /// Start watching, stopping a detector already running.
+    pub fn start<S, F, K>(
+        &mut self,
+        threshold: Duration,
+        poll_interval: Duration,
+        make_source: F,
+        on_return: K,
+    ) where
+        S: InputSource,
+        F: FnOnce() -> S + Send + 'static,
+        K: Fn(DateTime<Utc>, DateTime<Utc>) + Send + 'static,
+    {
+        self.stop();
+        let (tx, rx) = mpsc::channel();
+        self.stop = Some(tx);
+        let spawned = thread::Builder::new()
+            .name("idle-detector".into())
+            .spawn(move || {
+                let Ok(mut source) = panic::catch_unwind(AssertUnwindSafe(make_source)) else {
+                    eprintln!("[Idle] No input source, not detecting idle time");
+                    return;
+                };
+                let mut tracker = IdleTracker::new(threshold, Utc::now());
+                let mut was_idle = false;
+                while let Err(RecvTimeoutError::Timeout) = rx.recv_timeout(poll_interval) {
+                    let now = Utc::now();
+                    if !was_idle && tracker.is_idle(now) {
+                        println!("[Idle] No input for {:?}", threshold);
+                    }
+                    if let Some((start, end)) = tracker.record(now, source.read()) {
+                        on_return(start, end);
+                    }
+                    was_idle = tracker.is_idle(now);
+                }
+            });
+        if let Err(e) = spawned {
+            eprintln!("[Idle] Failed to start idle detection: {}", e);
+        }
+    }
+
+    /// Stop watching. An idle span still going on is not reported.
+    pub fn stop(&mut self) {
+        if let Some(stop) = self.stop.take() {
+            // The thread is gone already if this fails
+            let _ = stop.send(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::mpsc, time::Duration};
+
+    use chrono::{TimeZone, Utc};
+
+    use super::{IdleDetector, IdleTracker};
+    use crate::activity_sampler::{InputSource, InputState};
+
+    fn mouse(x: i32) -> InputState {
+        InputState {
+            mouse_position: (x, 0),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn reports_the_gap_when_input_returns() {
+        let start = Utc.with_ymd_and_hms(2025, 3, 1, 9, 0, 0).unwrap();
+        let at = |s: i64| start + chrono::Duration::seconds(s);
+        let mut tracker = IdleTracker::new(Duration::from_secs(30), start);
+
+        assert_eq!(tracker.record(at(0), mouse(0)), None);
+        assert_eq!(tracker.record(at(10), mouse(5)), None);
+        // Short pauses are not idle
+        assert_eq!(tracker.record(at(35), mouse(5)), None);
+        assert!(!tracker.is_idle(at(35)));
+        assert_eq!(tracker.record(at(39), mouse(6)), None);
+
+        assert_eq!(tracker.record(at(60), mouse(6)), None);
+        assert!(tracker.is_idle(at(69)));
+        assert_eq!(tracker.record(at(100), mouse(7)), Some((at(39), at(100))));
+        assert!(!tracker.is_idle(at(100)));
+        assert_eq!(tracker.record(at(101), mouse(8)), None);
+
+        // A threshold too long for chrono never trips
+        let tracker = IdleTracker::new(Duration::from_secs(u64::MAX), start);
+        assert!(!tracker.is_idle(at(365 * 24 * 3600)));
+    }
+
+    /// Still for `still` reads, then moving.
+    struct Return {
+        reads: u32,
+        still: u32,
+    }
+
+    impl InputSource for Return {
+        fn read(&mut self) -> InputState {
+            self.reads += 1;
+            mouse(self.reads.saturating_sub(self.still) as i32)
+        }
+    }
+
+    #[test]
+    fn detector_reports_the_return() {
+        let (tx, rx) = mpsc::channel();
+        let mut detector = IdleDetector::default();
+        detector.start(
+            Duration::from_millis(50),
+            Duration::from_millis(5),
+            || Return {
+                reads: 0,
+                still: 40,
+            },
+            move |start, end| tx.send((start, end)).unwrap(),
+        );
+
+        let (start, end) = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(end - start >= chrono::Duration::milliseconds(50));
+        detector.stop();
+        // Moving all along since, so nothing else is reported
+        assert!(rx.iter().next().is_none());
+    }
+}
@@ -1,26 +1,84 @@
+use config_store::{CONFIG_FILE, ConfigStore};
+use outbox::{OUTBOX_DIR, Outbox};
 use state::AppState;
 use std::sync::Mutex;
 use tauri::Manager;
+use timer_journal::{JOURNAL_FILE, TimerJournal};
 
+mod activity_sampler;
 mod cmd;
+mod config_store;
+mod idle_detector;
+mod outbox;
+mod screen_capture;
+mod screenshot_privacy;
+mod screenshot_store;
 mod services;
 mod state;
 mod task_timer;
+mod timer_journal;
 mod track_activity;
+mod window_tracker;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub async fn run() {
     tauri::Builder::default()
         .setup(|app| {
-            app.manage(Mutex::new(AppState::default()));
+            let data_dir = app.path().app_data_dir()?;
+            let journal = TimerJournal::new(data_dir.join(JOURNAL_FILE));
+            // A session left in the journal means the app did not shut down
+            // cleanly; the UI asks whether to resume or close it
+            let recovered_timer = journal.load().unwrap_or_else(|e| {
+                eprintln!("Ignoring timer journal: {}", e);
+                None
+            });
+            // A corrupt config file leaves the defaults, until the next
+            // change overwrites it
+            let config_store = ConfigStore::new(app.path().app_config_dir()?.join(CONFIG_FILE));
+            let stored = config_store
+                .load()
+                .unwrap_or_else(|e| {
+                    eprintln!("Ignoring config file: {}", e);
+                    None
+                })
+                .unwrap_or_default();
+            app.manage(Mutex::new(AppState {
+                app_config: stored.config,
+                policy: stored.policy,
+                recovered_timer,
+                ..Default::default()
+            }));
+            app.manage(config_store);
+            app.manage(journal);
+            app.manage(Outbox::new(data_dir.join(OUTBOX_DIR)));
+            cmd::sync_command::spawn_outbox_sync(app.handle().clone());
+            cmd::config_command::spawn_policy_sync(app.handle().clone());
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             task_timer::control_timer,
             task_timer::start_timer_updates,
+            task_timer::get_recovered_timer,
+            task_timer::recover_timer,
+            task_timer::get_pending_idle,
+            task_timer::resolve_idle,
+            cmd::budget_command::acknowledge_budget_alert,
+            cmd::budget_command::get_project_budget,
+            cmd::budget_command::start_budget_alert_updates,
+            cmd::config_command::get_app_config,
+            cmd::config_command::update_app_config,
             cmd::project_command::get_projects,
+            cmd::screenshot_command::delete_screenshot,
+            cmd::screenshot_command::get_screenshot_image,
+            cmd::screenshot_command::get_screenshot_review,
+            cmd::screenshot_command::get_screenshots,
+            cmd::screenshot_command::pause_screenshots,
+            cmd::screenshot_command::resume_screenshots,
+            cmd::search_command::search,
             cmd::section_command::get_sections_by_project_id,
             cmd::sub_task_command::get_sub_tasks_by_task_id,
+            cmd::sync_command::get_sync_status,
+            cmd::task_command::get_task_blockers,
             cmd::task_command::get_tasks_by_section_id
         ])
         .plugin(tauri_plugin_opener::init())
@@ -0,0 +1,334 @@
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Duration, Utc};
+use ot_server::{
+    activity_dto::{ActivityDataCreateDto, WindowActivityDataCreateDto},
+    timer_dto::TimerSessionCreateDto,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::state::TimerSession;
+
+/// Directory the outbox is kept in, inside the app data directory.
+pub const OUTBOX_DIR: &str = "outbox";
+/// Where items the server refused are moved, inside the outbox.
+const REJECTED_DIR: &str = "rejected";
+
+/// Wait before retrying after the first failed upload; doubled after each
+/// further failure, up to `MAX_BACKOFF`.
+const MIN_BACKOFF: Duration = Duration::seconds(5);
+const MAX_BACKOFF: Duration = Duration::minutes(15);
+
+#[derive(Debug, thiserror::Error)]
+pub enum OutboxError {
+    #[error("Failed to access sync outbox: {0}")]
+    Io(#[from] io::Error),
+    #[error("Sync outbox item is corrupt: {0}")]
+    Format(#[from] serde_json::Error),
+}
+
+/// Something tracked on this desktop that the server does not have yet.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "data", rename_all = "snake_case")]
+pub enum OutboxPayload {
+    Session(TimerSessionCreateDto),
+    Activity(ActivityDataCreateDto),
+    WindowActivity(WindowActivityDataCreateDto),
+}
+
+impl OutboxPayload {
+    /// A finished timer session, under a fresh id.
+    pub fn session(session: &TimerSession) -> Self {
+        Self::Session(TimerSessionCreateDto {
+            id: Uuid::new_v4(),
+            task_id: session.task_id,
+            sub_task_id: session.sub_task_id,
+            start_time: session.start_time.fixed_offset(),
+            end_time: session.end_time.fixed_offset(),
+            elapsed_seconds: i32::try_from(session.elapsed_seconds).unwrap_or(i32::MAX),
+            person: None,
+            note: session.note.clone(),
+        })
+    }
+
+    /// The id the server dedupes uploads by. Activity queued without one
+    /// is given one by [`Outbox::enqueue`].
+    pub fn id(&self) -> Option<Uuid> {
+        match self {
+            Self::Session(session) => Some(session.id),
+            Self::Activity(activity) => activity.id,
+            Self::WindowActivity(activity) => Some(activity.id),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboxItem {
+    pub payload: OutboxPayload,
+    pub queued_at: DateTime<Utc>,
+    /// Failed uploads so far
+    pub attempts: u32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+}
+
+impl OutboxItem {
+    fn id(&self) -> Uuid {
+        self.payload.id().expect("Queued items always have an id")
+    }
+}
+
+/// What the outbox holds, for the UI's "3 sessions pending sync".
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct OutboxStatus {
+    pub pending_sessions: usize,
+    /// Activity and window activity samples
+    pub pending_activity: usize,
+    /// Items the server refused, kept on disk rather than retried
+    pub rejected: usize,
+    /// Earliest retry of an item that failed to upload
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    /// Why the most recent failed upload failed
+    pub last_error: Option<String>,
+}
+
+/// Durable queue of uploads to the server, one file per item, so what
+/// was tracked offline survives restarts until the server has it.
+#[derive(Debug)]
+pub struct Outbox {
+    dir: PathBuf,
+}
+
+impl Outbox {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Queue `payload` for upload, due right away; returns its id.
+    pub fn enqueue(
+        &self,
+        mut payload: OutboxPayload,
+        now: DateTime<Utc>,
+    ) -> Result<Uuid, OutboxError> {
+        if let OutboxPayload::Activity(activity) = &mut payload {
+            activity.id.get_or_insert_with(Uuid::new_v4);
+        }
+        let item = OutboxItem {
+            payload,
+            queued_at: now,
+            attempts: 0,
+            next_attempt_at: now,
+            last_attempt_at: None,
+            last_error: None,
+        };
+        write_item(&self.dir, &item)?;
+        Ok(item.id())
+    }
+
+    /// Queued items, oldest first.
+    pub fn items(&self) -> Result<Vec<OutboxItem>, OutboxError> {
+        let mut items = read_items(&self.dir)?;
+        items.sort_by_key(|item| item.queued_at);
+        Ok(items)
+    }
+
+    /// Items due for an upload at `now`, oldest first.
+    pub fn due(&self, now: DateTime<Utc>) -> Result<Vec<OutboxItem>, OutboxError> {
+        let mut items = self.items()?;
+        items.retain(|item| item.next_attempt_at <= now);
+        Ok(items)
+    }
+
+    /// Forget an item the server now has.
+    pub fn remove(&self, item: &OutboxItem) -> Result<(), OutboxError> {
+        match fs::remove_file(item_path(&self.dir, item.id())) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Record a failed upload and put the next attempt off with backoff.
+    pub fn retry_later(
+        &self,
+        mut item: OutboxItem,
+        error: String,
+        now: DateTime<Utc>,
+    ) -> Result<(), OutboxError> {
+        item.attempts += 1;
+        item.next_attempt_at = now + backoff(item.attempts);
+        item.last_attempt_at = Some(now);
+        item.last_error = Some(error);
+        write_item(&self.dir, &item)
+    }
+
+    /// Move an item the server refused out of the queue. Retrying would
+    /// not help, but the tracked time is kept on disk.
+    pub fn reject(&self, mut item: OutboxItem, error: String) -> Result<(), OutboxError> {
+        item.last_error = Some(error);
+        write_item(&self.dir.join(REJECTED_DIR), &item)?;
+        self.remove(&item)
+    }
+
+    pub fn status(&self) -> Result<OutboxStatus, OutboxError> {
+        let mut status = OutboxStatus {
+            rejected: read_items(&self.dir.join(REJECTED_DIR))?.len(),
+            ..Default::default()
+        };
+        let mut last_failure = None;
+        for item in self.items()? {
+            match item.payload {
+                OutboxPayload::Session(_) => status.pending_sessions += 1,
+                OutboxPayload::Activity(_) | OutboxPayload::WindowActivity(_) => {
+                    status.pending_activity += 1
+                }
+            }
+            let Some(attempted_at) = item.last_attempt_at else {
+                continue;
+            };
+            status.next_attempt_at = Some(
+                status
+                    .next_attempt_at
+                    .map_or(item.next_attempt_at, |at| at.min(item.next_attempt_at)),
+            );
+            if last_failure < Some(attempted_at) {
+                last_failure = Some(attempted_at);
+                status.last_error = item.last_error;
+            }
+        }
+        Ok(status)
+    }
+}
+
+/// Wait before the next upload after `attempts` failed ones.
+pub fn backoff(attempts: u32) -> Duration {
+    let factor = 1 << attempts.saturating_sub(1).min(16);
+    (MIN_BACKOFF * factor).min(MAX_BACKOFF)
+}
+
+fn item_path(dir: &Path, id: Uuid) -> PathBuf {
+    dir.join(format!("{}.json", id))
+}
+
+/// Written next to its final path and renamed over it, so a crash
+/// mid-write leaves the previous version intact.
+fn write_item(dir: &Path, item: &OutboxItem) -> Result<(), OutboxError> {
+    fs::create_dir_all(dir)?;
+    let path = item_path(dir, item.id());
+    let tmp = path.with_extension("json.tmp");
+    let mut file = File::create(&tmp)?;
+    file.write_all(&serde_json::to_vec(item)?)?;
+    file.sync_all()?;
+    fs::rename(&tmp, &path)?;
+    Ok(())
+}
+
+fn read_items(dir: &Path) -> Result<Vec<OutboxItem>, OutboxError> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut items = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        // One bad file should not hold up the rest of the queue
+        match fs::read(&path)
+            .map_err(OutboxError::from)
+            .and_then(|bytes| Ok(serde_json::from_slice(&bytes)?))
+        {
+            Ok(item) => items.push(item),
+            Err(e) => eprintln!("Skipping outbox item {}: {}", path.display(), e),
+        }
+    }
+    Ok(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+    use uuid::Uuid;
+
+    use super::{Outbox, OutboxPayload, OutboxStatus, backoff};
+    use crate::state::TimerSession;
+
+    fn session(minute: u32) -> OutboxPayload {
+        OutboxPayload::session(&TimerSession {
+            task_id: Some(Uuid::from_u128(1)),
+            sub_task_id: None,
+            start_time: Utc.with_ymd_and_hms(2025, 3, 1, 9, minute, 0).unwrap(),
+            end_time: Utc.with_ymd_and_hms(2025, 3, 1, 10, 0, 0).unwrap(),
+            elapsed_seconds: 60,
+            note: None,
+        })
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        assert_eq!(backoff(1), Duration::seconds(5));
+        assert_eq!(backoff(2), Duration::seconds(10));
+        assert_eq!(backoff(5), Duration::seconds(80));
+        assert_eq!(backoff(9), Duration::minutes(15));
+        assert_eq!(backoff(u32::MAX), Duration::minutes(15));
+    }
+
+    #[test]
+    fn items_wait_out_their_backoff() {
+        let dir = tempfile::tempdir().unwrap();
+        let outbox = Outbox::new(dir.path().join("outbox"));
+        assert_eq!(outbox.status().unwrap(), OutboxStatus::default());
+
+        let now = Utc.with_ymd_and_hms(2025, 3, 1, 12, 0, 0).unwrap();
+        let first = outbox.enqueue(session(0), now).unwrap();
+        let second = outbox
+            .enqueue(session(30), now + Duration::seconds(1))
+            .unwrap();
+        let due = outbox.due(now + Duration::seconds(1)).unwrap();
+        assert_eq!(
+            due.iter().map(|item| item.id()).collect::<Vec<_>>(),
+            vec![first, second]
+        );
+
+        let mut due = due.into_iter();
+        let failed = due.next().unwrap();
+        outbox
+            .retry_later(failed, "offline".to_string(), now)
+            .unwrap();
+        outbox.remove(&due.next().unwrap()).unwrap();
+
+        // Not due again until the backoff has passed
+        assert!(outbox.due(now + Duration::seconds(4)).unwrap().is_empty());
+        let retried = outbox.due(now + Duration::seconds(5)).unwrap();
+        assert_eq!(retried.len(), 1);
+        assert_eq!(retried[0].attempts, 1);
+
+        let status = outbox.status().unwrap();
+        assert_eq!(status.pending_sessions, 1);
+        assert_eq!(status.next_attempt_at, Some(now + Duration::seconds(5)));
+        assert_eq!(status.last_error.as_deref(), Some("offline"));
+    }
+
+    #[test]
+    fn rejected_items_leave_the_queue() {
+        let dir = tempfile::tempdir().unwrap();
+        let outbox = Outbox::new(dir.path().to_path_buf());
+        let now = Utc::now();
+        outbox.enqueue(session(0), now).unwrap();
+        // Left over from a crash mid-write, and not an item
+        std::fs::write(dir.path().join("x.json.tmp"), b"{").unwrap();
+
+        let item = outbox.due(now).unwrap().remove(0);
+        outbox.reject(item, "Task not found".to_string()).unwrap();
+        assert!(outbox.due(now).unwrap().is_empty());
+        let status = outbox.status().unwrap();
+        assert_eq!((status.pending_sessions, status.rejected), (0, 1));
+    }
+}
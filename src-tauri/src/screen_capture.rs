@@ -0,0 +1,343 @@
+use std::{
+    fmt::Display,
+    panic::{self, AssertUnwindSafe},
+    sync::mpsc::{self, RecvTimeoutError, Sender},
+    thread,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use image::RgbaImage;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Shortest wait between two captures.
+const MIN_CAPTURE_DELAY: Duration = Duration::from_secs(5);
+/// Shortest window the wait is picked from, whatever the configured interval.
+const MIN_CAPTURE_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Debug, thiserror::Error)]
+pub enum CaptureError {
+    #[error("Failed to list monitors: {0}")]
+    Monitors(String),
+}
+
+/// One monitor's capture.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Capture {
+    /// Index of the monitor among all monitors
+    pub monitor: usize,
+    pub name: String,
+    pub image: RgbaImage,
+}
+
+/// Where screenshots are taken from; faked in tests.
+pub trait ScreenCapturer {
+    /// Capture every monitor. Monitors that fail to capture are left out.
+    fn capture_all(&mut self) -> Result<Vec<Capture>, CaptureError>;
+}
+
+impl<C: ScreenCapturer + ?Sized> ScreenCapturer for Box<C> {
+    fn capture_all(&mut self) -> Result<Vec<Capture>, CaptureError> {
+        (**self).capture_all()
+    }
+}
+
+/// Library screenshots are captured with, part of the app config.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptureBackend {
+    #[default]
+    Xcap,
+    Screenshots,
+}
+
+impl CaptureBackend {
+    pub fn capturer(self) -> Box<dyn ScreenCapturer> {
+        match self {
+            Self::Xcap => Box::new(xcap_backend::XcapCapturer),
+            Self::Screenshots => Box::new(screenshots_backend::ScreenshotsCapturer),
+        }
+    }
+}
+
+/// Number each monitor by its position among all of them, so a monitor
+/// keeps its index when another one fails to capture.
+fn collect_captures<E: Display>(
+    monitors: impl IntoIterator<Item = (String, Result<RgbaImage, E>)>,
+) -> Vec<Capture> {
+    monitors
+        .into_iter()
+        .enumerate()
+        .filter_map(|(monitor, (name, image))| match image {
+            Ok(image) => Some(Capture {
+                monitor,
+                name,
+                image,
+            }),
+            Err(e) => {
+                eprintln!("[Screenshot] Failed to capture monitor {}: {}", name, e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Random wait before the next capture, so captures cannot be
+/// anticipated: at least `MIN_CAPTURE_DELAY` and at most `interval`, or
+/// `MIN_CAPTURE_INTERVAL` when that is shorter.
+pub fn next_delay<R: Rng + ?Sized>(rng: &mut R, interval: Duration) -> Duration {
+    let max = interval.max(MIN_CAPTURE_INTERVAL).as_secs();
+    Duration::from_secs(rng.gen_range(MIN_CAPTURE_DELAY.as_secs()..=max))
+}
+
+/// Captures every monitor on a dedicated thread at random delays, handing
+/// each capture to the sink.
+#[derive(Debug, Default)]
+pub struct ScreenshotScheduler {
+    stop: Option<Sender<()>>,
+}
+
+impl ScreenshotScheduler {
+    /// Start capturing, stopping a scheduler already running. The capturer
+    /// is made on the scheduler thread, as display handles cannot be sent
+    /// across.
+    pub fn start<C, F, D, K>(&mut self, make_capturer: F, mut next_delay: D, sink: K)
+    where
+        C: ScreenCapturer,
+        F: FnOnce() -> C + Send + 'static,
+        D: FnMut() -> Duration + Send + 'static,
+        K: Fn(Capture, DateTime<Utc>) + Send + 'static,
+    {
+        self.stop();
+        let (tx, rx) = mpsc::channel();
+        self.stop = Some(tx);
+        let spawned = thread::Builder::new()
+            .name("screenshot-scheduler".into())
+            .spawn(move || {
+                let Ok(mut capturer) = panic::catch_unwind(AssertUnwindSafe(make_capturer)) else {
+                    eprintln!("[Screenshot] No screen capturer, not taking screenshots");
+                    return;
+                };
+                loop {
+                    let delay = next_delay();
+                    println!(
+                        "[Screenshot] Waiting {}s until next capture...",
+                        delay.as_secs()
+                    );
+                    // Stopped, or the scheduler was dropped
+                    if !matches!(rx.recv_timeout(delay), Err(RecvTimeoutError::Timeout)) {
+                        break;
+                    }
+                    let captured_at = Utc::now();
+                    match capturer.capture_all() {
+                        Ok(captures) => {
+                            println!("[Screenshot] Captured {} monitors", captures.len());
+                            for capture in captures {
+                                sink(capture, captured_at);
+                            }
+                        }
+                        Err(e) => eprintln!("[Screenshot] {}", e),
+                    }
+                }
+            });
+        if let Err(e) = spawned {
+            eprintln!("[Screenshot] Failed to start screenshots: {}", e);
+        }
+    }
+
+    /// Stop capturing. A capture already being taken is still handed to
+    /// the sink; no other is taken.
+    pub fn stop(&mut self) {
+        if let Some(stop) = self.stop.take() {
+            // The thread is gone already if this fails
+            let _ = stop.send(());
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.stop.is_some()
+    }
+}
+
+/// Capture with xcap, which also supports Wayland through the desktop
+/// portal.
+mod xcap_backend {
+    use xcap::Monitor;
+
+    use super::{Capture, CaptureError, ScreenCapturer, collect_captures};
+
+    pub struct XcapCapturer;
+
+    impl ScreenCapturer for XcapCapturer {
+        fn capture_all(&mut self) -> Result<Vec<Capture>, CaptureError> {
+            let monitors = Monitor::all().map_err(|e| CaptureError::Monitors(e.to_string()))?;
+            Ok(collect_captures(monitors.iter().map(|monitor| {
+                // xcap is on a newer `image` than this crate, so the pixels
+                // are moved over as they are
+                let image = monitor.capture_image().and_then(|image| {
+                    let (width, height) = (image.width(), image.height());
+                    image::RgbaImage::from_raw(width, height, image.into_raw())
+                        .ok_or_else(|| xcap::XCapError::new("Capture has the wrong size"))
+                });
+                (monitor.name().to_string(), image)
+            })))
+        }
+    }
+}
+
+/// Capture with the `screenshots` crate.
+mod screenshots_backend {
+    use screenshots::Screen;
+
+    use super::{Capture, CaptureError, ScreenCapturer, collect_captures};
+
+    pub struct ScreenshotsCapturer;
+
+    impl ScreenCapturer for ScreenshotsCapturer {
+        fn capture_all(&mut self) -> Result<Vec<Capture>, CaptureError> {
+            let screens = Screen::all().map_err(|e| CaptureError::Monitors(e.to_string()))?;
+            Ok(collect_captures(screens.iter().map(|screen| {
+                // The crate has no monitor names
+                let name = format!("Display {}", screen.display_info.id);
+                (name, screen.capture())
+            })))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::mpsc, time::Duration};
+
+    use image::RgbaImage;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::{
+        Capture, CaptureError, ScreenCapturer, ScreenshotScheduler, collect_captures, next_delay,
+    };
+
+    #[test]
+    fn delays_are_random_within_the_interval() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let delays: Vec<_> = (0..200)
+            .map(|_| next_delay(&mut rng, Duration::from_secs(60)))
+            .collect();
+        assert!(
+            delays
+                .iter()
+                .all(|delay| (5..=60).contains(&delay.as_secs()))
+        );
+        let min = delays.iter().min().unwrap().as_secs();
+        let max = delays.iter().max().unwrap().as_secs();
+        assert!(min < 15 && max > 50, "{}..{}", min, max);
+
+        // Short intervals still leave room for randomness
+        for _ in 0..50 {
+            let delay = next_delay(&mut rng, Duration::from_secs(1)).as_secs();
+            assert!((5..=10).contains(&delay));
+        }
+    }
+
+    #[test]
+    fn failed_monitors_keep_the_others_numbered() {
+        let image = RgbaImage::new(2, 2);
+        let captures = collect_captures([
+            ("HDMI-1".to_string(), Ok(image.clone())),
+            ("DP-1".to_string(), Err("unplugged")),
+            ("eDP-1".to_string(), Ok(image.clone())),
+        ]);
+        assert_eq!(
+            captures
+                .iter()
+                .map(|capture| (capture.monitor, capture.name.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(0, "HDMI-1"), (2, "eDP-1")]
+        );
+    }
+
+    /// In-memory monitors of the given widths.
+    struct FakeScreens(Vec<u32>);
+
+    impl ScreenCapturer for FakeScreens {
+        fn capture_all(&mut self) -> Result<Vec<Capture>, CaptureError> {
+            if self.0.is_empty() {
+                return Err(CaptureError::Monitors("no display".to_string()));
+            }
+            Ok(self
+                .0
+                .iter()
+                .enumerate()
+                .map(|(monitor, &width)| Capture {
+                    monitor,
+                    name: format!("Fake {}", monitor),
+                    image: RgbaImage::new(width, 1),
+                })
+                .collect())
+        }
+    }
+
+    #[test]
+    fn scheduler_captures_every_monitor_at_once() {
+        let (tx, rx) = mpsc::channel();
+        let mut scheduler = ScreenshotScheduler::default();
+        scheduler.start(
+            || FakeScreens(vec![1920, 1280]),
+            || Duration::from_millis(5),
+            move |capture, at| {
+                tx.send((capture.monitor, capture.image.width(), at))
+                    .unwrap()
+            },
+        );
+
+        let (first, second) = (rx.recv().unwrap(), rx.recv().unwrap());
+        assert_eq!((first.0, first.1), (0, 1920));
+        assert_eq!((second.0, second.1), (1, 1280));
+        // Taken together, so stored as one capture
+        assert_eq!(first.2, second.2);
+        scheduler.stop();
+    }
+
+    #[test]
+    fn nothing_is_captured_after_stop() {
+        let (tx, rx) = mpsc::channel();
+        let (delays_tx, delays) = mpsc::channel();
+        let mut scheduler = ScreenshotScheduler::default();
+        scheduler.start(
+            || FakeScreens(vec![800]),
+            move || {
+                delays_tx.send(()).unwrap();
+                Duration::from_secs(60)
+            },
+            move |capture, _| tx.send(capture.monitor).unwrap(),
+        );
+        assert!(scheduler.is_running());
+
+        // Waiting out the first delay when stopped
+        delays.recv_timeout(Duration::from_secs(5)).unwrap();
+        scheduler.stop();
+        assert!(!scheduler.is_running());
+        // The thread ends without capturing, dropping the sink
+        assert!(rx.iter().next().is_none());
+        assert!(delays.iter().next().is_none());
+    }
+
+    #[test]
+    fn capture_errors_do_not_stop_the_scheduler() {
+        let (tx, rx) = mpsc::channel();
+        let mut scheduler = ScreenshotScheduler::default();
+        let mut polls = 0;
+        scheduler.start(
+            || FakeScreens(vec![]),
+            move || {
+                polls += 1;
+                tx.send(polls).unwrap();
+                Duration::from_millis(1)
+            },
+            |_, _| panic!("Nothing to capture"),
+        );
+        // Still scheduling captures after failed ones
+        assert_eq!(rx.iter().nth(4), Some(5));
+        scheduler.stop();
+    }
+}
@@ -0,0 +1,256 @@
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    screen_capture::{Capture, CaptureError, ScreenCapturer},
+    window_tracker::{ActiveWindow, WindowBackend},
+};
+
+/// What employees can do to limit what screenshots show, part of the app
+/// config.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScreenshotPrivacy {
+    /// Gaussian blur applied before a screenshot is stored, as its sigma in
+    /// pixels of the stored image; 0 for none
+    pub blur: u8,
+    /// No screenshots while one of these applications has focus; matched
+    /// against the application name, ignoring case
+    pub excluded_apps: Vec<String>,
+    /// No screenshots while the focused window's title contains one of
+    /// these, ignoring case
+    pub excluded_titles: Vec<String>,
+    /// How long a screenshot can be deleted for after it was taken
+    pub review_minutes: u32,
+}
+
+impl Default for ScreenshotPrivacy {
+    fn default() -> Self {
+        Self {
+            blur: 0,
+            excluded_apps: Vec::new(),
+            excluded_titles: Vec::new(),
+            review_minutes: 10,
+        }
+    }
+}
+
+impl ScreenshotPrivacy {
+    pub fn review_period(&self) -> chrono::Duration {
+        chrono::Duration::minutes(self.review_minutes.into())
+    }
+
+    pub fn has_exclusions(&self) -> bool {
+        !self.excluded_apps.is_empty() || !self.excluded_titles.is_empty()
+    }
+
+    /// Whether no screenshot may be taken while `window` has focus.
+    pub fn excludes(&self, window: &ActiveWindow) -> bool {
+        let title = window.title.to_lowercase();
+        self.excluded_apps
+            .iter()
+            .any(|app| app.trim().eq_ignore_ascii_case(window.app_name.trim()))
+            || self
+                .excluded_titles
+                .iter()
+                .map(|excluded| excluded.trim().to_lowercase())
+                .any(|excluded| !excluded.is_empty() && title.contains(&excluded))
+    }
+}
+
+/// Screenshots paused by the user until a given time, shared with the
+/// capture thread.
+#[derive(Debug, Clone, Default)]
+pub struct ScreenshotPause(Arc<Mutex<Option<DateTime<Utc>>>>);
+
+impl ScreenshotPause {
+    pub fn pause_until(&self, until: DateTime<Utc>) {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = Some(until);
+    }
+
+    pub fn resume(&self) {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+
+    /// When screenshots resume; `None` when they are not paused at `now`.
+    pub fn paused_until(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .filter(|&until| until > now)
+    }
+}
+
+/// Captures through another capturer, except while screenshots are paused
+/// or an excluded window has focus.
+pub struct PrivacyFilter<C, W> {
+    capturer: C,
+    /// Only needed when there are exclusions
+    windows: Option<W>,
+    privacy: ScreenshotPrivacy,
+    pause: ScreenshotPause,
+}
+
+impl<C: ScreenCapturer, W: WindowBackend> PrivacyFilter<C, W> {
+    pub fn new(
+        capturer: C,
+        windows: Option<W>,
+        privacy: ScreenshotPrivacy,
+        pause: ScreenshotPause,
+    ) -> Self {
+        Self {
+            capturer,
+            windows,
+            privacy,
+            pause,
+        }
+    }
+
+    fn suppressed(&mut self) -> bool {
+        if let Some(until) = self.pause.paused_until(Utc::now()) {
+            println!("[Screenshot] Paused until {}, skipping capture", until);
+            return true;
+        }
+        if !self.privacy.has_exclusions() {
+            return false;
+        }
+        // Without a way to read the focused window, e.g. on Wayland,
+        // exclusions cannot apply
+        match self.windows.as_mut().map(|windows| windows.active_window()) {
+            Some(Ok(Some(window))) if self.privacy.excludes(&window) => {
+                println!(
+                    "[Screenshot] {} is excluded, skipping capture",
+                    window.app_name
+                );
+                true
+            }
+            Some(Err(e)) => {
+                eprintln!("[Screenshot] Failed to read the focused window: {}", e);
+                false
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<C: ScreenCapturer, W: WindowBackend> ScreenCapturer for PrivacyFilter<C, W> {
+    fn capture_all(&mut self) -> Result<Vec<Capture>, CaptureError> {
+        if self.suppressed() {
+            return Ok(Vec::new());
+        }
+        self.capturer.capture_all()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use chrono::{Duration, Utc};
+    use image::RgbaImage;
+
+    use super::{PrivacyFilter, ScreenshotPause, ScreenshotPrivacy};
+    use crate::{
+        screen_capture::{Capture, CaptureError, ScreenCapturer},
+        window_tracker::{ActiveWindow, WindowBackend, WindowError},
+    };
+
+    fn window(app_name: &str, title: &str) -> ActiveWindow {
+        ActiveWindow {
+            app_name: app_name.to_string(),
+            title: title.to_string(),
+            pid: None,
+        }
+    }
+
+    fn privacy() -> ScreenshotPrivacy {
+        ScreenshotPrivacy {
+            excluded_apps: vec!["KeePassXC".to_string()],
+            excluded_titles: vec!["Private Browsing".to_string(), " ".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn exclusions_match_apps_and_titles() {
+        let privacy = privacy();
+        assert!(privacy.excludes(&window("keepassxc", "Passwords")));
+        assert!(privacy.excludes(&window("firefox", "Bank — private browsing")));
+        assert!(!privacy.excludes(&window("firefox", "Docs")));
+        // Apps match by name, not part of it
+        assert!(!privacy.excludes(&window("KeePass", "Passwords")));
+        assert!(!ScreenshotPrivacy::default().has_exclusions());
+    }
+
+    #[test]
+    fn pause_expires() {
+        let pause = ScreenshotPause::default();
+        let now = Utc::now();
+        assert_eq!(pause.paused_until(now), None);
+        pause.pause_until(now + Duration::minutes(15));
+        // Shared with clones, like the capture thread's
+        let shared = pause.clone();
+        assert_eq!(shared.paused_until(now), Some(now + Duration::minutes(15)));
+        assert_eq!(shared.paused_until(now + Duration::minutes(15)), None);
+        pause.resume();
+        assert_eq!(shared.paused_until(now), None);
+    }
+
+    /// One in-memory monitor, counting captures.
+    struct Screen(Rc<RefCell<u32>>);
+
+    impl ScreenCapturer for Screen {
+        fn capture_all(&mut self) -> Result<Vec<Capture>, CaptureError> {
+            *self.0.borrow_mut() += 1;
+            Ok(vec![Capture {
+                monitor: 0,
+                name: "Fake".to_string(),
+                image: RgbaImage::new(1, 1),
+            }])
+        }
+    }
+
+    /// Focus as set by the test.
+    struct Focus(Rc<RefCell<Result<Option<ActiveWindow>, ()>>>);
+
+    impl WindowBackend for Focus {
+        fn active_window(&mut self) -> Result<Option<ActiveWindow>, WindowError> {
+            self.0
+                .borrow()
+                .clone()
+                .map_err(|_| WindowError::Unsupported)
+        }
+    }
+
+    #[test]
+    fn filter_skips_captures_while_paused_or_excluded() {
+        let captures = Rc::new(RefCell::new(0));
+        let focus = Rc::new(RefCell::new(Ok(Some(window("code", "main.rs")))));
+        let pause = ScreenshotPause::default();
+        let mut filter = PrivacyFilter::new(
+            Screen(captures.clone()),
+            Some(Focus(focus.clone())),
+            privacy(),
+            pause.clone(),
+        );
+
+        assert_eq!(filter.capture_all().unwrap().len(), 1);
+
+        *focus.borrow_mut() = Ok(Some(window("KeePassXC", "Vault")));
+        assert!(filter.capture_all().unwrap().is_empty());
+        // Nothing focused, or no way to tell
+        *focus.borrow_mut() = Ok(None);
+        assert_eq!(filter.capture_all().unwrap().len(), 1);
+        *focus.borrow_mut() = Err(());
+        assert_eq!(filter.capture_all().unwrap().len(), 1);
+
+        pause.pause_until(Utc::now() + Duration::minutes(5));
+        assert!(filter.capture_all().unwrap().is_empty());
+        pause.resume();
+        assert_eq!(filter.capture_all().unwrap().len(), 1);
+        // The screen itself is never captured when skipped
+        assert_eq!(*captures.borrow(), 4);
+    }
+}
@@ -0,0 +1,523 @@
+use std::{
+    collections::HashSet,
+    fs::{self, File, OpenOptions},
+    io::{self, BufWriter, Write},
+    path::{Component, Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use image::{
+    DynamicImage, ImageEncoder, RgbaImage,
+    codecs::{jpeg::JpegEncoder, webp::WebPEncoder},
+    imageops::FilterType,
+};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use uuid::Uuid;
+
+/// Index of everything captured, inside the screenshot directory.
+const INDEX_FILE: &str = "index.jsonl";
+/// Record of the screenshots users deleted, next to the index.
+const DELETIONS_FILE: &str = "deletions.jsonl";
+
+#[derive(Debug, thiserror::Error)]
+pub enum ScreenshotStoreError {
+    #[error("Failed to access screenshot directory: {0}")]
+    Io(#[from] io::Error),
+    #[error("Failed to encode screenshot: {0}")]
+    Encode(#[from] image::ImageError),
+    #[error("Screenshot index is corrupt: {0}")]
+    Index(#[from] serde_json::Error),
+    #[error("Screenshot not found")]
+    NotFound,
+    #[error("Screenshot is past its review period and can no longer be deleted")]
+    Final,
+}
+
+/// How screenshots are encoded on disk.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScreenshotFormat {
+    #[default]
+    Jpeg,
+    /// Lossless, so `quality` does not apply
+    Webp,
+}
+
+impl ScreenshotFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Jpeg => "jpg",
+            Self::Webp => "webp",
+        }
+    }
+}
+
+/// Where and how screenshots are kept, part of the app config.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScreenshotStorage {
+    /// Relative to the app data directory
+    pub directory: PathBuf,
+    pub format: ScreenshotFormat,
+    /// JPEG quality (1-100)
+    pub quality: u8,
+    /// Wider captures are scaled down to this width
+    pub max_width: Option<u32>,
+}
+
+impl Default for ScreenshotStorage {
+    fn default() -> Self {
+        Self {
+            directory: PathBuf::from("screenshots"),
+            format: ScreenshotFormat::Jpeg,
+            quality: 85,
+            max_width: None,
+        }
+    }
+}
+
+impl ScreenshotStorage {
+    /// The screenshot directory. Only the plain parts of `directory` are
+    /// used, so it cannot point outside the app data directory.
+    pub fn root(&self, data_dir: &Path) -> PathBuf {
+        self.directory
+            .components()
+            .filter(|component| matches!(component, Component::Normal(_)))
+            .fold(data_dir.to_path_buf(), |root, component| {
+                root.join(component)
+            })
+    }
+}
+
+/// A saved screenshot, as listed in the index.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScreenshotRecord {
+    pub id: Uuid,
+    /// Subfolder of the timer session it was taken in
+    pub session: String,
+    pub task_id: Option<Uuid>,
+    /// Index of the monitor among those captured
+    pub monitor: usize,
+    pub monitor_name: String,
+    /// Relative to the screenshot directory
+    pub path: PathBuf,
+    pub format: ScreenshotFormat,
+    /// Size as saved, after any downscaling
+    pub width: u32,
+    pub height: u32,
+    /// Size as captured
+    pub captured_width: u32,
+    pub captured_height: u32,
+    pub bytes: u64,
+    pub captured_at: DateTime<Utc>,
+}
+
+/// A screenshot the user deleted during its review period.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScreenshotDeletion {
+    pub id: Uuid,
+    pub session: String,
+    pub path: PathBuf,
+    pub captured_at: DateTime<Utc>,
+    pub deleted_at: DateTime<Utc>,
+}
+
+/// Saves the screenshots of one timer session into its own subfolder of
+/// the screenshot directory, listing each in the index.
+#[derive(Debug, Clone)]
+pub struct ScreenshotStore {
+    root: PathBuf,
+    session: String,
+    task_id: Option<Uuid>,
+    storage: ScreenshotStorage,
+    /// Sigma of the blur applied before saving; 0 for none
+    blur: u8,
+}
+
+impl ScreenshotStore {
+    /// Store for the session started at `session_start` on `task_id`.
+    pub fn new(
+        data_dir: &Path,
+        storage: ScreenshotStorage,
+        session_start: DateTime<Utc>,
+        task_id: Option<Uuid>,
+    ) -> Self {
+        Self {
+            root: storage.root(data_dir),
+            session: session_start.format("%Y%m%d_%H%M%S").to_string(),
+            task_id,
+            storage,
+            blur: 0,
+        }
+    }
+
+    /// Blur screenshots with `sigma` before saving them.
+    pub fn with_blur(self, sigma: u8) -> Self {
+        Self {
+            blur: sigma,
+            ..self
+        }
+    }
+
+    /// Encode and save a capture of monitor `monitor`.
+    pub fn save(
+        &self,
+        monitor: usize,
+        monitor_name: &str,
+        image: &RgbaImage,
+        captured_at: DateTime<Utc>,
+    ) -> Result<ScreenshotRecord, ScreenshotStoreError> {
+        let id = Uuid::new_v4();
+        let format = self.storage.format;
+        // Unique even for monitors captured in the same second
+        let name = format!(
+            "{}_m{}_{}.{}",
+            captured_at.format("%Y%m%d_%H%M%S"),
+            monitor,
+            &id.simple().to_string()[..8],
+            format.extension()
+        );
+        let path = Path::new(&self.session).join(name);
+        let (captured_width, captured_height) = image.dimensions();
+        let mut image = self.downscale(image);
+        // After downscaling, so the blur is as strong whatever the size
+        if self.blur > 0 {
+            image = image.blur(self.blur.into());
+        }
+        fs::create_dir_all(self.root.join(&self.session))?;
+        self.encode(&image, &self.root.join(&path))?;
+
+        let record = ScreenshotRecord {
+            id,
+            session: self.session.clone(),
+            task_id: self.task_id,
+            monitor,
+            monitor_name: monitor_name.to_string(),
+            bytes: fs::metadata(self.root.join(&path))?.len(),
+            path,
+            format,
+            width: image.width(),
+            height: image.height(),
+            captured_width,
+            captured_height,
+            captured_at,
+        };
+        append_line(&self.root.join(INDEX_FILE), &record)?;
+        Ok(record)
+    }
+
+    fn downscale(&self, image: &RgbaImage) -> DynamicImage {
+        let image = DynamicImage::ImageRgba8(image.clone());
+        match self.storage.max_width {
+            Some(max_width) if image.width() > max_width => {
+                let height =
+                    (image.height() as u64 * max_width as u64 / image.width() as u64).max(1) as u32;
+                image.resize_exact(max_width, height, FilterType::Triangle)
+            }
+            _ => image,
+        }
+    }
+
+    fn encode(&self, image: &DynamicImage, path: &Path) -> Result<(), ScreenshotStoreError> {
+        let rgb = image.to_rgb8();
+        let mut file = BufWriter::new(File::create(path)?);
+        match self.storage.format {
+            ScreenshotFormat::Jpeg => {
+                let quality = self.storage.quality.clamp(1, 100);
+                JpegEncoder::new_with_quality(&mut file, quality).write_image(
+                    &rgb,
+                    rgb.width(),
+                    rgb.height(),
+                    image::ColorType::Rgb8,
+                )?
+            }
+            ScreenshotFormat::Webp => WebPEncoder::new_lossless(&mut file).write_image(
+                &rgb,
+                rgb.width(),
+                rgb.height(),
+                image::ColorType::Rgb8,
+            )?,
+        }
+        file.flush()?;
+        Ok(())
+    }
+}
+
+/// Screenshots listed in the index of `root`, oldest first.
+pub fn read_index(root: &Path) -> Result<Vec<ScreenshotRecord>, ScreenshotStoreError> {
+    read_lines(&root.join(INDEX_FILE))
+}
+
+/// Screenshots deleted from `root`, oldest first.
+pub fn read_deletions(root: &Path) -> Result<Vec<ScreenshotDeletion>, ScreenshotStoreError> {
+    read_lines(&root.join(DELETIONS_FILE))
+}
+
+/// Screenshots in the index that were not deleted, oldest first.
+pub fn kept_screenshots(root: &Path) -> Result<Vec<ScreenshotRecord>, ScreenshotStoreError> {
+    let deleted: HashSet<_> = read_deletions(root)?
+        .into_iter()
+        .map(|deletion| deletion.id)
+        .collect();
+    let mut records = read_index(root)?;
+    records.retain(|record| !deleted.contains(&record.id));
+    Ok(records)
+}
+
+/// Delete a screenshot taken less than `review_period` before `now`. The
+/// deletion is recorded before the file is removed, so none goes
+/// unrecorded.
+pub fn delete_screenshot(
+    root: &Path,
+    id: Uuid,
+    review_period: chrono::Duration,
+    now: DateTime<Utc>,
+) -> Result<ScreenshotDeletion, ScreenshotStoreError> {
+    let record = kept_screenshots(root)?
+        .into_iter()
+        .find(|record| record.id == id)
+        .ok_or(ScreenshotStoreError::NotFound)?;
+    if now >= record.captured_at + review_period {
+        return Err(ScreenshotStoreError::Final);
+    }
+    let deletion = ScreenshotDeletion {
+        id,
+        session: record.session,
+        captured_at: record.captured_at,
+        deleted_at: now,
+        path: record.path,
+    };
+    append_line(&root.join(DELETIONS_FILE), &deletion)?;
+    match fs::remove_file(root.join(&deletion.path)) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(deletion),
+    }
+}
+
+fn read_lines<T: DeserializeOwned>(path: &Path) -> Result<Vec<T>, ScreenshotStoreError> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut items = Vec::new();
+    for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+        // A line cut short by a crash should not hide the rest
+        match serde_json::from_str(line) {
+            Ok(item) => items.push(item),
+            Err(e) => eprintln!("Skipping entry of {}: {}", path.display(), e),
+        }
+    }
+    Ok(items)
+}
+
+fn append_line<T: Serialize>(path: &Path, item: &T) -> Result<(), ScreenshotStoreError> {
+    let mut line = serde_json::to_vec(item)?;
+    line.push(b'\n');
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(&line)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use chrono::{TimeZone, Utc};
+    use image::{Rgba, RgbaImage};
+    use uuid::Uuid;
+
+    use super::{
+        ScreenshotFormat, ScreenshotStorage, ScreenshotStore, ScreenshotStoreError,
+        delete_screenshot, kept_screenshots, read_deletions, read_index,
+    };
+
+    fn capture(width: u32, height: u32) -> RgbaImage {
+        RgbaImage::from_fn(width, height, |x, y| {
+            Rgba([(x % 256) as u8, (y % 256) as u8, 128, 255])
+        })
+    }
+
+    #[test]
+    fn directory_stays_under_the_data_dir() {
+        let storage = |directory: &str| ScreenshotStorage {
+            directory: PathBuf::from(directory),
+            ..Default::default()
+        };
+        let data_dir = Path::new("/data");
+        assert_eq!(
+            storage("shots/work").root(data_dir),
+            Path::new("/data/shots/work")
+        );
+        assert_eq!(
+            storage("/etc/../x").root(data_dir),
+            Path::new("/data/etc/x")
+        );
+        assert_eq!(storage("").root(data_dir), data_dir);
+    }
+
+    #[test]
+    fn saves_each_monitor_under_its_session_and_indexes_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let started = Utc.with_ymd_and_hms(2025, 3, 1, 9, 0, 0).unwrap();
+        let at = Utc.with_ymd_and_hms(2025, 3, 1, 9, 5, 0).unwrap();
+        let task = Some(Uuid::from_u128(1));
+        let store = ScreenshotStore::new(
+            dir.path(),
+            ScreenshotStorage {
+                max_width: Some(40),
+                ..Default::default()
+            },
+            started,
+            task,
+        );
+
+        // Two monitors in the same second
+        let first = store.save(0, "HDMI-1", &capture(80, 30), at).unwrap();
+        let second = store.save(1, "eDP-1", &capture(20, 10), at).unwrap();
+        assert_ne!(first.path, second.path);
+        assert!(first.path.starts_with("20250301_090000"));
+        let name = first.path.file_name().unwrap().to_str().unwrap();
+        assert!(name.starts_with("20250301_090500_m0_"), "{}", name);
+        assert!(name.ends_with(".jpg"));
+
+        // Scaled down to the max width, keeping the aspect ratio
+        assert_eq!((first.width, first.height), (40, 15));
+        assert_eq!((first.captured_width, first.captured_height), (80, 30));
+        // Narrower captures are kept as they are
+        assert_eq!((second.width, second.height), (20, 10));
+
+        let root = dir.path().join("screenshots");
+        let saved = image::open(root.join(&first.path)).unwrap();
+        assert_eq!((saved.width(), saved.height()), (40, 15));
+        assert_eq!(
+            first.bytes,
+            std::fs::metadata(root.join(&first.path)).unwrap().len()
+        );
+
+        let index = read_index(&root).unwrap();
+        assert_eq!(index, vec![first, second]);
+        assert_eq!(index[1].task_id, task);
+        assert_eq!(index[1].monitor_name, "eDP-1");
+    }
+
+    #[test]
+    fn quality_and_format_are_applied() {
+        let dir = tempfile::tempdir().unwrap();
+        let save = |format, quality| {
+            let store = ScreenshotStore::new(
+                dir.path(),
+                ScreenshotStorage {
+                    format,
+                    quality,
+                    ..Default::default()
+                },
+                Utc::now(),
+                None,
+            );
+            store.save(0, "", &capture(300, 200), Utc::now()).unwrap()
+        };
+
+        let low = save(ScreenshotFormat::Jpeg, 10);
+        let high = save(ScreenshotFormat::Jpeg, 95);
+        assert!(low.bytes < high.bytes);
+
+        let webp = save(ScreenshotFormat::Webp, 85);
+        assert_eq!(webp.path.extension().unwrap(), "webp");
+        let path = dir.path().join("screenshots").join(&webp.path);
+        assert_eq!(
+            image::ImageFormat::from_path(&path).unwrap(),
+            image::ImageFormat::WebP
+        );
+        assert_eq!(
+            read_index(&dir.path().join("screenshots")).unwrap().len(),
+            3
+        );
+    }
+
+    #[test]
+    fn truncated_index_entries_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let store =
+            ScreenshotStore::new(dir.path(), ScreenshotStorage::default(), Utc::now(), None);
+        let record = store.save(0, "", &capture(4, 4), Utc::now()).unwrap();
+        let index = dir.path().join("screenshots").join("index.jsonl");
+        let mut contents = std::fs::read_to_string(&index).unwrap();
+        contents.push_str("{\"id\":");
+        std::fs::write(&index, contents).unwrap();
+
+        assert_eq!(
+            read_index(&dir.path().join("screenshots")).unwrap(),
+            vec![record]
+        );
+        assert!(read_index(&dir.path().join("missing")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn blur_is_applied_before_saving() {
+        let dir = tempfile::tempdir().unwrap();
+        // Black and white stripes
+        let stripes = RgbaImage::from_fn(40, 40, |x, _| {
+            let v = if x % 2 == 0 { 0 } else { 255 };
+            Rgba([v, v, v, 255])
+        });
+        let save = |blur| {
+            let store = ScreenshotStore::new(
+                dir.path(),
+                ScreenshotStorage {
+                    format: ScreenshotFormat::Webp,
+                    ..Default::default()
+                },
+                Utc::now(),
+                None,
+            )
+            .with_blur(blur);
+            let record = store.save(0, "", &stripes, Utc::now()).unwrap();
+            image::open(dir.path().join("screenshots").join(record.path))
+                .unwrap()
+                .to_luma8()
+        };
+
+        let sharp = save(0);
+        assert_eq!((sharp[(10, 20)][0], sharp[(11, 20)][0]), (0, 255));
+        // The stripes blend into grey
+        let blurred = save(4);
+        let grey = |v: u8| (100..=155).contains(&v);
+        assert!(grey(blurred[(10, 20)][0]) && grey(blurred[(11, 20)][0]));
+    }
+
+    #[test]
+    fn screenshots_can_be_deleted_during_review() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("screenshots");
+        let at = Utc.with_ymd_and_hms(2025, 3, 1, 9, 5, 0).unwrap();
+        let store = ScreenshotStore::new(dir.path(), ScreenshotStorage::default(), at, None);
+        let kept = store.save(0, "", &capture(4, 4), at).unwrap();
+        let deleted = store.save(1, "", &capture(4, 4), at).unwrap();
+        let review = chrono::Duration::minutes(10);
+
+        let deletion =
+            delete_screenshot(&root, deleted.id, review, at + chrono::Duration::minutes(9))
+                .unwrap();
+        assert!(!root.join(&deleted.path).exists());
+        assert_eq!(read_deletions(&root).unwrap(), vec![deletion]);
+        // Still in the index, but not kept
+        assert_eq!(read_index(&root).unwrap().len(), 2);
+        assert_eq!(kept_screenshots(&root).unwrap(), vec![kept.clone()]);
+        assert!(matches!(
+            delete_screenshot(&root, deleted.id, review, at),
+            Err(ScreenshotStoreError::NotFound)
+        ));
+
+        // Final once the review period is over
+        assert!(matches!(
+            delete_screenshot(&root, kept.id, review, at + review),
+            Err(ScreenshotStoreError::Final)
+        ));
+        assert!(root.join(&kept.path).exists());
+        assert!(matches!(
+            delete_screenshot(&root, Uuid::new_v4(), review, at),
+            Err(ScreenshotStoreError::NotFound)
+        ));
+    }
+}
@@ -0,0 +1,76 @@
+use once_cell::sync::OnceCell;
+use ot_server::budget_dto::{BudgetAlertDto, ProjectBudgetDto};
+use reqwest::{Client, ClientBuilder};
+use std::time::Duration;
+use thiserror::Error;
+use uuid::Uuid;
+
+const BASE_URL: &str = "http://localhost:4000/api/";
+const TIMEOUT_SECONDS: u64 = 30;
+
+#[derive(Error, Debug)]
+pub enum BudgetError {
+    #[error("Network error: {0}")]
+    NetworkError(#[from] reqwest::Error),
+    #[error("Serialization error: {0}")]
+    SerializationError(#[from] serde_json::Error),
+}
+
+pub struct BudgetService {
+    client: OnceCell<Client>,
+}
+
+impl BudgetService {
+    pub fn new() -> Self {
+        let client = ClientBuilder::new()
+            .timeout(Duration::from_secs(TIMEOUT_SECONDS))
+            .build()
+            .expect("Failed to create HTTP client");
+        let cell = OnceCell::new();
+        cell.set(client).expect("Failed to set client");
+        Self { client: cell }
+    }
+
+    pub async fn get_project_budget(
+        &self,
+        project_id: Uuid,
+    ) -> Result<ProjectBudgetDto, BudgetError> {
+        let client = self.client.get().expect("Client should be initialized");
+        let url = format!("{}project/{}/budget", BASE_URL, project_id);
+        let response = client.get(url).send().await?;
+
+        response
+            .error_for_status()?
+            .json()
+            .await
+            .map_err(BudgetError::NetworkError)
+    }
+
+    /// Alerts nobody acknowledged yet, newest first.
+    pub async fn get_open_alerts(&self) -> Result<Vec<BudgetAlertDto>, BudgetError> {
+        let client = self.client.get().expect("Client should be initialized");
+        let response = client
+            .get(BASE_URL.to_owned() + "budget/alerts")
+            .query(&[("acknowledged", "false")])
+            .send()
+            .await?;
+
+        response
+            .error_for_status()?
+            .json()
+            .await
+            .map_err(BudgetError::NetworkError)
+    }
+
+    pub async fn acknowledge_alert(&self, id: Uuid) -> Result<BudgetAlertDto, BudgetError> {
+        let client = self.client.get().expect("Client should be initialized");
+        let url = format!("{}budget/alerts/acknowledge/{}", BASE_URL, id);
+        let response = client.post(url).send().await?;
+
+        response
+            .error_for_status()?
+            .json()
+            .await
+            .map_err(BudgetError::NetworkError)
+    }
+}
@@ -1,4 +1,8 @@
+pub mod budget;
+pub mod policy;
 pub mod project;
+pub mod search;
 pub mod section;
 pub mod sub_task;
+pub mod sync;
 pub mod task;
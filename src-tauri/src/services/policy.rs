@@ -0,0 +1,51 @@
+use once_cell::sync::OnceCell;
+use ot_server::tracking_policy_dto::EffectiveTrackingPolicyDto;
+use reqwest::{Client, ClientBuilder};
+use std::time::Duration;
+use thiserror::Error;
+use uuid::Uuid;
+
+const BASE_URL: &str = "http://localhost:4000/api/tracking-policy/";
+const TIMEOUT_SECONDS: u64 = 30;
+
+#[derive(Error, Debug)]
+pub enum PolicyError {
+    #[error("Network error: {0}")]
+    NetworkError(#[from] reqwest::Error),
+}
+
+pub struct PolicyService {
+    client: OnceCell<Client>,
+}
+
+impl PolicyService {
+    pub fn new() -> Self {
+        let client = ClientBuilder::new()
+            .timeout(Duration::from_secs(TIMEOUT_SECONDS))
+            .build()
+            .expect("Failed to create HTTP client");
+        let cell = OnceCell::new();
+        cell.set(client).expect("Failed to set client");
+        Self { client: cell }
+    }
+
+    /// The tracking policy for time tracked on a project, or the global
+    /// one for `None`.
+    pub async fn get_effective_policy(
+        &self,
+        project_id: Option<Uuid>,
+    ) -> Result<EffectiveTrackingPolicyDto, PolicyError> {
+        let client = self.client.get().expect("Client should be initialized");
+        let mut request = client.get(BASE_URL.to_owned() + "effective");
+        if let Some(project_id) = project_id {
+            request = request.query(&[("project_id", project_id)]);
+        }
+        let response = request.send().await?;
+
+        response
+            .error_for_status()?
+            .json()
+            .await
+            .map_err(PolicyError::NetworkError)
+    }
+}
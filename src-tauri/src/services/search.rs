@@ -0,0 +1,56 @@
+use once_cell::sync::OnceCell;
+use ot_server::search_dto::SearchHitDto;
+use reqwest::{Client, ClientBuilder};
+use std::time::Duration;
+use thiserror::Error;
+use uuid::Uuid;
+
+const BASE_URL: &str = "http://localhost:4000/api/search/";
+const TIMEOUT_SECONDS: u64 = 30;
+
+#[derive(Error, Debug)]
+pub enum SearchError {
+    #[error("Network error: {0}")]
+    NetworkError(#[from] reqwest::Error),
+    #[error("Serialization error: {0}")]
+    SerializationError(#[from] serde_json::Error),
+}
+
+pub struct SearchService {
+    client: OnceCell<Client>,
+}
+
+impl SearchService {
+    pub fn new() -> Self {
+        let client = ClientBuilder::new()
+            .timeout(Duration::from_secs(TIMEOUT_SECONDS))
+            .build()
+            .expect("Failed to create HTTP client");
+        let cell = OnceCell::new();
+        cell.set(client).expect("Failed to set client");
+        Self { client: cell }
+    }
+
+    pub async fn search(
+        &self,
+        q: &str,
+        project_id: Option<Uuid>,
+        limit: Option<u64>,
+    ) -> Result<Vec<SearchHitDto>, SearchError> {
+        let client = self.client.get().expect("Client should be initialized");
+        let mut query = vec![("q", q.to_string())];
+        if let Some(project_id) = project_id {
+            query.push(("project_id", project_id.to_string()));
+        }
+        if let Some(limit) = limit {
+            query.push(("limit", limit.to_string()));
+        }
+        let response = client.get(BASE_URL).query(&query).send().await?;
+
+        response
+            .error_for_status()?
+            .json()
+            .await
+            .map_err(SearchError::NetworkError)
+    }
+}
@@ -0,0 +1,75 @@
+use once_cell::sync::OnceCell;
+use reqwest::{Client, ClientBuilder, StatusCode};
+use serde::Deserialize;
+use std::time::Duration;
+use thiserror::Error;
+
+use crate::outbox::OutboxPayload;
+
+const BASE_URL: &str = "http://localhost:4000/api/timer/";
+const TIMEOUT_SECONDS: u64 = 30;
+
+#[derive(Error, Debug)]
+pub enum SyncError {
+    #[error("Network error: {0}")]
+    NetworkError(#[from] reqwest::Error),
+    /// The server refused the upload; sending it again would not help
+    #[error("{0}")]
+    Rejected(String),
+}
+
+/// Body of the server's error responses
+#[derive(Deserialize)]
+struct ErrorBody {
+    message: String,
+}
+
+pub struct SyncService {
+    client: OnceCell<Client>,
+}
+
+impl SyncService {
+    pub fn new() -> Self {
+        let client = ClientBuilder::new()
+            .timeout(Duration::from_secs(TIMEOUT_SECONDS))
+            .build()
+            .expect("Failed to create HTTP client");
+        let cell = OnceCell::new();
+        cell.set(client).expect("Failed to set client");
+        Self { client: cell }
+    }
+
+    /// Upload a queued session or activity sample. The server keys both by
+    /// their client-generated id, so uploading one twice is harmless.
+    pub async fn upload(&self, payload: &OutboxPayload) -> Result<(), SyncError> {
+        let client = self.client.get().expect("Client should be initialized");
+        let request = match payload {
+            OutboxPayload::Session(session) => {
+                client.post(BASE_URL.to_owned() + "session").json(session)
+            }
+            OutboxPayload::Activity(activity) => {
+                client.post(BASE_URL.to_owned() + "activity").json(activity)
+            }
+            OutboxPayload::WindowActivity(activity) => client
+                .post(BASE_URL.to_owned() + "window-activity")
+                .json(activity),
+        };
+        let response = request.send().await?;
+
+        let status = response.status();
+        // Timeouts and rate limiting are worth retrying, other client
+        // errors are not
+        if status.is_client_error()
+            && status != StatusCode::REQUEST_TIMEOUT
+            && status != StatusCode::TOO_MANY_REQUESTS
+        {
+            let message = match response.json::<ErrorBody>().await {
+                Ok(body) => body.message,
+                Err(_) => status.to_string(),
+            };
+            return Err(SyncError::Rejected(message));
+        }
+        response.error_for_status()?;
+        Ok(())
+    }
+}
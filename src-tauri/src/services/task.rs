@@ -1,11 +1,19 @@
 use once_cell::sync::OnceCell;
-use ot_server::{sub_task_dto::SubTaskDto, task_dto::TaskDto};
+use ot_server::{
+    dependency_dto::TaskDependenciesDto,
+    sub_task_dto::SubTaskDto,
+    task_dto::{TaskDto, TaskFilterParams},
+    timer_dto::{TimerTargetCheckDto, TimerTargetDto},
+};
 use reqwest::{Client, ClientBuilder};
+use serde::Deserialize;
 use std::time::Duration;
 use thiserror::Error;
 use uuid::Uuid;
 
 const BASE_URL: &str = "http://localhost:4000/api/task/";
+const DEPENDENCY_URL: &str = "http://localhost:4000/api/dependency/";
+const TIMER_URL: &str = "http://localhost:4000/api/timer/";
 const TIMEOUT_SECONDS: u64 = 30;
 
 #[derive(Error, Debug)]
@@ -14,6 +22,14 @@ pub enum TaskError {
     NetworkError(#[from] reqwest::Error),
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
+    #[error("{0}")]
+    Rejected(String),
+}
+
+/// Body of the server's error responses
+#[derive(Deserialize)]
+struct ErrorBody {
+    message: String,
 }
 
 pub struct TaskService {
@@ -34,16 +50,58 @@ impl TaskService {
     pub async fn get_tasks_by_section_id(
         &self,
         section_id: Uuid,
+        filter: &TaskFilterParams,
     ) -> Result<Vec<TaskDto>, TaskError> {
         let client = self.client.get().expect("Client should be initialized");
         let response = client
             .get(BASE_URL.to_owned() + "by-section-id/" + section_id.to_string().as_str())
+            .query(filter)
+            .send()
+            .await?;
+
+        response
+            .json()
+            .await
+            .map_err(|e| TaskError::NetworkError(e))
+    }
+
+    /// Tasks `task_id` waits on and tasks waiting on it.
+    pub async fn get_dependencies(&self, task_id: Uuid) -> Result<TaskDependenciesDto, TaskError> {
+        let client = self.client.get().expect("Client should be initialized");
+        let response = client
+            .get(DEPENDENCY_URL.to_owned() + "by-task-id/" + task_id.to_string().as_str())
             .send()
             .await?;
 
         response
+            .error_for_status()?
             .json()
             .await
             .map_err(|e| TaskError::NetworkError(e))
     }
+
+    /// Check with the server that a timer can be started on the task, or
+    /// sub task. The server's reason is passed on when it refuses, e.g. for
+    /// a task that no longer exists or is in an archived project.
+    pub async fn check_timer_target(
+        &self,
+        target: &TimerTargetCheckDto,
+    ) -> Result<TimerTargetDto, TaskError> {
+        let client = self.client.get().expect("Client should be initialized");
+        let response = client
+            .post(TIMER_URL.to_owned() + "target")
+            .json(target)
+            .send()
+            .await?;
+
+        if response.status().is_client_error() {
+            let body: ErrorBody = response.json().await?;
+            return Err(TaskError::Rejected(body.message));
+        }
+        response
+            .error_for_status()?
+            .json()
+            .await
+            .map_err(TaskError::NetworkError)
+    }
 }
@@ -1,10 +1,20 @@
 use chrono::{DateTime, FixedOffset, Utc};
+use ot_server::tracking_policy_dto::{EffectiveTrackingPolicyDto, TrackingSetting, TrackingValue};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use tokio::time::{Duration, Instant};
 use uuid::Uuid;
 
+use crate::{
+    activity_sampler::ActivitySampler,
+    idle_detector::{IdleDetector, IdleSpan},
+    screen_capture::CaptureBackend,
+    screenshot_privacy::{ScreenshotPause, ScreenshotPrivacy},
+    screenshot_store::ScreenshotStorage,
+    window_tracker::WindowTracker,
+};
+
 /// Represents the state of a timer with elapsed time tracking and timestamps
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -15,20 +25,271 @@ pub struct TimerState {
     pub start_instant: Option<Instant>,
     pub start_date_time: Option<DateTime<Utc>>,
     pub end_date_time: Option<DateTime<Utc>>,
+    /// Task the session is tracked on
+    pub task_id: Option<Uuid>,
+    /// Sub task of `task_id` the session is tracked on
+    pub sub_task_id: Option<Uuid>,
+    /// Reasons given for idle time kept in the session
+    pub note: Option<String>,
+}
+
+impl TimerState {
+    /// Start a fresh session on a task; ignored while one is running or
+    /// paused.
+    pub fn start(
+        &mut self,
+        task_id: Uuid,
+        sub_task_id: Option<Uuid>,
+        now: Instant,
+        wall: DateTime<Utc>,
+    ) -> bool {
+        if self.running || self.elapsed.as_secs() > 0 {
+            return false;
+        }
+        self.task_id = Some(task_id);
+        self.sub_task_id = sub_task_id;
+        self.note = None;
+        self.running = true;
+        self.start_instant = Some(now);
+        self.start_date_time = Some(wall);
+        self.end_date_time = None;
+        self.elapsed = Duration::from_secs(0);
+        true
+    }
+
+    /// Pause a running session, preserving elapsed time.
+    pub fn pause(&mut self, now: Instant) -> bool {
+        if !self.running {
+            return false;
+        }
+        self.elapsed = self.elapsed_at(now);
+        self.running = false;
+        self.start_instant = None;
+        true
+    }
+
+    /// Resume a paused session.
+    pub fn resume(&mut self, now: Instant) -> bool {
+        if self.running || self.elapsed.as_secs() == 0 {
+            return false;
+        }
+        self.running = true;
+        self.start_instant = Some(now);
+        true
+    }
+
+    /// End the session and reset elapsed time; returns the session ended,
+    /// if there was one.
+    pub fn stop(&mut self, now: Instant, wall: DateTime<Utc>) -> Option<TimerSession> {
+        let session = self.session_until(now, wall);
+        self.end_date_time = Some(wall);
+        self.note = None;
+        self.running = false;
+        self.start_instant = None;
+        self.elapsed = Duration::from_secs(0);
+        session
+    }
+
+    /// Move a running session to another task without stopping: the time
+    /// so far is split off as a session of its own, and tracking continues
+    /// on the new task from `now`. Returns the split off session; `None`
+    /// when the timer is not running or already on that task.
+    pub fn switch_task(
+        &mut self,
+        task_id: Uuid,
+        sub_task_id: Option<Uuid>,
+        now: Instant,
+        wall: DateTime<Utc>,
+    ) -> Option<TimerSession> {
+        if !self.running || (self.task_id, self.sub_task_id) == (Some(task_id), sub_task_id) {
+            return None;
+        }
+        let session = self.session_until(now, wall)?;
+        self.task_id = Some(task_id);
+        self.sub_task_id = sub_task_id;
+        self.note = None;
+        self.start_instant = Some(now);
+        self.start_date_time = Some(wall);
+        self.elapsed = Duration::from_secs(0);
+        Some(session)
+    }
+
+    /// The session tracked so far, ending at `wall`.
+    fn session_until(&self, now: Instant, wall: DateTime<Utc>) -> Option<TimerSession> {
+        let elapsed = self.elapsed_at(now);
+        if !self.running && elapsed.is_zero() {
+            return None;
+        }
+        Some(TimerSession {
+            task_id: self.task_id,
+            sub_task_id: self.sub_task_id,
+            start_time: self.start_date_time?,
+            end_time: wall,
+            elapsed_seconds: elapsed.as_secs(),
+            note: self.note.clone(),
+        })
+    }
+
+    /// Take idle time back out of the session; returns what was taken,
+    /// which is less than `idle` if the session is shorter.
+    pub fn discard(&mut self, idle: Duration, now: Instant) -> Duration {
+        let elapsed = self.elapsed_at(now);
+        let discarded = idle.min(elapsed);
+        self.elapsed = elapsed - discarded;
+        if self.running {
+            self.start_instant = Some(now);
+        }
+        discarded
+    }
+
+    /// Add a reason to the session's note, one per line.
+    pub fn add_note(&mut self, reason: &str) {
+        let reason = reason.trim();
+        if reason.is_empty() {
+            return;
+        }
+        match &mut self.note {
+            Some(note) => {
+                note.push('\n');
+                note.push_str(reason);
+            }
+            None => self.note = Some(reason.to_string()),
+        }
+    }
+
+    /// Add time to a paused session.
+    pub fn add_time(&mut self, seconds: u64) -> bool {
+        if self.running || self.elapsed.as_secs() == 0 {
+            return false;
+        }
+        self.elapsed += Duration::from_secs(seconds);
+        true
+    }
+
+    /// Time tracked in the session up to `now`.
+    pub fn elapsed_at(&self, now: Instant) -> Duration {
+        match (self.running, self.start_instant) {
+            (true, Some(start)) => self.elapsed + now.saturating_duration_since(start),
+            _ => self.elapsed,
+        }
+    }
+
+    /// What the journal keeps of the session at `now`; `None` when there is
+    /// no session to recover.
+    pub fn snapshot(&self, now: Instant, wall: DateTime<Utc>) -> Option<TimerSnapshot> {
+        let elapsed = self.elapsed_at(now);
+        if !self.running && elapsed.is_zero() {
+            return None;
+        }
+        Some(TimerSnapshot {
+            running: self.running,
+            elapsed_seconds: elapsed.as_secs(),
+            start_time: self.start_date_time,
+            task_id: self.task_id,
+            sub_task_id: self.sub_task_id,
+            note: self.note.clone(),
+            heartbeat: wall,
+        })
+    }
+}
+
+/// Time tracked on one task in one go: from start to stop, or up to a
+/// switch to another task.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimerSession {
+    pub task_id: Option<Uuid>,
+    pub sub_task_id: Option<Uuid>,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    /// Tracked time, without pauses
+    pub elapsed_seconds: u64,
+    /// Why idle time in the session was kept or reassigned
+    pub note: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// A timer session as last written to the journal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimerSnapshot {
+    pub running: bool,
+    /// Time tracked up to `heartbeat`
+    pub elapsed_seconds: u64,
+    pub start_time: Option<DateTime<Utc>>,
+    pub task_id: Option<Uuid>,
+    #[serde(default)]
+    pub sub_task_id: Option<Uuid>,
+    #[serde(default)]
+    pub note: Option<String>,
+    /// Last time the session was known to be alive
+    pub heartbeat: DateTime<Utc>,
+}
+
+impl TimerSnapshot {
+    /// Pick the session up again at `now`. The time between the heartbeat
+    /// and `now` is not tracked.
+    pub fn resume(&self, now: Instant) -> TimerState {
+        TimerState {
+            elapsed: Duration::from_secs(self.elapsed_seconds),
+            running: self.running,
+            start_instant: self.running.then_some(now),
+            start_date_time: self.start_time,
+            end_date_time: None,
+            task_id: self.task_id,
+            sub_task_id: self.sub_task_id,
+            note: self.note.clone(),
+        }
+    }
+
+    /// The session stopped at its last heartbeat, and what it tracked.
+    pub fn close(&self) -> (TimerState, Option<TimerSession>) {
+        let state = TimerState {
+            start_date_time: self.start_time,
+            end_date_time: Some(self.heartbeat),
+            task_id: self.task_id,
+            sub_task_id: self.sub_task_id,
+            ..Default::default()
+        };
+        let session = self.start_time.map(|start_time| TimerSession {
+            task_id: self.task_id,
+            sub_task_id: self.sub_task_id,
+            start_time,
+            end_time: self.heartbeat,
+            elapsed_seconds: self.elapsed_seconds,
+            note: self.note.clone(),
+        });
+        (state, session)
+    }
+}
+
+/// What to do with a session found in the journal on startup.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecoveryAction {
+    /// Keep tracking, from where the last heartbeat left off
+    Resume,
+    /// End the session at its last heartbeat
+    Close,
+}
+
+/// What is tracked while the timer runs. Kept in the config file; settings
+/// locked by the tracking policy cannot be changed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AppConfig {
     // screenshots configs
     pub enable_screen_shots: bool,
+    #[serde(with = "duration_secs")]
     pub screen_shots_interval: Duration,
     pub enable_window_ss: bool,
     pub browser_window_ss: bool,
+    pub screenshot_storage: ScreenshotStorage,
+    pub screen_capture_backend: CaptureBackend,
+    pub screenshot_privacy: ScreenshotPrivacy,
     // activity configs
     pub enable_keyboard_activity: bool,
     pub enable_mouse_activity: bool,
     // idle configs
     pub enable_idle_time: bool,
+    #[serde(with = "duration_secs")]
     pub idle_time_threshold: Duration,
     pub ask_for_idle_reason: bool,
     // window configs
@@ -44,6 +305,9 @@ impl Default for AppConfig {
             screen_shots_interval: Duration::from_secs(10),
             enable_window_ss: true,
             browser_window_ss: false,
+            screenshot_storage: ScreenshotStorage::default(),
+            screen_capture_backend: CaptureBackend::default(),
+            screenshot_privacy: ScreenshotPrivacy::default(),
             enable_keyboard_activity: true,
             enable_mouse_activity: true,
             enable_idle_time: true,
@@ -55,8 +319,385 @@ impl Default for AppConfig {
     }
 }
 
+impl AppConfig {
+    /// Apply a tracking policy. Locked settings always take the policy's
+    /// value; the others only when it differs from the `previous` policy's,
+    /// so employees can still change them until the policy does.
+    pub fn apply_policy(
+        &mut self,
+        policy: &EffectiveTrackingPolicyDto,
+        previous: Option<&EffectiveTrackingPolicyDto>,
+    ) {
+        for (&setting, &value) in &policy.settings {
+            let changed =
+                previous.and_then(|previous| previous.settings.get(&setting)) != Some(&value);
+            if changed || policy.locked.contains(&setting) {
+                self.set(setting, value);
+            }
+        }
+    }
+
+    /// The settings among `locked` that `other` has a different value for.
+    pub fn locked_changes(
+        &self,
+        other: &AppConfig,
+        locked: &[TrackingSetting],
+    ) -> Vec<TrackingSetting> {
+        locked
+            .iter()
+            .copied()
+            .filter(|&setting| self.get(setting) != other.get(setting))
+            .collect()
+    }
+
+    fn get(&self, setting: TrackingSetting) -> TrackingValue {
+        use TrackingSetting::*;
+        use TrackingValue::{Enabled, Seconds};
+        match setting {
+            EnableScreenShots => Enabled(self.enable_screen_shots),
+            ScreenShotsInterval => Seconds(self.screen_shots_interval.as_secs()),
+            EnableWindowSs => Enabled(self.enable_window_ss),
+            BrowserWindowSs => Enabled(self.browser_window_ss),
+            EnableKeyboardActivity => Enabled(self.enable_keyboard_activity),
+            EnableMouseActivity => Enabled(self.enable_mouse_activity),
+            EnableIdleTime => Enabled(self.enable_idle_time),
+            IdleTimeThreshold => Seconds(self.idle_time_threshold.as_secs()),
+            AskForIdleReason => Enabled(self.ask_for_idle_reason),
+            EnableWindowActivity => Enabled(self.enable_window_activity),
+            EnableBrowserActivity => Enabled(self.enable_browser_activity),
+        }
+    }
+
+    /// Set a setting; a value of the wrong kind is ignored.
+    fn set(&mut self, setting: TrackingSetting, value: TrackingValue) {
+        use TrackingSetting::*;
+        use TrackingValue::{Enabled, Seconds};
+        let out_of_range = match (setting.seconds_range(), value) {
+            (Some((min, max)), Seconds(seconds)) => !(min..=max).contains(&seconds),
+            _ => false,
+        };
+        if out_of_range {
+            eprintln!("Ignoring policy value {:?} for {:?}", value, setting);
+            return;
+        }
+        match (setting, value) {
+            (EnableScreenShots, Enabled(on)) => self.enable_screen_shots = on,
+            (ScreenShotsInterval, Seconds(seconds)) => {
+                self.screen_shots_interval = Duration::from_secs(seconds)
+            }
+            (EnableWindowSs, Enabled(on)) => self.enable_window_ss = on,
+            (BrowserWindowSs, Enabled(on)) => self.browser_window_ss = on,
+            (EnableKeyboardActivity, Enabled(on)) => self.enable_keyboard_activity = on,
+            (EnableMouseActivity, Enabled(on)) => self.enable_mouse_activity = on,
+            (EnableIdleTime, Enabled(on)) => self.enable_idle_time = on,
+            (IdleTimeThreshold, Seconds(seconds)) => {
+                self.idle_time_threshold = Duration::from_secs(seconds)
+            }
+            (AskForIdleReason, Enabled(on)) => self.ask_for_idle_reason = on,
+            (EnableWindowActivity, Enabled(on)) => self.enable_window_activity = on,
+            (EnableBrowserActivity, Enabled(on)) => self.enable_browser_activity = on,
+            _ => eprintln!("Ignoring policy value {:?} for {:?}", value, setting),
+        }
+    }
+}
+
+/// Durations in the config file as whole seconds, between a second and a
+/// day.
+mod duration_secs {
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+    use tokio::time::Duration;
+
+    const MAX_SECS: u64 = 24 * 3600;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(duration.as_secs())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        let secs = u64::deserialize(deserializer)?;
+        if !(1..=MAX_SECS).contains(&secs) {
+            return Err(D::Error::custom(format!(
+                "duration of {} seconds is not between 1 and {}",
+                secs, MAX_SECS
+            )));
+        }
+        Ok(Duration::from_secs(secs))
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct AppState {
     pub timer_state: TimerState,
     pub app_config: AppConfig,
+    /// Session left in the journal by a crash, until the user decides what
+    /// to do with it
+    pub recovered_timer: Option<TimerSnapshot>,
+    /// Samples keyboard and mouse activity while the timer runs
+    pub activity_sampler: ActivitySampler,
+    /// Watches for idle time while the timer runs
+    pub idle_detector: IdleDetector,
+    /// Idle time in the current session the user has not decided on yet
+    pub pending_idle: Vec<IdleSpan>,
+    /// Tracks the focused window while the timer runs
+    pub window_tracker: WindowTracker,
+    /// Screenshots paused by the user, shared with the capture thread
+    pub screenshot_pause: ScreenshotPause,
+    /// Tracking policy last applied to `app_config`
+    pub policy: Option<EffectiveTrackingPolicyDto>,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use ot_server::tracking_policy_dto::{
+        EffectiveTrackingPolicyDto,
+        TrackingSetting::{self, *},
+        TrackingValue::{self, *},
+    };
+    use tokio::time::{Duration, Instant};
+    use uuid::Uuid;
+
+    use super::{AppConfig, TimerState};
+
+    fn secs(s: u64) -> Duration {
+        Duration::from_secs(s)
+    }
+
+    fn task(n: u128) -> Uuid {
+        Uuid::from_u128(n)
+    }
+
+    fn policy(
+        settings: &[(TrackingSetting, TrackingValue)],
+        locked: &[TrackingSetting],
+    ) -> EffectiveTrackingPolicyDto {
+        EffectiveTrackingPolicyDto {
+            settings: settings.iter().copied().collect(),
+            locked: locked.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn session_accumulates_across_pauses() {
+        let t0 = Instant::now();
+        let wall = Utc.with_ymd_and_hms(2025, 3, 1, 9, 0, 0).unwrap();
+        let mut timer = TimerState::default();
+
+        assert!(!timer.pause(t0));
+        assert!(!timer.resume(t0));
+        assert!(timer.start(task(1), None, t0, wall));
+        assert!(!timer.start(task(2), None, t0 + secs(5), wall));
+        assert_eq!(timer.elapsed_at(t0 + secs(10)), secs(10));
+
+        assert!(timer.pause(t0 + secs(10)));
+        assert_eq!(timer.elapsed_at(t0 + secs(100)), secs(10));
+        // A paused session is not restarted
+        assert!(!timer.start(task(2), None, t0 + secs(20), wall));
+        assert!(timer.add_time(5));
+        assert!(timer.resume(t0 + secs(30)));
+        assert!(!timer.add_time(5));
+        assert_eq!(timer.elapsed_at(t0 + secs(40)), secs(25));
+        assert_eq!(timer.start_date_time, Some(wall));
+
+        assert_eq!(timer.task_id, Some(task(1)));
+
+        let end = wall + chrono::Duration::seconds(40);
+        let session = timer.stop(t0 + secs(40), end).unwrap();
+        assert_eq!(session.task_id, Some(task(1)));
+        assert_eq!((session.start_time, session.end_time), (wall, end));
+        assert_eq!(session.elapsed_seconds, 25);
+        assert!(!timer.running);
+        assert_eq!(timer.elapsed, secs(0));
+        assert_eq!(timer.stop(t0 + secs(45), wall), None);
+        assert!(timer.start(task(2), None, t0 + secs(50), wall));
+    }
+
+    #[test]
+    fn snapshot_resumes_without_the_gap() {
+        let t0 = Instant::now();
+        let wall = Utc.with_ymd_and_hms(2025, 3, 1, 9, 0, 0).unwrap();
+        let heartbeat = wall + chrono::Duration::seconds(90);
+        let mut timer = TimerState::default();
+        assert_eq!(timer.snapshot(t0, wall), None);
+
+        timer.start(task(1), None, t0, wall);
+        let snapshot = timer.snapshot(t0 + secs(90), heartbeat).unwrap();
+        assert!(snapshot.running);
+        assert_eq!(snapshot.elapsed_seconds, 90);
+        assert_eq!(snapshot.start_time, Some(wall));
+        assert_eq!(snapshot.heartbeat, heartbeat);
+
+        // Restarted an hour later
+        let later = t0 + secs(3600);
+        let resumed = snapshot.resume(later);
+        assert!(resumed.running);
+        assert_eq!(resumed.elapsed_at(later + secs(10)), secs(100));
+        assert_eq!(resumed.start_date_time, Some(wall));
+
+        let (closed, session) = snapshot.close();
+        assert!(!closed.running);
+        assert_eq!(closed.elapsed, secs(0));
+        assert_eq!(closed.end_date_time, Some(heartbeat));
+        let session = session.unwrap();
+        assert_eq!(session.task_id, Some(task(1)));
+        assert_eq!(session.end_time, heartbeat);
+        assert_eq!(session.elapsed_seconds, 90);
+    }
+
+    #[test]
+    fn switching_task_splits_the_session() {
+        let t0 = Instant::now();
+        let wall = Utc.with_ymd_and_hms(2025, 3, 1, 9, 0, 0).unwrap();
+        let at = |s: i64| wall + chrono::Duration::seconds(s);
+        let mut timer = TimerState::default();
+
+        // Nothing to switch while idle
+        assert_eq!(timer.switch_task(task(2), None, t0, wall), None);
+        timer.start(task(1), Some(task(11)), t0, wall);
+        assert_eq!(
+            timer.switch_task(task(1), Some(task(11)), t0 + secs(5), at(5)),
+            None
+        );
+
+        let first = timer
+            .switch_task(task(2), None, t0 + secs(60), at(60))
+            .unwrap();
+        assert_eq!(
+            (first.task_id, first.sub_task_id),
+            (Some(task(1)), Some(task(11)))
+        );
+        assert_eq!((first.start_time, first.end_time), (wall, at(60)));
+        assert_eq!(first.elapsed_seconds, 60);
+
+        // The new task starts from zero without stopping
+        assert!(timer.running);
+        assert_eq!((timer.task_id, timer.sub_task_id), (Some(task(2)), None));
+        assert_eq!(timer.start_date_time, Some(at(60)));
+        assert_eq!(timer.elapsed_at(t0 + secs(90)), secs(30));
+
+        // A paused session is not switched
+        timer.pause(t0 + secs(90));
+        assert_eq!(
+            timer.switch_task(task(3), None, t0 + secs(95), at(95)),
+            None
+        );
+
+        let second = timer.stop(t0 + secs(100), at(100)).unwrap();
+        assert_eq!(second.task_id, Some(task(2)));
+        assert_eq!((second.start_time, second.end_time), (at(60), at(100)));
+        assert_eq!(second.elapsed_seconds, 30);
+    }
+
+    #[test]
+    fn idle_time_is_discarded_and_noted() {
+        let t0 = Instant::now();
+        let wall = Utc.with_ymd_and_hms(2025, 3, 1, 9, 0, 0).unwrap();
+        let mut timer = TimerState::default();
+        timer.start(task(1), None, t0, wall);
+
+        assert_eq!(timer.discard(secs(20), t0 + secs(60)), secs(20));
+        assert!(timer.running);
+        assert_eq!(timer.elapsed_at(t0 + secs(70)), secs(50));
+
+        timer.add_note(" Meeting ");
+        timer.add_note("");
+        timer.add_note("Phone call");
+        let session = timer.stop(t0 + secs(100), wall).unwrap();
+        assert_eq!(session.elapsed_seconds, 80);
+        assert_eq!(session.note.as_deref(), Some("Meeting\nPhone call"));
+        assert_eq!(timer.note, None);
+
+        // No more than the session holds
+        timer.start(task(1), None, t0 + secs(200), wall);
+        timer.pause(t0 + secs(210));
+        assert_eq!(timer.discard(secs(90), t0 + secs(220)), secs(10));
+        assert_eq!(timer.elapsed_at(t0 + secs(230)), secs(0));
+    }
+
+    #[test]
+    fn paused_snapshot_stays_paused() {
+        let t0 = Instant::now();
+        let wall = Utc.with_ymd_and_hms(2025, 3, 1, 9, 0, 0).unwrap();
+        let mut timer = TimerState::default();
+        timer.start(task(1), None, t0, wall);
+        timer.pause(t0 + secs(30));
+
+        let snapshot = timer.snapshot(t0 + secs(500), wall).unwrap();
+        assert!(!snapshot.running);
+        assert_eq!(snapshot.elapsed_seconds, 30);
+
+        let mut resumed = snapshot.resume(t0 + secs(600));
+        assert!(!resumed.running);
+        assert_eq!(resumed.start_instant, None);
+        assert!(resumed.resume(t0 + secs(600)));
+        assert_eq!(resumed.elapsed_at(t0 + secs(610)), secs(40));
+    }
+
+    #[test]
+    fn policy_locks_settings_and_suggests_the_others() {
+        let mut config = AppConfig {
+            enable_screen_shots: false,
+            ..Default::default()
+        };
+        let first = policy(
+            &[
+                (EnableScreenShots, Enabled(true)),
+                (ScreenShotsInterval, Seconds(600)),
+            ],
+            &[EnableScreenShots],
+        );
+        config.apply_policy(&first, None);
+        assert!(config.enable_screen_shots);
+        assert_eq!(config.screen_shots_interval, Duration::from_secs(600));
+
+        // Only locked settings are refused
+        let mut changed = config.clone();
+        changed.enable_screen_shots = false;
+        changed.screen_shots_interval = Duration::from_secs(60);
+        assert_eq!(
+            config.locked_changes(&changed, &first.locked),
+            vec![EnableScreenShots]
+        );
+
+        // The same policy again keeps what the user chose for unlocked
+        // settings, but not for locked ones
+        config = changed;
+        config.apply_policy(&first, Some(&first));
+        assert!(config.enable_screen_shots);
+        assert_eq!(config.screen_shots_interval, Duration::from_secs(60));
+
+        // A new value is applied, and a value of the wrong kind ignored
+        let second = policy(
+            &[
+                (ScreenShotsInterval, Seconds(900)),
+                (EnableIdleTime, Seconds(5)),
+            ],
+            &[],
+        );
+        config.apply_policy(&second, Some(&first));
+        assert_eq!(config.screen_shots_interval, Duration::from_secs(900));
+        assert!(config.enable_idle_time);
+
+        // Durations out of range are ignored too
+        let third = policy(
+            &[(IdleTimeThreshold, Seconds(u64::MAX))],
+            &[IdleTimeThreshold],
+        );
+        config.apply_policy(&third, Some(&second));
+        assert_eq!(
+            config.idle_time_threshold,
+            AppConfig::default().idle_time_threshold
+        );
+    }
+
+    #[test]
+    fn config_durations_out_of_range_are_refused() {
+        for secs in [0, 24 * 3600 + 1, u64::MAX] {
+            let json = format!(r#"{{"idle_time_threshold": {}}}"#, secs);
+            assert!(serde_json::from_str::<AppConfig>(&json).is_err());
+        }
+        let config: AppConfig = serde_json::from_str(r#"{"idle_time_threshold": 86400}"#).unwrap();
+        assert_eq!(config.idle_time_threshold, Duration::from_secs(24 * 3600));
+    }
 }
@@ -1,20 +1,47 @@
 use std::sync::{Arc, Mutex, atomic::AtomicBool};
 
 use chrono::{DateTime, Utc};
+use ot_server::timer_dto::TimerTargetCheckDto;
 use serde::{Deserialize, Serialize};
 use tauri::{Emitter, Manager, State};
 use tokio::time::{Duration, Instant};
+use uuid::Uuid;
 
 use crate::{
     AppState,
-    track_activity::{ScreenShotCommand, screenshot_command},
+    activity_sampler::{DeviceInput, POLL_INTERVAL, SamplerConfig, TRACK_INTERVAL},
+    cmd::{
+        config_command::{apply_policy, fetch_policy},
+        sync_command::notify_sync_status,
+    },
+    idle_detector::{IdleDecision, IdleSpan},
+    outbox::{Outbox, OutboxPayload},
+    screenshot_store::ScreenshotStore,
+    services::task::TaskService,
+    state::{RecoveryAction, TimerSession, TimerSnapshot},
+    timer_journal::TimerJournal,
+    track_activity::{ScreenShotCommand, ScreenshotSetup, screenshot_command},
+    window_tracker::{WINDOW_POLL_INTERVAL, system_backend},
 };
 
+/// How often a running session is written to the journal, bounding what a
+/// crash can lose.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+
 /// Commands that can be sent to control the timer
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum TimerCommand {
-    /// Start the timer
-    Start,
+    /// Start the timer on a task, or one of its sub tasks
+    Start {
+        task_id: Uuid,
+        sub_task_id: Option<Uuid>,
+    },
+    /// Move the running timer to another task without stopping it; the
+    /// time so far is ended as a session on the previous task
+    SwitchTask {
+        task_id: Uuid,
+        sub_task_id: Option<Uuid>,
+    },
     /// Pause the timer, preserving elapsed time
     Pause,
     /// Resume the timer from its paused state
@@ -33,126 +60,455 @@ pub enum TimerError {
     LockError(String),
     #[error("Failed to emit event: {0}")]
     EmitError(String),
+    #[error("A recovered session has to be resumed or closed first")]
+    RecoveryPending,
+    #[error("There is no recovered session")]
+    NothingToRecover,
+    #[error("The idle time was already resolved, or its session has ended")]
+    UnknownIdleSpan,
+    #[error("Cannot track time on this task: {0}")]
+    InvalidTarget(String),
 }
 
 #[tauri::command()]
 pub async fn control_timer(
     state: State<'_, Mutex<AppState>>,
+    journal: State<'_, TimerJournal>,
     command: TimerCommand,
     app_handle: tauri::AppHandle,
 ) -> Result<(), TimerError> {
+    // The server is asked before the state is locked, so the lock is never
+    // held across a request
+    let mut policy = None;
+    if let TimerCommand::Start {
+        task_id,
+        sub_task_id,
+    }
+    | TimerCommand::SwitchTask {
+        task_id,
+        sub_task_id,
+    } = command
+    {
+        let target = TaskService::new()
+            .check_timer_target(&TimerTargetCheckDto {
+                task_id,
+                sub_task_id,
+            })
+            .await
+            .map_err(|e| TimerError::InvalidTarget(e.to_string()))?;
+        policy = fetch_policy(Some(target.project_id)).await;
+    }
+
     let mut app_state = state
         .lock()
         .map_err(|e| TimerError::LockError(format!("{:?}", e)))?;
+    if app_state.recovered_timer.is_some() {
+        return Err(TimerError::RecoveryPending);
+    }
     match command {
-        TimerCommand::Start => {
-            let should_start =
-                !app_state.timer_state.running && app_state.timer_state.elapsed.as_secs() == 0;
-
-            if should_start {
-                // Reset and update timer state
-                app_state.timer_state.running = true;
-                app_state.timer_state.start_instant = Some(Instant::now());
-                app_state.timer_state.start_date_time = Some(Utc::now());
-                app_state.timer_state.end_date_time = None;
-                app_state.timer_state.elapsed = Duration::from_secs(0);
-                
-                // Always enable screenshots when starting fresh
-                app_state.app_config.enable_screen_shots = true;
-                let enable_screen_shots = app_state.app_config.enable_screen_shots;
-                let idle_time_threshold = app_state.app_config.screen_shots_interval;
-                
-                println!(
-                    "[Timer] Starting timer. Screen shots: {:?}, Idle threshold: {:?}",
-                    enable_screen_shots, idle_time_threshold
-                );
-                
-                tokio::spawn(async move {
-                    screenshot_command(
-                        ScreenShotCommand::Running,
-                        Some(idle_time_threshold),
-                        Some(enable_screen_shots),
-                    )
-                    .await;
-                });
+        TimerCommand::Start {
+            task_id,
+            sub_task_id,
+        } => {
+            if app_state
+                .timer_state
+                .start(task_id, sub_task_id, Instant::now(), Utc::now())
+            {
+                if let Some(policy) = policy {
+                    apply_policy(&app_handle, &mut app_state, policy);
+                }
+                start_tracking(&app_handle, &mut app_state);
+            }
+        }
+        TimerCommand::SwitchTask {
+            task_id,
+            sub_task_id,
+        } => {
+            if let Some(session) =
+                app_state
+                    .timer_state
+                    .switch_task(task_id, sub_task_id, Instant::now(), Utc::now())
+            {
+                finish_session(&app_handle, session);
+                // Idle time left undecided stays with the previous task
+                app_state.pending_idle.clear();
+                // The new task's project may have another policy
+                if let Some(policy) = policy {
+                    apply_policy(&app_handle, &mut app_state, policy);
+                }
+                // Restarted so the interval so far goes to the previous task
+                // and screenshots go to the new session's folder
+                start_tracking(&app_handle, &mut app_state);
             }
-
-            // Drop the lock before async operation
         }
         TimerCommand::Pause => {
             // Pause should only work if timer is running
-            if app_state.timer_state.running {
-                if let Some(start) = app_state.timer_state.start_instant {
-                    app_state.timer_state.elapsed += start.elapsed();
-                }
-                app_state.timer_state.running = false;
-                app_state.timer_state.start_instant = None;
-                app_state.app_config.enable_screen_shots = false;
-                tokio::spawn(async move {
-                    screenshot_command(ScreenShotCommand::Stopped, None, None).await;
-                });
+            if app_state.timer_state.pause(Instant::now()) {
+                stop_tracking(&mut app_state);
             }
         }
         TimerCommand::Resume => {
             // Resume should only work if timer is not running and has elapsed time
-            let should_resume =
-                !app_state.timer_state.running && app_state.timer_state.elapsed.as_secs() > 0;
-            if should_resume {
-                app_state.timer_state.running = true;
-                app_state.timer_state.start_instant = Some(Instant::now());
-                app_state.app_config.enable_screen_shots = true;
-                let enable_screen_shots = app_state.app_config.enable_screen_shots;
-                let idle_time_threshold = app_state.app_config.screen_shots_interval;
-                tokio::spawn(async move {
-                    screenshot_command(
-                        ScreenShotCommand::Running,
-                        Some(idle_time_threshold),
-                        Some(enable_screen_shots),
-                    )
-                    .await;
-                });
+            if app_state.timer_state.resume(Instant::now()) {
+                start_tracking(&app_handle, &mut app_state);
             }
         }
         TimerCommand::Stop => {
-            // Stop should accumulate elapsed time if running, then reset
-            if app_state.timer_state.running {
-                if let Some(start) = app_state.timer_state.start_instant {
-                    app_state.timer_state.elapsed += start.elapsed();
-                }
-                tokio::spawn(async move {
-                    screenshot_command(ScreenShotCommand::Stopped, None, None).await;
-                });
-            }
-            // Record end time before resetting
-            app_state.timer_state.end_date_time = Some(Utc::now());
-
-            // Reset timer state
-            app_state.timer_state.running = false;
-            app_state.timer_state.start_instant = None;
-            app_state.timer_state.elapsed = Duration::from_secs(0);
-            app_state.app_config.enable_screen_shots = false;
-
-            // Emit one final update after stopping
-            let timer_response = TimerResponse {
-                elapsed_seconds: 0,
-                running: false,
-                start_time: None,
-                end_time: app_state.timer_state.end_date_time,
-            };
-            if let Err(e) = app_handle.emit("timer-update", timer_response) {
-                eprintln!("Failed to emit final timer update: {:?}", e);
+            // Stop records the end time and resets the timer
+            stop_tracking(&mut app_state);
+            // Idle time left undecided stays in the session
+            app_state.pending_idle.clear();
+            if let Some(session) = app_state.timer_state.stop(Instant::now(), Utc::now()) {
+                finish_session(&app_handle, session);
             }
+            emit_final_update(&app_handle, &app_state);
         }
         TimerCommand::AddTime(seconds) => {
             // Add time should only work when timer is paused (not running but has elapsed time)
-            if !app_state.timer_state.running && app_state.timer_state.elapsed.as_secs() > 0 {
-                app_state.timer_state.elapsed += Duration::from_secs(seconds);
+            app_state.timer_state.add_time(seconds);
+        }
+    }
+    write_journal(&journal, &app_state);
+    Ok(())
+}
+
+/// The session found in the journal on startup, if it is still waiting for
+/// the user to resume or close it.
+#[tauri::command]
+pub fn get_recovered_timer(
+    state: State<'_, Mutex<AppState>>,
+) -> Result<Option<TimerSnapshot>, TimerError> {
+    let app_state = state
+        .lock()
+        .map_err(|e| TimerError::LockError(format!("{:?}", e)))?;
+    Ok(app_state.recovered_timer.clone())
+}
+
+/// Resume the recovered session, or close it at its last heartbeat.
+#[tauri::command]
+pub async fn recover_timer(
+    state: State<'_, Mutex<AppState>>,
+    journal: State<'_, TimerJournal>,
+    action: RecoveryAction,
+    app_handle: tauri::AppHandle,
+) -> Result<(), TimerError> {
+    let mut app_state = state
+        .lock()
+        .map_err(|e| TimerError::LockError(format!("{:?}", e)))?;
+    let snapshot = app_state
+        .recovered_timer
+        .take()
+        .ok_or(TimerError::NothingToRecover)?;
+    match action {
+        RecoveryAction::Resume => {
+            app_state.timer_state = snapshot.resume(Instant::now());
+            if app_state.timer_state.running {
+                start_tracking(&app_handle, &mut app_state);
+            }
+        }
+        RecoveryAction::Close => {
+            let (timer_state, session) = snapshot.close();
+            app_state.timer_state = timer_state;
+            if let Some(session) = session {
+                finish_session(&app_handle, session);
             }
+            emit_final_update(&app_handle, &app_state);
         }
     }
+    write_journal(&journal, &app_state);
     Ok(())
 }
 
+/// Idle time in the current session waiting for the user to keep, discard
+/// or reassign it.
+#[tauri::command]
+pub fn get_pending_idle(state: State<'_, Mutex<AppState>>) -> Result<Vec<IdleSpan>, TimerError> {
+    let app_state = state
+        .lock()
+        .map_err(|e| TimerError::LockError(format!("{:?}", e)))?;
+    Ok(app_state.pending_idle.clone())
+}
+
+/// Decide what happens to an idle span. Discarded time is taken out of the
+/// session; reassigned time is too, and tracked on the other task as a
+/// session of its own. The reason is noted on the session the time ends
+/// up in.
+#[tauri::command]
+pub async fn resolve_idle(
+    state: State<'_, Mutex<AppState>>,
+    journal: State<'_, TimerJournal>,
+    id: Uuid,
+    decision: IdleDecision,
+    reason: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), TimerError> {
+    if let IdleDecision::Reassign {
+        task_id,
+        sub_task_id,
+    } = decision
+    {
+        TaskService::new()
+            .check_timer_target(&TimerTargetCheckDto {
+                task_id,
+                sub_task_id,
+            })
+            .await
+            .map_err(|e| TimerError::InvalidTarget(e.to_string()))?;
+    }
+
+    let mut app_state = state
+        .lock()
+        .map_err(|e| TimerError::LockError(format!("{:?}", e)))?;
+    let index = app_state
+        .pending_idle
+        .iter()
+        .position(|span| span.id == id)
+        .ok_or(TimerError::UnknownIdleSpan)?;
+    let span = app_state.pending_idle.remove(index);
+    let idle = Duration::from_secs(span.idle_seconds);
+    match decision {
+        IdleDecision::Keep => {
+            if let Some(reason) = &reason {
+                app_state.timer_state.add_note(reason);
+            }
+        }
+        IdleDecision::Discard => {
+            app_state.timer_state.discard(idle, Instant::now());
+        }
+        IdleDecision::Reassign {
+            task_id,
+            sub_task_id,
+        } => {
+            let moved = app_state.timer_state.discard(idle, Instant::now());
+            finish_session(
+                &app_handle,
+                TimerSession {
+                    task_id: Some(task_id),
+                    sub_task_id,
+                    start_time: span.start_time,
+                    end_time: span.end_time,
+                    elapsed_seconds: moved.as_secs(),
+                    note: reason
+                        .map(|reason| reason.trim().to_string())
+                        .filter(|reason| !reason.is_empty()),
+                },
+            );
+        }
+    }
+    write_journal(&journal, &app_state);
+    Ok(())
+}
+
+/// Emit one final update after a session ends.
+fn emit_final_update(app_handle: &tauri::AppHandle, app_state: &AppState) {
+    let timer_response = TimerResponse {
+        elapsed_seconds: 0,
+        running: false,
+        start_time: None,
+        end_time: app_state.timer_state.end_date_time,
+        task_id: app_state.timer_state.task_id,
+        sub_task_id: app_state.timer_state.sub_task_id,
+    };
+    if let Err(e) = app_handle.emit("timer-update", timer_response) {
+        eprintln!("Failed to emit final timer update: {:?}", e);
+    }
+}
+
+/// Queue time tracked on a task for upload, once it is stopped or
+/// switched away from, and emit it as a `timer-session` event.
+fn finish_session(app_handle: &tauri::AppHandle, session: TimerSession) {
+    if session.elapsed_seconds > 0 {
+        let outbox = app_handle.state::<Outbox>();
+        match outbox.enqueue(OutboxPayload::session(&session), Utc::now()) {
+            Ok(_) => notify_sync_status(app_handle),
+            Err(e) => eprintln!("Failed to queue timer session: {}", e),
+        }
+    }
+    if let Err(e) = app_handle.emit("timer-session", session) {
+        eprintln!("Failed to emit timer session: {:?}", e);
+    }
+}
+
+/// Start everything tracked alongside a running timer, as far as the
+/// config allows; what is already running is restarted.
+fn start_tracking(app_handle: &tauri::AppHandle, app_state: &mut AppState) {
+    start_activity_sampler(app_handle, app_state);
+    start_idle_detector(app_handle, app_state);
+    start_window_tracker(app_handle, app_state);
+    start_screenshots(app_handle, app_state);
+}
+
+/// Pick up a changed config while the timer runs.
+pub fn restart_tracking(app_handle: &tauri::AppHandle, app_state: &mut AppState) {
+    if app_state.timer_state.running {
+        start_tracking(app_handle, app_state);
+    }
+}
+
+/// Stop what `start_tracking` started, reporting what was tracked so far.
+fn stop_tracking(app_state: &mut AppState) {
+    let now = Utc::now();
+    app_state.activity_sampler.stop(now);
+    app_state.idle_detector.stop();
+    app_state.window_tracker.stop(now);
+    tokio::spawn(screenshot_command(
+        ScreenShotCommand::Stopped,
+        None,
+        None,
+        None,
+    ));
+}
+
+/// Sample keyboard and mouse activity on the running task, as far as the
+/// config allows, queueing each interval for upload.
+fn start_activity_sampler(app_handle: &tauri::AppHandle, app_state: &mut AppState) {
+    let config = SamplerConfig {
+        task_id: app_state.timer_state.task_id,
+        keyboard: app_state.app_config.enable_keyboard_activity,
+        mouse: app_state.app_config.enable_mouse_activity,
+        poll_interval: POLL_INTERVAL,
+        track_interval: TRACK_INTERVAL,
+    };
+    if !config.keyboard && !config.mouse {
+        app_state.activity_sampler.stop(Utc::now());
+        return;
+    }
+    let app_handle = app_handle.clone();
+    app_state
+        .activity_sampler
+        .start(config, DeviceInput::new, move |sample| {
+            let outbox = app_handle.state::<Outbox>();
+            match outbox.enqueue(OutboxPayload::Activity(sample), Utc::now()) {
+                Ok(_) => notify_sync_status(&app_handle),
+                Err(e) => eprintln!("Failed to queue activity sample: {}", e),
+            }
+        });
+}
+
+/// Watch for idle time in the running session, if the config asks for
+/// it. Spans are held in `pending_idle` and emitted as `idle-detected`
+/// events when the user comes back.
+fn start_idle_detector(app_handle: &tauri::AppHandle, app_state: &mut AppState) {
+    if !app_state.app_config.enable_idle_time {
+        app_state.idle_detector.stop();
+        return;
+    }
+    let app_handle = app_handle.clone();
+    app_state.idle_detector.start(
+        app_state.app_config.idle_time_threshold,
+        POLL_INTERVAL,
+        DeviceInput::new,
+        move |start_time, end_time| {
+            let state = app_handle.state::<Mutex<AppState>>();
+            let Ok(mut app_state) = state.lock() else {
+                return;
+            };
+            if !app_state.timer_state.running {
+                return;
+            }
+            let span = IdleSpan {
+                id: Uuid::new_v4(),
+                start_time,
+                end_time,
+                idle_seconds: (end_time - start_time).num_seconds().max(0) as u64,
+                task_id: app_state.timer_state.task_id,
+                sub_task_id: app_state.timer_state.sub_task_id,
+                ask_for_reason: app_state.app_config.ask_for_idle_reason,
+            };
+            app_state.pending_idle.push(span.clone());
+            if let Err(e) = app_handle.emit("idle-detected", span) {
+                eprintln!("Failed to emit idle time: {:?}", e);
+            }
+        },
+    );
+}
+
+/// Track the focused window on the running task, if the config asks for
+/// it, queueing each interval for upload.
+fn start_window_tracker(app_handle: &tauri::AppHandle, app_state: &mut AppState) {
+    if !app_state.app_config.enable_window_activity {
+        app_state.window_tracker.stop(Utc::now());
+        return;
+    }
+    let app_handle = app_handle.clone();
+    app_state.window_tracker.start(
+        app_state.timer_state.task_id,
+        WINDOW_POLL_INTERVAL,
+        TRACK_INTERVAL,
+        system_backend,
+        move |record| {
+            let outbox = app_handle.state::<Outbox>();
+            match outbox.enqueue(OutboxPayload::WindowActivity(record), Utc::now()) {
+                Ok(_) => notify_sync_status(&app_handle),
+                Err(e) => eprintln!("Failed to queue window activity: {}", e),
+            }
+        },
+    );
+}
+
+/// Take screenshots of the running session, if the config asks for it.
+fn start_screenshots(app_handle: &tauri::AppHandle, app_state: &AppState) {
+    let enabled = app_state.app_config.enable_screen_shots;
+    let interval = app_state.app_config.screen_shots_interval;
+    let (command, setup) = if enabled {
+        (
+            ScreenShotCommand::Running,
+            screenshot_setup(app_handle, app_state),
+        )
+    } else {
+        (ScreenShotCommand::Stopped, None)
+    };
+    println!(
+        "[Timer] Screenshots: {:?}, interval: {:?}",
+        enabled, interval
+    );
+    tokio::spawn(screenshot_command(
+        command,
+        Some(interval),
+        Some(enabled),
+        setup,
+    ));
+}
+
+/// Where screenshots of the current session are saved and what they may
+/// show; `None` when there is no app data directory to save them in.
+fn screenshot_setup(
+    app_handle: &tauri::AppHandle,
+    app_state: &AppState,
+) -> Option<ScreenshotSetup> {
+    let data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| eprintln!("No app data directory for screenshots: {}", e))
+        .ok()?;
+    let config = &app_state.app_config;
+    let store = ScreenshotStore::new(
+        &data_dir,
+        config.screenshot_storage.clone(),
+        app_state
+            .timer_state
+            .start_date_time
+            .unwrap_or_else(Utc::now),
+        app_state.timer_state.task_id,
+    )
+    .with_blur(config.screenshot_privacy.blur);
+    Some(ScreenshotSetup {
+        store,
+        backend: config.screen_capture_backend,
+        privacy: config.screenshot_privacy.clone(),
+        pause: app_state.screenshot_pause.clone(),
+    })
+}
+
+/// Write the current session to the journal. Called with the state locked,
+/// so writes land in the order of the transitions they record.
+fn write_journal(journal: &TimerJournal, app_state: &AppState) {
+    let snapshot = app_state.timer_state.snapshot(Instant::now(), Utc::now());
+    if let Err(e) = journal.record(snapshot.as_ref()) {
+        eprintln!("Failed to write timer journal: {}", e);
+    }
+}
+
 /// Response type for timer state information
 #[derive(Debug, Serialize, Clone)]
 pub struct TimerResponse {
@@ -160,6 +516,9 @@ pub struct TimerResponse {
     running: bool,
     start_time: Option<DateTime<Utc>>,
     end_time: Option<DateTime<Utc>>,
+    /// Task being tracked, or last tracked once stopped
+    task_id: Option<Uuid>,
+    sub_task_id: Option<Uuid>,
 }
 
 /// Get the current timer state
@@ -167,19 +526,15 @@ fn get_current_timer_state(state: &Mutex<AppState>) -> Result<TimerResponse, Tim
     let app_state = state
         .lock()
         .map_err(|e| TimerError::LockError(format!("{:?}", e)))?;
-    let mut total_elapsed = app_state.timer_state.elapsed;
-
-    if app_state.timer_state.running {
-        if let Some(start) = app_state.timer_state.start_instant {
-            total_elapsed += start.elapsed();
-        }
-    }
+    let total_elapsed = app_state.timer_state.elapsed_at(Instant::now());
 
     Ok(TimerResponse {
         elapsed_seconds: total_elapsed.as_secs(),
         running: app_state.timer_state.running,
         start_time: app_state.timer_state.start_date_time,
         end_time: app_state.timer_state.end_date_time,
+        task_id: app_state.timer_state.task_id,
+        sub_task_id: app_state.timer_state.sub_task_id,
     })
 }
 
@@ -194,6 +549,7 @@ pub async fn start_timer_updates(app: tauri::AppHandle) -> Result<(), TimerError
 
     // Spawn a background task
     tokio::spawn(async move {
+        let mut last_heartbeat = Instant::now();
         loop {
             tokio::select! {
                 // Check for cancellation
@@ -209,6 +565,15 @@ pub async fn start_timer_updates(app: tauri::AppHandle) -> Result<(), TimerError
                             eprintln!("Failed to emit timer update: {:?}", e);
                         }
                     }
+                    if last_heartbeat.elapsed() >= HEARTBEAT_INTERVAL {
+                        last_heartbeat = Instant::now();
+                        if let Ok(app_state) = state.lock() {
+                            // Paused sessions are written on the transition
+                            if app_state.timer_state.running {
+                                write_journal(&app.state::<TimerJournal>(), &app_state);
+                            }
+                        }
+                    }
                 }
             }
         }
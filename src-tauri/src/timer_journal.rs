@@ -0,0 +1,110 @@
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use crate::state::TimerSnapshot;
+
+/// File the journal is kept in, inside the app data directory.
+pub const JOURNAL_FILE: &str = "timer-journal.json";
+
+#[derive(Debug, thiserror::Error)]
+pub enum JournalError {
+    #[error("Failed to access timer journal: {0}")]
+    Io(#[from] io::Error),
+    #[error("Timer journal is corrupt: {0}")]
+    Format(#[from] serde_json::Error),
+}
+
+/// On-disk record of the running timer session, rewritten on every timer
+/// transition and heartbeat so a session survives a crash or reboot.
+#[derive(Debug)]
+pub struct TimerJournal {
+    path: PathBuf,
+}
+
+impl TimerJournal {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// The session left in the journal, if any.
+    pub fn load(&self) -> Result<Option<TimerSnapshot>, JournalError> {
+        match fs::read(&self.path) {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Record `snapshot`, or clear the journal when there is no session.
+    ///
+    /// The snapshot is written next to the journal and renamed over it, so
+    /// a crash mid-write leaves the previous snapshot intact.
+    pub fn record(&self, snapshot: Option<&TimerSnapshot>) -> Result<(), JournalError> {
+        let Some(snapshot) = snapshot else {
+            return match fs::remove_file(&self.path) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+                _ => Ok(()),
+            };
+        };
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        let mut file = File::create(&tmp)?;
+        file.write_all(&serde_json::to_vec(snapshot)?)?;
+        file.sync_all()?;
+        fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use uuid::Uuid;
+
+    use super::{JOURNAL_FILE, TimerJournal};
+    use crate::state::TimerSnapshot;
+
+    #[test]
+    fn records_loads_and_clears() {
+        let dir = tempfile::tempdir().unwrap();
+        let journal = TimerJournal::new(dir.path().join("nested").join(JOURNAL_FILE));
+        assert!(journal.load().unwrap().is_none());
+        // Clearing an absent journal is fine
+        journal.record(None).unwrap();
+
+        let snapshot = TimerSnapshot {
+            running: true,
+            elapsed_seconds: 754,
+            start_time: Some(Utc.with_ymd_and_hms(2025, 3, 1, 9, 0, 0).unwrap()),
+            task_id: Some(Uuid::new_v4()),
+            sub_task_id: None,
+            note: Some("Reading the spec".to_string()),
+            heartbeat: Utc.with_ymd_and_hms(2025, 3, 1, 9, 12, 34).unwrap(),
+        };
+        journal.record(Some(&snapshot)).unwrap();
+        assert_eq!(journal.load().unwrap(), Some(snapshot.clone()));
+
+        let paused = TimerSnapshot {
+            running: false,
+            ..snapshot
+        };
+        journal.record(Some(&paused)).unwrap();
+        assert_eq!(journal.load().unwrap(), Some(paused));
+
+        journal.record(None).unwrap();
+        assert!(journal.load().unwrap().is_none());
+    }
+
+    #[test]
+    fn corrupt_journal_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(JOURNAL_FILE);
+        std::fs::write(&path, b"{\"running\": tr").unwrap();
+        assert!(TimerJournal::new(path).load().is_err());
+    }
+}
@@ -1,24 +1,24 @@
 use lazy_static::lazy_static;
-use rand::Rng;
 use rand::thread_rng;
 use serde::Deserialize;
 use serde::Serialize;
-use std::sync::Arc;
 use std::sync::Mutex;
-use std::sync::atomic::{AtomicBool, Ordering};
 
 lazy_static! {
-    static ref SCREENSHOT_RUNNING: Mutex<Option<Arc<AtomicBool>>> = Mutex::new(None);
+    static ref SCREENSHOT_SCHEDULER: Mutex<ScreenshotScheduler> =
+        Mutex::new(ScreenshotScheduler::default());
 }
 use std::time::Instant;
 use tauri::AppHandle;
 use tauri::Manager;
 use tauri::State;
-use tokio::time::{Duration, sleep};
-use xcap::Monitor;
-use xcap::XCapError;
+use tokio::time::Duration;
 
+use crate::screen_capture::{CaptureBackend, ScreenshotScheduler, next_delay};
+use crate::screenshot_privacy::{PrivacyFilter, ScreenshotPause, ScreenshotPrivacy};
+use crate::screenshot_store::ScreenshotStore;
 use crate::state::AppState;
+use crate::window_tracker::system_backend;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum ScreenShotCommand {
@@ -26,6 +26,14 @@ pub enum ScreenShotCommand {
     Stopped,
 }
 
+/// Where the screenshots of a session go and what they may show.
+pub struct ScreenshotSetup {
+    pub store: ScreenshotStore,
+    pub backend: CaptureBackend,
+    pub privacy: ScreenshotPrivacy,
+    pub pause: ScreenshotPause,
+}
+
 #[derive(Debug, thiserror::Error, Serialize)]
 #[allow(dead_code)]
 pub enum ActivityError {
@@ -37,18 +45,11 @@ pub enum ActivityError {
     MonitorError(String),
 }
 
-fn normalized(filename: &str) -> String {
-    filename
-        .replace("|", "")
-        .replace("\\", "")
-        .replace(":", "")
-        .replace("/", "")
-}
-
 pub async fn screenshot_command(
     command: ScreenShotCommand,
     duration: Option<Duration>,
     enabled: Option<bool>,
+    setup: Option<ScreenshotSetup>,
 ) {
     println!("Enabled: {:?}", enabled);
     println!("[Screenshot] Command received: {:?}", command);
@@ -62,77 +63,62 @@ pub async fn screenshot_command(
                 println!("[Screenshot] Screenshots disabled, exiting...");
                 return;
             }
+            let Some(ScreenshotSetup {
+                store,
+                backend,
+                privacy,
+                pause,
+            }) = setup
+            else {
+                eprintln!("[Screenshot] No screenshot store, exiting...");
+                return;
+            };
 
-            // Stop any existing screenshot service
-            if let Ok(mut running) = SCREENSHOT_RUNNING.lock() {
-                if let Some(flag) = running.take() {
-                    flag.store(false, Ordering::SeqCst);
-                    println!("[Screenshot] Stopping existing service...");
-                }
+            let Ok(mut scheduler) = SCREENSHOT_SCHEDULER.lock() else {
+                eprintln!("[Screenshot] Screenshot scheduler lock poisoned, exiting...");
+                return;
+            };
+            if scheduler.is_running() {
+                println!("[Screenshot] Stopping existing service...");
             }
 
             println!("[Screenshot] Starting screenshot service...");
-            let running = Arc::new(AtomicBool::new(true));
-            let running_clone = running.clone();
-
-            // Store the running flag in static state
-            if let Ok(mut screenshot_running) = SCREENSHOT_RUNNING.lock() {
-                *screenshot_running = Some(running_clone);
-            }
-
-            tokio::spawn(async move {
-                // Generate initial random delay within the duration window
-                let max_interval = duration
-                    .unwrap_or(Duration::from_secs(10))
-                    .as_secs()
-                    .max(10);
-
-                while running.load(Ordering::SeqCst) {
-                    // Generate a random delay between 5s and max_interval
-                    let random_delay =
-                        Duration::from_secs(thread_rng().gen_range(5..=max_interval));
-                    println!(
-                        "[Screenshot] Waiting {}s until next capture...",
-                        random_delay.as_secs()
-                    );
-                    sleep(random_delay).await;
-
-                    // Only proceed if we're still running after the delay
-                    if !running.load(Ordering::SeqCst) {
-                        break;
-                    }
-
-                    println!("[Screenshot] Taking new screenshots...");
-                    if let Some(monitors) = get_all_monitors().await {
-                        println!("[Screenshot] Found {} monitors", monitors.len());
-                        for (i, monitor) in monitors.iter().enumerate() {
-                            println!("[Screenshot] Processing monitor {}", i + 1);
-                            if let Ok(screenshot) = monitor.capture_image() {
-                                let timestamp =
-                                    chrono::Local::now().format("%Y%m%d_%H%M%S").to_string();
-                                let filename = format!("screenshot_{}.png", normalized(&timestamp));
-                                println!("[Screenshot] Saving screenshot to: {}", filename);
-
-                                // Save screenshot asynchronously
-                                tokio::spawn(async move {
-                                    if let Err(e) = screenshot.save(&filename) {
-                                        eprintln!(
-                                            "[Screenshot] Error: Failed to save screenshot {}: {}",
-                                            filename, e
-                                        );
-                                    }
-                                });
-                            }
-                        }
-                    }
-                }
-            });
+            let interval = duration.unwrap_or(Duration::from_secs(10));
+            scheduler.start(
+                move || {
+                    // The focused window is only needed to apply exclusions
+                    let windows = if privacy.has_exclusions() {
+                        system_backend()
+                            .map_err(|e| eprintln!("[Screenshot] Exclusions not applied: {}", e))
+                            .ok()
+                    } else {
+                        None
+                    };
+                    PrivacyFilter::new(backend.capturer(), windows, privacy, pause)
+                },
+                move || next_delay(&mut thread_rng(), interval),
+                move |capture, captured_at| match store.save(
+                    capture.monitor,
+                    &capture.name,
+                    &capture.image,
+                    captured_at,
+                ) {
+                    Ok(record) => println!(
+                        "[Screenshot] Saved screenshot to: {}",
+                        record.path.display()
+                    ),
+                    Err(e) => eprintln!(
+                        "[Screenshot] Error: Failed to save screenshot of monitor {}: {}",
+                        capture.monitor, e
+                    ),
+                },
+            );
         }
         ScreenShotCommand::Stopped => {
             println!("[Screenshot] Stopping screenshot service...");
-            if let Ok(mut running) = SCREENSHOT_RUNNING.lock() {
-                if let Some(flag) = running.take() {
-                    flag.store(false, Ordering::SeqCst);
+            if let Ok(mut scheduler) = SCREENSHOT_SCHEDULER.lock() {
+                if scheduler.is_running() {
+                    scheduler.stop();
                     println!("[Screenshot] Service stopped successfully");
                 } else {
                     println!("[Screenshot] No running service found");
@@ -142,16 +128,6 @@ pub async fn screenshot_command(
     }
 }
 
-async fn get_all_monitors() -> Option<Vec<Monitor>> {
-    match Monitor::all() {
-        Ok(m) => Some(m),
-        Err(e) => {
-            eprintln!("Error fetching monitors: {}", e);
-            None
-        }
-    }
-}
-
 // pub async fn start_capturing(duration: Duration, enabled: bool) {
 //     println!(
 //         "Starting screenshot capture... {:?} {:?}",
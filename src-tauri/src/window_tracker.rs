@@ -0,0 +1,579 @@
+use std::{
+    sync::mpsc::{self, RecvTimeoutError, Sender},
+    thread,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use ot_server::activity_dto::{WindowActivityDataCreateDto, WindowActivityDto, WindowSpanDto};
+use uuid::Uuid;
+
+/// How often the focused window is checked.
+pub const WINDOW_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Longest application name and window title sent to the server.
+const MAX_APP_NAME: usize = 200;
+const MAX_TITLE: usize = 1000;
+
+#[derive(Debug, thiserror::Error)]
+pub enum WindowError {
+    #[error("Window tracking is not supported on this desktop")]
+    Unsupported,
+    #[cfg(target_os = "linux")]
+    #[error("X11 error: {0}")]
+    X11(#[from] xcb::Error),
+    #[cfg(target_os = "linux")]
+    #[error("Failed to connect to the X server: {0}")]
+    Connect(#[from] xcb::ConnError),
+}
+
+/// The window with keyboard focus.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveWindow {
+    pub app_name: String,
+    pub title: String,
+    pub pid: Option<u32>,
+}
+
+/// Where the tracker asks for the focused window; faked in tests.
+pub trait WindowBackend {
+    /// The focused window; `None` when no window has focus.
+    fn active_window(&mut self) -> Result<Option<ActiveWindow>, WindowError>;
+}
+
+impl<B: WindowBackend + ?Sized> WindowBackend for Box<B> {
+    fn active_window(&mut self) -> Result<Option<ActiveWindow>, WindowError> {
+        (**self).active_window()
+    }
+}
+
+/// The backend for this desktop: X11 on Linux. Wayland has no way to
+/// read other clients' windows, so there only windows running under
+/// XWayland are seen.
+pub fn system_backend() -> Result<Box<dyn WindowBackend>, WindowError> {
+    #[cfg(target_os = "linux")]
+    if std::env::var_os("DISPLAY").is_some() {
+        return Ok(Box::new(x11::X11Backend::connect(None)?));
+    }
+    Err(WindowError::Unsupported)
+}
+
+/// Folds polls of the focused window into spans of unbroken focus, one
+/// interval at a time.
+#[derive(Debug)]
+pub struct WindowSpans {
+    task_id: Option<Uuid>,
+    interval_start: DateTime<Utc>,
+    /// Window in focus at the last poll, and since when
+    current: Option<(ActiveWindow, DateTime<Utc>)>,
+    spans: Vec<WindowSpanDto>,
+}
+
+impl WindowSpans {
+    pub fn new(task_id: Option<Uuid>, start: DateTime<Utc>) -> Self {
+        Self {
+            task_id,
+            interval_start: start,
+            current: None,
+            spans: Vec::new(),
+        }
+    }
+
+    pub fn interval_start(&self) -> DateTime<Utc> {
+        self.interval_start
+    }
+
+    /// Record the window in focus at `at`. A window still in focus since
+    /// the last poll extends its span.
+    pub fn record(&mut self, at: DateTime<Utc>, window: Option<ActiveWindow>) {
+        if self.current.as_ref().map(|(current, _)| current) == window.as_ref() {
+            return;
+        }
+        self.close_span(at);
+        self.current = window.map(|window| (window, at));
+    }
+
+    /// End the interval at `end` and start the next one there, with the
+    /// window in focus carried over; `None` if no window had focus.
+    pub fn finish(&mut self, end: DateTime<Utc>) -> Option<WindowActivityDataCreateDto> {
+        let current = self.current.clone();
+        self.close_span(end);
+        self.current = current.map(|(window, _)| (window, end));
+        let start = std::mem::replace(&mut self.interval_start, end);
+        let windows = std::mem::take(&mut self.spans);
+        if windows.is_empty() {
+            return None;
+        }
+        Some(WindowActivityDataCreateDto {
+            id: Uuid::new_v4(),
+            active_window_ss: vec![],
+            active_window_data: WindowActivityDto {
+                task_id: self.task_id,
+                start_time: start.fixed_offset(),
+                end_time: end.fixed_offset(),
+                windows,
+            },
+        })
+    }
+
+    fn close_span(&mut self, end: DateTime<Utc>) {
+        let Some((window, start)) = self.current.take() else {
+            return;
+        };
+        if end <= start {
+            return;
+        }
+        self.spans.push(WindowSpanDto {
+            app_name: window.app_name.chars().take(MAX_APP_NAME).collect(),
+            title: window.title.chars().take(MAX_TITLE).collect(),
+            pid: window.pid,
+            start_time: start.fixed_offset(),
+            end_time: end.fixed_offset(),
+            focus_seconds: (end - start).num_seconds() as i32,
+        });
+    }
+}
+
+/// Polls the focused window on a dedicated thread while the timer runs,
+/// handing a record to the sink at the end of every interval and when
+/// stopped.
+#[derive(Debug, Default)]
+pub struct WindowTracker {
+    stop: Option<Sender<DateTime<Utc>>>,
+}
+
+impl WindowTracker {
+    /// Start tracking, stopping a tracker already running. The backend is
+    /// made on the tracker thread.
+    pub fn start<B, F, K>(
+        &mut self,
+        task_id: Option<Uuid>,
+        poll_interval: Duration,
+        track_interval: Duration,
+        make_backend: F,
+        sink: K,
+    ) where
+        B: WindowBackend,
+        F: FnOnce() -> Result<B, WindowError> + Send + 'static,
+        K: Fn(WindowActivityDataCreateDto) + Send + 'static,
+    {
+        self.stop(Utc::now());
+        let (tx, rx) = mpsc::channel();
+        self.stop = Some(tx);
+        let spawned = thread::Builder::new()
+            .name("window-tracker".into())
+            .spawn(move || {
+                let mut backend = match make_backend() {
+                    Ok(backend) => backend,
+                    Err(e) => {
+                        eprintln!("[Window] Not tracking windows: {}", e);
+                        return;
+                    }
+                };
+                let mut spans = WindowSpans::new(task_id, Utc::now());
+                let track_interval = chrono::Duration::from_std(track_interval)
+                    .expect("Track interval fits a chrono duration");
+                let finish = |spans: &mut WindowSpans, end| {
+                    if let Some(record) = spans.finish(end) {
+                        sink(record);
+                    }
+                };
+                loop {
+                    let now = Utc::now();
+                    let interval_end = spans.interval_start() + track_interval;
+                    if now >= interval_end {
+                        finish(&mut spans, interval_end);
+                    }
+                    match backend.active_window() {
+                        Ok(window) => spans.record(now, window),
+                        Err(e) => {
+                            eprintln!("[Window] Stopped tracking windows: {}", e);
+                            break finish(&mut spans, now);
+                        }
+                    }
+                    match rx.recv_timeout(poll_interval) {
+                        Ok(end) => break finish(&mut spans, end),
+                        Err(RecvTimeoutError::Disconnected) => {
+                            break finish(&mut spans, Utc::now());
+                        }
+                        Err(RecvTimeoutError::Timeout) => {}
+                    }
+                }
+            });
+        if let Err(e) = spawned {
+            eprintln!("[Window] Failed to start window tracking: {}", e);
+        }
+    }
+
+    /// Stop tracking, reporting the interval so far as ending at `end`.
+    pub fn stop(&mut self, end: DateTime<Utc>) {
+        if let Some(stop) = self.stop.take() {
+            // The thread is gone already if this fails
+            let _ = stop.send(end);
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod x11 {
+    use xcb::{Xid, x};
+
+    use super::{ActiveWindow, WindowBackend, WindowError};
+
+    /// Parents walked up from the focused window to find its top-level
+    /// window.
+    const MAX_DEPTH: usize = 16;
+
+    xcb::atoms_struct! {
+        struct Atoms {
+            net_active_window => b"_NET_ACTIVE_WINDOW" only_if_exists = false,
+            net_wm_name => b"_NET_WM_NAME" only_if_exists = false,
+            net_wm_pid => b"_NET_WM_PID" only_if_exists = false,
+            utf8_string => b"UTF8_STRING" only_if_exists = false,
+        }
+    }
+
+    /// Reads the focused window from an X server: `_NET_ACTIVE_WINDOW`
+    /// where a window manager sets it, the input focus otherwise.
+    pub struct X11Backend {
+        conn: xcb::Connection,
+        root: x::Window,
+        atoms: Atoms,
+    }
+
+    impl X11Backend {
+        /// Connect to `display`, or to `$DISPLAY`.
+        pub fn connect(display: Option<&str>) -> Result<Self, WindowError> {
+            let (conn, screen) = xcb::Connection::connect(display)?;
+            let root = conn
+                .get_setup()
+                .roots()
+                .nth(screen as usize)
+                .ok_or(xcb::ConnError::ClosedInvalidScreen)?
+                .root();
+            let atoms = Atoms::intern_all(&conn)?;
+            Ok(Self { conn, root, atoms })
+        }
+
+        fn property<P: x::PropEl + Copy>(
+            &self,
+            window: x::Window,
+            property: x::Atom,
+            r#type: x::Atom,
+        ) -> xcb::Result<Vec<P>> {
+            let reply = self
+                .conn
+                .wait_for_reply(self.conn.send_request(&x::GetProperty {
+                    delete: false,
+                    window,
+                    property,
+                    r#type,
+                    long_offset: 0,
+                    long_length: 1024,
+                }))?;
+            // Set by other clients, so not trusted to have the right format
+            if reply.format() != P::FORMAT {
+                return Ok(Vec::new());
+            }
+            Ok(reply.value::<P>().to_vec())
+        }
+
+        fn text(
+            &self,
+            window: x::Window,
+            property: x::Atom,
+            r#type: x::Atom,
+        ) -> xcb::Result<String> {
+            let bytes = self.property::<u8>(window, property, r#type)?;
+            Ok(String::from_utf8_lossy(&bytes).into_owned())
+        }
+
+        fn focused(&self) -> xcb::Result<Option<x::Window>> {
+            let active = self.property::<x::Window>(
+                self.root,
+                self.atoms.net_active_window,
+                x::ATOM_WINDOW,
+            )?;
+            if let Some(&window) = active.first().filter(|window| !window.is_none()) {
+                return Ok(Some(window));
+            }
+            let focus = self
+                .conn
+                .wait_for_reply(self.conn.send_request(&x::GetInputFocus {}))?
+                .focus();
+            // 0 and 1 are None and PointerRoot, not windows
+            if focus.resource_id() <= 1 || focus == self.root {
+                return Ok(None);
+            }
+            Ok(Some(focus))
+        }
+
+        /// The top-level window `window` belongs to: the nearest one with
+        /// a `WM_CLASS`, as focus may be on a child window.
+        fn top_level(&self, window: x::Window) -> xcb::Result<x::Window> {
+            let mut current = window;
+            for _ in 0..MAX_DEPTH {
+                if !self
+                    .property::<u8>(current, x::ATOM_WM_CLASS, x::ATOM_STRING)?
+                    .is_empty()
+                {
+                    return Ok(current);
+                }
+                let tree = self
+                    .conn
+                    .wait_for_reply(self.conn.send_request(&x::QueryTree { window: current }))?;
+                if tree.parent().is_none() || tree.parent() == self.root {
+                    break;
+                }
+                current = tree.parent();
+            }
+            Ok(window)
+        }
+
+        fn describe(&self, window: x::Window) -> xcb::Result<ActiveWindow> {
+            let window = self.top_level(window)?;
+            let mut title = self.text(window, self.atoms.net_wm_name, self.atoms.utf8_string)?;
+            if title.is_empty() {
+                title = self.text(window, x::ATOM_WM_NAME, x::ATOM_ANY)?;
+            }
+            let pid = self
+                .property::<u32>(window, self.atoms.net_wm_pid, x::ATOM_CARDINAL)?
+                .first()
+                .copied();
+            // Instance and class name, each ending in a nul
+            let class = self.text(window, x::ATOM_WM_CLASS, x::ATOM_STRING)?;
+            let mut names = class.split('\0').filter(|name| !name.is_empty());
+            let instance = names.next();
+            let app_name = match names.next().or(instance) {
+                Some(name) => name.to_string(),
+                None => pid.map(process_name).unwrap_or_default(),
+            };
+            Ok(ActiveWindow {
+                app_name,
+                title,
+                pid,
+            })
+        }
+    }
+
+    impl WindowBackend for X11Backend {
+        fn active_window(&mut self) -> Result<Option<ActiveWindow>, WindowError> {
+            let described = self
+                .focused()
+                .and_then(|window| window.map(|window| self.describe(window)).transpose());
+            match described {
+                Ok(window) => Ok(window),
+                // The window closed while it was being read
+                Err(xcb::Error::Protocol(_)) => Ok(None),
+                Err(e) => Err(e.into()),
+            }
+        }
+    }
+
+    /// Name of the process `pid` runs, for windows without a class.
+    fn process_name(pid: u32) -> String {
+        std::fs::read_to_string(format!("/proc/{}/comm", pid))
+            .map(|name| name.trim().to_string())
+            .unwrap_or_default()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use xcb::x;
+
+        use super::X11Backend;
+        use crate::window_tracker::{ActiveWindow, WindowBackend};
+
+        #[test]
+        #[ignore = "needs an X server, e.g. `xvfb-run cargo test -- --ignored`"]
+        fn reads_the_focused_window() {
+            // Another client, as the tracked application would be
+            let (conn, screen) = xcb::Connection::connect(None).unwrap();
+            let screen = conn.get_setup().roots().nth(screen as usize).unwrap();
+            let window: x::Window = conn.generate_id();
+            conn.send_request(&x::CreateWindow {
+                depth: x::COPY_FROM_PARENT as u8,
+                wid: window,
+                parent: screen.root(),
+                x: 0,
+                y: 0,
+                width: 100,
+                height: 100,
+                border_width: 0,
+                class: x::WindowClass::InputOutput,
+                visual: screen.root_visual(),
+                value_list: &[],
+            });
+            let net_wm_pid = conn
+                .wait_for_reply(conn.send_request(&x::InternAtom {
+                    only_if_exists: false,
+                    name: b"_NET_WM_PID",
+                }))
+                .unwrap()
+                .atom();
+            for (property, r#type, data) in [
+                (x::ATOM_WM_NAME, x::ATOM_STRING, &b"Quarterly report"[..]),
+                (x::ATOM_WM_CLASS, x::ATOM_STRING, &b"report\0Editor\0"[..]),
+            ] {
+                conn.send_request(&x::ChangeProperty {
+                    mode: x::PropMode::Replace,
+                    window,
+                    property,
+                    r#type,
+                    data,
+                });
+            }
+            conn.send_request(&x::ChangeProperty {
+                mode: x::PropMode::Replace,
+                window,
+                property: net_wm_pid,
+                r#type: x::ATOM_CARDINAL,
+                data: &[4242u32],
+            });
+            conn.send_and_check_request(&x::MapWindow { window })
+                .unwrap();
+            conn.send_and_check_request(&x::SetInputFocus {
+                revert_to: x::InputFocus::PointerRoot,
+                focus: window,
+                time: x::CURRENT_TIME,
+            })
+            .unwrap();
+
+            let mut backend = X11Backend::connect(None).unwrap();
+            assert_eq!(
+                backend.active_window().unwrap(),
+                Some(ActiveWindow {
+                    app_name: "Editor".to_string(),
+                    title: "Quarterly report".to_string(),
+                    pid: Some(4242),
+                })
+            );
+
+            conn.send_and_check_request(&x::DestroyWindow { window })
+                .unwrap();
+            assert_eq!(backend.active_window().unwrap(), None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::mpsc, time::Duration};
+
+    use chrono::{TimeZone, Utc};
+    use uuid::Uuid;
+
+    use super::{ActiveWindow, WindowBackend, WindowError, WindowSpans, WindowTracker};
+
+    fn window(app_name: &str, title: &str) -> Option<ActiveWindow> {
+        Some(ActiveWindow {
+            app_name: app_name.to_string(),
+            title: title.to_string(),
+            pid: Some(7),
+        })
+    }
+
+    #[test]
+    fn consecutive_polls_make_one_span() {
+        let start = Utc.with_ymd_and_hms(2025, 3, 1, 9, 0, 0).unwrap();
+        let at = |s: i64| start + chrono::Duration::seconds(s);
+        let mut spans = WindowSpans::new(Some(Uuid::from_u128(1)), start);
+
+        spans.record(at(0), window("Editor", "main.rs"));
+        spans.record(at(1), window("Editor", "main.rs"));
+        spans.record(at(2), window("Editor", "main.rs"));
+        // Another tab is another span
+        spans.record(at(3), window("Browser", "Docs"));
+        spans.record(at(5), window("Browser", "Issues"));
+        // Nothing focused, e.g. the desktop
+        spans.record(at(6), None);
+        spans.record(at(8), window("Editor", "main.rs"));
+
+        let record = spans.finish(at(10)).unwrap();
+        let activity = record.active_window_data;
+        assert_eq!(activity.task_id, Some(Uuid::from_u128(1)));
+        assert_eq!(
+            (activity.start_time, activity.end_time),
+            (start.fixed_offset(), at(10).fixed_offset())
+        );
+        let seen: Vec<_> = activity
+            .windows
+            .iter()
+            .map(|span| (span.title.as_str(), span.focus_seconds))
+            .collect();
+        assert_eq!(
+            seen,
+            vec![("main.rs", 3), ("Docs", 2), ("Issues", 1), ("main.rs", 2)]
+        );
+        assert_eq!(activity.windows[0].app_name, "Editor");
+        assert_eq!(activity.windows[0].pid, Some(7));
+
+        // The focused window carries over into the next interval
+        let record = spans.finish(at(15)).unwrap();
+        let windows = record.active_window_data.windows;
+        assert_eq!(windows.len(), 1);
+        assert_eq!(windows[0].start_time, at(10).fixed_offset());
+        assert_eq!(windows[0].focus_seconds, 5);
+
+        spans.record(at(15), None);
+        assert!(spans.finish(at(20)).is_none());
+    }
+
+    /// Plays back a list of focused windows, then keeps the last one.
+    struct Scripted(Vec<Option<ActiveWindow>>);
+
+    impl WindowBackend for Scripted {
+        fn active_window(&mut self) -> Result<Option<ActiveWindow>, WindowError> {
+            if self.0.len() > 1 {
+                Ok(self.0.remove(0))
+            } else {
+                Ok(self.0[0].clone())
+            }
+        }
+    }
+
+    #[test]
+    fn tracker_reports_spans_on_stop() {
+        let (tx, rx) = mpsc::channel();
+        let mut tracker = WindowTracker::default();
+        tracker.start(
+            None,
+            Duration::from_millis(5),
+            Duration::from_secs(600),
+            || {
+                Ok(Scripted(vec![
+                    window("Editor", "main.rs"),
+                    window("Browser", "Docs"),
+                ]))
+            },
+            move |record| tx.send(record).unwrap(),
+        );
+        std::thread::sleep(Duration::from_millis(100));
+        tracker.stop(Utc::now());
+
+        let records: Vec<_> = rx.iter().collect();
+        assert_eq!(records.len(), 1);
+        let titles: Vec<_> = records[0]
+            .active_window_data
+            .windows
+            .iter()
+            .map(|span| span.title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["main.rs", "Docs"]);
+    }
+
+    #[test]
+    fn tracker_without_a_backend_reports_nothing() {
+        let (tx, rx) = mpsc::channel();
+        let mut tracker = WindowTracker::default();
+        tracker.start(
+            None,
+            Duration::from_millis(5),
+            Duration::from_secs(600),
+            || Err::<Scripted, _>(WindowError::Unsupported),
+            move |record| tx.send(record).unwrap(),
+        );
+        tracker.stop(Utc::now());
+        assert!(rx.iter().next().is_none());
+    }
+}
@@ -0,0 +1,56 @@
+import { useEffect } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { invoke } from "@tauri-apps/api/core";
+import { BudgetAlert } from "../../types";
+
+const KIND_LABELS: Record<BudgetAlert["kind"], string> = {
+  time: "time budget",
+  money: "money budget",
+  estimate: "estimate",
+};
+
+function alertTitle(alert: BudgetAlert): string {
+  return alert.task_name
+    ? `${alert.project_name} / ${alert.task_name}`
+    : alert.project_name;
+}
+
+function alertBody(alert: BudgetAlert): string {
+  return `${alert.threshold}% of the ${KIND_LABELS[alert.kind]} used`;
+}
+
+/** Shows budget alerts from the server as system notifications. */
+export function BudgetAlerts() {
+  useEffect(() => {
+    if ("Notification" in window && Notification.permission === "default") {
+      Notification.requestPermission().catch(console.error);
+    }
+    invoke("start_budget_alert_updates").catch(console.error);
+
+    const unlisten = listen<BudgetAlert>("budget-alert", (event) => {
+      const alert = event.payload;
+      if (
+        !("Notification" in window) ||
+        Notification.permission !== "granted"
+      ) {
+        console.warn(`${alertTitle(alert)}: ${alertBody(alert)}`);
+        return;
+      }
+      const notification = new Notification(alertTitle(alert), {
+        body: alertBody(alert),
+        tag: alert.id,
+      });
+      notification.onclick = () => {
+        invoke("acknowledge_budget_alert", { id: alert.id }).catch(
+          console.error
+        );
+      };
+    });
+
+    return () => {
+      unlisten.then((unlistenFn) => unlistenFn());
+    };
+  }, []);
+
+  return null;
+}
@@ -1,4 +1,4 @@
-import { Task, SubTask } from "../../types";
+import { Task, SubTask, TaskDetails } from "../../types";
 import { formatDate } from "../../utils/formatDate";
 
 interface TaskItemProps {
@@ -11,6 +11,26 @@ interface TaskItemProps {
   onUpdateSubtask: (subtask: SubTask) => void;
 }
 
-export function TaskItem({ task }: { task: any }) {
-  return <div>{task.name}</div>;
+export function TaskItem({
+  task,
+}: {
+  task: Pick<Task, "name"> & Partial<TaskDetails> & { blocked?: boolean };
+}) {
+  const details = [
+    task.blocked ? "blocked" : null,
+    task.status?.replace(/_/g, " "),
+    task.priority && task.priority !== "medium" ? task.priority : null,
+    task.due_date ? `due ${new Date(task.due_date).toLocaleDateString()}` : null,
+    task.estimated_minutes ? `${task.estimated_minutes}m est.` : null,
+    task.assignee,
+  ].filter(Boolean);
+
+  return (
+    <div>
+      <div>{task.name}</div>
+      {details.length > 0 && (
+        <div className="text-tiny text-default-400">{details.join(" · ")}</div>
+      )}
+    </div>
+  );
 }
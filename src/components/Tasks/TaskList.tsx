@@ -41,7 +41,7 @@ export function TaskList() {
       >
         {tasks.map((item) => (
           <ListboxItem
-            startContent={<SmallControlButton />}
+            startContent={<SmallControlButton taskId={item.id} />}
             key={item.id}
             textValue={item.name}
           >
@@ -68,7 +68,18 @@ function ControlButton({
     command: "Start" | "Pause" | "Resume" | "Stop"
   ) => {
     try {
-      await invoke("control_timer", { command });
+      await invoke("control_timer", {
+        // Starting again tracks the task last tracked
+        command:
+          command === "Start"
+            ? {
+                Start: {
+                  task_id: timerState.task_id,
+                  sub_task_id: timerState.sub_task_id,
+                },
+              }
+            : command,
+      });
     } catch (error) {
       console.error("Failed to control timer:", error);
     }
@@ -196,6 +207,7 @@ function ControlButton({
           >
             <Button
               onPress={() => handleTimerControl(buttonConfig.command as any)}
+              isDisabled={buttonConfig.command === "Start" && !timerState.task_id}
               variant="shadow"
               color={buttonConfig.color as any}
               className={cn(
@@ -0,0 +1,169 @@
+import { useEffect, useState } from "react";
+import { Button, Input, Listbox, ListboxItem } from "@heroui/react";
+import { listen } from "@tauri-apps/api/event";
+import { invoke } from "@tauri-apps/api/core";
+import { IdleDecision, IdleSpan, SearchHit } from "../../types";
+
+function minutes(seconds: number): string {
+  const count = Math.max(1, Math.round(seconds / 60));
+  return `${count} minute${count === 1 ? "" : "s"}`;
+}
+
+/**
+ * Asks what to do with time the user was away from the keyboard while the
+ * timer ran: keep it, discard it, or move it to another task.
+ */
+export function IdlePrompt() {
+  const [spans, setSpans] = useState<IdleSpan[]>([]);
+  const [reason, setReason] = useState("");
+  const [reassigning, setReassigning] = useState(false);
+  const [query, setQuery] = useState("");
+  const [hits, setHits] = useState<SearchHit[]>([]);
+
+  useEffect(() => {
+    const refresh = () => {
+      invoke<IdleSpan[]>("get_pending_idle")
+        .then(setSpans)
+        .catch(console.error);
+    };
+    refresh();
+
+    const unlistenIdle = listen<IdleSpan>("idle-detected", (event) => {
+      setSpans((current) => [...current, event.payload]);
+    });
+    // Idle time still undecided when its session ends is kept in it
+    const unlistenSession = listen("timer-session", refresh);
+
+    return () => {
+      unlistenIdle.then((unlistenFn) => unlistenFn());
+      unlistenSession.then((unlistenFn) => unlistenFn());
+    };
+  }, []);
+
+  useEffect(() => {
+    if (!reassigning || query.trim() === "") {
+      setHits([]);
+      return;
+    }
+    invoke<SearchHit[]>("search", { q: query, limit: 8 })
+      .then((found) =>
+        setHits(
+          found.filter((hit) => hit.kind === "task" || hit.kind === "sub_task")
+        )
+      )
+      .catch(console.error);
+  }, [reassigning, query]);
+
+  const span = spans[0];
+  if (!span) {
+    return null;
+  }
+
+  const resolve = (decision: IdleDecision) => {
+    invoke("resolve_idle", {
+      id: span.id,
+      decision,
+      reason: reason.trim() || null,
+    })
+      .catch(console.error)
+      .finally(() => {
+        // A refused reassignment leaves the span pending
+        invoke<IdleSpan[]>("get_pending_idle")
+          .then(setSpans)
+          .catch(console.error);
+        setReason("");
+        setReassigning(false);
+        setQuery("");
+      });
+  };
+
+  const reassignTo = (hit: SearchHit) => {
+    const task_id = hit.kind === "task" ? hit.id : hit.task_id;
+    if (!task_id) {
+      return;
+    }
+    resolve({
+      reassign: {
+        task_id,
+        sub_task_id: hit.kind === "sub_task" ? hit.id : null,
+      },
+    });
+  };
+
+  const from = new Date(span.start_time).toLocaleTimeString();
+  const to = new Date(span.end_time).toLocaleTimeString();
+
+  return (
+    <div className="mx-4 mb-4 rounded-lg border border-warning-200 bg-warning-50 p-3 text-sm">
+      <p className="font-semibold text-gray-800">
+        You were idle for {minutes(span.idle_seconds)}
+      </p>
+      <p className="text-xs text-gray-600 mb-3">
+        From {from} to {to}, while the timer was running.
+      </p>
+      {span.ask_for_reason && (
+        <Input
+          size="sm"
+          className="mb-3"
+          placeholder="What were you doing?"
+          variant="bordered"
+          value={reason}
+          onChange={(e) => setReason(e.target.value)}
+        />
+      )}
+      <div className="flex gap-2">
+        <Button size="sm" color="primary" onPress={() => resolve("keep")}>
+          Keep
+        </Button>
+        <Button
+          size="sm"
+          variant="bordered"
+          onPress={() => resolve("discard")}
+        >
+          Discard
+        </Button>
+        <Button
+          size="sm"
+          variant="bordered"
+          onPress={() => setReassigning(!reassigning)}
+        >
+          Move to task
+        </Button>
+      </div>
+      {reassigning && (
+        <div className="mt-3">
+          <Input
+            size="sm"
+            placeholder="Search tasks"
+            variant="bordered"
+            value={query}
+            onChange={(e) => setQuery(e.target.value)}
+          />
+          <Listbox
+            aria-label="Tasks"
+            variant="faded"
+            color="primary"
+            onAction={(key) => {
+              const hit = hits.find((other) => other.id === key);
+              if (hit) {
+                reassignTo(hit);
+              }
+            }}
+          >
+            {hits.map((hit) => (
+              <ListboxItem
+                key={hit.id}
+                textValue={hit.name}
+                description={hit.breadcrumbs
+                  .map((crumb) => crumb.name)
+                  .join(" › ")}
+              >
+                {hit.name}
+              </ListboxItem>
+            ))}
+          </Listbox>
+        </div>
+      )}
+    </div>
+  );
+}
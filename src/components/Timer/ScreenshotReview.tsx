@@ -0,0 +1,150 @@
+import { useCallback, useEffect, useState } from "react";
+import { Button } from "@heroui/react";
+import { invoke } from "@tauri-apps/api/core";
+import { ScreenshotReview as Review } from "../../types";
+
+/** Captures are taken in the background, so the list is polled */
+const REFRESH_MS = 30_000;
+const PAUSE_OPTIONS = [15, 60];
+
+/**
+ * Screenshots the user can still delete before they are final, and a way
+ * to pause screenshots for a while.
+ */
+export function ScreenshotReview() {
+  const [review, setReview] = useState<Review | null>(null);
+  const [open, setOpen] = useState(false);
+  const [previews, setPreviews] = useState<Record<string, string>>({});
+
+  const refresh = useCallback(() => {
+    invoke<Review>("get_screenshot_review")
+      .then(setReview)
+      .catch(console.error);
+  }, []);
+
+  useEffect(() => {
+    refresh();
+    const interval = setInterval(refresh, REFRESH_MS);
+    return () => clearInterval(interval);
+  }, [refresh]);
+
+  if (!review) {
+    return null;
+  }
+
+  const pause = (minutes: number) => {
+    invoke("pause_screenshots", { minutes })
+      .catch(console.error)
+      .finally(refresh);
+  };
+
+  const resume = () => {
+    invoke("resume_screenshots").catch(console.error).finally(refresh);
+  };
+
+  const preview = (id: string) => {
+    invoke<string>("get_screenshot_image", { id })
+      .then((url) => setPreviews((current) => ({ ...current, [id]: url })))
+      .catch(console.error);
+  };
+
+  const remove = (id: string) => {
+    // Past its review period the deletion is refused; the list catches up
+    invoke("delete_screenshot", { id })
+      .catch(console.error)
+      .finally(refresh);
+  };
+
+  const count = review.pending.length;
+
+  return (
+    <div className="mx-4 mb-4 text-sm">
+      <div className="flex items-center justify-between gap-2">
+        {review.paused_until ? (
+          <>
+            <span className="text-xs text-gray-600">
+              Screenshots paused until{" "}
+              {new Date(review.paused_until).toLocaleTimeString()}
+            </span>
+            <Button size="sm" variant="bordered" onPress={resume}>
+              Resume
+            </Button>
+          </>
+        ) : (
+          <>
+            <span className="text-xs text-gray-600">Pause screenshots</span>
+            <div className="flex gap-2">
+              {PAUSE_OPTIONS.map((minutes) => (
+                <Button
+                  key={minutes}
+                  size="sm"
+                  variant="bordered"
+                  onPress={() => pause(minutes)}
+                >
+                  {minutes < 60 ? `${minutes} min` : `${minutes / 60} h`}
+                </Button>
+              ))}
+            </div>
+          </>
+        )}
+      </div>
+      {count > 0 && (
+        <Button
+          size="sm"
+          variant="light"
+          className="mt-2"
+          onPress={() => setOpen(!open)}
+        >
+          {open ? "Hide" : "Review"} {count} recent screenshot
+          {count === 1 ? "" : "s"}
+        </Button>
+      )}
+      {open &&
+        review.pending.map((screenshot) => (
+          <div
+            key={screenshot.id}
+            className="mt-2 rounded-lg border border-gray-200 p-2"
+          >
+            <div className="flex items-center justify-between gap-2">
+              <div className="text-xs text-gray-600">
+                <p>
+                  {new Date(screenshot.captured_at).toLocaleTimeString()} ·{" "}
+                  {screenshot.monitor_name ||
+                    `Monitor ${screenshot.monitor + 1}`}
+                </p>
+                <p>
+                  Final at {new Date(screenshot.final_at).toLocaleTimeString()}
+                </p>
+              </div>
+              <div className="flex gap-2">
+                {!previews[screenshot.id] && (
+                  <Button
+                    size="sm"
+                    variant="bordered"
+                    onPress={() => preview(screenshot.id)}
+                  >
+                    View
+                  </Button>
+                )}
+                <Button
+                  size="sm"
+                  color="danger"
+                  variant="flat"
+                  onPress={() => remove(screenshot.id)}
+                >
+                  Delete
+                </Button>
+              </div>
+            </div>
+            {previews[screenshot.id] && (
+              <img
+                src={previews[screenshot.id]}
+                alt={`Screenshot of ${screenshot.monitor_name}`}
+                className="mt-2 w-full rounded"
+              />
+            )}
+          </div>
+        ))}
+    </div>
+  );
+}
@@ -1,9 +1,74 @@
 import { Button } from "@heroui/react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { motion, AnimatePresence } from "framer-motion";
-import React, { useState } from "react";
+import { useEffect, useState } from "react";
+import { Task, TimerResponse } from "../../types";
 
-function SmallControlButton() {
-  const [isPlaying, setIsPlaying] = useState(false);
+interface SmallControlButtonProps {
+  taskId: string;
+  subTaskId?: string;
+}
+
+/**
+ * Play / pause for one task or sub task. Playing while another task is
+ * tracked switches the timer over without stopping it.
+ */
+function SmallControlButton({ taskId, subTaskId }: SmallControlButtonProps) {
+  const [timerState, setTimerState] = useState<TimerResponse | null>(null);
+
+  useEffect(() => {
+    const unlisten = listen<TimerResponse>("timer-update", (event) => {
+      setTimerState(event.payload);
+    });
+    return () => {
+      unlisten.then((unlistenFn) => unlistenFn());
+    };
+  }, []);
+
+  const isTracked =
+    timerState?.task_id === taskId &&
+    (timerState?.sub_task_id ?? undefined) === subTaskId;
+  const isPlaying = !!timerState?.running && isTracked;
+
+  const handlePress = async () => {
+    try {
+      if (isPlaying) {
+        await invoke("control_timer", { command: "Pause" });
+        return;
+      }
+      if (isTracked && timerState && timerState.elapsed_seconds > 0) {
+        await invoke("control_timer", { command: "Resume" });
+        return;
+      }
+      const blockers = await invoke<Task[]>("get_task_blockers", {
+        taskId,
+      });
+      if (
+        blockers.length > 0 &&
+        !window.confirm(
+          `This task is waiting on ${blockers
+            .map((task) => task.name)
+            .join(", ")}. Track time on it anyway?`
+        )
+      ) {
+        return;
+      }
+      const target = { task_id: taskId, sub_task_id: subTaskId ?? null };
+      if (timerState?.running) {
+        await invoke("control_timer", { command: { SwitchTask: target } });
+        return;
+      }
+      if (timerState && timerState.elapsed_seconds > 0) {
+        // A paused session on another task is ended first
+        await invoke("control_timer", { command: "Stop" });
+      }
+      await invoke("control_timer", { command: { Start: target } });
+    } catch (error) {
+      console.error("Failed to control timer:", error);
+      window.alert(String(error));
+    }
+  };
 
   const iconVariants = {
     play: {
@@ -28,7 +93,7 @@ function SmallControlButton() {
       variant="light" 
       isIconOnly 
       size="sm"
-      onClick={() => setIsPlaying(!isPlaying)}
+      onPress={handlePress}
     >
       <motion.div
         whileTap={{ scale: 0.95 }}
@@ -0,0 +1,58 @@
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { invoke } from "@tauri-apps/api/core";
+import { OutboxStatus } from "../../types";
+
+function plural(count: number, noun: string): string {
+  return `${count} ${noun}${count === 1 ? "" : "s"}`;
+}
+
+/** Tracked time not uploaded to the server yet, e.g. while offline. */
+export function SyncStatus() {
+  const [status, setStatus] = useState<OutboxStatus | null>(null);
+
+  useEffect(() => {
+    invoke<OutboxStatus>("get_sync_status")
+      .then(setStatus)
+      .catch(console.error);
+
+    const unlisten = listen<OutboxStatus>("sync-status", (event) => {
+      setStatus(event.payload);
+    });
+
+    return () => {
+      unlisten.then((unlistenFn) => unlistenFn());
+    };
+  }, []);
+
+  if (!status) {
+    return null;
+  }
+
+  const pending = [
+    status.pending_sessions > 0 && plural(status.pending_sessions, "session"),
+    status.pending_activity > 0 &&
+      plural(status.pending_activity, "activity sample"),
+  ].filter(Boolean);
+
+  if (pending.length === 0 && status.rejected === 0) {
+    return null;
+  }
+
+  return (
+    <div className="mx-4 text-xs text-gray-500 text-center">
+      {pending.length > 0 && <p>{pending.join(" and ")} pending sync</p>}
+      {status.next_attempt_at && (
+        <p title={status.last_error ?? undefined}>
+          Server unreachable, retrying at{" "}
+          {new Date(status.next_attempt_at).toLocaleTimeString()}
+        </p>
+      )}
+      {status.rejected > 0 && (
+        <p className="text-danger-600">
+          {plural(status.rejected, "upload")} refused by the server
+        </p>
+      )}
+    </div>
+  );
+}
@@ -4,6 +4,10 @@ import { invoke } from "@tauri-apps/api/core";
 import { TimerResponse } from "../../types";
 import { formatTime } from "../../utils/formatTime";
 import ControlButton from "./ControlButton";
+import { IdlePrompt } from "./IdlePrompt";
+import { ScreenshotReview } from "./ScreenshotReview";
+import { SyncStatus } from "./SyncStatus";
+import { TimerRecovery } from "./TimerRecovery";
 
 export function Timer() {
   const [timerState, setTimerState] = useState<TimerResponse>({
@@ -11,6 +15,8 @@ export function Timer() {
     running: false,
     start_time: null,
     end_time: null,
+    task_id: null,
+    sub_task_id: null,
   });
 
   useEffect(() => {
@@ -43,6 +49,12 @@ export function Timer() {
       </div>
 
       <ControlButton timerState={timerState} />
+      <div className="mt-4 w-full">
+        <TimerRecovery />
+        <IdlePrompt />
+        <ScreenshotReview />
+        <SyncStatus />
+      </div>
     </div>
   );
 }
@@ -0,0 +1,49 @@
+import { useEffect, useState } from "react";
+import { Button } from "@heroui/react";
+import { invoke } from "@tauri-apps/api/core";
+import { RecoveryAction, TimerSnapshot } from "../../types";
+import { formatTime } from "../../utils/formatTime";
+
+/**
+ * Asks what to do with a timer session the app did not shut down cleanly,
+ * e.g. after a crash or reboot. The timer stays locked until answered.
+ */
+export function TimerRecovery() {
+  const [snapshot, setSnapshot] = useState<TimerSnapshot | null>(null);
+
+  useEffect(() => {
+    invoke<TimerSnapshot | null>("get_recovered_timer")
+      .then(setSnapshot)
+      .catch(console.error);
+  }, []);
+
+  if (!snapshot) {
+    return null;
+  }
+
+  const recover = (action: RecoveryAction) => {
+    invoke("recover_timer", { action })
+      .then(() => setSnapshot(null))
+      .catch(console.error);
+  };
+
+  const lastSeen = new Date(snapshot.heartbeat).toLocaleString();
+
+  return (
+    <div className="mx-4 mb-4 rounded-lg border border-warning-200 bg-warning-50 p-3 text-sm">
+      <p className="font-semibold text-gray-800">Unfinished session</p>
+      <p className="text-xs text-gray-600 mb-3">
+        {formatTime(snapshot.elapsed_seconds)} tracked
+        {snapshot.running ? "" : " (paused)"}, last seen {lastSeen}.
+      </p>
+      <div className="flex gap-2">
+        <Button size="sm" color="primary" onPress={() => recover("resume")}>
+          Resume
+        </Button>
+        <Button size="sm" variant="bordered" onPress={() => recover("close")}>
+          Close at {new Date(snapshot.heartbeat).toLocaleTimeString()}
+        </Button>
+      </div>
+    </div>
+  );
+}
@@ -12,6 +12,7 @@ import {
   Input,
 } from "@heroui/react";
 import { TaskList } from "./Tasks/TaskList";
+import { BudgetAlerts } from "./Budget/BudgetAlerts";
 
 const TrackerContainer = () => {
   const [isSidebarOpen, setIsSidebarOpen] = useState(true);
@@ -66,6 +67,7 @@ const TrackerContainer = () => {
       >
         <div className="border-b border-gray-200/80 bg-white">
           <Timer />
+          <BudgetAlerts />
         </div>
         <div className="flex-1 bg-white/50 backdrop-blur-sm">
           <ProjectList />
@@ -3,6 +3,168 @@ export interface TimerResponse {
   running: boolean;
   start_time: string | null;
   end_time: string | null;
+  /** Task being tracked, or last tracked once stopped */
+  task_id: string | null;
+  sub_task_id: string | null;
+}
+
+/** Time tracked on one task in one go, sent with `timer-session` events */
+export interface TimerSession {
+  task_id: string | null;
+  sub_task_id: string | null;
+  start_time: string;
+  end_time: string;
+  /** Tracked time, without pauses */
+  elapsed_seconds: number;
+  /** Why idle time in the session was kept or reassigned */
+  note: string | null;
+}
+
+/** A timer session left behind by a crash, as last written to the journal */
+export interface TimerSnapshot {
+  running: boolean;
+  /** Time tracked up to `heartbeat` */
+  elapsed_seconds: number;
+  start_time: string | null;
+  task_id: string | null;
+  sub_task_id?: string | null;
+  note?: string | null;
+  /** Last time the session was known to be alive */
+  heartbeat: string;
+}
+
+export type RecoveryAction = "resume" | "close";
+
+/** Time without input while the timer ran, sent with `idle-detected` events */
+export interface IdleSpan {
+  id: string;
+  /** Last input before the user went idle */
+  start_time: string;
+  /** First input after */
+  end_time: string;
+  idle_seconds: number;
+  task_id: string | null;
+  sub_task_id: string | null;
+  /** Whether to ask why the user was away */
+  ask_for_reason: boolean;
+}
+
+export type IdleDecision =
+  | "keep"
+  | "discard"
+  | { reassign: { task_id: string; sub_task_id: string | null } };
+
+export type SearchHitKind = "project" | "section" | "task" | "sub_task";
+
+export interface SearchHit {
+  kind: SearchHitKind;
+  id: string;
+  name: string;
+  project_id: string;
+  /** Containing section, or the hit itself for sections */
+  section_id: string | null;
+  /** Containing task, or the hit itself for tasks */
+  task_id: string | null;
+  /** Ancestors from the project down */
+  breadcrumbs: { kind: SearchHitKind; id: string; name: string }[];
+  rank: number;
+}
+
+/** Uploads waiting in the desktop's sync outbox */
+export interface OutboxStatus {
+  pending_sessions: number;
+  pending_activity: number;
+  /** Uploads the server refused; kept on disk, not retried */
+  rejected: number;
+  /** Earliest retry of an upload that failed */
+  next_attempt_at: string | null;
+  /** Why the most recent failed upload failed */
+  last_error: string | null;
+}
+
+/** A screenshot saved on this desktop, from its local index */
+export interface ScreenshotRecord {
+  id: string;
+  /** Folder of the timer session it was taken in */
+  session: string;
+  task_id: string | null;
+  monitor: number;
+  monitor_name: string;
+  /** Relative to the screenshot directory */
+  path: string;
+  format: "jpeg" | "webp";
+  width: number;
+  height: number;
+  captured_width: number;
+  captured_height: number;
+  bytes: number;
+  captured_at: string;
+}
+
+/** A screenshot that can still be deleted */
+export interface PendingScreenshot extends ScreenshotRecord {
+  /** When it can no longer be deleted */
+  final_at: string;
+}
+
+export interface ScreenshotReview {
+  /** Newest first */
+  pending: PendingScreenshot[];
+  /** Set while screenshots are paused */
+  paused_until: string | null;
+}
+
+/** Settings a tracking policy from the server can set and lock */
+export type TrackingSetting =
+  | "enable_screen_shots"
+  | "screen_shots_interval"
+  | "enable_window_ss"
+  | "browser_window_ss"
+  | "enable_keyboard_activity"
+  | "enable_mouse_activity"
+  | "enable_idle_time"
+  | "idle_time_threshold"
+  | "ask_for_idle_reason"
+  | "enable_window_activity"
+  | "enable_browser_activity";
+
+/** The desktop's tracking config, saved in its config directory */
+export interface AppConfig {
+  enable_screen_shots: boolean;
+  /** In seconds */
+  screen_shots_interval: number;
+  enable_window_ss: boolean;
+  browser_window_ss: boolean;
+  screenshot_storage: {
+    /** Relative to the app data directory */
+    directory: string;
+    format: "jpeg" | "webp";
+    /** JPEG quality (1-100) */
+    quality: number;
+    max_width: number | null;
+  };
+  screen_capture_backend: "xcap" | "screenshots";
+  screenshot_privacy: {
+    /** Blur sigma in pixels; 0 for none */
+    blur: number;
+    excluded_apps: string[];
+    excluded_titles: string[];
+    review_minutes: number;
+  };
+  enable_keyboard_activity: boolean;
+  enable_mouse_activity: boolean;
+  enable_idle_time: boolean;
+  /** In seconds */
+  idle_time_threshold: number;
+  ask_for_idle_reason: boolean;
+  enable_window_activity: boolean;
+  enable_browser_activity: boolean;
+}
+
+export interface AppConfigResponse {
+  config: AppConfig;
+  /** Settings the user cannot change */
+  locked: TrackingSetting[];
 }
 
 export interface Project {
@@ -11,36 +173,181 @@ export interface Project {
   created_at: string;
   updated_at: string;
   version: number;
+  client_id?: string | null;
+  billable: boolean;
+  budget_minutes?: number | null;
+  /** In the minor unit of `budget_currency` */
+  budget_amount?: number | null;
+  budget_currency?: string | null;
+  /** Set while the project is archived and read-only */
+  archived_at?: string | null;
+}
+
+export interface Client {
+  id: string;
+  name: string;
+  email?: string | null;
+  created_at: string;
+  updated_at: string;
 }
 
 export interface Section {
   id: string;
   name: string;
   project_id: string;
+  // Ordering key within the project; compare as plain strings
+  position: string;
   created_at: string;
   updated_at: string;
-  version: number;
 }
 
-export interface Task {
+// Built-in statuses; any other lower-case slug is a custom status
+export type TaskStatus = 'todo' | 'in_progress' | 'done' | (string & {});
+
+export type TaskPriority = 'low' | 'medium' | 'high' | 'urgent';
+
+export interface TaskDetails {
+  description: string | null;
+  status: TaskStatus;
+  priority: TaskPriority;
+  due_date: string | null;
+  estimated_minutes: number | null;
+  assignee: string | null;
+}
+
+export interface Task extends TaskDetails {
   id: string;
   name: string;
   section_id: string;
+  // Ordering key within the section
+  position: string;
+  // A task blocking this one is not done yet
+  blocked: boolean;
+  billable: boolean;
   created_at: string;
   updated_at: string;
-  version: number;
-  description?: string;
-  status: 'todo' | 'in_progress' | 'completed';
 }
 
-export interface SubTask {
+export interface SubTask extends TaskDetails {
   id: string;
   name: string;
+  section_id: string;
+  task_id: string | null;
+  // Ordering key within the task, or the section when task_id is null
+  position: string;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface TaskFilter {
+  status?: TaskStatus;
+  priority?: TaskPriority;
+  assignee?: string;
+  due_before?: string;
+  due_after?: string;
+  /** Comma separated label names; matches items carrying any of them */
+  label?: string;
+}
+
+export interface Label {
+  id: string;
+  name: string;
+  /** `#rrggbb` */
+  color: string;
+  created_at: string;
+  updated_at: string;
+}
+
+export type RecurrenceFrequency = 'daily' | 'weekly' | 'monthly';
+
+export interface TaskRecurrence {
+  id: string;
   task_id: string;
+  /** RRULE subset, e.g. `FREQ=WEEKLY;INTERVAL=2;COUNT=10` */
+  rule: string;
+  frequency: RecurrenceFrequency;
+  interval: number;
+  starts_on: string;
+  until?: string | null;
+  count?: number | null;
+  /** `null` once the series ended */
+  next_occurrence?: string | null;
+  created_at: string;
+  updated_at: string;
+}
+
+// Set ids pick what a rate covers: a person on a project, a project, a
+// client, or none for the default; the most specific rate applies
+export interface BillingRate {
+  id: string;
+  client_id?: string | null;
+  project_id?: string | null;
+  person?: string | null;
+  /** Per hour, in the currency's minor unit */
+  hourly_rate: number;
+  currency: string;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface TimeEntry {
+  id: string;
+  task_id?: string | null;
+  project_id?: string | null;
+  client_id?: string | null;
+  person?: string | null;
+  start_time: string;
+  end_time: string;
+  duration_seconds: number;
+  billable: boolean;
+  /** `null` when the time is not billable or no rate applies */
+  hourly_rate?: number | null;
+  currency?: string | null;
+  amount?: number | null;
+  invoice_id?: string | null;
+}
+
+export interface TimeSummary {
+  total_seconds: number;
+  billable_seconds: number;
+  // Billable time no rate applies to
+  unpriced_seconds: number;
+  totals: { currency: string; billable_seconds: number; amount: number }[];
+}
+
+export type InvoiceStatus = "draft" | "finalized";
+
+export interface InvoiceLine {
+  id: string;
+  invoice_id: string;
+  project_id?: string | null;
+  task_id?: string | null;
+  description: string;
+  seconds: number;
+  /** Per hour, in the currency's minor unit */
+  hourly_rate: number;
+  amount: number;
+  position: number;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface Invoice {
+  id: string;
+  client_id: string;
+  /** Set when the invoice is finalized */
+  number?: number | null;
+  status: InvoiceStatus;
+  currency: string;
+  period_start: string;
+  period_end: string;
+  issued_on?: string | null;
+  due_on?: string | null;
+  notes?: string | null;
+  total: number;
+  lines: InvoiceLine[];
   created_at: string;
   updated_at: string;
-  version: number;
-  completed: boolean;
 }
 
 export interface LoadingState {
@@ -49,3 +356,46 @@ export interface LoadingState {
   tasks: Set<string>;
   subtasks: Set<string>;
 }
+
+/** Time in seconds; money in the currency's minor unit */
+export interface BudgetProgress {
+  budget: number;
+  used: number;
+  remaining: number;
+  percent_used: number;
+  burn_rate_per_day: number;
+  projected_overrun_on?: string | null;
+  currency?: string | null;
+}
+
+export interface ProjectBudget {
+  project_id: string;
+  time?: BudgetProgress | null;
+  money?: BudgetProgress | null;
+  unpriced_seconds: number;
+  estimated_seconds: number;
+  tasks: {
+    task_id: string;
+    name: string;
+    estimated_seconds: number;
+    used_seconds: number;
+    percent_used: number;
+  }[];
+}
+
+export type BudgetAlertKind = "time" | "money" | "estimate";
+
+export interface BudgetAlert {
+  id: string;
+  project_id: string;
+  project_name: string;
+  task_id?: string | null;
+  task_name?: string | null;
+  kind: BudgetAlertKind;
+  threshold: number;
+  used: number;
+  budget: number;
+  currency?: string | null;
+  created_at: string;
+  acknowledged_at?: string | null;
+}